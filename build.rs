@@ -13,6 +13,63 @@ const COMMANDS: &[&str] = &[
     "compress",
     "decompress",
     "transfer",
+    "backup",
+    "index_build",
+    "index_query",
+    "index_status",
+    "stat_many",
+    "parse_path",
+    "size_report",
+    "icon_for_extension",
+    "watch_file",
+    "unwatch_file",
+    "recent_changes",
+    "largest",
+    "type_stats",
+    "compress_manifest",
+    "icon_asset",
+    "clone_metadata",
+    "organize",
+    "import_files",
+    "read_dir_stream",
+    "exe_info",
+    "resolve_dropped",
+    "remove",
+    "copy",
+    "plan_operations",
+    "commit_operations",
+    "cancel_operation",
+    "undo",
+    "undo_history",
+    "auto_sync_start",
+    "auto_sync_stop",
+    "bidirectional_sync",
+    "archive_entries",
+    "volumes",
+    "benchmark",
+    "copy_file",
+    "disk_usage",
+    "is_writable",
+    "compress_many",
+    "workspace_create",
+    "workspace_dispose",
+    "convert_archive",
+    "store_cas",
+    "retrieve_cas",
+    "register_file_association",
+    "unregister_file_association",
+    "create_launcher",
+    "punch_hole",
+    "extents",
+    "change_token",
+    "has_changed",
+    "gzip",
+    "gunzip",
+    "cwd",
+    "resolve_app_path",
+    "diagnose",
+    "watch_volumes",
+    "unwatch_volumes",
 ];
 
 fn main() {
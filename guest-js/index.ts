@@ -1,4 +1,16 @@
-import { invoke } from "@tauri-apps/api/core";
+import { Channel, invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+export interface FsProError {
+  /**
+   * A stable identifier of the error, e.g. `notFound`, `permissionDenied` or `deviceDisconnected`.
+   */
+  kind: string;
+  /**
+   * A human-readable description of the error.
+   */
+  message: string;
+}
 
 export interface IconOptions {
   /**
@@ -9,6 +21,52 @@ export interface IconOptions {
    * The path to save the icon, defaults to the default save path.
    */
   savePath?: string;
+  /**
+   * Multiply the size by the scale factor of the display, so the icon stays sharp on high DPI displays, defaults to `false`.
+   */
+  hiDpi?: boolean;
+  /**
+   * The scale factor to multiply the size by, e.g. of the window showing the icon, takes precedence over `hiDpi`.
+   */
+  scaleFactor?: number;
+}
+
+export interface IconAsset extends PathEntry {
+  /**
+   * The path of the saved icon.
+   */
+  path: string;
+  /**
+   * The asset protocol URL of the icon, ready to be used as an image source.
+   */
+  url: string;
+}
+
+export interface ExeInfo {
+  /**
+   * The product name, `CFBundleDisplayName` or `CFBundleName` for app bundles.
+   */
+  productName: string | null;
+  /**
+   * The product version, `CFBundleShortVersionString` for app bundles.
+   */
+  productVersion: string | null;
+  /**
+   * The file version, `CFBundleVersion` for app bundles.
+   */
+  fileVersion: string | null;
+  /**
+   * The company name, not available for app bundles.
+   */
+  companyName: string | null;
+  /**
+   * The file description, `CFBundleGetInfoString` for app bundles.
+   */
+  description: string | null;
+  /**
+   * The copyright notice, `NSHumanReadableCopyright` for app bundles.
+   */
+  copyright: string | null;
 }
 
 export interface MetadataOptions {
@@ -16,13 +74,88 @@ export interface MetadataOptions {
    * When getting the metadata of a path, if you don't need to calculate the size, you can omit it to save time and return 0 after omitting it.
    */
   omitSize?: boolean;
+  /**
+   * Return immediately with a `0` size and a `sizeId`, then emit the `fs-pro://size` event with the same id once the size is calculated, defaults to `false`.
+   */
+  deferSize?: boolean;
 }
 
+export type SkipPreset = "vcs" | "dependencies" | "osJunk";
+
+export interface SizeOptions {
+  /**
+   * The preset sets of names to skip: `vcs` (e.g. `.git`), `dependencies` (e.g. `node_modules`) and `osJunk` (e.g. `.DS_Store`).
+   */
+  skip?: SkipPreset[];
+  /**
+   * The glob patterns to skip, matched against the path relative to the measured path, e.g. `*.log`.
+   */
+  excludes?: string[];
+  /**
+   * Treat macOS bundles such as `.app` as single files, nothing inside them is skipped, defaults to `false`.
+   */
+  treatBundlesAsFiles?: boolean;
+  /**
+   * An id of your choice to cancel the operation with `cancelOperation`.
+   */
+  operationId?: string;
+}
+
+export interface SizeEvent extends PathEntry {
+  /**
+   * The id returned as `sizeId` by `metadata`.
+   */
+  id: number;
+  /**
+   * The path whose size was calculated.
+   */
+  path: string;
+  /**
+   * The size of the path in bytes.
+   */
+  size: number;
+}
+
+export interface PathEntry {
+  /**
+   * The path, with invalid Unicode replaced by `U+FFFD`.
+   */
+  path: string;
+  /**
+   * The raw encoding of the path (bytes on Unix, UTF-16 code units on Windows), only present when the path is not valid Unicode.
+   */
+  rawPath?: number[];
+}
+
+/**
+ * A path argument, given as a string or as a `PathEntry` returned by another command. The raw encoding of an entry is used when present, so a path that is not valid Unicode still reaches the right file.
+ */
+export type PathInput = string | PathEntry;
+
+/**
+ * - `socket`: A Unix domain socket.
+ * - `fifo`: A named pipe.
+ * - `block`: A block device, such as a disk.
+ * - `char`: A character device, such as a terminal.
+ */
+export type FileType =
+  | "file"
+  | "dir"
+  | "symlink"
+  | "socket"
+  | "fifo"
+  | "block"
+  | "char";
+
 export interface Metadata {
   /**
    * The size of the path in bytes.
    */
   size: number;
+  /**
+   * The id of the `fs-pro://size` event carrying the size with `deferSize`, `null` otherwise.
+   */
+  sizeId: number | null;
   /**
    * The file or directory name of the path.
    */
@@ -35,6 +168,14 @@ export interface Metadata {
    * The full name of the path including extension.
    */
   fullName: string;
+  /**
+   * The raw encoding of the full name (bytes on Unix, UTF-16 code units on Windows), only present when the full name is not valid Unicode.
+   */
+  rawFullName?: number[];
+  /**
+   * The raw encoding of the path (bytes on Unix, UTF-16 code units on Windows), only present when the path is not valid Unicode.
+   */
+  rawPath?: number[];
   /**
    * The parent directory name of the path.
    */
@@ -55,6 +196,10 @@ export interface Metadata {
    * Whether the path is a symbolic link.
    */
   isSymlink: boolean;
+  /**
+   * The type of the path itself, symbolic links are not followed. Sockets, named pipes and devices are neither files nor directories and shouldn't be read like files.
+   */
+  fileType: FileType;
   /**
    * Whether the path is an absolute path.
    */
@@ -77,324 +222,3486 @@ export interface Metadata {
   modifiedAt: number;
 }
 
-export interface CompressOptions {
-  /**
-   * The name of the file or directory to be compressed.
-   */
-  includes?: string[];
-  /**
-   * The name of the file or directory not to be compressed.
-   */
-  excludes?: string[];
-}
+export interface DirEntry extends PathEntry {
+  /**
+   * The file name, with invalid Unicode replaced by `U+FFFD`.
+   */
+  name: string;
+  /**
+   * Whether the entry is a directory.
+   */
+  isDir: boolean;
+  /**
+   * Whether the entry is a file.
+   */
+  isFile: boolean;
+  /**
+   * Whether the entry is a symbolic link.
+   */
+  isSymlink: boolean;
+}
+
+export interface DroppedPath extends PathEntry {
+  /**
+   * The file name, with invalid Unicode replaced by `U+FFFD`.
+   */
+  name: string;
+  /**
+   * Whether the path is a directory.
+   */
+  isDir: boolean;
+  /**
+   * Whether the path is a file.
+   */
+  isFile: boolean;
+  /**
+   * The size of files in bytes, `0` for directories.
+   */
+  size: number;
+  /**
+   * The modification time in milliseconds since the Unix epoch.
+   */
+  modifiedAt: number;
+}
+
+export interface ReadDirStreamOptions {
+  /**
+   * The number of entries sent per message, defaults to `100`.
+   */
+  batchSize?: number;
+  /**
+   * The minimum time between two messages in milliseconds, a batch grows past the batch size in the meantime.
+   */
+  minIntervalMs?: number;
+}
+
+export interface PathStat {
+  /**
+   * Whether the path exists.
+   */
+  exists: boolean;
+  /**
+   * Whether the path is a file.
+   */
+  isFile: boolean;
+  /**
+   * Whether the path is a directory.
+   */
+  isDir: boolean;
+}
+
+export interface ParsedPath {
+  /**
+   * The kind of the Windows path prefix, if any.
+   */
+  prefixKind?:
+    | "verbatim"
+    | "verbatimUnc"
+    | "verbatimDisk"
+    | "deviceNs"
+    | "unc"
+    | "disk";
+  /**
+   * The drive letter, e.g. `C`.
+   */
+  drive?: string;
+  /**
+   * The UNC server name.
+   */
+  host?: string;
+  /**
+   * The UNC share name.
+   */
+  share?: string;
+  /**
+   * The verbatim or device prefix that is neither a drive nor a UNC share.
+   */
+  device?: string;
+  /**
+   * Whether the path has a root, e.g. `/` or `\\`.
+   */
+  hasRoot: boolean;
+  /**
+   * Whether the path is an absolute path.
+   */
+  isAbsolute: boolean;
+  /**
+   * The directory components between the prefix and the file name.
+   */
+  directories: string[];
+  /**
+   * The full name including extension of the last component.
+   */
+  fileName?: string;
+  /**
+   * The name of the last component without extension.
+   */
+  name?: string;
+  /**
+   * The extension name of the last component.
+   */
+  extname?: string;
+}
+
+export interface RecentChangesOptions {
+  /**
+   * Only return files modified after this time, in milliseconds since the Unix epoch, defaults to `0`.
+   */
+  since?: number;
+  /**
+   * The maximum number of files to return, defaults to `100`.
+   */
+  limit?: number;
+}
+
+export interface RecentChange extends PathEntry {
+  /**
+   * The modification time in milliseconds since the Unix epoch.
+   */
+  modifiedAt: number;
+}
+
+export interface LargestOptions {
+  /**
+   * The maximum number of files and directories to return, defaults to `10`.
+   */
+  count?: number;
+  /**
+   * Only return files and directories of at least this size in bytes, defaults to `0`.
+   */
+  minSize?: number;
+  /**
+   * Treat macOS bundles such as `.app` as single files, nothing inside them is returned, defaults to `false`.
+   */
+  treatBundlesAsFiles?: boolean;
+}
+
+export interface LargestEntry extends PathEntry {
+  /**
+   * The size in bytes, including everything inside for directories.
+   */
+  size: number;
+  /**
+   * Whether the path is a directory.
+   */
+  isDir: boolean;
+}
+
+export type FileCategory =
+  | "image"
+  | "video"
+  | "audio"
+  | "document"
+  | "archive"
+  | "code"
+  | "application"
+  | "other";
+
+export interface TypeStatsOptions {
+  /**
+   * Treat macOS bundles such as `.app` as single files, counted once with the size of everything inside, defaults to `false`.
+   */
+  treatBundlesAsFiles?: boolean;
+}
+
+export interface TypeStat {
+  /**
+   * The number of files.
+   */
+  count: number;
+  /**
+   * The total size of the files in bytes.
+   */
+  size: number;
+}
+
+export interface TypeStats {
+  /**
+   * The files grouped by category.
+   */
+  categories: Partial<Record<FileCategory, TypeStat>>;
+  /**
+   * The files grouped by lowercased extension name without the leading dot, files without one are grouped under `""`.
+   */
+  extensions: Record<string, TypeStat>;
+}
+
+export interface SizeReport {
+  /**
+   * The size of the readable part of the path in bytes.
+   */
+  size: number;
+  /**
+   * The paths that could not be read because of missing permissions.
+   */
+  inaccessible: PathEntry[];
+}
+
+export interface CompressReport extends PathEntry {
+  /**
+   * The path of the written archive, the first volume when it's split.
+   */
+  path: string;
+  /**
+   * The paths of all volumes in order when the archive is split, empty otherwise.
+   */
+  volumes: PathEntry[];
+  /**
+   * The paths that were left out of the archive because of missing permissions.
+   */
+  inaccessible: PathEntry[];
+  /**
+   * The path of the manifest written next to the archive with `writeManifest`.
+   */
+  manifestPath: PathEntry | null;
+  /**
+   * What would be compressed with `dryRun`, nothing is written then.
+   */
+  plan: DryRunPlan | null;
+}
+
+export interface TransferReport {
+  /**
+   * The paths that were not moved or copied because of missing permissions.
+   */
+  inaccessible: PathEntry[];
+  /**
+   * The paths that were locked by another process at first and had to be retried.
+   */
+  retried: PathEntry[];
+  /**
+   * The paths of the existing files the items left out with `skip` would have replaced.
+   */
+  skipped: PathEntry[];
+  /**
+   * The items moved or copied under another name with `rename`.
+   */
+  renamed: RenamedEntry[];
+  /**
+   * What would be moved or copied with `dryRun`, nothing is changed then.
+   */
+  plan: DryRunPlan | null;
+}
+
+export interface DryRunPlan {
+  /**
+   * The files and directories that would be moved, copied or compressed, in order.
+   */
+  items: PlannedItem[];
+  /**
+   * The total size of the items in bytes.
+   */
+  totalBytes: number;
+  /**
+   * The existing paths the items would collide with, files in the destination or entries in the archive appended to.
+   */
+  collisions: PathEntry[];
+}
+
+export interface PlannedItem {
+  /**
+   * The path of the file or directory on disk.
+   */
+  srcPath: PathEntry;
+  /**
+   * The path it would be moved or copied to, or its path inside the archive.
+   */
+  dstPath: PathEntry;
+  /**
+   * The size in bytes, of everything in it for a directory that is moved or copied and `0` for a directory entry of an archive.
+   */
+  size: number;
+}
+
+export interface TransferProgress {
+  /**
+   * The number of bytes moved or copied so far.
+   */
+  copiedBytes: number;
+  /**
+   * The total number of bytes to move or copy.
+   */
+  totalBytes: number;
+  /**
+   * The name of the file being moved or copied.
+   */
+  currentFile: string;
+  /**
+   * The recent throughput in bytes per second, smoothed over the last seconds.
+   */
+  bytesPerSecond: number;
+  /**
+   * The estimated time remaining in milliseconds at the recent throughput, `null` as long as nothing is moving.
+   */
+  remainingMs: number | null;
+}
+
+export interface RemoveReport {
+  /**
+   * The paths that were locked by another process at first and had to be retried.
+   */
+  retried: PathEntry[];
+}
+
+export interface RetryOptions {
+  /**
+   * The number of retries after the first attempt, defaults to `5`.
+   */
+  attempts?: number;
+  /**
+   * The delay before the first retry in milliseconds, doubled for every following retry, defaults to `50`.
+   */
+  delay?: number;
+}
+
+export interface RemoveOptions {
+  /**
+   * How deleting files locked by another process is retried, only on Windows.
+   */
+  retry?: RetryOptions;
+}
+
+/**
+ * Gitignore-style rules, either the path of a file such as `.exportignore` or its contents.
+ */
+export type IgnoreRules = { path: string } | { contents: string };
+
+/**
+ * The format of an archive written by `compress`.
+ */
+export type ArchiveFormat = "tar.gz" | "tar.zst" | "tar.xz" | "tar.bz2" | "zip";
+
+/**
+ * A system notification shown once an operation finished or failed, not when it's cancelled, through `notify-send` on Linux, `osascript` on macOS and a PowerShell toast on Windows, which only appears for apps installed with a Start Menu shortcut of their identifier as Tauri installers create. One that can't be shown emits the `fs-pro://notification-error` event, see `onNotificationError`.
+ */
+export interface NotificationOptions {
+  /**
+   * The title once the operation succeeded, defaults to `{name}`.
+   */
+  title?: string;
+  /**
+   * The text once the operation succeeded, defaults to `Completed`.
+   */
+  body?: string;
+  /**
+   * The title once the operation failed, defaults to `title`.
+   */
+  failureTitle?: string;
+  /**
+   * The text once the operation failed, defaults to `{error}`.
+   */
+  failureBody?: string;
+}
+
+export interface NotificationError {
+  /**
+   * The title of the notification that could not be shown.
+   */
+  title: string;
+  /**
+   * Why it could not be shown, such as `notify-send` missing.
+   */
+  message: string;
+}
+
+export interface CompressOptions {
+  /**
+   * The glob patterns of the paths to be compressed, matched against the path relative to the source path, e.g. `src`, `src/config/settings.json` or `**/*.rs`, with or without a leading `./`. Directories are searched for matching entries.
+   */
+  includes?: string[];
+  /**
+   * The glob patterns of the paths not to be compressed, matched against the path relative to the source path, e.g. `*.log` or `target`.
+   */
+  excludes?: string[];
+  /**
+   * Gitignore-style rules of the paths not to be compressed, matched relative to the source path.
+   */
+  ignore?: IgnoreRules;
+  /**
+   * Only compress files of at least this many bytes.
+   */
+  minSize?: number;
+  /**
+   * Only compress files of at most this many bytes.
+   */
+  maxSize?: number;
+  /**
+   * Only compress files modified after this time, in milliseconds since the Unix epoch.
+   */
+  modifiedAfter?: number;
+  /**
+   * Only compress files modified before this time, in milliseconds since the Unix epoch.
+   */
+  modifiedBefore?: number;
+  /**
+   * Store every directory that isn't excluded, even when `includes` or the size and time options keep nothing in it, defaults to `false`.
+   */
+  includeEmptyDirs?: boolean;
+  /**
+   * Whether to overwrite an existing destination file, rejects instead when `false`, defaults to `true`.
+   */
+  overwrite?: boolean;
+  /**
+   * Append the current timestamp in milliseconds to the destination file name, defaults to `false`.
+   */
+  appendTimestamp?: boolean;
+  /**
+   * An id of your choice to cancel the operation with `cancelOperation`, the partial archive is removed.
+   */
+  operationId?: string;
+  /**
+   * The format of the archive, defaults to `tar.gz`. `tar.zst` is much faster than `tar.gz` for a similar size.
+   */
+  format?: ArchiveFormat;
+  /**
+   * The compression level, from `0` to `9` defaulting to `6` for `tar.gz`, `zip` and `tar.xz`, from `1` to `22` defaulting to `3` for `tar.zst` and from `1` to `9` defaulting to `6` for `tar.bz2`. Higher levels are smaller but slower, `0` stores without compressing.
+   */
+  level?: number;
+  /**
+   * Add the entries to an existing archive at the destination path instead of replacing it, in its own format, defaults to `false`.
+   */
+  append?: boolean;
+  /**
+   * Split the archive into volumes of this many bytes, named after the destination path with `.001`, `.002` and so on.
+   */
+  volumeSize?: number;
+  /**
+   * Leave out modified times, owners and groups so identical trees give byte-identical archives, defaults to `false`.
+   */
+  deterministic?: boolean;
+  /**
+   * Store symbolic links as links instead of the files and directories they point to, defaults to `false`.
+   */
+  preserveSymlinks?: boolean;
+  /**
+   * Write a JSON manifest of the entries with their sizes and SHA-256 hashes next to the archive, defaults to `false`.
+   */
+  writeManifest?: boolean;
+  /**
+   * Return what would be compressed, with the sizes and collisions, without writing anything, defaults to `false`.
+   */
+  dryRun?: boolean;
+  /**
+   * Show a system notification once the archive is written or the compression failed.
+   */
+  notify?: NotificationOptions;
+}
+
+export interface CompressManyOptions extends CompressOptions {
+  /**
+   * The paths of the sources inside the archive keyed by source path, e.g. `backup/config.json`, defaults to the name of each source.
+   */
+  names?: Record<string, string>;
+}
+
+export interface CompressManifestOptions {
+  /**
+   * Whether to overwrite an existing destination file, rejects instead when `false`, defaults to `true`.
+   */
+  overwrite?: boolean;
+  /**
+   * Append the current timestamp in milliseconds to the destination file name, defaults to `false`.
+   */
+  appendTimestamp?: boolean;
+  /**
+   * An id of your choice to cancel the operation with `cancelOperation`, the partial archive is removed.
+   */
+  operationId?: string;
+  /**
+   * The format of the archive, defaults to `tar.gz`.
+   */
+  format?: ArchiveFormat;
+  /**
+   * The compression level, within the levels of the format, see `compress`.
+   */
+  level?: number;
+}
+
+export interface ManifestEntry {
+  /**
+   * The path of the file or directory on disk.
+   */
+  src: string;
+  /**
+   * The relative path of the entry inside the archive.
+   */
+  archivePath: string;
+}
+
+export interface CloneMetadataOptions {
+  /**
+   * Also copy the extended attributes, only supported on Unix, defaults to `false`.
+   */
+  xattrs?: boolean;
+}
+
+export type OrganizeRule =
+  | {
+      by: "extension";
+      /**
+       * The extension names to match, any extension name when omitted.
+       */
+      extensions?: string[];
+      /**
+       * The folder to move the files into, defaults to the extension name.
+       */
+      folder?: string;
+    }
+  | {
+      by: "date";
+      /**
+       * The folder named after the local modification date, `YYYY`, `MM` and `DD` are replaced, e.g. `YYYY/MM`.
+       */
+      pattern: string;
+    }
+  | {
+      by: "regex";
+      /**
+       * The regex the file name must match.
+       */
+      pattern: string;
+      /**
+       * The folder to move the files into, may refer to capture groups, e.g. `$1`.
+       */
+      folder: string;
+    };
+
+export interface OrganizeOptions {
+  /**
+   * Only return the plan without moving anything, defaults to `false`.
+   */
+  dryRun?: boolean;
+}
+
+export interface OrganizeMove {
+  /**
+   * The current path of the file.
+   */
+  src: PathEntry;
+  /**
+   * The path the file is moved to.
+   */
+  dst: PathEntry;
+  /**
+   * Whether the destination is already taken, such files are left in place.
+   */
+  conflict: boolean;
+}
+
+export interface ImportOptions {
+  /**
+   * Skip files whose content already exists anywhere under the destination directory, defaults to `false`.
+   */
+  skipIfIdenticalExists?: boolean;
+}
+
+export interface ImportedFile {
+  /**
+   * The path of the imported file.
+   */
+  src: PathEntry;
+  /**
+   * The path of the copy, renamed to `name (1).ext` and so on when the name is taken.
+   */
+  dst: PathEntry;
+}
+
+export interface DedupedFile {
+  /**
+   * The path of the skipped file.
+   */
+  src: PathEntry;
+  /**
+   * The path of the file with the same content under the destination directory.
+   */
+  existing: PathEntry;
+}
+
+export interface ImportReport {
+  /**
+   * The files that were copied.
+   */
+  imported: ImportedFile[];
+  /**
+   * The files that were skipped because their content already exists.
+   */
+  deduped: DedupedFile[];
+}
+
+export interface BackupOptions {
+  /**
+   * The directory to store the backups in, defaults to the directory of the path.
+   */
+  dir?: string;
+  /**
+   * The number of backups to keep, the oldest ones are removed first, defaults to keeping all.
+   */
+  keep?: number;
+}
+
+export type OverwritePolicy = "overwrite" | "skip" | "error" | "rename";
+
+export type NameEncoding = "auto" | "cp437" | "gbk" | "shift-jis";
+
+export interface DecompressOptions {
+  /**
+   * Glob patterns of the entries to extract, matched against their path inside the archive, the entries inside a matched directory are extracted too.
+   */
+  entries?: string[];
+  /**
+   * Move existing destination files to the system trash before overwriting them, defaults to `false`.
+   */
+  backupToTrash?: boolean;
+  /**
+   * An id of your choice to cancel the operation with `cancelOperation`, the entries extracted so far are kept.
+   */
+  operationId?: string;
+  /**
+   * Restore the setuid, setgid and sticky bits of tar entries too, defaults to `false`.
+   */
+  preservePermissions?: boolean;
+  /**
+   * Restore the owners and groups of tar entries, defaults to `false`.
+   */
+  preserveOwnership?: boolean;
+  /**
+   * Extract the entries and links leading outside the destination path instead of refusing them, only for trusted archives, defaults to `false`.
+   */
+  allowExternalPaths?: boolean;
+  /**
+   * The number of leading components to remove from the names of the entries, as `tar --strip-components` does, defaults to `0`.
+   */
+  stripComponents?: number;
+  /**
+   * What happens to the existing files where entries go, defaults to `overwrite`.
+   */
+  onConflict?: OverwritePolicy;
+  /**
+   * Leave out the files an earlier extraction into the same destination completed before it failed, defaults to `false`.
+   */
+  resume?: boolean;
+  /**
+   * The minimum time between two progress updates in milliseconds, defaults to `100`.
+   */
+  onProgressIntervalMs?: number;
+  /**
+   * Show a system notification once the archive is extracted or the extraction failed.
+   */
+  notify?: NotificationOptions;
+  /**
+   * How the names of zip entries not flagged as UTF-8 are decoded, defaults to `auto`.
+   */
+  encoding?: NameEncoding;
+}
+
+export interface ArchiveEntriesOptions {
+  /**
+   * How the names of zip entries not flagged as UTF-8 are decoded, defaults to `auto`.
+   */
+  encoding?: NameEncoding;
+}
+
+export interface DecompressProgress {
+  /**
+   * The number of entries extracted so far.
+   */
+  extractedEntries: number;
+  /**
+   * The total number of entries to extract.
+   */
+  totalEntries: number;
+  /**
+   * The uncompressed size of the entries extracted so far in bytes.
+   */
+  extractedBytes: number;
+  /**
+   * The total uncompressed size of the entries to extract in bytes.
+   */
+  totalBytes: number;
+  /**
+   * The path of the entry being extracted, relative to the destination path.
+   */
+  currentEntry: PathEntry;
+  /**
+   * The recent throughput in uncompressed bytes per second, smoothed over the last seconds.
+   */
+  bytesPerSecond: number;
+  /**
+   * The estimated time remaining in milliseconds at the recent throughput, `null` as long as nothing is extracted.
+   */
+  remainingMs: number | null;
+}
+
+export interface RenamedEntry extends PathEntry {
+  /**
+   * The path of the existing file the entry or item would have replaced.
+   */
+  path: string;
+  /**
+   * The path the entry was extracted to, or the item moved or copied to, instead.
+   */
+  renamedTo: PathEntry;
+}
+
+export interface DecompressReport {
+  /**
+   * The number of entries written, directories and links included.
+   */
+  entriesExtracted: number;
+  /**
+   * The uncompressed size of the files written in bytes.
+   */
+  bytesWritten: number;
+  /**
+   * The top-level directories in the destination the entries were written to, in the order they first appear in the archive.
+   */
+  rootDirs: PathEntry[];
+  /**
+   * The paths of the existing files the entries left out with `skip` would have replaced.
+   */
+  skipped: PathEntry[];
+  /**
+   * The entries extracted under another name with `rename`.
+   */
+  renamed: RenamedEntry[];
+}
+
+export interface ConvertArchiveOptions {
+  /**
+   * The format of the new archive, defaults to `tar.gz`.
+   */
+  format?: ArchiveFormat;
+  /**
+   * The compression level, within the levels of the format.
+   */
+  level?: number;
+  /**
+   * Whether to overwrite an existing destination file, rejects instead when `false`, defaults to `true`.
+   */
+  overwrite?: boolean;
+  /**
+   * An id of your choice to cancel the operation with `cancelOperation`, the partial archive is removed.
+   */
+  operationId?: string;
+}
+
+export interface GzipOptions {
+  /**
+   * The compression level, from `0` to `9`, defaults to `6`.
+   */
+  level?: number;
+  /**
+   * Whether to overwrite an existing destination file, rejects instead when `false`, defaults to `true`.
+   */
+  overwrite?: boolean;
+  /**
+   * An id of your choice to cancel the operation with `cancelOperation`, the partial file is removed.
+   */
+  operationId?: string;
+}
+
+export interface GunzipOptions {
+  /**
+   * Whether to overwrite an existing destination file, rejects instead when `false`, defaults to `true`.
+   */
+  overwrite?: boolean;
+  /**
+   * An id of your choice to cancel the operation with `cancelOperation`, the partial file is removed.
+   */
+  operationId?: string;
+}
+
+export type BaseDir =
+  | "appData"
+  | "appLocalData"
+  | "appConfig"
+  | "appCache"
+  | "appLog"
+  | "home"
+  | "desktop"
+  | "document"
+  | "download"
+  | "picture"
+  | "audio"
+  | "video"
+  | "temp"
+  | "resource";
+
+export interface ArchiveEntry extends PathEntry {
+  /**
+   * The path of the entry inside the archive.
+   */
+  path: string;
+  /**
+   * The uncompressed size of the entry in bytes.
+   */
+  size: number;
+  /**
+   * The type of the entry, tar files may also hold named pipes and devices.
+   */
+  fileType: FileType;
+  /**
+   * The modified time of the entry in milliseconds, `0` if the archive doesn't store one.
+   */
+  modifiedAt: number;
+}
+
+export interface ArchiveManifestEntry extends ArchiveEntry {
+  /**
+   * The SHA-256 hash of the data of the entry in lowercase hex, `null` for directories, links and special files.
+   */
+  sha256: string | null;
+}
+
+export interface ArchiveManifest {
+  /**
+   * The number of entries in the archive.
+   */
+  entryCount: number;
+  /**
+   * The total uncompressed size of the entries in bytes.
+   */
+  totalSize: number;
+  /**
+   * The entries in the order they are stored.
+   */
+  entries: ArchiveManifestEntry[];
+}
+
+export type SymlinkPolicy = "follow" | "preserve" | "skip";
+
+export interface TransferOptions {
+  /**
+   * The glob patterns of the paths to be moved, matched against the path relative to the source path, e.g. `src`, `src/config/settings.json` or `**/*.rs`, with or without a leading `./`. Directories are searched for matching entries.
+   */
+  includes?: string[];
+  /**
+   * The glob patterns of the paths not to be moved, matched against the path relative to the source path, e.g. `*.log` or `target`.
+   */
+  excludes?: string[];
+  /**
+   * Gitignore-style rules of the paths not to be moved, matched relative to the source path. Ignored entries stay in the source.
+   */
+  ignore?: IgnoreRules;
+  /**
+   * Only move files of at least this many bytes.
+   */
+  minSize?: number;
+  /**
+   * Only move files of at most this many bytes.
+   */
+  maxSize?: number;
+  /**
+   * Only move files modified after this time, in milliseconds since the Unix epoch.
+   */
+  modifiedAfter?: number;
+  /**
+   * Only move files modified before this time, in milliseconds since the Unix epoch.
+   */
+  modifiedBefore?: number;
+  /**
+   * Move existing destination files to the system trash before overwriting them, defaults to `false`.
+   */
+  backupToTrash?: boolean;
+  /**
+   * Snapshot existing destination files into numbered backups before overwriting them.
+   */
+  backup?: BackupOptions;
+  /**
+   * Treat macOS bundles such as `.app` as single files, a bundle is never filtered inside or left half moved and a bundle source is moved into the destination as a whole, defaults to `false`.
+   */
+  treatBundlesAsFiles?: boolean;
+  /**
+   * How moving or copying items locked by another process is retried, only on Windows.
+   */
+  retry?: RetryOptions;
+  /**
+   * An id of your choice to cancel the operation with `cancelOperation`, the item in progress is still moved or copied completely.
+   */
+  operationId?: string;
+  /**
+   * What happens to the existing files where items go, defaults to `overwrite`.
+   */
+  onConflict?: OverwritePolicy;
+  /**
+   * Return what would be moved or copied, with the sizes and collisions, without touching anything, defaults to `false`.
+   */
+  dryRun?: boolean;
+  /**
+   * Put back what was moved or copied when the operation fails or is cancelled part way, defaults to `false`.
+   */
+  rollback?: boolean;
+  /**
+   * Give the copies the access and modification times of the originals, defaults to `false`.
+   */
+  preserveTimestamps?: boolean;
+  /**
+   * Give the copies the permissions of the originals, the modes on Unix, defaults to `false`.
+   */
+  preservePermissions?: boolean;
+  /**
+   * How the symbolic links inside the source are handled, `follow`, `preserve` or `skip`. Left as the system handles them when unset.
+   */
+  symlinks?: SymlinkPolicy;
+  /**
+   * The minimum time between two progress updates in milliseconds, defaults to `100`.
+   */
+  onProgressIntervalMs?: number;
+  /**
+   * Show a system notification once the items are moved or copied, or the operation failed.
+   */
+  notify?: NotificationOptions;
+}
+
+export interface CopyFileOptions {
+  /**
+   * Whether to overwrite an existing destination file, rejects instead when `false`, defaults to `true`.
+   */
+  overwrite?: boolean;
+  /**
+   * The minimum time between two progress updates in milliseconds, defaults to `100`.
+   */
+  onProgressIntervalMs?: number;
+  /**
+   * An id of your choice to cancel the operation with `cancelOperation`.
+   */
+  operationId?: string;
+  /**
+   * Show a system notification once the file is copied or the copy failed.
+   */
+  notify?: NotificationOptions;
+}
+
+export interface IndexQueryOptions {
+  /**
+   * The maximum number of paths to return, defaults to `100`.
+   */
+  limit?: number;
+}
+
+export interface IndexStatus {
+  /**
+   * The state of the index.
+   */
+  status: "idle" | "building" | "ready" | "failed";
+  /**
+   * The indexed directory.
+   */
+  dir?: PathEntry;
+  /**
+   * The number of indexed paths.
+   */
+  count: number;
+  /**
+   * The time the index was built in milliseconds.
+   */
+  builtAt: number;
+  /**
+   * The paths that could not be read, with everything below them left out of the index.
+   */
+  inaccessible: PathEntry[];
+  /**
+   * The error message of the last failed build.
+   */
+  error?: string;
+}
+
+export interface WatchFileOptions {
+  /**
+   * Keep the watch when the volume of the file is unmounted and resume it once the volume is mounted again, defaults to `false`.
+   */
+  autoResume?: boolean;
+  /**
+   * The minimum time between two `modify` events in milliseconds, the changes in the meantime are sent as one.
+   */
+  minIntervalMs?: number;
+}
+
+/**
+ * - `modify`: The content or the attributes of the file changed.
+ * - `remove`: The file was deleted.
+ * - `rename`: The file was moved away from the watched path.
+ * - `lost`: The volume of the file was unmounted. The watch ends, or waits for the volume to be mounted again with `autoResume`.
+ * - `resume`: The volume of a lost file was mounted again and the file is watched again.
+ */
+export type WatchFileEventKind =
+  | "modify"
+  | "remove"
+  | "rename"
+  | "lost"
+  | "resume";
+
+export interface WatchFileEvent extends PathEntry {
+  /**
+   * The id of the watcher.
+   */
+  id: number;
+  /**
+   * The kind of the change, the watcher stops after a `remove` or `rename`, and after a `lost` without `autoResume`.
+   */
+  kind: WatchFileEventKind;
+  /**
+   * The path of the watched file.
+   */
+  path: string;
+  /**
+   * The metadata of the file after the change, `null` once it no longer exists.
+   */
+  metadata: Metadata | null;
+}
+
+export interface AutoSyncOptions {
+  /**
+   * Gitignore-style rules of the paths not to be synced, matched relative to the source path. Ignored paths in the destination are left alone.
+   */
+  ignore?: IgnoreRules;
+  /**
+   * Remove the paths that no longer exist in the source from the destination, defaults to `true`.
+   */
+  delete?: boolean;
+  /**
+   * How long the source must be left unchanged before the changes are synced in milliseconds, defaults to `500`.
+   */
+  debounce?: number;
+}
+
+/**
+ * - `copy`: A file was copied to the destination, or a directory created there.
+ * - `remove`: A path that no longer exists in the source was removed from the destination.
+ * - `error`: A path could not be synced, it's synced again on its next change.
+ */
+export type AutoSyncEventKind = "copy" | "remove" | "error";
+
+export interface AutoSyncEvent extends PathEntry {
+  /**
+   * The id of the auto sync.
+   */
+  id: number;
+  kind: AutoSyncEventKind;
+  /**
+   * The path relative to the source and the destination.
+   */
+  path: string;
+  /**
+   * The error of an `error` event.
+   */
+  error: FsProError | null;
+}
+
+export interface Volume extends PathEntry {
+  /**
+   * The path the volume is mounted at, e.g. `/Volumes/USB` or `E:\`.
+   */
+  path: string;
+  /**
+   * The label of the volume, or the name of its mount point when it has none.
+   */
+  name: string;
+  /**
+   * The file system of the volume, e.g. `apfs`, `ext4` or `NTFS`.
+   */
+  fsType: string;
+}
+
+export type VolumeEventKind = "mounted" | "unmounted";
+
+export interface VolumeEvent {
+  kind: VolumeEventKind;
+  volume: Volume;
+}
+
+/**
+ * - `ask`: Leave both versions untouched and report the conflict, it's reported on every sync until both sides are the same.
+ * - `newerWins`: Keep the version modified last, an edited file wins over a deleted one.
+ * - `keepBoth`: Keep the left version under its name and the right version next to it as `name (conflict <timestamp>).ext`, on both sides. An edited file wins over a deleted one.
+ */
+export type ConflictStrategy = "ask" | "newerWins" | "keepBoth";
+
+export interface BidirectionalSyncOptions {
+  /**
+   * How files changed on both sides since the last sync are resolved, defaults to `ask`.
+   */
+  strategy?: ConflictStrategy;
+  /**
+   * Gitignore-style rules of the paths not to be synced, matched relative to each directory.
+   */
+  ignore?: IgnoreRules;
+}
+
+export interface SyncConflict extends PathEntry {
+  /**
+   * The path relative to both directories.
+   */
+  path: string;
+  /**
+   * When the left version was last modified in milliseconds, `null` if it was deleted.
+   */
+  leftModifiedAt: number | null;
+  /**
+   * When the right version was last modified in milliseconds, `null` if it was deleted.
+   */
+  rightModifiedAt: number | null;
+  /**
+   * Whether the strategy resolved the conflict, `false` with `ask`.
+   */
+  resolved: boolean;
+  /**
+   * The path relative to both directories the right version was kept as with `keepBoth`.
+   */
+  keptAs: PathEntry | null;
+}
+
+export interface SyncReport {
+  /**
+   * The paths relative to both directories that were copied from the right directory to the left one.
+   */
+  copiedToLeft: PathEntry[];
+  /**
+   * The paths relative to both directories that were copied from the left directory to the right one.
+   */
+  copiedToRight: PathEntry[];
+  /**
+   * The paths relative to both directories that were removed from the left directory, as they were deleted from the right one.
+   */
+  removedFromLeft: PathEntry[];
+  /**
+   * The paths relative to both directories that were removed from the right directory, as they were deleted from the left one.
+   */
+  removedFromRight: PathEntry[];
+  /**
+   * The files changed on both sides since the last sync.
+   */
+  conflicts: SyncConflict[];
+  /**
+   * The paths relative to both directories that could not be synced, they're synced again next time.
+   */
+  failed: PathEntry[];
+}
+
+export interface DiskUsage {
+  /**
+   * The size of the volume in bytes.
+   */
+  total: number;
+  /**
+   * The free space in bytes available to the current user.
+   */
+  available: number;
+  /**
+   * The number of inodes of the volume, `null` when the file system has no fixed number, such as btrfs, FAT or NTFS.
+   */
+  totalInodes: number | null;
+  /**
+   * The number of free inodes available to the current user, each file or directory takes one.
+   */
+  availableInodes: number | null;
+}
+
+/**
+ * What `benchmark` measures. With `read` the file is still written first, but only reading it is timed.
+ */
+export type BenchmarkMode = "read" | "write" | "readWrite";
+
+export interface BenchmarkOptions {
+  /**
+   * The size of the temporary file in bytes, rounded up to whole MiB, defaults to 128 MiB.
+   */
+  fileSize?: number;
+  /**
+   * Whether to measure reading, writing or both, defaults to `readWrite`.
+   */
+  mode?: BenchmarkMode;
+  /**
+   * An id of your choice to cancel the operation with `cancelOperation`.
+   */
+  operationId?: string;
+}
+
+export interface BenchmarkReport {
+  /**
+   * The size of the file written and read in bytes.
+   */
+  fileSize: number;
+  /**
+   * The sequential write throughput in bytes per second, `null` when not measured.
+   */
+  writeBytesPerSecond: number | null;
+  /**
+   * The sequential read throughput in bytes per second, `null` when not measured.
+   */
+  readBytesPerSecond: number | null;
+}
+
+/**
+ * A file operation of `planOperations` and `commitOperations`, paths must be absolute.
+ *
+ * - `copy`: Copy a file or directory, `dst` is the full path of the copy.
+ * - `move`: Move a file or directory, `dst` is the full path of the new location.
+ * - `rename`: Give a file or directory a new name in the same directory.
+ * - `delete`: Delete a file or directory.
+ * - `mkdir`: Create a directory, its parent must exist.
+ */
+export type Operation =
+  | { op: "copy"; src: string; dst: string }
+  | { op: "move"; src: string; dst: string }
+  | { op: "rename"; path: string; name: string }
+  | { op: "delete"; path: string }
+  | { op: "mkdir"; path: string };
+
+/**
+ * - `notFound`: The path does not exist at this point of the plan.
+ * - `alreadyExists`: The destination already exists at this point of the plan.
+ * - `parentNotFound`: The parent directory of the destination does not exist, or is not a directory, at this point of the plan.
+ * - `invalidPath`: The path is not absolute, contains `..` or is a root.
+ * - `insideSource`: A directory would be copied or moved into itself.
+ * - `insufficientSpace`: The volume of the destination runs out of space at this operation.
+ * - `insufficientInodes`: The volume of the destination runs out of inodes at this operation, only on file systems with a fixed number such as ext4.
+ */
+export type PlanIssueKind =
+  | "notFound"
+  | "alreadyExists"
+  | "parentNotFound"
+  | "invalidPath"
+  | "insideSource"
+  | "insufficientSpace"
+  | "insufficientInodes";
+
+export interface PlanIssue extends PathEntry {
+  /**
+   * The index of the operation.
+   */
+  index: number;
+  kind: PlanIssueKind;
+  /**
+   * The path the issue is about.
+   */
+  path: string;
+}
+
+export interface Plan {
+  /**
+   * The problems found, the operations can only be committed when there are none.
+   */
+  issues: PlanIssue[];
+  /**
+   * The number of bytes the copies and the moves across volumes write.
+   */
+  requiredBytes: number;
+}
+
+export interface CommitReport {
+  /**
+   * Whether every operation was applied.
+   */
+  committed: boolean;
+  /**
+   * The index of the operation that failed.
+   */
+  failedAt: number | null;
+  /**
+   * The error of the operation that failed.
+   */
+  error: FsProError | null;
+  /**
+   * The paths that could not be restored when rolling back the applied operations.
+   */
+  notRolledBack: PathEntry[];
+  /**
+   * Where the deleted paths that could not be moved to the trash are kept instead, to remove or restore.
+   */
+  notTrashed: PathEntry[];
+  /**
+   * The id to undo the operations with, `null` when they were not committed.
+   */
+  operationId: string | null;
+}
+
+export interface CommitOptions {
+  /**
+   * An id of your choice to undo the operations with `undo`, defaults to a generated one.
+   */
+  operationId?: string;
+}
+
+export interface HistoryEntry {
+  /**
+   * The id to undo the operations with.
+   */
+  operationId: string;
+  operations: Operation[];
+  /**
+   * When the operations were committed, in milliseconds since the Unix epoch.
+   */
+  committedAt: number;
+}
+
+export interface UndoReport {
+  operationId: string;
+  /**
+   * Whether every operation was reverted.
+   */
+  undone: boolean;
+  /**
+   * The paths that could not be restored, e.g. because another file took their place in the meantime.
+   */
+  notUndone: PathEntry[];
+}
+
+export interface Workspace extends PathEntry {
+  /**
+   * The id to dispose the workspace with.
+   */
+  id: number;
+  /**
+   * The empty directory created for the workspace.
+   */
+  path: string;
+}
+
+export interface FileAssociationOptions {
+  /**
+   * The description of the file type, e.g. `My Document`, defaults to the app name.
+   */
+  description?: string;
+  /**
+   * The MIME type of the files on Linux, defaults to `application/x-<extension>`.
+   */
+  mimeType?: string;
+}
+
+export type StoreMode = "copy" | "link" | "move";
+
+export type LauncherLocation = "menu" | "desktop";
+
+export interface LauncherOptions {
+  /**
+   * The name shown for the launcher, defaults to the name of the file or directory.
+   */
+  name?: string;
+  /**
+   * The icon of the launcher, an `.ico` file on Windows, an image file or an icon theme name on Linux.
+   */
+  icon?: string;
+  /**
+   * Where the launcher is created, `menu` or `desktop`, defaults to `menu`.
+   */
+  location?: LauncherLocation;
+}
+
+export interface StoreCasOptions {
+  /**
+   * How the file is put into the store, defaults to `copy`.
+   */
+  mode?: StoreMode;
+}
+
+export interface StoredObject extends PathEntry {
+  /**
+   * The SHA-256 digest of the content, in lowercase hex.
+   */
+  digest: string;
+  /**
+   * The path of the content inside the store.
+   */
+  path: string;
+  /**
+   * Whether the store already had the content, nothing was written then.
+   */
+  deduplicated: boolean;
+}
+
+export type ExtentKind = "data" | "hole";
+
+export interface Extent {
+  /**
+   * The offset of the range in bytes.
+   */
+  offset: number;
+  /**
+   * The length of the range in bytes.
+   */
+  len: number;
+  /**
+   * Whether the range holds data or is a hole, which reads back as zeros.
+   */
+  kind: ExtentKind;
+}
+
+/**
+ * The checks of `diagnose` that may fail.
+ */
+export type DiagnosisCheck = "metadata" | "links" | "diskUsage" | "read" | "write";
+
+export interface DiagnosisProblem {
+  /**
+   * The check that failed.
+   */
+  check: DiagnosisCheck;
+  error: FsProError;
+}
+
+export interface PathAccess {
+  /**
+   * Whether the file can be opened or the directory listed.
+   */
+  readable: boolean;
+  /**
+   * Whether the path can be written to, probed with a file like `isWritable` does.
+   */
+  writable: boolean;
+  /**
+   * Whether the path is marked read-only, without any write permission on Unix.
+   */
+  readOnly: boolean;
+  /**
+   * The permission bits on Unix, e.g. `0o644`.
+   */
+  mode: number | null;
+  /**
+   * The id of the user owning the path on Unix.
+   */
+  uid: number | null;
+  /**
+   * The id of the group owning the path on Unix.
+   */
+  gid: number | null;
+}
+
+export interface PathScope {
+  /**
+   * Whether the asset protocol scope of the app allows the path, e.g. to show it with `convertFileSrc`, always `false` without the `icon-asset` cargo feature.
+   */
+  assetProtocol: boolean;
+  /**
+   * Whether the commands reject the path as relative, when the plugin is built with `require_absolute_paths`.
+   */
+  rejectedAsRelative: boolean;
+}
+
+export interface Diagnosis extends PathEntry {
+  path: string;
+  /**
+   * Whether the path exists, a broken symbolic link exists as well.
+   */
+  exists: boolean;
+  /**
+   * The metadata of the path, `null` when it could not be read.
+   */
+  metadata: Metadata | null;
+  /**
+   * The targets of the symbolic links followed from the path in order, as they are stored.
+   */
+  links: PathEntry[];
+  /**
+   * The path with every symbolic link, `.` and `..` resolved, `null` when it doesn't exist or a link is broken.
+   */
+  canonicalPath: PathEntry | null;
+  /**
+   * The volume of the path, or of its nearest existing parent.
+   */
+  volume: Volume | null;
+  /**
+   * The size and free space of that volume.
+   */
+  diskUsage: DiskUsage | null;
+  access: PathAccess;
+  scope: PathScope;
+  /**
+   * The checks that failed with their errors, the other fields keep what could be found out.
+   */
+  problems: DiagnosisProblem[];
+}
+
+export const COMMAND = {
+  IS_EXIST: "plugin:fs-pro|is_exist",
+  IS_FILE: "plugin:fs-pro|is_file",
+  IS_DIR: "plugin:fs-pro|is_dir",
+  SIZE: "plugin:fs-pro|size",
+  NAME: "plugin:fs-pro|name",
+  EXTNAME: "plugin:fs-pro|extname",
+  FULL_NAME: "plugin:fs-pro|full_name",
+  PARENT_NAME: "plugin:fs-pro|parent_name",
+  GET_DEFAULT_SAVE_ICON_PATH: "plugin:fs-pro|get_default_save_icon_path",
+  ICON: "plugin:fs-pro|icon",
+  METADATA: "plugin:fs-pro|metadata",
+  COMPRESS: "plugin:fs-pro|compress",
+  DECOMPRESS: "plugin:fs-pro|decompress",
+  TRANSFER: "plugin:fs-pro|transfer",
+  BACKUP: "plugin:fs-pro|backup",
+  INDEX_BUILD: "plugin:fs-pro|index_build",
+  INDEX_QUERY: "plugin:fs-pro|index_query",
+  INDEX_STATUS: "plugin:fs-pro|index_status",
+  STAT_MANY: "plugin:fs-pro|stat_many",
+  PARSE_PATH: "plugin:fs-pro|parse_path",
+  SIZE_REPORT: "plugin:fs-pro|size_report",
+  ICON_FOR_EXTENSION: "plugin:fs-pro|icon_for_extension",
+  WATCH_FILE: "plugin:fs-pro|watch_file",
+  UNWATCH_FILE: "plugin:fs-pro|unwatch_file",
+  RECENT_CHANGES: "plugin:fs-pro|recent_changes",
+  LARGEST: "plugin:fs-pro|largest",
+  TYPE_STATS: "plugin:fs-pro|type_stats",
+  COMPRESS_MANIFEST: "plugin:fs-pro|compress_manifest",
+  ICON_ASSET: "plugin:fs-pro|icon_asset",
+  CLONE_METADATA: "plugin:fs-pro|clone_metadata",
+  ORGANIZE: "plugin:fs-pro|organize",
+  IMPORT_FILES: "plugin:fs-pro|import_files",
+  READ_DIR_STREAM: "plugin:fs-pro|read_dir_stream",
+  EXE_INFO: "plugin:fs-pro|exe_info",
+  RESOLVE_DROPPED: "plugin:fs-pro|resolve_dropped",
+  REMOVE: "plugin:fs-pro|remove",
+  COPY: "plugin:fs-pro|copy",
+  PLAN_OPERATIONS: "plugin:fs-pro|plan_operations",
+  COMMIT_OPERATIONS: "plugin:fs-pro|commit_operations",
+  CANCEL_OPERATION: "plugin:fs-pro|cancel_operation",
+  UNDO: "plugin:fs-pro|undo",
+  UNDO_HISTORY: "plugin:fs-pro|undo_history",
+  AUTO_SYNC_START: "plugin:fs-pro|auto_sync_start",
+  AUTO_SYNC_STOP: "plugin:fs-pro|auto_sync_stop",
+  BIDIRECTIONAL_SYNC: "plugin:fs-pro|bidirectional_sync",
+  ARCHIVE_ENTRIES: "plugin:fs-pro|archive_entries",
+  VOLUMES: "plugin:fs-pro|volumes",
+  BENCHMARK: "plugin:fs-pro|benchmark",
+  COPY_FILE: "plugin:fs-pro|copy_file",
+  DISK_USAGE: "plugin:fs-pro|disk_usage",
+  IS_WRITABLE: "plugin:fs-pro|is_writable",
+  COMPRESS_MANY: "plugin:fs-pro|compress_many",
+  WORKSPACE_CREATE: "plugin:fs-pro|workspace_create",
+  WORKSPACE_DISPOSE: "plugin:fs-pro|workspace_dispose",
+  CONVERT_ARCHIVE: "plugin:fs-pro|convert_archive",
+  STORE_CAS: "plugin:fs-pro|store_cas",
+  RETRIEVE_CAS: "plugin:fs-pro|retrieve_cas",
+  REGISTER_FILE_ASSOCIATION: "plugin:fs-pro|register_file_association",
+  UNREGISTER_FILE_ASSOCIATION: "plugin:fs-pro|unregister_file_association",
+  CREATE_LAUNCHER: "plugin:fs-pro|create_launcher",
+  PUNCH_HOLE: "plugin:fs-pro|punch_hole",
+  EXTENTS: "plugin:fs-pro|extents",
+  CHANGE_TOKEN: "plugin:fs-pro|change_token",
+  HAS_CHANGED: "plugin:fs-pro|has_changed",
+  GZIP: "plugin:fs-pro|gzip",
+  GUNZIP: "plugin:fs-pro|gunzip",
+  CWD: "plugin:fs-pro|cwd",
+  RESOLVE_APP_PATH: "plugin:fs-pro|resolve_app_path",
+  DIAGNOSE: "plugin:fs-pro|diagnose",
+  WATCH_VOLUMES: "plugin:fs-pro|watch_volumes",
+  UNWATCH_VOLUMES: "plugin:fs-pro|unwatch_volumes",
+};
+
+/**
+ * Check if a path exists.
+ *
+ * @param path Specify the path.
+ *
+ * @example
+ * ```
+ * import { isExist } from "tauri-plugin-fs-pro-api"
+ *
+ * const exists = await isExist("/path/to/file.txt")
+ * console.log(exists) // true
+ * ```
+ */
+export const isExist = (path: PathInput) => {
+  return invoke<boolean>(COMMAND.IS_EXIST, {
+    path,
+  });
+};
+
+/**
+ * Check if a path is a file.
+ *
+ * @param path Specify the path.
+ *
+ * @example
+ * ```
+ * import { isFile } from "tauri-plugin-fs-pro-api"
+ *
+ * const isFile = await isFile("/path/to/file.txt")
+ * console.log(isFile) // true
+ * ```
+ */
+export const isFile = (path: PathInput) => {
+  return invoke<boolean>(COMMAND.IS_FILE, {
+    path,
+  });
+};
+
+/**
+ * Check if a path is a directory.
+ *
+ * @param path Specify the path.
+ *
+ * @example
+ * ```
+ * import { isDir } from "tauri-plugin-fs-pro-api"
+ *
+ * const isDir = await isDir("/path/to/dir")
+ * console.log(isDir) // true
+ * ```
+ */
+export const isDir = (path: PathInput) => {
+  return invoke<boolean>(COMMAND.IS_DIR, {
+    path,
+  });
+};
+
+/**
+ * Check the existence and type of many paths in one call.
+ *
+ * @param paths Specify the paths.
+ *
+ * @example
+ * ```
+ * import { statMany } from "tauri-plugin-fs-pro-api"
+ *
+ * const stats = await statMany(["/path/to/file.txt", "/path/to/dir"])
+ * console.log(stats) // [{ exists: true, isFile: true, isDir: false }, ...]
+ * ```
+ */
+export const statMany = (paths: PathInput[]) => {
+  return invoke<PathStat[]>(COMMAND.STAT_MANY, {
+    paths,
+  });
+};
+
+/**
+ * Parse a path into structured components using the path rules of the current platform.
+ *
+ * @param path Specify the path.
+ *
+ * @example
+ * ```
+ * import { parsePath } from "tauri-plugin-fs-pro-api"
+ *
+ * const parsed = await parsePath("\\\\server\\share\\docs\\file.txt")
+ * console.log(parsed) // { host: "server", share: "share", directories: ["docs"], ... }
+ * ```
+ */
+export const parsePath = (path: PathInput) => {
+  return invoke<ParsedPath>(COMMAND.PARSE_PATH, {
+    path,
+  });
+};
+
+/**
+ * Read the entries of a directory and receive them in batches as they are read, instead of all at once.
+ *
+ * @param path Specify the path of the directory.
+ * @param callback Called with each batch of entries.
+ * @param options.batchSize The number of entries sent per message, defaults to `100`. A batch is sent early rather than growing past the buffer limit set in the plugin builder.
+ * @param options.minIntervalMs The minimum time between two messages in milliseconds, no limit by default or the interval set in the plugin builder. In the meantime a batch grows past the batch size, or the listing waits when it reached the buffer limit. The last batch is sent as soon as the directory is read.
+ *
+ * @returns The total number of entries, once all of them were received.
+ *
+ * @example
+ * ```
+ * import { readDirStream } from "tauri-plugin-fs-pro-api"
+ *
+ * const count = await readDirStream("/path/to/dir", (entries) => {
+ *   console.log(entries)
+ * })
+ * ```
+ */
+export const readDirStream = (
+  path: PathInput,
+  callback: (entries: DirEntry[]) => void,
+  options?: ReadDirStreamOptions
+) => {
+  const onEntries = new Channel<DirEntry[]>();
+  onEntries.onmessage = callback;
+
+  return invoke<number>(COMMAND.READ_DIR_STREAM, {
+    path,
+    onEntries,
+    options,
+  });
+};
+
+/**
+ * Turn the paths or `file://` URIs of a drag and drop event into existing, canonical paths with basic metadata.
+ *
+ * Symbolic links and Windows shortcuts (`.lnk`) are resolved to their targets. Paths that don't exist are left out and duplicates are removed.
+ *
+ * @param paths Specify the dropped paths or `file://` URIs.
+ *
+ * @example
+ * ```
+ * import { getCurrentWebview } from "@tauri-apps/api/webview"
+ * import { resolveDropped } from "tauri-plugin-fs-pro-api"
+ *
+ * await getCurrentWebview().onDragDropEvent(async ({ payload }) => {
+ *   if (payload.type !== "drop") return
+ *
+ *   const dropped = await resolveDropped(payload.paths)
+ *   console.log(dropped)
+ * })
+ * ```
+ */
+export const resolveDropped = (paths: string[]) => {
+  return invoke<DroppedPath[]>(COMMAND.RESOLVE_DROPPED, {
+    paths,
+  });
+};
+
+/**
+ * Get the size of the path, or 0 if it does not exist.
+ *
+ * Symbolic links are not followed, the path itself included, and count for their own size. Paths that can't be read are left out, `sizeReport` lists them.
+ *
+ * @param path Specify the path.
+ * @param options.skip The preset sets of names to skip: `vcs`, `dependencies` and `osJunk`.
+ * @param options.excludes The glob patterns to skip, matched against the path relative to `path`, e.g. `*.log`.
+ * @param options.treatBundlesAsFiles Treat macOS bundles such as `.app` as single files, nothing inside them is skipped, defaults to `false`.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`.
+ *
+ * @example
+ * ```
+ * import { size } from "tauri-plugin-fs-pro-api"
+ *
+ * const size = await size("/path/to/file.txt")
+ * console.log(size) // 1024
+ *
+ * const documentSize = await size("/path/to/project", { skip: ["vcs", "dependencies"] })
+ * ```
+ */
+export const size = (path: PathInput, options?: SizeOptions) => {
+  return invoke<number>(COMMAND.SIZE, {
+    path,
+    options,
+  });
+};
+
+/**
+ * Get the size of the path as `size` does, along with the paths that could not be read because of missing permissions.
+ *
+ * @param path Specify the path.
+ * @param options The same options as `size`.
+ *
+ * @example
+ * ```
+ * import { sizeReport } from "tauri-plugin-fs-pro-api"
+ *
+ * const { size, inaccessible } = await sizeReport("/path/to/dir")
+ * console.log(size, inaccessible)
+ * ```
+ */
+export const sizeReport = (path: PathInput, options?: SizeOptions) => {
+  return invoke<SizeReport>(COMMAND.SIZE_REPORT, {
+    path,
+    options,
+  });
+};
+
+/**
+ * Get the files in a directory, recursively, that were modified after a point in time, most recently modified first.
+ *
+ * @param dir Specify the directory.
+ * @param options.since Only return files modified after this time, in milliseconds since the Unix epoch, defaults to `0`.
+ * @param options.limit The maximum number of files to return, defaults to `100`.
+ *
+ * @example
+ * ```
+ * import { recentChanges } from "tauri-plugin-fs-pro-api"
+ *
+ * const changes = await recentChanges("/path/to/dir", { since: Date.now() - 86400000, limit: 20 })
+ * console.log(changes)
+ * ```
+ */
+export const recentChanges = (dir: PathInput, options?: RecentChangesOptions) => {
+  return invoke<RecentChange[]>(COMMAND.RECENT_CHANGES, {
+    dir,
+    options,
+  });
+};
+
+/**
+ * Get the largest files and directories inside a directory, largest first, in a single walk.
+ *
+ * @param dir Specify the directory.
+ * @param options.count The maximum number of files and directories to return, defaults to `10`.
+ * @param options.minSize Only return files and directories of at least this size in bytes, defaults to `0`.
+ * @param options.treatBundlesAsFiles Treat macOS bundles such as `.app` as single files, nothing inside them is returned, defaults to `false`.
+ *
+ * @example
+ * ```
+ * import { largest } from "tauri-plugin-fs-pro-api"
+ *
+ * const entries = await largest("/path/to/dir", { count: 20, minSize: 1024 * 1024 })
+ * console.log(entries)
+ * ```
+ */
+export const largest = (dir: PathInput, options?: LargestOptions) => {
+  return invoke<LargestEntry[]>(COMMAND.LARGEST, {
+    dir,
+    options,
+  });
+};
+
+/**
+ * Get the number and total size of the files in a directory, recursively, grouped by category and by extension name.
+ *
+ * @param dir Specify the directory.
+ * @param options.treatBundlesAsFiles Treat macOS bundles such as `.app` as single files, counted once with the size of everything inside, defaults to `false`.
+ *
+ * @example
+ * ```
+ * import { typeStats } from "tauri-plugin-fs-pro-api"
+ *
+ * const { categories, extensions } = await typeStats("/path/to/dir")
+ * console.log(categories.image?.size)
+ * ```
+ */
+export const typeStats = (dir: PathInput, options?: TypeStatsOptions) => {
+  return invoke<TypeStats>(COMMAND.TYPE_STATS, {
+    dir,
+    options,
+  });
+};
+
+/**
+ * Get the name of the path.
+ *
+ * @param path Specify the path.
+ *
+ * @example
+ * ```
+ * import { name } from "tauri-plugin-fs-pro-api"
+ *
+ * const name = await name("/path/to/file.txt")
+ * console.log(name) // file
+ * ```
+ */
+export const name = (path: PathInput) => {
+  return invoke<string>(COMMAND.NAME, {
+    path,
+  });
+};
+
+/**
+ * Get the extension name of the path.
+ *
+ * @param path Specify the path.
+ *
+ * @example
+ * ```
+ * import { extname } from "tauri-plugin-fs-pro-api"
+ *
+ * const extname = await extname("/path/to/file.txt")
+ * console.log(extname) // txt
+ * ```
+ */
+export const extname = (path: PathInput) => {
+  return invoke<string>(COMMAND.EXTNAME, {
+    path,
+  });
+};
+
+/**
+ * Get the full name of a file or directory including extension.
+ *
+ * @param path Specify the path.
+ *
+ * @example
+ * ```
+ * import { fullName } from "tauri-plugin-fs-pro-api"
+ *
+ * const fullName = await fullName("/path/to/file.txt")
+ * console.log(fullName) // file.txt
+ * ```
+ */
+export const fullName = (path: PathInput) => {
+  return invoke<string>(COMMAND.FULL_NAME, {
+    path,
+  });
+};
+
+/**
+ * Get the parent name of the path.
+ *
+ * @param path Specify the path.
+ * @param level Specify the level of the parent directory, defaults to `1`.
+ *
+ * @example
+ * ```
+ * import { parentName } from "tauri-plugin-fs-pro-api"
+ *
+ * const parentName = await parentName("/path/to/file.txt")
+ * console.log(parentName) // to
+ * ```
+ */
+export const parentName = (path: PathInput, level = 1) => {
+  return invoke<string>(COMMAND.PARENT_NAME, {
+    path,
+    level,
+  });
+};
+
+/**
+ * Get the default save icon path.
+ *
+ * @example
+ * ```
+ * import { getDefaultSaveIconPath } from "tauri-plugin-fs-pro-api"
+ *
+ * const savePath = await getDefaultSaveIconPath()
+ * console.log(savePath)
+ * ```
+ */
+export const getDefaultSaveIconPath = () => {
+  return invoke<string>(COMMAND.GET_DEFAULT_SAVE_ICON_PATH);
+};
+
+/**
+ * Get the icon of the path.
+ *
+ * @param path Specify the path.
+ * @param options.size Specify the size of the icon, defaults to `32`.
+ * @param options.savePath Specify the path to save the icon, defaults to the default save path.
+ * @param options.hiDpi Multiply the size by the highest scale factor of the connected monitors, e.g. a `64` pixels icon for a size of `32` on a 200% display, so it isn't blurry on high DPI displays, defaults to `false`. Scaled icons are saved apart from the others.
+ * @param options.scaleFactor Multiply the size by this scale factor instead, e.g. of the window showing the icon.
+ *
+ * @example
+ * ```
+ * import { getCurrentWindow } from "@tauri-apps/api/window"
+ * import { icon } from "tauri-plugin-fs-pro-api"
+ *
+ * const iconPath = await icon("/path/to/file.txt")
+ * console.log(iconPath)
+ *
+ * const scaleFactor = await getCurrentWindow().scaleFactor()
+ * const sharpPath = await icon("/path/to/photo.jpg", { size: 32, scaleFactor })
+ * ```
+ */
+export const icon = (path: PathInput, options: IconOptions) => {
+  return invoke<string>(COMMAND.ICON, {
+    path,
+    options,
+  });
+};
+
+/**
+ * Get the icon of the path along with its asset protocol URL, the icon is added to the asset protocol scope.
+ *
+ * Needs the `icon-asset` cargo feature, which turns on the asset protocol of Tauri, the command is not registered without it. The CSP of the app must allow the asset protocol for images, e.g. `img-src 'self' asset: http://asset.localhost`.
+ *
+ * @param path Specify the path.
+ * @param options.size Specify the size of the icon, defaults to `32`.
+ * @param options.savePath Specify the path to save the icon, defaults to the default save path.
+ * @param options.hiDpi Multiply the size by the scale factor of the current window, see `icon`.
+ * @param options.scaleFactor Multiply the size by this scale factor instead.
+ *
+ * @example
+ * ```
+ * import { iconAsset } from "tauri-plugin-fs-pro-api"
+ *
+ * const { url } = await iconAsset("/path/to/file.txt")
+ * document.querySelector("img").src = url
+ *
+ * const { url: sharpUrl } = await iconAsset("/path/to/photo.jpg", { size: 32, hiDpi: true })
+ * ```
+ */
+export const iconAsset = (path: PathInput, options?: IconOptions) => {
+  return invoke<IconAsset>(COMMAND.ICON_ASSET, {
+    path,
+    options,
+  });
+};
+
+/**
+ * Get the system icon associated with an extension name, without requiring an existing file.
+ *
+ * @param extname Specify the extension name, with or without the leading dot, case-insensitive.
+ * @param options.size Specify the size of the icon, defaults to `32`.
+ * @param options.savePath Specify the path to save the icon, defaults to the default save path.
+ * @param options.hiDpi Multiply the size by the highest scale factor of the connected monitors, see `icon`.
+ * @param options.scaleFactor Multiply the size by this scale factor instead.
+ *
+ * @example
+ * ```
+ * import { iconForExtension } from "tauri-plugin-fs-pro-api"
+ *
+ * const iconPath = await iconForExtension("pdf")
+ * console.log(iconPath)
+ * ```
+ */
+export const iconForExtension = (extname: string, options?: IconOptions) => {
+  return invoke<string>(COMMAND.ICON_FOR_EXTENSION, {
+    extname,
+    options,
+  });
+};
+
+/**
+ * Get the product name, version, company and description of an executable, from the version resources on Windows and the `Info.plist` of app bundles on macOS.
+ *
+ * Fields that are missing, and all fields on other platforms, are `null`.
+ *
+ * @param path Specify the path of the executable or app bundle.
+ *
+ * @example
+ * ```
+ * import { exeInfo } from "tauri-plugin-fs-pro-api"
+ *
+ * const { productName, productVersion } = await exeInfo("/Applications/Safari.app")
+ * console.log(productName, productVersion)
+ * ```
+ */
+export const exeInfo = (path: PathInput) => {
+  return invoke<ExeInfo>(COMMAND.EXE_INFO, {
+    path,
+  });
+};
+
+/**
+ * Get the metadata of the path.
+ *
+ * @param path Specify the path.
+ * @param options.omitSize When getting the metadata of a path, if you don't need to calculate the size, you can omit it to save time and return 0 after omitting it,
+ * defaults to `false`.
+ * @param options.deferSize Return immediately with a `0` size and a `sizeId`, then emit the `fs-pro://size` event with the same id once the size is calculated, defaults to `false`.
+ *
+ * @example
+ * ```
+ * import { metadata } from "tauri-plugin-fs-pro-api"
+ *
+ * const metadata = await metadata("/path/to/file.txt")
+ * console.log(metadata)
+ * ```
+ */
+export const metadata = (path: PathInput, options?: MetadataOptions) => {
+  return invoke<Metadata>(COMMAND.METADATA, {
+    path,
+    options,
+  });
+};
+
+/**
+ * Listen for sizes calculated in the background by `metadata` with `deferSize`. Listen before calling `metadata`, the event may arrive before it returns.
+ *
+ * @param callback Called with the id returned as `sizeId`, the path and its size.
+ *
+ * @example
+ * ```
+ * import { metadata, onSize } from "tauri-plugin-fs-pro-api"
+ *
+ * const sizes = new Map<number, number>()
+ * const unlisten = await onSize(({ id, size }) => sizes.set(id, size))
+ * const { sizeId } = await metadata("/path/to/dir", { deferSize: true })
+ * ```
+ */
+export const onSize = (callback: (payload: SizeEvent) => void) => {
+  return listen<SizeEvent>("fs-pro://size", (event) => callback(event.payload));
+};
+
+/**
+ * Listen for the notifications asked for with `options.notify` that could not be shown, such as when `notify-send` is missing on Linux.
+ *
+ * @param callback Called with the title of the notification and the reason.
+ *
+ * @example
+ * ```
+ * import { onNotificationError } from "tauri-plugin-fs-pro-api"
+ *
+ * const unlisten = await onNotificationError(({ title, message }) => console.warn(title, message))
+ * ```
+ */
+export const onNotificationError = (callback: (payload: NotificationError) => void) => {
+  return listen<NotificationError>("fs-pro://notification-error", (event) => callback(event.payload));
+};
+
+/**
+ * Compress the source path into a tar.gz, tar.zst, tar.xz, tar.bz2 or zip file to the destination path.
+ *
+ * The contents of a source directory are stored at the root of the archive, a source file is stored under its name and the filter options don't apply to it. Directories are stored as explicit entries, so empty ones survive a round trip through `decompress`, but with `options.includes` or the size and time options only those of the entries kept are, unless `options.includeEmptyDirs` is set. The entries are stored sorted by name.
+ *
+ * @param srcPath Specify the source path, a directory or a single file.
+ * @param dstPath Specify the destination path.
+ * @param options.includes The glob patterns of the paths to be compressed, matched against the path relative to the source path, e.g. `src`, `src/config/settings.json` or `**/*.rs`, with or without a leading `./`. Directories are searched for matching entries.
+ * @param options.excludes The glob patterns of the paths not to be compressed, matched against the path relative to the source path, e.g. `*.log` or `target`.
+ * @param options.ignore Gitignore-style rules of the paths not to be compressed, matched relative to the source path, either `{ path }` of a file such as `.exportignore` or `{ contents }`.
+ * @param options.minSize Only compress files of at least this many bytes.
+ * @param options.maxSize Only compress files of at most this many bytes.
+ * @param options.modifiedAfter Only compress files modified after this time, in milliseconds since the Unix epoch.
+ * @param options.modifiedBefore Only compress files modified before this time, in milliseconds since the Unix epoch. With any of the size and time options, directories are only stored for the files compressed in them.
+ * @param options.includeEmptyDirs Store every directory that isn't excluded, even when `options.includes` or the size and time options keep nothing in it, defaults to `false`.
+ * @param options.overwrite Whether to overwrite an existing destination file, rejects instead when `false`, defaults to `true`. The archive is written next to it and only replaces it once complete, so a failed compression leaves it intact.
+ * @param options.appendTimestamp Append the current timestamp in milliseconds to the destination file name, defaults to `false`.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`, the partial archive is removed.
+ * @param options.format The format of the archive, `tar.gz`, `tar.zst`, `tar.xz` with the `xz` cargo feature, `tar.bz2` with the `bzip2` cargo feature or `zip`, defaults to `tar.gz`.
+ * @param options.level The compression level, from `0` to `9` defaulting to `6` for `tar.gz`, `zip` and `tar.xz`, from `1` to `22` defaulting to `3` for `tar.zst` and from `1` to `9` defaulting to `6` for `tar.bz2`. Higher levels are smaller but slower, `0` stores without compressing. Rejects with an `invalidCompressionLevel` error when out of range.
+ * @param options.append Add the entries to an existing archive at the destination path instead of replacing it, in its own format detected from its contents, defaults to `false`. The archive is rewritten next to it and replaced once complete. An entry already in a zip file can't be added again, a tar file keeps both and the later one wins when extracting.
+ * @param options.volumeSize Split the archive into volumes of this many bytes, e.g. for services with an upload limit, named after the destination path with `.001`, `.002` and so on. `decompress` reads the set back from its first volume. A split archive is always written anew, `append` doesn't apply to it.
+ * @param options.deterministic Leave out modified times, owners and groups so identical trees give byte-identical archives, e.g. to hash them for cache keys, defaults to `false`. Tar entries get a fixed modified time and the permissions `0o755` or `0o644`, zip entries the earliest time zip files can store.
+ * @param options.preserveSymlinks Store the symbolic links inside the source directories as links instead of the files and directories they point to, defaults to `false`. A link is stored with its target as it is, relative or absolute. Tar entries always carry the permissions, owners and groups of the files, zip entries their permissions.
+ * @param options.writeManifest Write a JSON manifest next to the archive, named after it with `.manifest.json`, e.g. for an updater to compare archives without opening them, defaults to `false`. It's read back from the written archive and is an `ArchiveManifest` of the entries in order with their SHA-256 hashes.
+ * @param options.dryRun Only plan the compression without writing anything, defaults to `false`. The report holds the `plan` instead, with the `items` that would be stored, each with its `srcPath`, its `dstPath` inside the archive and its `size`, the `totalBytes` of their files before compression and the `collisions`: the destination file that would be replaced, or the entries of the archive appended to that would be stored again.
+ * @param options.notify Show a system notification once the archive is written or failed, see `NotificationOptions`. The texts are templates of `{name}`, `{srcPath}`, `{dstPath}`, `{error}` and the fields of the report, arrays by their length, e.g. `Exported {name} to {path}`.
+ *
+ * @returns The path of the written archive, or of its first volume along with the paths of all volumes when split, the paths left out because of missing permissions, the path of the manifest when written and the plan of a dry run.
+ *
+ * @example
+ * ```
+ * import { compress } from "tauri-plugin-fs-pro-api"
+ *
+ * await compress("/path/to/source.txt", "/path/to/destination.tar.gz")
+ *
+ * await compress("/path/to/source", "/path/to/destination.zip", { format: "zip" })
+ *
+ * await compress("/path/to/assets", "/path/to/assets.tar.zst", { format: "tar.zst", level: 19 })
+ *
+ * await compress("/path/to/today", "/path/to/backup.tar.gz", { append: true })
+ *
+ * await compress("/path/to/videos", "/path/to/videos.tar.gz", { volumeSize: 100 * 1024 * 1024 })
+ *
+ * await compress("/path/to/logs", "/path/to/old-logs.tar.gz", {
+ *   includes: ["**/*.log"],
+ *   modifiedBefore: Date.now() - 30 * 24 * 60 * 60 * 1000,
+ * })
+ *
+ * const { manifestPath } = await compress("/path/to/app", "/path/to/app-1.2.0.tar.zst", {
+ *   format: "tar.zst",
+ *   writeManifest: true,
+ * })
+ *
+ * const { plan } = await compress("/path/to/photos", "/path/to/photos.zip", { format: "zip", dryRun: true })
+ *
+ * await compress("/path/to/project", "/path/to/export.zip", {
+ *   format: "zip",
+ *   notify: { title: "Export finished", body: "{name} is ready", failureBody: "Export failed: {error}" },
+ * })
+ * ```
+ */
+export const compress = (
+  srcPath: PathInput,
+  dstPath: PathInput,
+  options?: CompressOptions
+) => {
+  return invoke<CompressReport>(COMMAND.COMPRESS, {
+    srcPath,
+    dstPath,
+    options,
+  });
+};
+
+/**
+ * Compress several files and directories from different locations into one tar.gz, tar.zst, tar.xz, tar.bz2 or zip file.
+ *
+ * Each source is stored under its name at the root of the archive, or under the name given in `options.names`, e.g. a config file and a data directory next to each other. Takes the same options as `compress`, the filter options are matched relative to each source directory.
+ *
+ * @param srcPaths Specify the source paths, directories or files.
+ * @param dstPath Specify the destination path.
+ * @param options.names The paths of the sources inside the archive keyed by source path, e.g. `backup/config.json`, defaults to the name of each source.
+ * @param options.notify Show a system notification once finished, see `compress`.
+ *
+ * @returns The path of the written archive, the paths left out because of missing permissions, the path of the manifest when written and the plan of a dry run. Rejects with an `alreadyExists` error when two sources have the same name inside the archive.
+ *
+ * @example
+ * ```
+ * import { compressMany } from "tauri-plugin-fs-pro-api"
+ *
+ * await compressMany(["/etc/app/config.json", "/var/lib/app/data"], "/path/to/backup.zip", {
+ *   format: "zip",
+ *   names: { "/etc/app/config.json": "settings/config.json" },
+ * })
+ * ```
+ */
+export const compressMany = (
+  srcPaths: PathInput[],
+  dstPath: PathInput,
+  options?: CompressManyOptions
+) => {
+  return invoke<CompressReport>(COMMAND.COMPRESS_MANY, {
+    srcPaths,
+    dstPath,
+    options,
+  });
+};
+
+/**
+ * Compress an explicit list of files and directories into a tar.gz, tar.zst, tar.xz, tar.bz2 or zip file, without walking any directory.
+ *
+ * Directories are added as empty entries, list their contents in the manifest to include them.
+ *
+ * @param manifest Specify the path of a JSON file containing the entries, or the entries themselves.
+ * @param dstPath Specify the destination path.
+ * @param options.overwrite Whether to overwrite an existing destination file, rejects instead when `false`, defaults to `true`.
+ * @param options.appendTimestamp Append the current timestamp in milliseconds to the destination file name, defaults to `false`.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`, the partial archive is removed.
+ * @param options.format The format of the archive, see `compress`, defaults to `tar.gz`.
+ * @param options.level The compression level, within the levels of the format, see `compress`. Rejects with an `invalidCompressionLevel` error when out of range.
+ *
+ * @returns The path of the written archive and the paths left out because of missing permissions.
+ *
+ * @example
+ * ```
+ * import { compressManifest } from "tauri-plugin-fs-pro-api"
+ *
+ * await compressManifest(
+ *   [{ src: "/path/to/build/app.js", archivePath: "dist/app.js" }],
+ *   "/path/to/destination.tar.gz"
+ * )
+ * ```
+ */
+export const compressManifest = (
+  manifest: string | ManifestEntry[],
+  dstPath: PathInput,
+  options?: CompressManifestOptions
+) => {
+  return invoke<CompressReport>(COMMAND.COMPRESS_MANIFEST, {
+    manifest,
+    dstPath,
+    options,
+  });
+};
+
+/**
+ * Decompress the archive from the source path to the destination path.
+ *
+ * The format is detected from the contents rather than the extension: a tar file, compressed with gzip, zstd, xz or bzip2 or not at all, or a zip file. Rejects with an `unsupportedArchive` error for other files, and with a `notWritable` error before extracting anything when the destination is on read-only media.
+ * tar.xz files need the `xz` cargo feature and tar.bz2 files the `bzip2` cargo feature, both off by default.
+ *
+ * An archive split into volumes is reassembled while reading, given by its first volume such as `backup.tar.gz.001`, or by its name without the number.
+ *
+ * Directory entries are recreated even when they are empty, and symbolic links as links on Linux and macOS.
+ *
+ * Entries leading outside the destination path reject with an `invalidPath` error, protecting against crafted archives ("zip slip"): absolute paths, paths through `..` or through a link extracted before, and links pointing outside. The extraction stops at the first one, the entries extracted before it are kept.
+ *
+ * With `onProgress`, the entries to extract are counted first to report the progress against, reading a compressed tar file through an extra time, a zip file only has its central directory read. The progress is updated as each entry starts, by the entries and their uncompressed size.
+ *
+ * @param srcPath Specify the source path.
+ * @param dstPath Specify the destination path.
+ * @param options.entries Glob patterns of the entries to extract, matched against their path inside the archive as listed by `archiveEntries` without a leading `./`, e.g. `assets/*.png`. The entries inside a matched directory are extracted too. Extracts all entries when omitted.
+ * @param options.backupToTrash Move existing destination files to the system trash before overwriting them, defaults to `false`.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`, the entries extracted so far are kept.
+ * @param options.preservePermissions Restore the setuid, setgid and sticky bits of tar entries too, defaults to `false`. The other permission bits are always restored, zip entries only carry those.
+ * @param options.preserveOwnership Restore the owners and groups of tar entries, defaults to `false`. Changing them takes the privileges to do so, such as running as root, the extraction fails otherwise.
+ * @param options.allowExternalPaths Extract the entries and links leading outside the destination path as they are instead of refusing them, defaults to `false`. Only for archives from a trusted source, such as backups of the system the app created itself.
+ * @param options.stripComponents The number of leading components to remove from the names of the entries, as `tar --strip-components` does, defaults to `0`. With `1`, `foo-1.2.3/src/lib.rs` is extracted to `src/lib.rs` in the destination path, and the entries with no components left, such as `foo-1.2.3/` itself, are skipped. `options.entries` still matches the full names.
+ * @param options.onConflict What happens to a file that exists before the extraction where an entry goes, defaults to `overwrite`. `skip` keeps the file and leaves the entry out, `error` rejects with an `alreadyExists` error, keeping the entries extracted before, and `rename` keeps the file and extracts the entry next to it as `name (1).ext`. Existing directories are merged into either way.
+ * @param options.resume Continue an extraction into the same destination that failed part way, such as when the disk filled up or the app quit, defaults to `false`. The files it completed are left as they are rather than extracted again: files of the same size and modified time for tar entries, of the same size and CRC-32 for zip entries. A compressed tar file is still read through up to where it failed, a zip file is not. The other existing files are handled after `options.onConflict`.
+ * @param options.onProgressIntervalMs The minimum time between two progress updates in milliseconds, defaults to `100` or the interval set in the plugin builder.
+ * @param options.notify Show a system notification once finished, see `compress`.
+ * @param options.encoding How the names of zip entries not flagged as UTF-8 are decoded, defaults to `auto`. Zip files made by Windows or older tools store them in the code page of the system, `auto` detects GBK, Shift-JIS, Big5, EUC-KR and EUC-JP from the names and decodes them as CP437, the encoding of the zip format, otherwise. Give the encoding when the names still come out garbled.
+ * @param onProgress Specify a callback that receives the progress.
+ *
+ * @returns The number of entries written, the size of the files written, the top-level directories in the destination path they were written to, such as `foo-1.2.3` to open afterwards, and the paths of the existing files entries were skipped or renamed for.
+ *
+ * @example
+ * import { decompress } from "tauri-plugin-fs-pro-api"
+ *
+ * await decompress("/path/to/destination.tar.gz", "/path/to/source")
+ *
+ * await decompress("/path/to/videos.tar.gz.001", "/path/to/videos")
+ *
+ * await decompress("/path/to/assets.zip", "/path/to/textures", {
+ *   entries: ["textures/*.png"],
+ * })
+ *
+ * await decompress("/path/to/foo-1.2.3.tar.gz", "/path/to/foo", { stripComponents: 1 })
+ *
+ * const { skipped } = await decompress("/path/to/notes.zip", "/path/to/notes", { onConflict: "skip" })
+ * console.log(skipped) // ["/path/to/notes/todo.md"]
+ *
+ * await decompress("/path/to/backup.tar.zst", "/path/to/restore", { resume: true })
+ *
+ * await decompress("/path/to/报告.zip", "/path/to/reports", { encoding: "gbk" })
+ *
+ * await decompress("/path/to/dataset.zip", "/path/to/dataset", {}, ({ extractedBytes, totalBytes }) => {
+ *   console.log(extractedBytes / totalBytes)
+ * })
+ */
+export const decompress = (
+  srcPath: PathInput,
+  dstPath: PathInput,
+  options?: DecompressOptions,
+  onProgress?: (progress: DecompressProgress) => void
+) => {
+  let channel: Channel<DecompressProgress> | undefined;
+
+  if (onProgress) {
+    channel = new Channel<DecompressProgress>();
+    channel.onmessage = onProgress;
+  }
+
+  return invoke<DecompressReport>(COMMAND.DECOMPRESS, {
+    srcPath,
+    dstPath,
+    options,
+    onProgress: channel,
+  });
+};
+
+/**
+ * List the entries of an archive without extracting it, nothing is written to disk.
+ *
+ * The format is detected from the contents like `decompress` does.
+ *
+ * @param path Specify the archive path.
+ * @param options.encoding How the names of zip entries not flagged as UTF-8 are decoded, defaults to `auto`, as `decompress` does.
+ *
+ * @returns The entries in the order they are stored, with their path inside the archive, uncompressed size, type and modified time. Rejects with an error of kind `unsupportedArchive` when the format is not recognized.
+ *
+ * @example
+ * import { archiveEntries } from "tauri-plugin-fs-pro-api"
+ *
+ * const entries = await archiveEntries("/path/to/archive.zip")
+ *
+ * for (const entry of entries) {
+ *   console.log(entry.path, entry.size, entry.fileType)
+ * }
+ */
+export const archiveEntries = (
+  path: PathInput,
+  options?: ArchiveEntriesOptions
+) => {
+  return invoke<ArchiveEntry[]>(COMMAND.ARCHIVE_ENTRIES, { path, options });
+};
+
+/**
+ * Move the source path to the destination path.
+ *
+ * The contents of a source directory are moved into the destination directory, while a source file is moved into it on its own, e.g. a download into a folder of the user. The filter options only apply inside a source directory.
+ *
+ * Moving to another volume, such as another drive on Windows, copies each item with everything in it before removing it from the source, so an item that fails part way is left whole in the source.
+ *
+ * @param srcPath Specify the source path, a directory or a file.
+ * @param dstPath Specify the destination directory path.
+ * @param options.includes The glob patterns of the paths to be moved, matched against the path relative to the source path, e.g. `src`, `src/config/settings.json` or `**/*.rs`, with or without a leading `./`. Directories are searched for matching entries.
+ * @param options.excludes The glob patterns of the paths not to be moved, matched against the path relative to the source path, e.g. `*.log` or `target`.
+ * @param options.ignore Gitignore-style rules of the paths not to be moved, matched relative to the source path, either `{ path }` of a file such as `.exportignore` or `{ contents }`. Ignored entries stay in the source.
+ * @param options.minSize Only move files of at least this many bytes.
+ * @param options.maxSize Only move files of at most this many bytes.
+ * @param options.modifiedAfter Only move files modified after this time, in milliseconds since the Unix epoch.
+ * @param options.modifiedBefore Only move files modified before this time, in milliseconds since the Unix epoch. The files outside the size and time limits stay in the source.
+ * @param options.backupToTrash Move existing destination files to the system trash before overwriting them, defaults to `false`.
+ * @param options.backup Snapshot existing destination files into numbered backups before overwriting them, see `backup`.
+ * @param options.onConflict What happens to an existing file where an item goes, defaults to `overwrite`. `skip` keeps the file and leaves the item in the source, listed in `skipped` of the report, `error` rejects with an `alreadyExists` error before anything is moved, and `rename` keeps the file and moves the item next to it as `name (1).ext`, listed in `renamed`. Existing directories are merged into either way, with the conflicts resolved file by file inside them. Backups are only made of overwritten files.
+ * @param options.dryRun Only plan the move without touching the source or the destination, defaults to `false`. The includes, excludes and other filters and `options.onConflict` are evaluated as for the move, `skipped` and `renamed` of the report are filled in and `plan` holds the `items` that would be moved, each with its `srcPath`, its `dstPath` and its `size`, their `totalBytes` and the `collisions`, the existing paths where items go. An `error` conflict is listed in the collisions rather than rejected.
+ * @param options.rollback Put everything back when the move fails or is cancelled part way, so the source and the destination are left as they were, defaults to `false`. The items moved so far are moved back, including what reached the destination of the item that failed, the directories created for them are removed and overwritten files are moved aside until every item is moved, then put back. The part of an item listed in `inaccessible` that was moved is put back as well. Rejects with the error of the move, or with a `notRolledBack` error with the number of paths that could not be restored. Backups made with `backup` are kept, while the files moved to the trash with `backupToTrash` are put back.
+ * @param options.preserveTimestamps Give the items that are copied rather than renamed, when moving to another volume or a device, the access and modification times of the originals, down to every entry inside them, defaults to `false`. The creation time is left untouched like with `cloneMetadata`.
+ * @param options.preservePermissions Give them the permissions of the originals as well, the modes on Unix and the read-only attribute on Windows, defaults to `false`. File systems without them, such as FAT, keep their own.
+ * @param options.symlinks How the symbolic links inside the source directory, or a source file that is a link, are handled. `follow` moves a copy of what a link points to in its place and removes the link, a link that is broken or points to a directory containing it is listed in `inaccessible` instead. `preserve` recreates a link with the same target, so a relative link still points to the moved entry next to it. `skip` leaves links in the source like excluded entries. A directory with links in it is recreated in the destination and its entries moved one by one. When unset, a link is moved as it is when the item holding it is renamed and replaced by what it points to when it's copied, such as to another volume. The source path itself and bundles moved as a whole are always taken as they are.
+ * @param options.treatBundlesAsFiles Treat macOS bundles such as `.app` as single files, a bundle is never filtered inside or left half moved and a bundle source is moved into the destination as a whole, defaults to `false`.
+ * @param options.retry.attempts The number of retries of an item locked by another process after the first attempt, only on Windows, defaults to `5`.
+ * @param options.retry.delay The delay before the first retry in milliseconds, doubled for every following retry, defaults to `50`.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`, the item in progress is still moved completely.
+ * @param options.onProgressIntervalMs The minimum time between two progress updates in milliseconds, defaults to `100` or the interval set in the plugin builder. The last update is always sent.
+ * @param options.notify Show a system notification once finished, see `compress`.
+ * @param onProgress Specify a callback that receives the progress, at most every `options.onProgressIntervalMs`.
+ *
+ * @example
+ * import { transfer } from "tauri-plugin-fs-pro-api"
+ *
+ * await transfer("/path/to/source", "/path/to/destination", {}, ({ copiedBytes, totalBytes }) => {
+ *   console.log(copiedBytes / totalBytes)
+ * })
+ *
+ * await transfer("/Users/xxx/Downloads/report.pdf", "/Users/xxx/Documents")
+ *
+ * const { renamed } = await transfer("/path/to/import", "/path/to/library", { onConflict: "rename" })
+ *
+ * const { plan } = await transfer("/path/to/import", "/path/to/library", { excludes: ["*.tmp"], dryRun: true })
+ * console.log(plan?.totalBytes, plan?.collisions)
+ *
+ * await transfer("/path/to/project", "/Volumes/Backup/project", { rollback: true })
+ *
+ * await transfer("/path/to/photos", "/media/usb/photos", { preserveTimestamps: true, preservePermissions: true })
+ *
+ * await transfer("/path/to/node-project", "/Volumes/External/projects", { symlinks: "preserve" })
+ */
+export const transfer = (
+  srcPath: PathInput,
+  dstPath: PathInput,
+  options?: TransferOptions,
+  onProgress?: (progress: TransferProgress) => void
+) => {
+  let channel: Channel<TransferProgress> | undefined;
+
+  if (onProgress) {
+    channel = new Channel<TransferProgress>();
+    channel.onmessage = onProgress;
+  }
+
+  return invoke<TransferReport>(COMMAND.TRANSFER, {
+    srcPath,
+    dstPath,
+    options,
+    onProgress: channel,
+  });
+};
+
+/**
+ * Copy the source path to the destination path, leaving the source untouched.
+ *
+ * Takes the same options as `transfer`.
+ *
+ * @param srcPath Specify the source path.
+ * @param dstPath Specify the destination path.
+ * @param options.includes The glob patterns of the paths to be copied, matched against the path relative to the source path, e.g. `src`, `src/config/settings.json` or `**/*.rs`, with or without a leading `./`. Directories are searched for matching entries.
+ * @param options.excludes The glob patterns of the paths not to be copied, matched against the path relative to the source path, e.g. `*.log` or `target`.
+ * @param options.ignore Gitignore-style rules of the paths not to be copied, matched relative to the source path, either `{ path }` of a file such as `.exportignore` or `{ contents }`.
+ * @param options.minSize Only copy files of at least this many bytes.
+ * @param options.maxSize Only copy files of at most this many bytes.
+ * @param options.modifiedAfter Only copy files modified after this time, in milliseconds since the Unix epoch.
+ * @param options.modifiedBefore Only copy files modified before this time, in milliseconds since the Unix epoch. The size and time limits only apply to files.
+ * @param options.backupToTrash Move existing destination files to the system trash before overwriting them, defaults to `false`.
+ * @param options.backup Snapshot existing destination files into numbered backups before overwriting them, see `backup`.
+ * @param options.onConflict What happens to an existing file where an item goes, like `transfer` does, defaults to `overwrite`.
+ * @param options.dryRun Only return the planned items with their sizes and the collisions without copying anything, like `transfer` does.
+ * @param options.rollback Remove the copies made so far and put back the overwritten files when the copy fails or is cancelled part way, like `transfer` does, defaults to `false`.
+ * @param options.preserveTimestamps Give the copies the access and modification times of the originals, see `transfer`.
+ * @param options.preservePermissions Give the copies the permissions of the originals, see `transfer`.
+ * @param options.symlinks How the symbolic links inside the source are handled, `follow` copies what they point to, `preserve` recreates them and `skip` leaves them out, like `transfer` does. Links are copied as what they point to when unset.
+ * @param options.treatBundlesAsFiles Treat macOS bundles such as `.app` as single files, a bundle is never filtered inside or left half copied and a bundle source is copied into the destination as a whole, defaults to `false`.
+ * @param options.retry.attempts The number of retries of an item locked by another process after the first attempt, only on Windows, defaults to `5`.
+ * @param options.retry.delay The delay before the first retry in milliseconds, doubled for every following retry, defaults to `50`.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`, the item in progress is still copied completely.
+ * @param options.onProgressIntervalMs The minimum time between two progress updates in milliseconds, see `transfer`.
+ * @param options.notify Show a system notification once finished, see `compress`.
+ * @param onProgress Specify a callback that receives the progress, at most every `options.onProgressIntervalMs`.
+ *
+ * @example
+ * import { copy } from "tauri-plugin-fs-pro-api"
+ *
+ * await copy("/path/to/source", "/path/to/backup", {}, ({ copiedBytes, totalBytes }) => {
+ *   console.log(copiedBytes / totalBytes)
+ * })
+ */
+export const copy = (
+  srcPath: PathInput,
+  dstPath: PathInput,
+  options?: TransferOptions,
+  onProgress?: (progress: TransferProgress) => void
+) => {
+  let channel: Channel<TransferProgress> | undefined;
+
+  if (onProgress) {
+    channel = new Channel<TransferProgress>();
+    channel.onmessage = onProgress;
+  }
+
+  return invoke<TransferReport>(COMMAND.COPY, {
+    srcPath,
+    dstPath,
+    options,
+    onProgress: channel,
+  });
+};
+
+/**
+ * Copy a single file to the destination file path, optimized for large files.
+ *
+ * Unlike `copy`, which copies into a destination directory, the file is copied in large chunks, using the fast copy of the system where available, and can be cancelled in the middle. It's written next to the destination first and renamed once complete, so a failed or cancelled copy leaves no partial file behind and an existing destination untouched.
+ *
+ * @param srcPath Specify the source file path.
+ * @param dstPath Specify the destination file path.
+ * @param options.overwrite Whether to overwrite an existing destination file, rejects instead when `false`, defaults to `true`.
+ * @param options.onProgressIntervalMs The minimum time between two progress updates in milliseconds, defaults to `100` or the interval set in the plugin builder.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`.
+ * @param options.notify Show a system notification once finished, see `compress`.
+ * @param onProgress Specify a callback that receives the progress.
+ *
+ * @example
+ * import { copyFile } from "tauri-plugin-fs-pro-api"
+ *
+ * await copyFile("/path/to/video.mkv", "/Volumes/External/video.mkv", { onProgressIntervalMs: 250 }, ({ copiedBytes, totalBytes }) => {
+ *   console.log(copiedBytes / totalBytes)
+ * })
+ */
+export const copyFile = (
+  srcPath: PathInput,
+  dstPath: PathInput,
+  options?: CopyFileOptions,
+  onProgress?: (progress: TransferProgress) => void
+) => {
+  let channel: Channel<TransferProgress> | undefined;
+
+  if (onProgress) {
+    channel = new Channel<TransferProgress>();
+    channel.onmessage = onProgress;
+  }
+
+  return invoke(COMMAND.COPY_FILE, {
+    srcPath,
+    dstPath,
+    options,
+    onProgress: channel,
+  });
+};
+
+/**
+ * Permanently remove a file or a directory with everything in it, without following symbolic links.
+ *
+ * Deep trees such as `node_modules` are deleted in parallel. On Windows, paths beyond the 260 character limit are supported, read-only files are deleted too, and deletions briefly blocked by other processes such as antivirus scanners are retried.
+ *
+ * @param path Specify the path.
+ * @param options.retry.attempts The number of retries of a locked file after the first attempt, only on Windows, defaults to `5`.
+ * @param options.retry.delay The delay before the first retry in milliseconds, doubled for every following retry, defaults to `50`.
+ *
+ * @returns The paths that had to be retried.
+ *
+ * @example
+ * import { remove } from "tauri-plugin-fs-pro-api"
+ *
+ * const { retried } = await remove("/path/to/node_modules")
+ */
+export const remove = (path: PathInput, options?: RemoveOptions) => {
+  return invoke<RemoveReport>(COMMAND.REMOVE, {
+    path,
+    options,
+  });
+};
+
+/**
+ * Check a list of operations against the disk and against each other without changing anything, for an "Apply changes" workflow.
+ *
+ * Each operation is checked against the state the operations before it leave behind: sources must exist, destinations must not, their parents must, and the copies, or moves to another volume, must fit in the free space and, on file systems with a fixed number of inodes such as ext4, in the free inodes.
+ *
+ * @param operations Specify the operations, paths must be absolute.
+ *
+ * @example
+ * import { planOperations } from "tauri-plugin-fs-pro-api"
+ *
+ * const { issues } = await planOperations([
+ *   { op: "mkdir", path: "/path/to/archive" },
+ *   { op: "move", src: "/path/to/old.txt", dst: "/path/to/archive/old.txt" },
+ * ])
+ */
+export const planOperations = (operations: Operation[]) => {
+  return invoke<Plan>(COMMAND.PLAN_OPERATIONS, {
+    operations,
+  });
+};
+
+/**
+ * Apply a list of operations in order, after checking them like `planOperations`.
+ *
+ * When an operation fails, the ones applied before it are reverted, last first, on a best-effort basis. Deleted paths are kept next to where they were until every operation succeeded, then they are moved to the system trash. Where there is no trash, such as on some network shares, they are kept under their hidden staged names instead and listed in `notTrashed`, to remove once the operations don't need to be undone anymore.
+ *
+ * Committed operations are added to the undo history, see `undo`.
+ *
+ * @param operations Specify the operations, see `planOperations`.
+ * @param options.operationId An id of your choice to undo the operations with `undo`, defaults to a generated one.
+ *
+ * @returns Whether every operation was applied and the id to undo them with, otherwise the failed operation, its error and the paths that could not be restored. Rejects when the operations have issues.
+ *
+ * @example
+ * import { commitOperations } from "tauri-plugin-fs-pro-api"
+ *
+ * const { committed } = await commitOperations([{ op: "delete", path: "/path/to/file.txt" }])
+ */
+export const commitOperations = (
+  operations: Operation[],
+  options?: CommitOptions
+) => {
+  return invoke<CommitReport>(COMMAND.COMMIT_OPERATIONS, {
+    operations,
+    options,
+  });
+};
+
+/**
+ * Revert operations committed with `commitOperations`, last first, and remove them from the undo history.
+ *
+ * Copies and created directories are removed, moves and renames are moved back and deleted paths are restored from the system trash. A path is not restored when another file took its place in the meantime, and directories are only removed when they are empty. On macOS, only deleted paths of the startup volume can be restored.
+ *
+ * @param operationId Specify the id of the committed operations, defaults to the most recently committed ones.
+ *
+ * @returns Whether every operation was reverted and the paths that could not be restored. Rejects when there are no such operations in the undo history.
+ *
+ * @example
+ * import { undo } from "tauri-plugin-fs-pro-api"
+ *
+ * const { undone, notUndone } = await undo()
+ */
+export const undo = (operationId?: string) => {
+  return invoke<UndoReport>(COMMAND.UNDO, {
+    operationId,
+  });
+};
+
+/**
+ * Get the committed operations that can be undone, most recent first. Up to 50 are kept.
+ *
+ * @example
+ * import { undoHistory } from "tauri-plugin-fs-pro-api"
+ *
+ * const history = await undoHistory()
+ */
+export const undoHistory = () => {
+  return invoke<HistoryEntry[]>(COMMAND.UNDO_HISTORY);
+};
+
+/**
+ * Cancel a running operation that was started with an `operationId`, the operation then rejects with a `cancelled` error.
+ *
+ * @param id Specify the id passed as `operationId`.
+ *
+ * @returns Whether an operation with the id was running.
+ *
+ * @example
+ * import { cancelOperation, copy } from "tauri-plugin-fs-pro-api"
+ *
+ * const operationId = crypto.randomUUID()
+ *
+ * copy("/path/to/source", "/path/to/backup", { operationId })
+ *
+ * await cancelOperation(operationId)
+ */
+export const cancelOperation = (id: string) => {
+  return invoke<boolean>(COMMAND.CANCEL_OPERATION, {
+    id,
+  });
+};
+
+/**
+ * Snapshot the path into a numbered backup (`file.txt.~1~`, `file.txt.~2~`, ...), removing the oldest backups beyond `keep`.
+ *
+ * @param path Specify the path.
+ * @param options.dir The directory to store the backups in, defaults to the directory of the path.
+ * @param options.keep The number of backups to keep, the oldest ones are removed first, defaults to keeping all.
+ *
+ * @example
+ * import { backup } from "tauri-plugin-fs-pro-api"
+ *
+ * const backupPath = await backup("/path/to/file.txt", { keep: 5 })
+ * console.log(backupPath) // /path/to/file.txt.~1~
+ */
+export const backup = (path: PathInput, options?: BackupOptions) => {
+  return invoke<string>(COMMAND.BACKUP, {
+    path,
+    options,
+  });
+};
+
+/**
+ * Move the files of a directory into subfolders by rules, the first matching rule wins and unmatched files stay in place.
+ *
+ * @param dir Specify the directory, only the files directly inside it are organized.
+ * @param rules Specify the rules, by extension name, by modification date or by regex.
+ * @param options.dryRun Only return the plan without moving anything, defaults to `false`.
+ *
+ * @returns The planned or performed moves, conflicting ones are left in place.
+ *
+ * @example
+ * ```
+ * import { organize } from "tauri-plugin-fs-pro-api"
+ *
+ * const rules = [{ by: "extension", extensions: ["pdf"], folder: "Documents" }, { by: "date", pattern: "YYYY/MM" }]
+ * const plan = await organize("/path/to/downloads", rules, { dryRun: true })
+ * await organize("/path/to/downloads", rules)
+ * ```
+ */
+export const organize = (
+  dir: PathInput,
+  rules: OrganizeRule[],
+  options?: OrganizeOptions
+) => {
+  return invoke<OrganizeMove[]>(COMMAND.ORGANIZE, {
+    dir,
+    rules,
+    options,
+  });
+};
+
+/**
+ * Copy files into a directory, optionally skipping the ones whose content already exists anywhere under it.
+ *
+ * @param files Specify the paths of the files to import.
+ * @param dstDir Specify the destination directory.
+ * @param options.skipIfIdenticalExists Skip files whose content already exists anywhere under the destination directory, defaults to `false`.
+ *
+ * @returns The imported files and the skipped duplicates.
+ *
+ * @example
+ * ```
+ * import { importFiles } from "tauri-plugin-fs-pro-api"
+ *
+ * const { imported, deduped } = await importFiles(["/path/to/camera/IMG_0001.jpg"], "/path/to/photos", {
+ *   skipIfIdenticalExists: true,
+ * })
+ * ```
+ */
+export const importFiles = (
+  files: PathInput[],
+  dstDir: PathInput,
+  options?: ImportOptions
+) => {
+  return invoke<ImportReport>(COMMAND.IMPORT_FILES, {
+    files,
+    dstDir,
+    options,
+  });
+};
+
+/**
+ * Copy the access and modification times, the permissions and optionally the extended attributes from one path to another.
+ *
+ * The creation time is left untouched, as most platforms don't allow changing it.
+ *
+ * @param srcPath Specify the path to copy the metadata from.
+ * @param dstPath Specify the path to copy the metadata to.
+ * @param options.xattrs Also copy the extended attributes, only supported on Unix, defaults to `false`.
+ *
+ * @example
+ * ```
+ * import { cloneMetadata } from "tauri-plugin-fs-pro-api"
+ *
+ * await cloneMetadata("/path/to/original.txt", "/path/to/regenerated.txt")
+ * ```
+ */
+export const cloneMetadata = (
+  srcPath: PathInput,
+  dstPath: PathInput,
+  options?: CloneMetadataOptions
+) => {
+  return invoke(COMMAND.CLONE_METADATA, {
+    srcPath,
+    dstPath,
+    options,
+  });
+};
+
+/**
+ * Build the filename index of a directory in the background, replacing the previous index.
+ *
+ * The directory is watched from then on, so the index follows the entries created, removed and renamed in it, a second after the
+ * changes settled. An index loaded from disk when the app starts is watched as well, changes made while the app wasn't running
+ * are only picked up by the next build. The paths that could not be read are listed in `inaccessible` of `indexStatus`.
+ *
+ * @param dir Specify the directory to index.
+ *
+ * @example
+ * import { indexBuild } from "tauri-plugin-fs-pro-api"
+ *
+ * await indexBuild("/path/to/directory")
+ */
+export const indexBuild = (dir: PathInput) => {
+  return invoke(COMMAND.INDEX_BUILD, {
+    dir,
+  });
+};
+
+/**
+ * Query the filename index for paths whose name contains the text, ignoring case.
+ *
+ * @param text Specify the text to search for.
+ * @param options.limit The maximum number of paths to return, defaults to `100`.
+ *
+ * @example
+ * import { indexQuery } from "tauri-plugin-fs-pro-api"
+ *
+ * const paths = await indexQuery("report")
+ * console.log(paths)
+ */
+export const indexQuery = (text: string, options?: IndexQueryOptions) => {
+  return invoke<PathEntry[]>(COMMAND.INDEX_QUERY, {
+    text,
+    options,
+  });
+};
+
+/**
+ * Get the status of the filename index.
+ *
+ * @example
+ * import { indexStatus } from "tauri-plugin-fs-pro-api"
+ *
+ * const status = await indexStatus()
+ * console.log(status)
+ */
+export const indexStatus = () => {
+  return invoke<IndexStatus>(COMMAND.INDEX_STATUS);
+};
+
+/**
+ * Watch a single file for changes, without watching its parent directory.
+ *
+ * Files replaced by an editor saving atomically keep being watched and are reported as modified. The watcher stops by itself once the file is removed or renamed.
+ *
+ * When the volume of the file is unmounted, such as an external drive being ejected, a `lost` event is emitted instead of `remove`.
+ *
+ * @param path Specify the path of the file.
+ * @param callback Called with the kind of the change and the metadata of the file after it.
+ * @param options.autoResume Keep the watch after a `lost` event and resume it with a `resume` event once the volume is mounted again, defaults to `false`. If the file is gone by then, a `remove` event ends the watch.
+ * @param options.minIntervalMs The minimum time between two `modify` events in milliseconds, no limit by default or the interval set in the plugin builder. The changes in the meantime are sent as one event once the interval passed, with the metadata of the file by then. `remove` and `rename` events are never held back.
+ * @returns A function that stops watching the file.
+ *
+ * @example
+ * ```
+ * import { watchFile } from "tauri-plugin-fs-pro-api"
+ *
+ * const unwatch = await watchFile("/path/to/config.json", ({ kind, metadata }) => {
+ *   console.log(kind, metadata)
+ * })
+ * ```
+ */
+export const watchFile = async (
+  path: PathInput,
+  callback: (payload: WatchFileEvent) => void,
+  options?: WatchFileOptions,
+) => {
+  const id = await invoke<number>(COMMAND.WATCH_FILE, { path, options });
+
+  const unlisten = await listen<WatchFileEvent>(
+    "fs-pro://watch-file",
+    (event) => {
+      if (event.payload.id !== id) return;
+
+      callback(event.payload);
+    },
+  );
+
+  return async () => {
+    unlisten();
+
+    await unwatchFile(id);
+  };
+};
+
+/**
+ * Stop watching a file.
+ *
+ * @param id Specify the id of the watcher.
+ *
+ * @example
+ * ```
+ * import { unwatchFile } from "tauri-plugin-fs-pro-api"
+ *
+ * await unwatchFile(id)
+ * ```
+ */
+export const unwatchFile = (id: number) => {
+  return invoke<boolean>(COMMAND.UNWATCH_FILE, { id });
+};
+
+/**
+ * Mirror the source directory into the destination directory, then keep it mirrored as the source changes.
+ *
+ * Files are copied when their size or modification time differ, through a temporary file so the destination never holds half a file. Symbolic links are not synced. Listen for the synced paths with `onAutoSync`.
+ *
+ * @param srcPath Specify the source directory.
+ * @param dstPath Specify the destination directory, created if it doesn't exist. Neither directory may contain the other.
+ * @param options.ignore Gitignore-style rules of the paths not to be synced, matched relative to the source path, either `{ path }` of a file such as `.syncignore` or `{ contents }`. Ignored paths in the destination are left alone.
+ * @param options.delete Remove the paths that no longer exist in the source from the destination, defaults to `true`.
+ * @param options.debounce How long the source must be left unchanged before the changes are synced in milliseconds, defaults to `500`.
+ *
+ * @returns The id of the auto sync.
+ *
+ * @example
+ * import { autoSyncStart } from "tauri-plugin-fs-pro-api"
+ *
+ * const id = await autoSyncStart("/path/to/documents", "/path/to/backup", {
+ *   ignore: { contents: "node_modules/" },
+ * })
+ */
+export const autoSyncStart = (
+  srcPath: PathInput,
+  dstPath: PathInput,
+  options?: AutoSyncOptions
+) => {
+  return invoke<number>(COMMAND.AUTO_SYNC_START, {
+    srcPath,
+    dstPath,
+    options,
+  });
+};
+
+/**
+ * Stop an auto sync, changes that are not synced yet are left out.
+ *
+ * @param id Specify the id of the auto sync.
+ *
+ * @returns `false` if there is no auto sync with the id.
+ *
+ * @example
+ * import { autoSyncStop } from "tauri-plugin-fs-pro-api"
+ *
+ * await autoSyncStop(id)
+ */
+export const autoSyncStop = (id: number) => {
+  return invoke<boolean>(COMMAND.AUTO_SYNC_STOP, { id });
+};
+
+/**
+ * Listen for the paths copied, removed or failed by the auto syncs.
+ *
+ * @param callback Called with the id of the auto sync, the kind of the change and the path relative to the source.
+ *
+ * @example
+ * import { autoSyncStart, onAutoSync } from "tauri-plugin-fs-pro-api"
+ *
+ * const id = await autoSyncStart("/path/to/documents", "/path/to/backup")
+ *
+ * const unlisten = await onAutoSync((event) => {
+ *   if (event.id !== id) return
+ *
+ *   console.log(event.kind, event.path)
+ * })
+ */
+export const onAutoSync = (callback: (payload: AutoSyncEvent) => void) => {
+  return listen<AutoSyncEvent>("fs-pro://auto-sync", (event) =>
+    callback(event.payload)
+  );
+};
+
+/**
+ * Sync two directories both ways: files added, changed or deleted on one side since the last sync are copied to or removed from the other side.
+ *
+ * The size and modification time of every synced file is kept in the plugin data directory, a file changed on both sides since is a conflict and is never overwritten silently. The first sync of two directories treats files that exist on both sides with different contents as conflicts. Only files are synced, symbolic links and empty directories are not.
+ *
+ * @param leftPath Specify the left directory.
+ * @param rightPath Specify the right directory. Neither directory may contain the other.
+ * @param options.strategy How conflicts are resolved, `ask`, `newerWins` or `keepBoth`, defaults to `ask`.
+ * @param options.ignore Gitignore-style rules of the paths not to be synced, matched relative to each directory, either `{ path }` of a file such as `.syncignore` or `{ contents }`.
+ *
+ * @returns The copied and removed paths, the conflicts and the paths that could not be synced, relative to both directories. Failed paths are synced again next time.
+ *
+ * @example
+ * import { bidirectionalSync } from "tauri-plugin-fs-pro-api"
+ *
+ * const report = await bidirectionalSync("/path/to/documents", "/Volumes/USB/documents")
+ *
+ * for (const conflict of report.conflicts) {
+ *   console.log(conflict.path, conflict.leftModifiedAt, conflict.rightModifiedAt)
+ * }
+ */
+export const bidirectionalSync = (
+  leftPath: PathInput,
+  rightPath: PathInput,
+  options?: BidirectionalSyncOptions
+) => {
+  return invoke<SyncReport>(COMMAND.BIDIRECTIONAL_SYNC, {
+    leftPath,
+    rightPath,
+    options,
+  });
+};
+
+/**
+ * Get the mounted volumes, such as disks, USB drives and network shares.
+ *
+ * Listen with `onVolume` to learn about volumes mounted or unmounted later on instead of polling.
+ *
+ * @returns The mount path, name and file system of each volume.
+ *
+ * @example
+ * import { volumes } from "tauri-plugin-fs-pro-api"
+ *
+ * const drives = await volumes()
+ */
+export const volumes = () => {
+  return invoke<Volume[]>(COMMAND.VOLUMES);
+};
+
+/**
+ * Listen for volumes being mounted or unmounted, such as a USB drive plugged in or removed. Changes are noticed within a second.
+ *
+ * Volumes are only watched while something listens, so call the returned function once the events are no longer needed.
+ *
+ * @param callback Called with the kind of the change and the volume.
+ *
+ * @example
+ * import { onVolume, volumes } from "tauri-plugin-fs-pro-api"
+ *
+ * let drives = await volumes()
+ *
+ * const unlisten = await onVolume(async () => {
+ *   drives = await volumes()
+ * })
+ */
+export const onVolume = async (callback: (payload: VolumeEvent) => void) => {
+  const unlisten = await listen<VolumeEvent>("fs-pro://volume", (event) =>
+    callback(event.payload)
+  );
+
+  await invoke(COMMAND.WATCH_VOLUMES);
 
-export interface TransferOptions {
-  /**
-   * The name of the file or directory to be moved.
-   */
-  includes?: string[];
-  /**
-   * The name of the file or directory not to be moved.
-   */
-  excludes?: string[];
-}
+  return () => {
+    unlisten();
+    invoke(COMMAND.UNWATCH_VOLUMES);
+  };
+};
 
-export const COMMAND = {
-  IS_EXIST: "plugin:fs-pro|is_exist",
-  IS_FILE: "plugin:fs-pro|is_file",
-  IS_DIR: "plugin:fs-pro|is_dir",
-  SIZE: "plugin:fs-pro|size",
-  NAME: "plugin:fs-pro|name",
-  EXTNAME: "plugin:fs-pro|extname",
-  FULL_NAME: "plugin:fs-pro|full_name",
-  PARENT_NAME: "plugin:fs-pro|parent_name",
-  GET_DEFAULT_SAVE_ICON_PATH: "plugin:fs-pro|get_default_save_icon_path",
-  ICON: "plugin:fs-pro|icon",
-  METADATA: "plugin:fs-pro|metadata",
-  COMPRESS: "plugin:fs-pro|compress",
-  DECOMPRESS: "plugin:fs-pro|decompress",
-  TRANSFER: "plugin:fs-pro|transfer",
+/**
+ * Measure the sequential read and write throughput of the volume of a directory.
+ *
+ * A temporary file is written to the directory, flushed to the device and read back, bypassing the cache of the system where possible. It's removed afterwards, also on failure or cancellation.
+ *
+ * @param path Specify a directory on the volume to measure.
+ * @param options.fileSize The size of the temporary file in bytes, rounded up to whole MiB, defaults to 128 MiB. Larger files give more reliable results. It's written and read in chunks of 1 MiB, or of the buffer limit set in the plugin builder when smaller.
+ * @param options.mode Whether to measure `read`, `write` or `readWrite`, defaults to `readWrite`.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`.
+ *
+ * @returns The size of the file and the measured throughput in bytes per second.
+ *
+ * @example
+ * import { benchmark } from "tauri-plugin-fs-pro-api"
+ *
+ * const { writeBytesPerSecond } = await benchmark("/Volumes/External", { mode: "write" })
+ *
+ * if (writeBytesPerSecond! < 50 * 1024 * 1024) {
+ *   console.warn("The drive is too slow for 4K exports")
+ * }
+ */
+export const benchmark = (path: PathInput, options?: BenchmarkOptions) => {
+  return invoke<BenchmarkReport>(COMMAND.BENCHMARK, {
+    path,
+    options,
+  });
 };
 
 /**
- * Check if a path exists.
+ * Get the size and free space of the volume of a path, including the free inodes on file systems with a fixed number of them.
  *
- * @param path Specify the path.
+ * Each file or directory takes an inode, so extracting or copying many small files can fail on a volume with plenty of free space left, e.g. on ext4.
+ *
+ * @param path Specify an existing path on the volume.
+ *
+ * @returns The total and available bytes and inodes, the inodes are `null` on file systems such as btrfs, FAT or NTFS.
  *
  * @example
- * ```
- * import { isExist } from "tauri-plugin-fs-pro-api"
+ * import { diskUsage } from "tauri-plugin-fs-pro-api"
  *
- * const exists = await isExist("/path/to/file.txt")
- * console.log(exists) // true
- * ```
+ * const { availableInodes } = await diskUsage("/home/user/projects")
+ *
+ * if (availableInodes !== null && availableInodes < 100_000) {
+ *   console.warn("The volume is running out of inodes")
+ * }
  */
-export const isExist = (path: string) => {
-  return invoke<boolean>(COMMAND.IS_EXIST, {
+export const diskUsage = (path: PathInput) => {
+  return invoke<DiskUsage>(COMMAND.DISK_USAGE, {
     path,
   });
 };
 
 /**
- * Check if a path is a file.
+ * Check whether a path can be written to, by creating and removing a hidden probe file rather than trusting the permissions alone.
+ *
+ * An existing file is checked by opening it for writing, a path that doesn't exist yet through its nearest existing ancestor.
+ * Read-only mounts and write-protected media, e.g. a locked SD card or a mounted disk image, are reported as not writable.
+ *
+ * `transfer`, `copy` and `decompress` run the same check and fail with a `notWritable` error before writing anything.
  *
  * @param path Specify the path.
  *
+ * @returns Whether the path can be written to.
+ *
  * @example
- * ```
- * import { isFile } from "tauri-plugin-fs-pro-api"
+ * import { isWritable } from "tauri-plugin-fs-pro-api"
  *
- * const isFile = await isFile("/path/to/file.txt")
- * console.log(isFile) // true
- * ```
+ * if (!(await isWritable("/Volumes/Camera/DCIM"))) {
+ *   console.warn("The card is write-protected")
+ * }
  */
-export const isFile = (path: string) => {
-  return invoke<boolean>(COMMAND.IS_FILE, {
+export const isWritable = (path: PathInput) => {
+  return invoke<boolean>(COMMAND.IS_WRITABLE, {
     path,
   });
 };
 
 /**
- * Check if a path is a directory.
+ * Create an empty scratch directory for a multi-step job, e.g. download, extract, transform and move.
  *
- * @param path Specify the path.
+ * The workspace is removed with `workspaceDispose`, or with everything in it once the app exits. Workspaces left behind by a crash are removed the next time the app starts.
+ *
+ * @returns The id and the path of the workspace, inside the app cache directory.
  *
  * @example
  * ```
- * import { isDir } from "tauri-plugin-fs-pro-api"
+ * import { decompress, transfer, workspaceCreate, workspaceDispose } from "tauri-plugin-fs-pro-api"
  *
- * const isDir = await isDir("/path/to/dir")
- * console.log(isDir) // true
+ * const workspace = await workspaceCreate()
+ *
+ * try {
+ *   await decompress("/path/to/download.zip", workspace.path)
+ *   await transfer(workspace.path, "/path/to/library")
+ * } finally {
+ *   await workspaceDispose(workspace.id)
+ * }
  * ```
  */
-export const isDir = (path: string) => {
-  return invoke<boolean>(COMMAND.IS_DIR, {
-    path,
-  });
+export const workspaceCreate = () => {
+  return invoke<Workspace>(COMMAND.WORKSPACE_CREATE);
 };
 
 /**
- * Get the size of the path, or 0 if it does not exist.
+ * Remove a workspace created with `workspaceCreate` with everything in it.
  *
- * @param path Specify the path.
+ * @param id Specify the id of the workspace.
+ *
+ * @returns Whether there was a workspace with the id.
  *
  * @example
  * ```
- * import { size } from "tauri-plugin-fs-pro-api"
+ * import { workspaceDispose } from "tauri-plugin-fs-pro-api"
  *
- * const size = await size("/path/to/file.txt")
- * console.log(size) // 1024
+ * await workspaceDispose(workspace.id)
  * ```
  */
-export const size = (path: string) => {
-  return invoke<number>(COMMAND.SIZE, {
-    path,
-  });
+export const workspaceDispose = (id: number) => {
+  return invoke<boolean>(COMMAND.WORKSPACE_DISPOSE, { id });
 };
 
 /**
- * Get the name of the path.
+ * Convert an archive to another format, e.g. a tar.gz file to a zip file, without extracting it to disk.
  *
- * @param path Specify the path.
+ * The source format is detected from the contents like `decompress` does. The entries are streamed from one archive into the other with their permissions and modified times, hard links, devices and named pipes of tar files are left out. The new archive is written next to the destination and renamed once complete, so the destination may be the source itself.
+ *
+ * @param srcPath Specify the source archive path.
+ * @param dstPath Specify the destination path.
+ * @param options.format The format of the new archive, see `compress`, defaults to `tar.gz`.
+ * @param options.level The compression level, within the levels of the format, see `compress`. Rejects with an `invalidCompressionLevel` error when out of range.
+ * @param options.overwrite Whether to overwrite an existing destination file, rejects instead when `false`, defaults to `true`.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`, the partial archive is removed.
  *
  * @example
  * ```
- * import { name } from "tauri-plugin-fs-pro-api"
+ * import { convertArchive } from "tauri-plugin-fs-pro-api"
  *
- * const name = await name("/path/to/file.txt")
- * console.log(name) // file
+ * await convertArchive("/path/to/archive.tar.gz", "/path/to/archive.zip", { format: "zip" })
+ *
+ * await convertArchive("/path/to/archive.zip", "/path/to/archive.tar.zst", { format: "tar.zst", level: 19 })
  * ```
  */
-export const name = (path: string) => {
-  return invoke<string>(COMMAND.NAME, {
-    path,
+export const convertArchive = (
+  srcPath: PathInput,
+  dstPath: PathInput,
+  options?: ConvertArchiveOptions
+) => {
+  return invoke(COMMAND.CONVERT_ARCHIVE, {
+    srcPath,
+    dstPath,
+    options,
   });
 };
 
 /**
- * Get the extension name of the path.
+ * Put a file into a content-addressed store, under `ab/cdef…` in the store directory after the SHA-256 digest of its content.
  *
- * @param path Specify the path.
+ * Identical content is stored once, storing it again only returns the existing object. New content is written next to its place in the store and renamed once complete, so the store never holds half a file.
+ *
+ * @param srcPath Specify the path of the file to store.
+ * @param casRoot Specify the directory of the store, created when missing.
+ * @param options.mode How the file is put into the store, defaults to `copy`. `copy` hashes the file in the same pass, `link` hard links it and copies it when the store is on another file system, changing the file in place afterwards changes the stored object too, and `move` moves it, removing it when the store already has the content.
+ *
+ * @returns The digest in lowercase hex, the path inside the store and whether the store already had the content.
  *
  * @example
  * ```
- * import { extname } from "tauri-plugin-fs-pro-api"
+ * import { storeCas } from "tauri-plugin-fs-pro-api"
  *
- * const extname = await extname("/path/to/file.txt")
- * console.log(extname) // txt
+ * const { digest } = await storeCas("/path/to/texture.png", "/path/to/assets")
+ *
+ * await storeCas("/path/to/download.bin", "/path/to/assets", { mode: "move" })
  * ```
  */
-export const extname = (path: string) => {
-  return invoke<string>(COMMAND.EXTNAME, {
-    path,
+export const storeCas = (
+  srcPath: PathInput,
+  casRoot: PathInput,
+  options?: StoreCasOptions
+) => {
+  return invoke<StoredObject>(COMMAND.STORE_CAS, {
+    srcPath,
+    casRoot,
+    options,
   });
 };
 
 /**
- * Get the full name of a file or directory including extension.
+ * Get the path of the content with a digest in a content-addressed store written by `storeCas`.
  *
- * @param path Specify the path.
+ * @param casRoot Specify the directory of the store.
+ * @param digest Specify the SHA-256 digest of the content in hex.
+ *
+ * @returns The path of the content inside the store, rejects with a `notFound` error when the store doesn't have it, or with an `invalidDigest` error when the digest is not 64 hex characters.
  *
  * @example
  * ```
- * import { fullName } from "tauri-plugin-fs-pro-api"
+ * import { retrieveCas } from "tauri-plugin-fs-pro-api"
  *
- * const fullName = await fullName("/path/to/file.txt")
- * console.log(fullName) // file.txt
+ * const path = await retrieveCas("/path/to/assets", digest)
  * ```
  */
-export const fullName = (path: string) => {
-  return invoke<string>(COMMAND.FULL_NAME, {
-    path,
-  });
+export const retrieveCas = (casRoot: PathInput, digest: string) => {
+  return invoke<string>(COMMAND.RETRIEVE_CAS, { casRoot, digest });
 };
 
 /**
- * Get the parent name of the path.
+ * Make the app the handler of a file extension for the current user, so the files open in it on double-click.
  *
- * @param path Specify the path.
- * @param level Specify the level of the parent directory, defaults to `1`.
+ * On Windows a file type named after the app identifier and the extension is registered under `HKEY_CURRENT_USER\Software\Classes`, a default app the user picked in the Windows settings still takes precedence. On macOS the app is set as the default handler with Launch Services, the extension has to be declared in the `Info.plist` of the app, e.g. with `bundle.fileAssociations` in the Tauri configuration, and the app must be installed. On Linux a MIME type for the extension and a hidden desktop entry of the app are installed, then made the default with `xdg-mime`.
+ *
+ * The opened file is passed as an argument to the app on Windows and Linux, and through `RunEvent::Opened` on macOS.
+ *
+ * @param extension Specify the extension, with or without a leading dot, e.g. `mydoc`. Rejects with an `invalidExtension` error when it contains anything but letters, digits, `-`, `_` and `.`.
+ * @param options.description The description of the file type shown in the file manager, e.g. `My Document`, defaults to the app name.
+ * @param options.mimeType The MIME type of the files on Linux as `type/subtype`, e.g. `application/x-mydoc`, defaults to `application/x-<extension>`. Rejects with an `invalidMimeType` error when it contains anything but letters, digits and `!#$&^_.+-` around the `/`.
  *
  * @example
  * ```
- * import { parentName } from "tauri-plugin-fs-pro-api"
+ * import { registerFileAssociation } from "tauri-plugin-fs-pro-api"
  *
- * const parentName = await parentName("/path/to/file.txt")
- * console.log(parentName) // to
+ * await registerFileAssociation("mydoc", { description: "My Document" })
  * ```
  */
-export const parentName = (path: string, level = 1) => {
-  return invoke<string>(COMMAND.PARENT_NAME, {
-    path,
-    level,
-  });
+export const registerFileAssociation = (
+  extension: string,
+  options?: FileAssociationOptions
+) => {
+  return invoke(COMMAND.REGISTER_FILE_ASSOCIATION, { extension, options });
 };
 
 /**
- * Get the default save icon path.
+ * Remove what `registerFileAssociation` set up for an extension.
+ *
+ * Another app the extension was handled by before is not restored, the system picks one or asks. On macOS Launch Services has no way to remove a default handler, nothing is changed and `false` is returned, choose another app in Finder instead.
+ *
+ * @param extension Specify the extension, with or without a leading dot, e.g. `mydoc`.
+ *
+ * @returns Whether the app was registered for the extension.
  *
  * @example
  * ```
- * import { getDefaultSaveIconPath } from "tauri-plugin-fs-pro-api"
+ * import { unregisterFileAssociation } from "tauri-plugin-fs-pro-api"
  *
- * const savePath = await getDefaultSaveIconPath()
- * console.log(savePath)
+ * await unregisterFileAssociation("mydoc")
  * ```
  */
-export const getDefaultSaveIconPath = () => {
-  return invoke<string>(COMMAND.GET_DEFAULT_SAVE_ICON_PATH);
+export const unregisterFileAssociation = (extension: string) => {
+  return invoke<boolean>(COMMAND.UNREGISTER_FILE_ASSOCIATION, { extension });
 };
 
 /**
- * Get the icon of the path.
+ * Create a launcher that opens a file or directory with its default app, replacing an existing launcher of the same name.
  *
- * @param path Specify the path.
- * @param options.size Specify the size of the icon, defaults to `32`.
- * @param options.savePath Specify the path to save the icon, defaults to the default save path.
+ * - Windows: A shortcut (`.lnk`) in the Start Menu programs of the current user or on the desktop.
+ * - macOS: An alias in `~/Applications` or on the desktop, the same Finder creates with Make Alias. It follows the target when moved and shows its icon, `options.icon` is not used.
+ * - Linux: A desktop entry in `~/.local/share/applications`, which the app menus pick up, or on the desktop. It opens the target with `xdg-open`. Some desktops ask to trust an entry on the desktop before starting it the first time.
+ *
+ * @param path Specify the path of the file or directory to open. Rejects with a `notFound` error when it doesn't exist.
+ * @param options.name The name shown for the launcher, also the name of its file, defaults to the name of the file or directory. Rejects with an `invalidPath` error when it contains a path separator or another character file names can't hold.
+ * @param options.icon The icon of the launcher, an `.ico` file on Windows, an image file or the name of an icon of the theme on Linux. Defaults to the icon of the target on Windows and the generic file or folder icon on Linux. Rejects with an `invalidPath` error when it, or the path, contains a control character such as a line break.
+ * @param options.location Where the launcher is created, `menu` or `desktop`, defaults to `menu`.
+ *
+ * @returns The path of the launcher.
  *
  * @example
  * ```
- * import { icon } from "tauri-plugin-fs-pro-api"
+ * import { createLauncher } from "tauri-plugin-fs-pro-api"
  *
- * const iconPath = await icon("/path/to/file.txt")
- * console.log(iconPath)
+ * const launcher = await createLauncher("/path/to/projects")
+ * console.log(launcher) // "/home/user/.local/share/applications/projects.desktop"
+ *
+ * await createLauncher("/path/to/report.pdf", { name: "Monthly Report", location: "desktop" })
  * ```
  */
-export const icon = (path: string, options: IconOptions) => {
-  return invoke<string>(COMMAND.ICON, {
-    path,
-    options,
-  });
+export const createLauncher = (path: PathInput, options?: LauncherOptions) => {
+  return invoke<string>(COMMAND.CREATE_LAUNCHER, { path, options });
 };
 
 /**
- * Get the metadata of the path.
+ * Deallocate a range of a file, the range reads back as zeros and the size of the file stays the same. Freeing the stale parts of a large cache file gives their disk space back without rewriting the rest.
  *
- * @param path Specify the path.
- * @param options.omitSize When getting the metadata of a path, if you don't need to calculate the size, you can omit it to save time and return 0 after omitting it,
- * defaults to `false`.
+ * - Windows: The file is marked as sparse and the range zeroed, NTFS and ReFS give back the whole clusters inside the range.
+ * - macOS: APFS gives back the whole blocks inside the range, the partial blocks at its edges are zeroed.
+ * - Linux: Most file systems give back the whole blocks inside the range, the partial blocks at its edges are zeroed.
+ *
+ * @param path Specify the path of the file. Rejects with a `notFound` error when it doesn't exist.
+ * @param offset Specify the offset of the range in bytes.
+ * @param len Specify the length of the range in bytes, a range past the end of the file stops at the end.
  *
  * @example
  * ```
- * import { metadata } from "tauri-plugin-fs-pro-api"
+ * import { punchHole } from "tauri-plugin-fs-pro-api"
  *
- * const metadata = await metadata("/path/to/file.txt")
- * console.log(metadata)
+ * await punchHole("/path/to/cache.bin", 4096, 1024 * 1024)
  * ```
  */
-export const metadata = (path: string, options?: MetadataOptions) => {
-  return invoke<Metadata>(COMMAND.METADATA, {
-    path,
-    options,
-  });
+export const punchHole = (path: PathInput, offset: number, len: number) => {
+  return invoke(COMMAND.PUNCH_HOLE, { path, offset, len });
 };
 
 /**
- * Compress the source path into a tar.gz file to the destination path.
+ * Get the data and hole extents of a file, in order and covering the whole file.
  *
- * @param srcPath Specify the source path.
- * @param dstPath Specify the destination path.
- * @param options.includes The name of the file or directory to be compressed.
- * @param options.excludes The name of the file or directory not to be compressed.
+ * - Windows: The allocated ranges of a sparse file, other files are a single data extent.
+ * - macOS: The extents APFS keeps track of, other file systems give a single data extent.
+ * - Linux: The extents the file system keeps track of, to the block, some report a hole written with zeros as data.
+ *
+ * @param path Specify the path of the file. Rejects with a `notFound` error when it doesn't exist.
+ *
+ * @returns The extents of the file, each with its offset, length and whether it's data or a hole.
  *
  * @example
  * ```
- * import { compress } from "tauri-plugin-fs-pro-api"
+ * import { extents } from "tauri-plugin-fs-pro-api"
  *
- * await compress("/path/to/source.txt", "/path/to/destination.tar.gz")
+ * const holes = (await extents("/path/to/cache.bin")).filter((extent) => extent.kind === "hole")
+ * console.log(holes) // [{ offset: 4096, len: 1048576, kind: "hole" }]
  * ```
  */
-export const compress = (
-  srcPath: string,
-  dstPath: string,
-  options?: CompressOptions
-) => {
-  return invoke(COMMAND.COMPRESS, {
-    srcPath,
-    dstPath,
-    options,
-  });
+export const extents = (path: PathInput) => {
+  return invoke<Extent[]>(COMMAND.EXTENTS, { path });
 };
 
 /**
- * Decompress the tar.gz file from the source path to the destination path.
+ * Get a compact token of the state of a file or directory, which changes when it's modified, resized or replaced by another file. Check it later with `hasChanged` to know whether what was derived from the file, such as a cached preview, is still valid, without reading the file again.
  *
- * @param srcPath Specify the source path.
+ * The token hashes the modification time, the size and the identity of the file, so writing a new file and renaming it over the old one changes it even when the time and size come out the same.
+ *
+ * - Windows: The identity is the volume serial number and the file index.
+ * - macOS: The identity is the device and inode, along with the inode change time.
+ * - Linux: The identity is the device and inode, along with the inode change time.
+ *
+ * @param path Specify the path of the file or directory. Rejects with a `notFound` error when it doesn't exist.
+ *
+ * @returns The token, 16 hex characters. It's only meant to be compared with a token of the same path on the same machine.
+ *
+ * @example
+ * ```
+ * import { changeToken } from "tauri-plugin-fs-pro-api"
+ *
+ * const token = await changeToken("/path/to/photo.jpg")
+ * console.log(token) // "3f2a9c0d41b7e6a8"
+ * ```
+ */
+export const changeToken = (path: PathInput) => {
+  return invoke<string>(COMMAND.CHANGE_TOKEN, { path });
+};
+
+/**
+ * Check whether a file or directory has changed since a token was got from `changeToken`.
+ *
+ * @param path Specify the path of the file or directory.
+ * @param token Specify the token got from `changeToken` for the path.
+ *
+ * @returns Whether the file or directory has changed, `true` when it no longer exists.
+ *
+ * @example
+ * ```
+ * import { changeToken, hasChanged } from "tauri-plugin-fs-pro-api"
+ *
+ * const token = await changeToken("/path/to/photo.jpg")
+ *
+ * if (await hasChanged("/path/to/photo.jpg", token)) {
+ *   // Render the preview again.
+ * }
+ * ```
+ */
+export const hasChanged = (path: PathInput, token: string) => {
+  return invoke<boolean>(COMMAND.HAS_CHANGED, { path, token });
+};
+
+/**
+ * Compress a single file with gzip, without a tar archive around it, e.g. `data.json` to `data.json.gz`, as the `gzip` tool does.
+ *
+ * The name and modified time of the file are stored in the gzip header. The compressed file is written next to the destination and renamed once complete.
+ *
+ * @param srcPath Specify the path of the file to compress. Rejects with a `notFound` error when it's not a file.
  * @param dstPath Specify the destination path.
+ * @param options.level The compression level, from `0` to `9`, defaults to `6`. Higher levels are smaller but slower, `0` stores without compressing. Rejects with an `invalidCompressionLevel` error when out of range.
+ * @param options.overwrite Whether to overwrite an existing destination file, rejects with an `alreadyExists` error instead when `false`, defaults to `true`.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`, the partial file is removed.
  *
  * @example
- * import { decompress } from "tauri-plugin-fs-pro-api"
+ * ```
+ * import { gzip } from "tauri-plugin-fs-pro-api"
  *
- * await decompress("/path/to/destination.tar.gz", "/path/to/source")
+ * await gzip("/path/to/data.json", "/path/to/data.json.gz")
+ * ```
  */
-export const decompress = (srcPath: string, dstPath: string) => {
-  return invoke(COMMAND.DECOMPRESS, {
+export const gzip = (
+  srcPath: PathInput,
+  dstPath: PathInput,
+  options?: GzipOptions
+) => {
+  return invoke(COMMAND.GZIP, {
     srcPath,
     dstPath,
+    options,
   });
 };
 
 /**
- * Move the source path to the destination path.
+ * Decompress a gzip file holding a single file rather than a tar archive, e.g. `data.json.gz` to `data.json`, as the `gunzip` tool does.
  *
- * @param srcPath Specify the source path.
+ * A file of several gzip members, as concatenating gzip files or `pigz` writes, is decompressed whole. The decompressed file is written next to the destination and renamed once complete. A tar.gz file decompresses to its tar archive, use `decompress` to extract it.
+ *
+ * @param srcPath Specify the path of the gzip file. Rejects with a `notFound` error when it's not a file, and with an `unsupportedArchive` error when it's not a gzip file.
  * @param dstPath Specify the destination path.
- * @param options.includes The name of the file or directory to be moved.
- * @param options.excludes The name of the file or directory not to be moved.
- *b
+ * @param options.overwrite Whether to overwrite an existing destination file, rejects with an `alreadyExists` error instead when `false`, defaults to `true`.
+ * @param options.operationId An id of your choice to cancel the operation with `cancelOperation`, the partial file is removed.
+ *
  * @example
- * import { transfer } from "tauri-plugin-fs-pro-api"
+ * ```
+ * import { gunzip } from "tauri-plugin-fs-pro-api"
  *
- * await transfer("/path/to/source", "/path/to/destination")
+ * await gunzip("/path/to/data.json.gz", "/path/to/data.json")
+ * ```
  */
-export const transfer = (
-  srcPath: string,
-  dstPath: string,
-  options?: TransferOptions
+export const gunzip = (
+  srcPath: PathInput,
+  dstPath: PathInput,
+  options?: GunzipOptions
 ) => {
-  return invoke(COMMAND.TRANSFER, {
+  return invoke(COMMAND.GUNZIP, {
     srcPath,
     dstPath,
     options,
   });
 };
+
+/**
+ * Get the current working directory of the app process, which relative paths are resolved against.
+ *
+ * @example
+ * ```
+ * import { cwd } from "tauri-plugin-fs-pro-api"
+ *
+ * const dir = await cwd()
+ * ```
+ */
+export const cwd = () => {
+  return invoke<string>(COMMAND.CWD);
+};
+
+/**
+ * Resolve a path inside a well-known directory, e.g. the app data or downloads directory, with the separators of the current platform, rather than joining paths by hand in the frontend.
+ *
+ * @param base Specify the directory, such as `appData`, `download` or `desktop`.
+ * @param segments Specify the names to join to it in order, each may hold several names separated by `/`, e.g. `logs/today.txt`.
+ *
+ * @returns The absolute path, which doesn't have to exist. Rejects with an `invalidPath` error when a segment is absolute or leads outside the directory with `..`.
+ *
+ * @example
+ * ```
+ * import { resolveAppPath, compress } from "tauri-plugin-fs-pro-api"
+ *
+ * const dstPath = await resolveAppPath("appData", ["backups", "latest.tar.gz"])
+ *
+ * await compress("/path/to/project", dstPath)
+ * ```
+ */
+export const resolveAppPath = (base: BaseDir, segments: string[]) => {
+  return invoke<string>(COMMAND.RESOLVE_APP_PATH, { base, segments });
+};
+
+/**
+ * Gather everything the plugin can find out about a path into one report, e.g. to attach to a support request instead of calling many commands.
+ *
+ * Every check is run even when others fail, the failed ones are listed in `problems` with their errors.
+ *
+ * @param path Specify the path, which doesn't have to exist.
+ *
+ * @returns The metadata of the path as `metadata` returns it, the symbolic links followed from it and its canonical path, the volume and its free space, whether it can be read and written with its permission bits and owner on Unix, and whether the asset protocol scope allows it and the plugin rejects it as relative.
+ *
+ * @example
+ * ```
+ * import { diagnose } from "tauri-plugin-fs-pro-api"
+ *
+ * const diagnosis = await diagnose("/Users/xxx/Documents/report.pdf")
+ * console.log(JSON.stringify(diagnosis, null, 2))
+ * ```
+ */
+export const diagnose = (path: PathInput) => {
+  return invoke<Diagnosis>(COMMAND.DIAGNOSE, { path });
+};
@@ -0,0 +1,1903 @@
+use crate::{
+    buffer::BufferLimit,
+    cancel::CancelToken,
+    commands::{available_path, PlannedItem},
+    volume::Volumes,
+    Error, FileType, Result,
+};
+use chardetng::EncodingDetector;
+use chrono::{Datelike, Local, NaiveDate, Timelike};
+use encoding_rs::{Encoding, BIG5, EUC_JP, EUC_KR, GBK, SHIFT_JIS};
+use filetime::FileTime;
+use flate2::{
+    read::{GzDecoder, MultiGzDecoder},
+    write::GzEncoder,
+    Compression, GzBuilder,
+};
+use globset::GlobSet;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fs::{self, create_dir_all, File},
+    io::{self, Read, Seek, Write},
+    path::{Component, Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tar::{Archive, EntryType, HeaderMode};
+use zip::{
+    read::ZipFile, write::SimpleFileOptions, CompressionMethod, DateTime, ZipArchive, ZipWriter,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub enum ArchiveFormat {
+    // A gzip-compressed tar file.
+    #[default]
+    #[serde(rename = "tar.gz")]
+    TarGz,
+    // A zip file, which Windows and macOS open without extra tools.
+    #[serde(rename = "zip")]
+    Zip,
+    // A zstd-compressed tar file, much faster than gzip for a similar size.
+    #[serde(rename = "tar.zst")]
+    TarZst,
+    // An xz-compressed tar file, smaller but slower than the others.
+    #[cfg(feature = "xz")]
+    #[serde(rename = "tar.xz")]
+    TarXz,
+    // A bzip2-compressed tar file.
+    #[cfg(feature = "bzip2")]
+    #[serde(rename = "tar.bz2")]
+    TarBz2,
+}
+
+impl ArchiveFormat {
+    fn name(self) -> &'static str {
+        match self {
+            ArchiveFormat::TarGz => "tar.gz",
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::TarZst => "tar.zst",
+            #[cfg(feature = "xz")]
+            ArchiveFormat::TarXz => "tar.xz",
+            #[cfg(feature = "bzip2")]
+            ArchiveFormat::TarBz2 => "tar.bz2",
+        }
+    }
+
+    /// The compression level to write the format with, the default level of its compressor without one.
+    ///
+    /// Returns [`Error::InvalidCompressionLevel`] when the compressor doesn't support the level.
+    pub(crate) fn level(self, level: Option<u32>) -> Result<u32> {
+        let (min, max, default) = match self {
+            ArchiveFormat::TarGz | ArchiveFormat::Zip => (0, 9, 6),
+            ArchiveFormat::TarZst => (1, 22, zstd::DEFAULT_COMPRESSION_LEVEL as u32),
+            #[cfg(feature = "xz")]
+            ArchiveFormat::TarXz => (0, 9, 6),
+            #[cfg(feature = "bzip2")]
+            ArchiveFormat::TarBz2 => (1, 9, 6),
+        };
+
+        match level {
+            None => Ok(default),
+            Some(level) if (min..=max).contains(&level) => Ok(level),
+            Some(level) => Err(Error::InvalidCompressionLevel {
+                format: self.name(),
+                level,
+                min,
+                max,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveEntry {
+    // The path of the entry inside the archive.
+    #[serde(flatten, serialize_with = "crate::commands::serialize_path_entry")]
+    pub path: PathBuf,
+    // The uncompressed size of the entry in bytes.
+    pub size: u64,
+    // The type of the entry, tar files may also hold named pipes and devices.
+    pub file_type: FileType,
+    // The modified time of the entry in milliseconds, `0` if the archive doesn't store one.
+    pub modified_at: u128,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveManifestEntry {
+    #[serde(flatten)]
+    pub entry: ArchiveEntry,
+    // The SHA-256 hash of the data of the entry in lowercase hex, `null` for directories, links and special files.
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveManifest {
+    // The number of entries in the archive.
+    pub entry_count: u64,
+    // The total uncompressed size of the entries in bytes.
+    pub total_size: u64,
+    // The entries in the order they are stored.
+    pub entries: Vec<ArchiveManifestEntry>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverwritePolicy {
+    // Replace the existing file with the entry.
+    #[default]
+    Overwrite,
+    // Keep the existing file and leave the entry out.
+    Skip,
+    // Stop the extraction with an `alreadyExists` error, the entries extracted before are kept.
+    Error,
+    // Keep the existing file and extract the entry next to it as `name (1).ext`.
+    Rename,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NameEncoding {
+    // Detected from the names of the archive, GBK, Shift-JIS, Big5, EUC-KR or EUC-JP, CP437 when none of them fits.
+    #[default]
+    Auto,
+    // The encoding of the zip format, used by most Western tools.
+    Cp437,
+    // Simplified Chinese, as written by Windows in China.
+    Gbk,
+    // Japanese, as written by Windows in Japan.
+    #[serde(rename = "shift-jis")]
+    ShiftJis,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecompressReport {
+    // The number of entries written, directories and links included.
+    pub entries_extracted: u64,
+    // The uncompressed size of the files written in bytes.
+    pub bytes_written: u64,
+    // The top-level directories in the destination the entries were written to, in the order they first appear in the archive.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub root_dirs: Vec<PathBuf>,
+    // The paths of the existing files the entries left out with `OverwritePolicy::Skip` would have replaced.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub skipped: Vec<PathBuf>,
+    // The entries extracted under another name with `OverwritePolicy::Rename`.
+    pub renamed: Vec<RenamedEntry>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedEntry {
+    // The path of the existing file the entry or item would have replaced.
+    #[serde(flatten, serialize_with = "crate::commands::serialize_path_entry")]
+    pub path: PathBuf,
+    // The path the entry was extracted to, or the item moved or copied to, instead.
+    #[serde(serialize_with = "crate::commands::serialize_path_entry")]
+    pub renamed_to: PathBuf,
+}
+
+// The archives `unpack` and `entries` recognize, a tar file compressed or not, or a zip file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DetectedFormat {
+    Tar,
+    TarGz,
+    TarZst,
+    #[cfg(feature = "xz")]
+    TarXz,
+    #[cfg(feature = "bzip2")]
+    TarBz2,
+    Zip,
+}
+
+// Recognize an archive by its first bytes, the extension is not trusted.
+fn detect(header: &[u8]) -> Option<DetectedFormat> {
+    const USTAR_OFFSET: usize = 257;
+
+    match header {
+        [0x1f, 0x8b, ..] => Some(DetectedFormat::TarGz),
+        [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(DetectedFormat::TarZst),
+        #[cfg(feature = "xz")]
+        [0xfd, b'7', b'z', b'X', b'Z', 0x00, ..] => Some(DetectedFormat::TarXz),
+        // The block size follows the magic, from `1` to `9`.
+        #[cfg(feature = "bzip2")]
+        [b'B', b'Z', b'h', b'1'..=b'9', ..] => Some(DetectedFormat::TarBz2),
+        // A local file header, an empty archive or a spanned archive.
+        [b'P', b'K', 3, 4, ..] | [b'P', b'K', 5, 6, ..] | [b'P', b'K', 7, 8, ..] => {
+            Some(DetectedFormat::Zip)
+        }
+        _ if header.get(USTAR_OFFSET..USTAR_OFFSET + 5) == Some(b"ustar") => {
+            Some(DetectedFormat::Tar)
+        }
+        _ => None,
+    }
+}
+
+// Zip files store local times without a time zone, from 1980 on.
+fn zip_time(modified: io::Result<SystemTime>) -> DateTime {
+    let Ok(modified) = modified else {
+        return DateTime::default();
+    };
+    let local = chrono::DateTime::<Local>::from(modified);
+
+    u16::try_from(local.year())
+        .ok()
+        .and_then(|year| {
+            DateTime::from_date_and_time(
+                year,
+                local.month() as u8,
+                local.day() as u8,
+                local.hour() as u8,
+                local.minute() as u8,
+                local.second() as u8,
+            )
+            .ok()
+        })
+        .unwrap_or_default()
+}
+
+fn file_time(time: DateTime) -> Option<FileTime> {
+    let local =
+        NaiveDate::from_ymd_opt(time.year().into(), time.month().into(), time.day().into())?
+            .and_hms_opt(
+                time.hour().into(),
+                time.minute().into(),
+                time.second().into(),
+            )?
+            .and_local_timezone(Local)
+            .earliest()?;
+
+    Some(FileTime::from_unix_time(local.timestamp(), 0))
+}
+
+// The options of an entry added from disk, with its modified time unless `deterministic`.
+fn zip_options(metadata: &fs::Metadata, level: u32, deterministic: bool) -> SimpleFileOptions {
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+
+        Some(metadata.permissions().mode())
+    };
+    #[cfg(not(unix))]
+    let mode = None;
+
+    let modified = match deterministic {
+        true => DateTime::default(),
+        false => zip_time(metadata.modified()),
+    };
+
+    entry_options(level, modified, metadata.len(), mode)
+}
+
+// A path as zip files store it, with `/` separators. Unlike `add_symlink_from_path`, the `..` of a relative link target is kept.
+fn zip_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+// Level `0` stores the entries as they are, deflate only supports the levels from `1` on.
+fn entry_options(
+    level: u32,
+    modified: DateTime,
+    size: u64,
+    mode: Option<u32>,
+) -> SimpleFileOptions {
+    let options = match level {
+        0 => SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+        level => SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .compression_level(Some(level.into())),
+    };
+    let options = options
+        .last_modified_time(modified)
+        .large_file(size >= u32::MAX as u64);
+
+    match mode {
+        Some(mode) => options.unix_permissions(mode),
+        None => options,
+    }
+}
+
+// The header of an entry that is not read from disk.
+fn tar_header(entry_type: EntryType, size: u64, mode: u32, modified: SystemTime) -> tar::Header {
+    let mut header = tar::Header::new_gnu();
+
+    header.set_entry_type(entry_type);
+    header.set_size(size);
+    header.set_mode(mode & 0o7777);
+    header.set_mtime(
+        modified
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+    );
+
+    header
+}
+
+// The compressor of a tar file, which must be finished to write the end of its stream.
+pub(crate) enum TarEncoder {
+    // An uncompressed tar file, only written when adding to one.
+    Plain(Volumes),
+    Gz(GzEncoder<Volumes>),
+    Zst(zstd::Encoder<'static, Volumes>),
+    #[cfg(feature = "xz")]
+    Xz(xz2::write::XzEncoder<Volumes>),
+    #[cfg(feature = "bzip2")]
+    Bz2(bzip2::write::BzEncoder<Volumes>),
+}
+
+impl Write for TarEncoder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            TarEncoder::Plain(file) => file.write(buf),
+            TarEncoder::Gz(encoder) => encoder.write(buf),
+            TarEncoder::Zst(encoder) => encoder.write(buf),
+            #[cfg(feature = "xz")]
+            TarEncoder::Xz(encoder) => encoder.write(buf),
+            #[cfg(feature = "bzip2")]
+            TarEncoder::Bz2(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            TarEncoder::Plain(file) => file.flush(),
+            TarEncoder::Gz(encoder) => encoder.flush(),
+            TarEncoder::Zst(encoder) => encoder.flush(),
+            #[cfg(feature = "xz")]
+            TarEncoder::Xz(encoder) => encoder.flush(),
+            #[cfg(feature = "bzip2")]
+            TarEncoder::Bz2(encoder) => encoder.flush(),
+        }
+    }
+}
+
+impl TarEncoder {
+    fn finish(self) -> io::Result<Volumes> {
+        match self {
+            TarEncoder::Plain(file) => Ok(file),
+            TarEncoder::Gz(encoder) => encoder.finish(),
+            TarEncoder::Zst(encoder) => encoder.finish(),
+            #[cfg(feature = "xz")]
+            TarEncoder::Xz(encoder) => encoder.finish(),
+            #[cfg(feature = "bzip2")]
+            TarEncoder::Bz2(encoder) => encoder.finish(),
+        }
+    }
+}
+
+/// Writes the entries of an archive in one of the supported formats.
+pub(crate) enum ArchiveWriter {
+    Tar(Box<tar::Builder<TarEncoder>>),
+    // The zip file, the compression level of its entries, the names of the entries it already has and whether the modified times
+    // of the files added from disk are left out.
+    Zip(Box<ZipWriter<Volumes>>, u32, HashSet<PathBuf>, bool),
+    // Nothing is written, the entries that would be are collected for a dry run.
+    DryRun(Vec<PlannedItem>),
+}
+
+impl ArchiveWriter {
+    /// Start an archive in `file`, with a compression level checked by [`ArchiveFormat::level`].
+    pub(crate) fn new(file: Volumes, format: ArchiveFormat, level: u32) -> io::Result<Self> {
+        let encoder = match format {
+            ArchiveFormat::Zip => {
+                return Ok(ArchiveWriter::Zip(
+                    Box::new(ZipWriter::new(file)),
+                    level,
+                    HashSet::new(),
+                    false,
+                ))
+            }
+            ArchiveFormat::TarGz => TarEncoder::Gz(GzEncoder::new(file, Compression::new(level))),
+            ArchiveFormat::TarZst => TarEncoder::Zst(zstd::Encoder::new(file, level as i32)?),
+            #[cfg(feature = "xz")]
+            ArchiveFormat::TarXz => TarEncoder::Xz(xz2::write::XzEncoder::new(file, level)),
+            #[cfg(feature = "bzip2")]
+            ArchiveFormat::TarBz2 => TarEncoder::Bz2(bzip2::write::BzEncoder::new(
+                file,
+                bzip2::Compression::new(level),
+            )),
+        };
+
+        Ok(tar_writer(encoder))
+    }
+
+    /// Leave the modified times, owners and groups of the entries added from disk out, so identical trees give identical archives.
+    ///
+    /// Tar entries get a fixed modified time, no owner and the permissions `0o755` or `0o644` depending on whether the file is
+    /// executable. Zip entries get the earliest modified time zip files can store, they have no owners.
+    pub(crate) fn set_deterministic(&mut self) {
+        match self {
+            ArchiveWriter::Tar(tar) => tar.mode(HeaderMode::Deterministic),
+            ArchiveWriter::Zip(.., deterministic) => *deterministic = true,
+            ArchiveWriter::DryRun(_) => {}
+        }
+    }
+
+    /// Add a directory entry with the metadata of the directory at `path`.
+    pub(crate) fn append_dir(&mut self, name: &Path, path: &Path) -> io::Result<()> {
+        match self {
+            ArchiveWriter::Tar(tar) => tar.append_dir(name, path),
+            // A directory that is already in the archive is shared with the entries added to it.
+            ArchiveWriter::Zip(_, _, names, _) if names.contains(name) => Ok(()),
+            ArchiveWriter::Zip(zip, level, names, deterministic) => {
+                let options = zip_options(&fs::metadata(path)?, *level, *deterministic);
+
+                zip.add_directory_from_path(name, options)?;
+                names.insert(name.to_path_buf());
+                Ok(())
+            }
+            ArchiveWriter::DryRun(items) => {
+                items.push(planned(name, path, 0));
+                Ok(())
+            }
+        }
+    }
+
+    /// Add the file at `path`, following symbolic links.
+    pub(crate) fn append_file(&mut self, name: &Path, path: &Path) -> io::Result<()> {
+        match self {
+            ArchiveWriter::Tar(tar) => tar.append_file(name, &mut File::open(path)?),
+            // Zip files can't hold two entries of the same name, unlike tar files where the later one wins when extracting.
+            ArchiveWriter::Zip(_, _, names, _) if names.contains(name) => Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} is already in the archive", name.display()),
+            )),
+            ArchiveWriter::Zip(zip, level, names, deterministic) => {
+                let mut file = File::open(path)?;
+
+                zip.start_file_from_path(
+                    name,
+                    zip_options(&file.metadata()?, *level, *deterministic),
+                )?;
+                io::copy(&mut file, zip)?;
+                names.insert(name.to_path_buf());
+                Ok(())
+            }
+            ArchiveWriter::DryRun(items) => {
+                items.push(planned(name, path, fs::metadata(path)?.len()));
+                Ok(())
+            }
+        }
+    }
+
+    /// Add the symbolic link at `path` as a link to its target, rather than the file or directory it points to.
+    pub(crate) fn append_symlink(&mut self, name: &Path, path: &Path) -> io::Result<()> {
+        match self {
+            ArchiveWriter::Tar(tar) => tar.append_path_with_name(path, name),
+            ArchiveWriter::Zip(_, _, names, _) if names.contains(name) => Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} is already in the archive", name.display()),
+            )),
+            ArchiveWriter::Zip(zip, level, names, deterministic) => {
+                let options = zip_options(&fs::symlink_metadata(path)?, *level, *deterministic);
+
+                zip.add_symlink(zip_path(name), zip_path(&fs::read_link(path)?), options)?;
+                names.insert(name.to_path_buf());
+                Ok(())
+            }
+            ArchiveWriter::DryRun(items) => {
+                items.push(planned(name, path, 0));
+                Ok(())
+            }
+        }
+    }
+
+    /// Add a directory entry read from another archive.
+    fn append_dir_entry(&mut self, name: &Path, mode: u32, modified: SystemTime) -> io::Result<()> {
+        match self {
+            ArchiveWriter::Tar(tar) => {
+                let mut header = tar_header(EntryType::Directory, 0, mode, modified);
+
+                tar.append_data(&mut header, name, io::empty())
+            }
+            ArchiveWriter::Zip(_, _, names, _) if names.contains(name) => Ok(()),
+            ArchiveWriter::Zip(zip, level, names, _) => {
+                let options = entry_options(*level, zip_time(Ok(modified)), 0, Some(mode));
+
+                zip.add_directory_from_path(name, options)?;
+                names.insert(name.to_path_buf());
+                Ok(())
+            }
+            // Entries are only read from another archive to convert or append to it, which a dry run doesn't do.
+            ArchiveWriter::DryRun(_) => Ok(()),
+        }
+    }
+
+    /// Add a file entry of `size` bytes read from another archive.
+    fn append_file_entry(
+        &mut self,
+        name: &Path,
+        size: u64,
+        mode: u32,
+        modified: SystemTime,
+        data: &mut impl Read,
+    ) -> io::Result<()> {
+        match self {
+            ArchiveWriter::Tar(tar) => {
+                let mut header = tar_header(EntryType::Regular, size, mode, modified);
+
+                tar.append_data(&mut header, name, data)
+            }
+            ArchiveWriter::Zip(zip, level, names, _) => {
+                let options = entry_options(*level, zip_time(Ok(modified)), size, Some(mode));
+
+                zip.start_file_from_path(name, options)?;
+                io::copy(data, zip)?;
+                names.insert(name.to_path_buf());
+                Ok(())
+            }
+            ArchiveWriter::DryRun(_) => Ok(()),
+        }
+    }
+
+    /// Add a symbolic link entry read from another archive.
+    fn append_symlink_entry(
+        &mut self,
+        name: &Path,
+        target: &Path,
+        modified: SystemTime,
+    ) -> io::Result<()> {
+        match self {
+            ArchiveWriter::Tar(tar) => {
+                let mut header = tar_header(EntryType::Symlink, 0, 0o777, modified);
+
+                tar.append_link(&mut header, name, target)
+            }
+            ArchiveWriter::Zip(zip, level, names, _) => {
+                let options = entry_options(*level, zip_time(Ok(modified)), 0, None);
+
+                zip.add_symlink(zip_path(name), zip_path(target), options)?;
+                names.insert(name.to_path_buf());
+                Ok(())
+            }
+            ArchiveWriter::DryRun(_) => Ok(()),
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<()> {
+        match self {
+            ArchiveWriter::Tar(tar) => {
+                tar.into_inner()?.finish()?;
+            }
+            ArchiveWriter::Zip(zip, ..) => {
+                zip.finish()?;
+            }
+            ArchiveWriter::DryRun(_) => {}
+        }
+
+        Ok(())
+    }
+}
+
+fn planned(name: &Path, path: &Path, size: u64) -> PlannedItem {
+    PlannedItem {
+        src_path: path.to_path_buf(),
+        dst_path: name.to_path_buf(),
+        size,
+    }
+}
+
+// Symbolic links are only stored as links when added with `append_symlink`, `append_file` opens the file they point to.
+fn tar_writer(encoder: TarEncoder) -> ArchiveWriter {
+    let mut builder = tar::Builder::new(encoder);
+    builder.follow_symlinks(false);
+
+    ArchiveWriter::Tar(Box::new(builder))
+}
+
+/// Start a copy of the archive at `src_path` in `file` to add more entries to, in the format detected from its contents.
+///
+/// Compressed streams can't be extended in place, so the existing entries are copied over first: zip entries as stored without
+/// decompressing them, tar entries recompressed with `level`, which is checked against the detected format.
+pub(crate) fn reopen(src_path: &Path, file: Volumes, level: Option<u32>) -> Result<ArchiveWriter> {
+    let (src_file, format) = open(src_path)?;
+
+    let (format, reader): (Option<ArchiveFormat>, Box<dyn Read>) = match format {
+        DetectedFormat::Zip => return reopen_zip(src_file, file, ArchiveFormat::Zip.level(level)?),
+        // A plain tar file stays uncompressed.
+        DetectedFormat::Tar => (None, Box::new(src_file)),
+        DetectedFormat::TarGz => (
+            Some(ArchiveFormat::TarGz),
+            Box::new(GzDecoder::new(src_file)),
+        ),
+        DetectedFormat::TarZst => (
+            Some(ArchiveFormat::TarZst),
+            Box::new(zstd::Decoder::new(src_file)?),
+        ),
+        #[cfg(feature = "xz")]
+        DetectedFormat::TarXz => (
+            Some(ArchiveFormat::TarXz),
+            Box::new(xz2::read::XzDecoder::new_multi_decoder(src_file)),
+        ),
+        #[cfg(feature = "bzip2")]
+        DetectedFormat::TarBz2 => (
+            Some(ArchiveFormat::TarBz2),
+            Box::new(bzip2::read::MultiBzDecoder::new(src_file)),
+        ),
+    };
+
+    let mut writer = match format {
+        Some(format) => ArchiveWriter::new(file, format, format.level(level)?)?,
+        None => tar_writer(TarEncoder::Plain(file)),
+    };
+
+    if let ArchiveWriter::Tar(tar) = &mut writer {
+        // Raw entries include the extension headers of long names and PAX attributes, so they are copied as they are.
+        for entry in Archive::new(reader).entries()?.raw(true) {
+            let mut entry = entry?;
+            let header = entry.header().clone();
+
+            tar.append(&header, &mut entry)?;
+        }
+    }
+
+    Ok(writer)
+}
+
+fn reopen_zip(src_file: Volumes, file: Volumes, level: u32) -> Result<ArchiveWriter> {
+    let mut archive = ZipArchive::new(src_file)?;
+    let mut zip = ZipWriter::new(file);
+    let mut names = HashSet::with_capacity(archive.len());
+
+    for index in 0..archive.len() {
+        let entry = archive.by_index_raw(index)?;
+
+        names.insert(PathBuf::from(entry.name()));
+        zip.raw_copy_file(entry)?;
+    }
+
+    Ok(ArchiveWriter::Zip(Box::new(zip), level, names, false))
+}
+
+/// Write the entries of the archive at `src_path`, in any format `unpack` supports, to `writer`, streamed from one to the other
+/// without extracting anything.
+///
+/// Files, directories and symbolic links are converted with their permissions and modified times. Hard links, devices and named
+/// pipes of tar files have no equivalent in zip files and are left out.
+pub(crate) fn convert(
+    src_path: &Path,
+    writer: &mut ArchiveWriter,
+    cancel: &CancelToken,
+) -> Result<()> {
+    let (src_file, format) = open(src_path)?;
+
+    match format {
+        DetectedFormat::Tar => convert_tar(src_file, writer, cancel),
+        DetectedFormat::TarGz => convert_tar(GzDecoder::new(src_file), writer, cancel),
+        DetectedFormat::TarZst => convert_tar(zstd::Decoder::new(src_file)?, writer, cancel),
+        #[cfg(feature = "xz")]
+        DetectedFormat::TarXz => convert_tar(
+            xz2::read::XzDecoder::new_multi_decoder(src_file),
+            writer,
+            cancel,
+        ),
+        #[cfg(feature = "bzip2")]
+        DetectedFormat::TarBz2 => {
+            convert_tar(bzip2::read::MultiBzDecoder::new(src_file), writer, cancel)
+        }
+        DetectedFormat::Zip => convert_zip(src_file, writer, cancel),
+    }
+}
+
+fn convert_tar<R: Read>(reader: R, writer: &mut ArchiveWriter, cancel: &CancelToken) -> Result<()> {
+    let mut archive = Archive::new(reader);
+
+    for entry in archive.entries()? {
+        cancel.check()?;
+
+        let mut entry = entry?;
+        let path = entry.path()?.to_path_buf();
+        // Tar files often store their entries as `./name`, which other archives don't.
+        let name = path.strip_prefix(".").unwrap_or(&path).to_path_buf();
+
+        if name.as_os_str().is_empty() {
+            continue;
+        }
+
+        let header = entry.header();
+        let mode = header.mode()?;
+        let modified = UNIX_EPOCH + Duration::from_secs(header.mtime().unwrap_or_default());
+
+        match header.entry_type() {
+            EntryType::Directory => writer.append_dir_entry(&name, mode, modified)?,
+            EntryType::Regular | EntryType::Continuous => {
+                let size = entry.size();
+
+                writer.append_file_entry(&name, size, mode, modified, &mut entry)?;
+            }
+            EntryType::Symlink => {
+                if let Some(target) = entry.link_name()? {
+                    writer.append_symlink_entry(&name, &target, modified)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn convert_zip(src_file: Volumes, writer: &mut ArchiveWriter, cancel: &CancelToken) -> Result<()> {
+    let mut archive = ZipArchive::new(src_file)?;
+
+    for index in 0..archive.len() {
+        cancel.check()?;
+
+        let mut entry = archive.by_index(index)?;
+
+        // Entries that would end up outside the destination when extracted, e.g. through `..`, are refused.
+        let Some(name) = entry.enclosed_name() else {
+            return Err(Error::InvalidPath(PathBuf::from(entry.name())));
+        };
+        let modified = entry
+            .last_modified()
+            .and_then(file_time)
+            .and_then(|time| u64::try_from(time.unix_seconds()).ok())
+            .map_or(UNIX_EPOCH, |secs| UNIX_EPOCH + Duration::from_secs(secs));
+
+        if entry.is_dir() {
+            let mode = entry.unix_mode().unwrap_or(0o755);
+
+            writer.append_dir_entry(&name, mode, modified)?;
+        } else if entry.is_symlink() {
+            let mut target = String::new();
+            entry.read_to_string(&mut target)?;
+
+            writer.append_symlink_entry(&name, Path::new(&target), modified)?;
+        } else {
+            let mode = entry.unix_mode().unwrap_or(0o644);
+            let size = entry.size();
+
+            writer.append_file_entry(&name, size, mode, modified, &mut entry)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Open an archive, or the set of volumes it was split into, and detect its format from the first bytes.
+fn open(src_path: &Path) -> Result<(Volumes, DetectedFormat)> {
+    let mut src_file = Volumes::open(src_path)?;
+    let mut header = Vec::with_capacity(512);
+
+    (&mut src_file).take(512).read_to_end(&mut header)?;
+    src_file.rewind()?;
+
+    match detect(&header) {
+        Some(format) => Ok((src_file, format)),
+        None => Err(Error::UnsupportedArchive(src_path.to_path_buf())),
+    }
+}
+
+/// Compress the file at `src_path` with gzip on its own, without a tar archive around it, as the `gzip` tool does. Its name and
+/// modified time are stored in the gzip header.
+pub(crate) fn gzip<W: Write>(
+    src_path: &Path,
+    writer: W,
+    level: u32,
+    buffer_limit: BufferLimit,
+    cancel: &CancelToken,
+) -> Result<()> {
+    let mut src_file = File::open(src_path)?;
+    let modified = src_file
+        .metadata()?
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_secs() as u32);
+    let mut builder = GzBuilder::new().mtime(modified);
+
+    if let Some(name) = src_path.file_name() {
+        builder = builder.filename(name.to_string_lossy().as_bytes());
+    }
+
+    let mut encoder = builder.write(writer, Compression::new(level));
+
+    copy_stream(&mut src_file, &mut encoder, buffer_limit, cancel)?;
+    encoder.finish()?;
+
+    Ok(())
+}
+
+/// Decompress the gzip file at `src_path` holding a single file rather than a tar archive. A file of several gzip members, as
+/// concatenating gzip files or `pigz` writes, is decompressed whole.
+pub(crate) fn gunzip<W: Write>(
+    src_path: &Path,
+    mut writer: W,
+    buffer_limit: BufferLimit,
+    cancel: &CancelToken,
+) -> Result<()> {
+    let (src_file, format) = open(src_path)?;
+
+    // A tar.gz file is a gzip file too, and gives the tar archive.
+    if format != DetectedFormat::TarGz {
+        return Err(Error::UnsupportedArchive(src_path.to_path_buf()));
+    }
+
+    copy_stream(
+        &mut MultiGzDecoder::new(src_file),
+        &mut writer,
+        buffer_limit,
+        cancel,
+    )?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn copy_stream<R: Read, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    buffer_limit: BufferLimit,
+    cancel: &CancelToken,
+) -> Result<()> {
+    let mut buffer = vec![0; buffer_limit.size(1024 * 1024)];
+
+    loop {
+        cancel.check()?;
+
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        };
+
+        writer.write_all(&buffer[..read])?;
+    }
+}
+
+/// List the entries of an archive in the formats `unpack` supports, nothing is extracted.
+///
+/// The names of zip entries not flagged as UTF-8 are decoded with `name_encoding`.
+pub(crate) fn entries(src_path: &Path, name_encoding: NameEncoding) -> Result<Vec<ArchiveEntry>> {
+    let entries = read_entries(src_path, false, name_encoding)?;
+
+    Ok(entries.into_iter().map(|entry| entry.entry).collect())
+}
+
+/// Read the entries of an archive with the SHA-256 hashes of their data, to describe it without extracting it.
+pub(crate) fn manifest(src_path: &Path) -> Result<ArchiveManifest> {
+    let entries = read_entries(src_path, true, NameEncoding::default())?;
+
+    Ok(ArchiveManifest {
+        entry_count: entries.len() as u64,
+        total_size: entries.iter().map(|entry| entry.entry.size).sum(),
+        entries,
+    })
+}
+
+// The entries are hashed when `hashed` is set, which reads their data, only their headers are read otherwise.
+fn read_entries(
+    src_path: &Path,
+    hashed: bool,
+    name_encoding: NameEncoding,
+) -> Result<Vec<ArchiveManifestEntry>> {
+    let (src_file, format) = open(src_path)?;
+
+    match format {
+        DetectedFormat::Tar => tar_entries(src_file, hashed),
+        DetectedFormat::TarGz => tar_entries(GzDecoder::new(src_file), hashed),
+        DetectedFormat::TarZst => tar_entries(zstd::Decoder::new(src_file)?, hashed),
+        #[cfg(feature = "xz")]
+        DetectedFormat::TarXz => {
+            tar_entries(xz2::read::XzDecoder::new_multi_decoder(src_file), hashed)
+        }
+        #[cfg(feature = "bzip2")]
+        DetectedFormat::TarBz2 => tar_entries(bzip2::read::MultiBzDecoder::new(src_file), hashed),
+        DetectedFormat::Zip => zip_entries(src_file, hashed, name_encoding),
+    }
+}
+
+fn sha256<R: Read>(reader: &mut R) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(reader, &mut hasher)?;
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+fn tar_entries<R: Read>(reader: R, hashed: bool) -> Result<Vec<ArchiveManifestEntry>> {
+    let mut archive = Archive::new(reader);
+    let mut entries = Vec::new();
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let header = entry.header();
+        let file_type = match header.entry_type() {
+            // Hard links are listed as the files they link to.
+            EntryType::Regular | EntryType::Continuous | EntryType::Link => FileType::File,
+            EntryType::Directory => FileType::Dir,
+            EntryType::Symlink => FileType::Symlink,
+            EntryType::Fifo => FileType::Fifo,
+            EntryType::Block => FileType::Block,
+            EntryType::Char => FileType::Char,
+            // Metadata of the following entry, such as long names or extended headers.
+            _ => continue,
+        };
+
+        let archive_entry = ArchiveEntry {
+            path: entry.path()?.to_path_buf(),
+            size: entry.size(),
+            file_type,
+            modified_at: u128::from(header.mtime().unwrap_or_default()) * 1000,
+        };
+        // A hard link holds no data of its own.
+        let has_data = matches!(
+            header.entry_type(),
+            EntryType::Regular | EntryType::Continuous
+        );
+        let sha256 = match hashed && has_data {
+            true => Some(sha256(&mut entry)?),
+            false => None,
+        };
+
+        entries.push(ArchiveManifestEntry {
+            entry: archive_entry,
+            sha256,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn zip_entries(
+    src_file: Volumes,
+    hashed: bool,
+    name_encoding: NameEncoding,
+) -> Result<Vec<ArchiveManifestEntry>> {
+    let mut archive = ZipArchive::new(src_file)?;
+    let mut entries = Vec::with_capacity(archive.len());
+    let encoding = zip_name_encoding(&mut archive, name_encoding)?;
+
+    for index in 0..archive.len() {
+        // Read the entry as stored unless it's hashed, its data is not decompressed then.
+        let mut entry = match hashed {
+            true => archive.by_index(index)?,
+            false => archive.by_index_raw(index)?,
+        };
+        let file_type = if entry.is_dir() {
+            FileType::Dir
+        } else if entry.is_symlink() {
+            FileType::Symlink
+        } else {
+            FileType::File
+        };
+        let modified_at = entry
+            .last_modified()
+            .and_then(file_time)
+            .map(|time| u128::try_from(time.unix_seconds()).unwrap_or_default() * 1000)
+            .unwrap_or_default();
+
+        let archive_entry = ArchiveEntry {
+            path: PathBuf::from(&*zip_name(&entry, encoding)),
+            size: entry.size(),
+            file_type,
+            modified_at,
+        };
+        let sha256 = match hashed && file_type == FileType::File {
+            true => Some(sha256(&mut entry)?),
+            false => None,
+        };
+
+        entries.push(ArchiveManifestEntry {
+            entry: archive_entry,
+            sha256,
+        });
+    }
+
+    Ok(entries)
+}
+
+// The encoding to decode the names not flagged as UTF-8 with, `None` for CP437 which the zip crate decodes them with already.
+fn zip_name_encoding(
+    archive: &mut ZipArchive<Volumes>,
+    name_encoding: NameEncoding,
+) -> Result<Option<&'static Encoding>> {
+    match name_encoding {
+        NameEncoding::Cp437 => return Ok(None),
+        NameEncoding::Gbk => return Ok(Some(GBK)),
+        NameEncoding::ShiftJis => return Ok(Some(SHIFT_JIS)),
+        NameEncoding::Auto => {}
+    }
+
+    let mut detector = EncodingDetector::new();
+    let mut fed = false;
+
+    for index in 0..archive.len() {
+        let entry = archive.by_index_raw(index)?;
+
+        if !is_utf8_name(&entry) {
+            detector.feed(entry.name_raw(), false);
+            fed = true;
+        }
+    }
+
+    if !fed {
+        return Ok(None);
+    }
+
+    detector.feed(&[], true);
+
+    // A single-byte guess is as likely to be CP437, which the detector doesn't know.
+    let encoding = detector.guess(None, true);
+
+    Ok([GBK, SHIFT_JIS, BIG5, EUC_KR, EUC_JP]
+        .contains(&encoding)
+        .then_some(encoding))
+}
+
+// The name is kept as decoded when it's UTF-8, flagged as such, given in a Unicode extra field or plain ASCII.
+fn is_utf8_name(entry: &ZipFile<'_>) -> bool {
+    entry.name().as_bytes() == entry.name_raw()
+}
+
+fn zip_name<'a>(entry: &'a ZipFile<'_>, encoding: Option<&'static Encoding>) -> Cow<'a, str> {
+    match encoding {
+        Some(encoding) if !is_utf8_name(entry) => {
+            encoding.decode_without_bom_handling(entry.name_raw()).0
+        }
+        _ => Cow::Borrowed(entry.name()),
+    }
+}
+
+// The name as a relative path that stays inside the directory it's extracted to, as `ZipFile::enclosed_name` checks it.
+fn enclosed_name(name: &str) -> Option<PathBuf> {
+    if name.contains('\0') {
+        return None;
+    }
+
+    let path = PathBuf::from(name);
+    let mut depth = 0usize;
+
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => return None,
+            Component::ParentDir => depth = depth.checked_sub(1)?,
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+        }
+    }
+
+    Some(path)
+}
+
+// Whether an entry is to be extracted, matched itself or inside a matched directory, all entries are without a selection.
+fn is_selected(selection: Option<&GlobSet>, path: &Path) -> bool {
+    let Some(selection) = selection else {
+        return true;
+    };
+    // Tar files often store their entries as `./name`.
+    let path = path.strip_prefix(".").unwrap_or(path);
+
+    path.ancestors()
+        .any(|ancestor| !ancestor.as_os_str().is_empty() && selection.is_match(ancestor))
+}
+
+/// How [`unpack`] extracts the entries.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct UnpackOptions {
+    // Move the files the entries replace to the trash.
+    pub(crate) backup_to_trash: bool,
+    // Restore the setuid, setgid and sticky bits of tar entries too, only the `0o777` bits are restored otherwise.
+    pub(crate) preserve_permissions: bool,
+    // Restore the owners and groups of tar entries, which takes the privileges to change them.
+    pub(crate) preserve_ownership: bool,
+    // Extract the entries and links leading outside the destination as they are, for trusted archives.
+    pub(crate) allow_external_paths: bool,
+    // The number of leading components removed from the names of the entries, the entries with no more are skipped.
+    pub(crate) strip_components: u32,
+    // What happens to the files that exist before the extraction where an entry goes.
+    pub(crate) on_conflict: OverwritePolicy,
+    // Leave out the files a previous extraction into the same destination already completed.
+    pub(crate) resume: bool,
+    // How the names of zip entries not flagged as UTF-8 are decoded.
+    pub(crate) name_encoding: NameEncoding,
+    // The largest buffer held at once.
+    pub(crate) buffer_limit: BufferLimit,
+}
+
+/// Extract an archive into `dst_path`.
+///
+/// The format is detected from the contents: a tar file, compressed with gzip, zstd, xz or bzip2 or not at all, or a zip file. With a
+/// `selection`, only the entries it matches and the entries inside the directories it matches are extracted.
+///
+/// With `strip_components`, the leading components are removed from the names of the entries and the targets of hard links, as
+/// `tar --strip-components` does, after the selection is matched against the full names.
+///
+/// A file that exists before the extraction where an entry goes is handled after `on_conflict`, the files and links the entries
+/// skipped or renamed are in the report. Existing directories are merged into, and an entry of the same name as an earlier entry
+/// replaces it.
+///
+/// With `resume`, the files a previous extraction completed before it failed are left as they are instead of being extracted
+/// again: files of the same size and modified time for tar entries, of the same size and CRC-32 for zip entries. The files that
+/// don't match are handled after `on_conflict`.
+///
+/// Unless `allow_external_paths` is set, an entry leading outside `dst_path` stops the extraction with [`Error::InvalidPath`]: an
+/// absolute path, a path through `..`, a path through a link extracted before it, or a link pointing outside. The entries extracted
+/// before it are kept.
+///
+/// `progress` is called before each entry the selection and `strip_components` leave is extracted, with its name in the destination
+/// and uncompressed size.
+pub(crate) fn unpack(
+    src_path: &Path,
+    dst_path: &Path,
+    selection: Option<&GlobSet>,
+    options: UnpackOptions,
+    cancel: &CancelToken,
+    progress: impl FnMut(&Path, u64),
+) -> Result<DecompressReport> {
+    let (src_file, format) = open(src_path)?;
+
+    match format {
+        DetectedFormat::Tar => unpack_tar(src_file, dst_path, selection, options, cancel, progress),
+        DetectedFormat::TarGz => unpack_tar(
+            GzDecoder::new(src_file),
+            dst_path,
+            selection,
+            options,
+            cancel,
+            progress,
+        ),
+        DetectedFormat::TarZst => unpack_tar(
+            zstd::Decoder::new(src_file)?,
+            dst_path,
+            selection,
+            options,
+            cancel,
+            progress,
+        ),
+        // Archives of parallel compressors such as `pixz` or `pbzip2` consist of several streams.
+        #[cfg(feature = "xz")]
+        DetectedFormat::TarXz => unpack_tar(
+            xz2::read::XzDecoder::new_multi_decoder(src_file),
+            dst_path,
+            selection,
+            options,
+            cancel,
+            progress,
+        ),
+        #[cfg(feature = "bzip2")]
+        DetectedFormat::TarBz2 => unpack_tar(
+            bzip2::read::MultiBzDecoder::new(src_file),
+            dst_path,
+            selection,
+            options,
+            cancel,
+            progress,
+        ),
+        DetectedFormat::Zip => unpack_zip(src_file, dst_path, selection, options, cancel, progress),
+    }
+}
+
+/// Count the entries [`unpack`] extracts with the same `selection` and `strip_components`, along with their uncompressed size. A
+/// compressed tar file is read through once for it, a zip file only has its central directory read.
+pub(crate) fn totals(
+    src_path: &Path,
+    selection: Option<&GlobSet>,
+    strip_components: u32,
+    name_encoding: NameEncoding,
+) -> Result<(u64, u64)> {
+    let mut count = 0;
+    let mut size = 0;
+
+    for entry in entries(src_path, name_encoding)? {
+        let path = entry.path;
+
+        #[cfg(target_os = "windows")]
+        let path = PathBuf::from(path.to_string_lossy().replace("\\", "/"));
+
+        if is_selected(selection, &path)
+            && self::strip_components(&path, strip_components).is_some()
+        {
+            count += 1;
+            size += entry.size;
+        }
+    }
+
+    Ok((count, size))
+}
+
+fn unpack_tar<R: Read>(
+    reader: R,
+    dst_path: &Path,
+    selection: Option<&GlobSet>,
+    options: UnpackOptions,
+    cancel: &CancelToken,
+    mut progress: impl FnMut(&Path, u64),
+) -> Result<DecompressReport> {
+    let mut archive = Archive::new(reader);
+    archive.set_preserve_permissions(options.preserve_permissions);
+    archive.set_preserve_ownerships(options.preserve_ownership);
+
+    let canonical_dst = dst_path.canonicalize()?;
+    let mut report = DecompressReport::default();
+    // The paths of the entries extracted so far and where they were written, `None` for the skipped ones.
+    let mut extracted = HashMap::new();
+
+    for entry in archive.entries()? {
+        cancel.check()?;
+
+        let mut entry = entry?;
+        let name = entry.path()?.to_path_buf();
+
+        #[cfg(target_os = "windows")]
+        let name = Path::new(&name.to_string_lossy().replace("\\", "/")).to_path_buf();
+
+        if !is_selected(selection, &name) {
+            continue;
+        }
+
+        let Some(name) = strip_components(&name, options.strip_components) else {
+            continue;
+        };
+
+        progress(&name, entry.size());
+
+        let path = dst_path.join(&name);
+        let entry_type = entry.header().entry_type();
+        let link_target = match (entry_type, entry.link_name()?) {
+            // Hard links are relative to the root of the archive, which loses the same components.
+            (EntryType::Link, Some(target)) => {
+                match strip_components(&target, options.strip_components) {
+                    Some(target) => Some(target),
+                    None => return Err(Error::InvalidPath(target.to_path_buf())),
+                }
+            }
+            (_, target) => target.map(|target| target.to_path_buf()),
+        };
+
+        if !options.allow_external_paths {
+            // Unlike zip files, tar files have no `..` in the names of well-formed entries, even when it would stay inside.
+            let is_relative = name
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+            // Symbolic links are relative to their directory, hard links to the root of the archive.
+            let is_enclosed_link = match (entry_type, &link_target) {
+                (EntryType::Symlink, Some(target)) => {
+                    let link_dir = name.parent().unwrap_or(Path::new(""));
+
+                    is_enclosed(&name, target)
+                        && is_link_inside(&canonical_dst, dst_path, link_dir, target)
+                }
+                (EntryType::Link, Some(target)) => {
+                    is_enclosed(Path::new(""), target)
+                        && is_link_inside(&canonical_dst, dst_path, Path::new(""), target)
+                }
+                _ => true,
+            };
+
+            if !is_relative || !is_enclosed_link || !is_inside(&canonical_dst, dst_path, &name)? {
+                return Err(Error::InvalidPath(name));
+            }
+        }
+
+        // The directory entries above a selected entry may not be selected themselves.
+        if selection.is_some() {
+            if let Some(parent) = path.parent() {
+                create_dir_all(parent)?;
+            }
+        }
+
+        if options.resume && entry_type.is_file() && is_extracted_tar(&path, entry.header())? {
+            extracted.insert(path.clone(), Some(path));
+            continue;
+        }
+
+        let written_path = match entry_type {
+            EntryType::Directory => path.clone(),
+            _ => match place(&path, options.on_conflict, &mut extracted, &mut report)? {
+                Some(written_path) => written_path,
+                None => continue,
+            },
+        };
+
+        if options.backup_to_trash && entry_type.is_file() && written_path.is_file() {
+            trash::delete(&written_path)?;
+        }
+
+        // `unpack_in` only knows the names in the archive, so stripped and renamed entries are written to their paths here. It
+        // doesn't replace hard links either, which a resumed extraction finds in place.
+        let is_moved =
+            options.strip_components > 0 || options.on_conflict == OverwritePolicy::Rename;
+
+        match (entry_type, link_target) {
+            (EntryType::Link, Some(target)) if is_moved || options.resume => {
+                let target = dst_path.join(target);
+                let target = match extracted.get(&target) {
+                    Some(Some(written_target)) => written_target,
+                    _ => &target,
+                };
+
+                create_dir_all(written_path.parent().unwrap_or(dst_path))?;
+
+                if fs::symlink_metadata(&written_path).is_ok() {
+                    fs::remove_file(&written_path)?;
+                }
+
+                fs::hard_link(target, &written_path)?;
+            }
+            _ if is_moved => {
+                create_dir_all(written_path.parent().unwrap_or(dst_path))?;
+                entry.unpack(&written_path)?;
+            }
+            _ if options.allow_external_paths => {
+                entry.unpack(&path)?;
+            }
+            // Also resolves hard links inside the destination, rather than from the working directory.
+            _ => {
+                entry.unpack_in(dst_path)?;
+            }
+        }
+
+        let size = match entry_type.is_file() {
+            true => entry.size(),
+            false => 0,
+        };
+
+        report.add(dst_path, &written_path, entry_type.is_dir(), size);
+    }
+
+    Ok(report)
+}
+
+fn unpack_zip(
+    src_file: Volumes,
+    dst_path: &Path,
+    selection: Option<&GlobSet>,
+    options: UnpackOptions,
+    cancel: &CancelToken,
+    mut progress: impl FnMut(&Path, u64),
+) -> Result<DecompressReport> {
+    let mut archive = ZipArchive::new(src_file)?;
+    let canonical_dst = dst_path.canonicalize()?;
+    let mut report = DecompressReport::default();
+    let mut extracted = HashMap::new();
+    let encoding = zip_name_encoding(&mut archive, options.name_encoding)?;
+
+    for index in 0..archive.len() {
+        cancel.check()?;
+
+        let mut entry = archive.by_index(index)?;
+
+        let decoded = zip_name(&entry, encoding).into_owned();
+
+        // Entries that would end up outside the destination, e.g. through `..`, are refused.
+        let name = match enclosed_name(&decoded) {
+            Some(name) => name,
+            None if options.allow_external_paths => PathBuf::from(decoded),
+            None => return Err(Error::InvalidPath(PathBuf::from(decoded))),
+        };
+
+        if !is_selected(selection, &name) {
+            continue;
+        }
+
+        let Some(name) = strip_components(&name, options.strip_components) else {
+            continue;
+        };
+
+        progress(&name, entry.size());
+
+        let path = dst_path.join(&name);
+
+        if !options.allow_external_paths && !is_inside(&canonical_dst, dst_path, &name)? {
+            return Err(Error::InvalidPath(name));
+        }
+
+        if entry.is_dir() {
+            create_dir_all(&path)?;
+            report.add(dst_path, &path, true, 0);
+            continue;
+        }
+
+        if options.resume
+            && entry.is_file()
+            && is_extracted_zip(&path, entry.size(), entry.crc32(), options.buffer_limit)?
+        {
+            extracted.insert(path.clone(), Some(path));
+            continue;
+        }
+
+        let Some(path) = place(&path, options.on_conflict, &mut extracted, &mut report)? else {
+            continue;
+        };
+
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+
+        if options.backup_to_trash && path.is_file() {
+            trash::delete(&path)?;
+        }
+
+        // Replaced like a file would be, rather than written through.
+        if fs::symlink_metadata(&path).is_ok_and(|metadata| metadata.is_symlink()) {
+            fs::remove_file(&path)?;
+        }
+
+        // Symbolic links are restored as links where they can be created without privileges, as files holding the target otherwise.
+        #[cfg(unix)]
+        if entry.is_symlink() {
+            let mut target = String::new();
+            entry.read_to_string(&mut target)?;
+
+            if !options.allow_external_paths {
+                let target = Path::new(&target);
+                let link_dir = name.parent().unwrap_or(Path::new(""));
+
+                if !is_enclosed(&name, target)
+                    || !is_link_inside(&canonical_dst, dst_path, link_dir, target)
+                {
+                    return Err(Error::InvalidPath(name));
+                }
+            }
+
+            if path.is_file() {
+                fs::remove_file(&path)?;
+            }
+
+            std::os::unix::fs::symlink(target, &path)?;
+            report.add(dst_path, &path, false, 0);
+            continue;
+        }
+
+        let written = io::copy(&mut entry, &mut File::create(&path)?)?;
+
+        if let Some(modified) = entry.last_modified().and_then(file_time) {
+            filetime::set_file_mtime(&path, modified)?;
+        }
+
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mask = match options.preserve_permissions {
+                true => 0o7777,
+                false => 0o777,
+            };
+
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode & mask))?;
+        }
+
+        report.add(dst_path, &path, false, written);
+    }
+
+    Ok(report)
+}
+
+impl DecompressReport {
+    // Count an entry written to `path` in `dst_path`, along with the top-level directory it's in or is.
+    fn add(&mut self, dst_path: &Path, path: &Path, is_dir: bool, size: u64) {
+        self.entries_extracted += 1;
+        self.bytes_written += size;
+
+        // Entries written outside the destination with `allow_external_paths` have no directory in it.
+        let Ok(relative) = path.strip_prefix(dst_path) else {
+            return;
+        };
+        let mut components = relative.components();
+        let Some(root) = components.next() else {
+            return;
+        };
+
+        if !is_dir && components.next().is_none() {
+            return;
+        }
+
+        let root = dst_path.join(root);
+
+        if !self.root_dirs.contains(&root) {
+            self.root_dirs.push(root);
+        }
+    }
+}
+
+// Whether the file at `path` is the tar entry as a previous extraction wrote it. The modified time is set once the content is
+// written, so a file cut short has the time it was last written to instead.
+fn is_extracted_tar(path: &Path, header: &tar::Header) -> Result<bool> {
+    let Ok(metadata) = fs::metadata(path) else {
+        return Ok(false);
+    };
+    let modified = FileTime::from_last_modification_time(&metadata);
+
+    Ok(metadata.is_file()
+        && metadata.len() == header.size()?
+        && modified.unix_seconds() == header.mtime()? as i64)
+}
+
+// Whether the file at `path` is the zip entry as a previous extraction wrote it, comparing the checksum the entry carries.
+fn is_extracted_zip(
+    path: &Path,
+    size: u64,
+    crc32: u32,
+    buffer_limit: BufferLimit,
+) -> io::Result<bool> {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() && metadata.len() == size => {}
+        _ => return Ok(false),
+    }
+
+    let mut file = File::open(path)?;
+    let mut hasher = crc32fast::Hasher::new();
+    let mut buffer = vec![0; buffer_limit.size(1024 * 1024)];
+
+    loop {
+        let read = match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher.finalize() == crc32)
+}
+
+// Where the entry going to `path` is written after the policy, `None` when it's skipped. Only the files that existed before the
+// extraction conflict, a later entry of the same name goes where the earlier one went.
+fn place(
+    path: &Path,
+    policy: OverwritePolicy,
+    extracted: &mut HashMap<PathBuf, Option<PathBuf>>,
+    report: &mut DecompressReport,
+) -> Result<Option<PathBuf>> {
+    if let Some(written_path) = extracted.get(path) {
+        return Ok(written_path.clone());
+    }
+
+    let written_path = match fs::symlink_metadata(path) {
+        Err(_) => Some(path.to_path_buf()),
+        Ok(_) => match policy {
+            OverwritePolicy::Overwrite => Some(path.to_path_buf()),
+            OverwritePolicy::Skip => {
+                report.skipped.push(path.to_path_buf());
+                None
+            }
+            OverwritePolicy::Error => return Err(Error::AlreadyExists(path.to_path_buf())),
+            OverwritePolicy::Rename => {
+                let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+                    return Err(Error::InvalidPath(path.to_path_buf()));
+                };
+                let renamed_to = available_path(dir, file_name);
+
+                report.renamed.push(RenamedEntry {
+                    path: path.to_path_buf(),
+                    renamed_to: renamed_to.clone(),
+                });
+
+                Some(renamed_to)
+            }
+        },
+    };
+
+    extracted.insert(path.to_path_buf(), written_path.clone());
+
+    Ok(written_path)
+}
+
+// The name without its first `count` components, e.g. `foo-1.2.3/src/lib.rs` becomes `src/lib.rs` with 1. The root and `.` don't
+// count, as with tar. `None` when no component is left.
+fn strip_components(name: &Path, count: u32) -> Option<PathBuf> {
+    if count == 0 {
+        return Some(name.to_path_buf());
+    }
+
+    let stripped: PathBuf = name
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_) | Component::ParentDir))
+        .skip(count as usize)
+        .collect();
+
+    match stripped.as_os_str().is_empty() {
+        true => None,
+        false => Some(stripped),
+    }
+}
+
+// Whether the target of a link at `name` inside the archive points inside it, resolving `..` without touching the disk.
+fn is_enclosed(name: &Path, target: &Path) -> bool {
+    let mut depth = name.components().count().saturating_sub(1);
+
+    for component in target.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+// Whether the target of a link in `link_dir` leads inside the canonical destination on disk. Unlike `is_enclosed`, the `..` after
+// a link extracted before is resolved from where that link points, as the system resolves it. A target that can't be resolved,
+// such as through a file, is refused.
+fn is_link_inside(canonical_dst: &Path, dst_path: &Path, link_dir: &Path, target: &Path) -> bool {
+    !target.has_root()
+        && resolve(&dst_path.join(link_dir).join(target))
+            .is_ok_and(|resolved| resolved.starts_with(canonical_dst))
+}
+
+// Where `path` leads: the part that exists is resolved through the links on the way, the rest without touching the disk.
+fn resolve(path: &Path) -> io::Result<PathBuf> {
+    let mut resolved = PathBuf::new();
+    let mut exists = true;
+
+    for component in path.components() {
+        if component == Component::CurDir {
+            continue;
+        }
+
+        if exists {
+            match resolved.join(component).canonicalize() {
+                Ok(canonical) => {
+                    resolved = canonical;
+                    continue;
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => exists = false,
+                Err(err) => return Err(err),
+            }
+        }
+
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
+        }
+    }
+
+    Ok(resolved)
+}
+
+// Whether the entry at `name` is written inside the canonical destination, following the links extracted before on the way. Only
+// the part of its directory that exists so far can be resolved, the rest is created as plain directories.
+fn is_inside(canonical_dst: &Path, dst_path: &Path, name: &Path) -> io::Result<bool> {
+    let Some(parent) = name.parent() else {
+        return Ok(false);
+    };
+
+    for ancestor in dst_path.join(parent).ancestors() {
+        match ancestor.canonicalize() {
+            Ok(canonical) => return Ok(canonical.starts_with(canonical_dst)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, SeekFrom};
+
+    // The version needed to extract the entry, read from the local header at the start of a zip file holding only it.
+    fn version_needed(size: u64) -> u16 {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("entry", entry_options(0, DateTime::default(), size, None))
+            .unwrap();
+        let bytes = zip.finish().unwrap().into_inner();
+
+        u16::from_le_bytes([bytes[4], bytes[5]])
+    }
+
+    #[test]
+    fn entry_options_use_zip64_from_u32_max() {
+        // Zip64 needs version 4.5 to extract.
+        assert!(version_needed(u32::MAX as u64 - 1) < 45);
+        assert_eq!(version_needed(u32::MAX as u64), 45);
+        assert_eq!(version_needed(u32::MAX as u64 + 1), 45);
+    }
+
+    #[test]
+    #[ignore = "writes more than 4 GiB to the temp directory, run with `cargo test -- --ignored`"]
+    fn zip_round_trips_a_sparse_file_over_4_gib() {
+        let dir = std::env::temp_dir().join(format!("fs-pro-zip64-{}", std::process::id()));
+        let src_path = dir.join("large.bin");
+        let zip_path = dir.join("large.zip");
+        let dst_path = dir.join("extracted");
+        let size = u32::MAX as u64 + 4096;
+
+        fs::create_dir_all(&dst_path).unwrap();
+
+        // Only the tail is written, the rest is a hole on file systems that support them.
+        let mut file = File::create(&src_path).unwrap();
+        file.set_len(size).unwrap();
+        file.seek(SeekFrom::End(-4)).unwrap();
+        file.write_all(b"tail").unwrap();
+        drop(file);
+
+        let file = Volumes::create(&zip_path, None).unwrap();
+        let mut archive = ArchiveWriter::new(file, ArchiveFormat::Zip, 1).unwrap();
+        archive
+            .append_file(Path::new("large.bin"), &src_path)
+            .unwrap();
+        archive.finish().unwrap();
+
+        let report = unpack(
+            &zip_path,
+            &dst_path,
+            None,
+            UnpackOptions::default(),
+            &CancelToken::default(),
+            |_, _| {},
+        )
+        .unwrap();
+
+        let mut tail = [0; 4];
+        let mut file = File::open(dst_path.join("large.bin")).unwrap();
+        file.seek(SeekFrom::End(-4)).unwrap();
+        file.read_exact(&mut tail).unwrap();
+
+        assert_eq!(report.bytes_written, size);
+        assert_eq!(file.metadata().unwrap().len(), size);
+        assert_eq!(&tail, b"tail");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // An empty directory for a test with a `dst` directory in it to extract to, cleared of what an earlier run left behind.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fs-pro-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("dst")).unwrap();
+
+        dir
+    }
+
+    // A tar file of files with their contents and links with their targets, the names are written as given, `..` included.
+    fn tar_of(entries: &[(&str, EntryType, &str)]) -> Vec<u8> {
+        let mut tar = tar::Builder::new(Vec::new());
+
+        for (name, entry_type, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_entry_type(*entry_type);
+            header.set_mode(0o644);
+
+            if entry_type.is_file() {
+                header.set_size(data.len() as u64);
+            } else {
+                header.set_link_name(data).unwrap();
+                header.set_size(0);
+            }
+
+            header.set_cksum();
+            tar.append(&header, data.as_bytes().take(header.size().unwrap()))
+                .unwrap();
+        }
+
+        tar.into_inner().unwrap()
+    }
+
+    fn extract(dir: &Path, archive: &[u8], options: UnpackOptions) -> Result<DecompressReport> {
+        let src_path = dir.join("archive");
+        fs::write(&src_path, archive).unwrap();
+
+        unpack(
+            &src_path,
+            &dir.join("dst"),
+            None,
+            options,
+            &CancelToken::default(),
+            |_, _| {},
+        )
+    }
+
+    #[test]
+    fn unpack_refuses_parent_dir_entries() {
+        let dir = test_dir("parent-dir");
+        let tar = tar_of(&[("../evil", EntryType::Regular, "evil")]);
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("../evil", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"evil").unwrap();
+        let zip = zip.finish().unwrap().into_inner();
+
+        for archive in [tar, zip] {
+            let result = extract(&dir, &archive, UnpackOptions::default());
+
+            assert!(matches!(result, Err(Error::InvalidPath(_))));
+            assert!(!dir.join("evil").exists());
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unpack_refuses_symlinks_leaving_through_an_extracted_symlink() {
+        let dir = test_dir("symlink-chain");
+        let tar = tar_of(&[
+            ("d/b", EntryType::Symlink, ".."),
+            ("e", EntryType::Symlink, "d/b/../.."),
+        ]);
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.add_symlink("d/b", "..", SimpleFileOptions::default())
+            .unwrap();
+        zip.add_symlink("e", "d/b/../..", SimpleFileOptions::default())
+            .unwrap();
+        let zip = zip.finish().unwrap().into_inner();
+
+        for archive in [tar, zip] {
+            let result = extract(&dir, &archive, UnpackOptions::default());
+
+            // `d/b` leads back to the destination and is kept, `e` leads two levels above it.
+            assert!(matches!(result, Err(Error::InvalidPath(_))));
+            assert!(fs::symlink_metadata(dir.join("dst/d/b")).is_ok());
+            assert!(fs::symlink_metadata(dir.join("dst/e")).is_err());
+
+            fs::remove_dir_all(dir.join("dst")).unwrap();
+            fs::create_dir(dir.join("dst")).unwrap();
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unpack_refuses_hard_links_leaving_through_an_extracted_symlink() {
+        let dir = test_dir("hard-link-chain");
+        fs::write(dir.join("x"), "outside").unwrap();
+
+        let tar = tar_of(&[
+            ("d/b", EntryType::Symlink, ".."),
+            ("y", EntryType::Link, "d/b/../x"),
+        ]);
+        let moved = UnpackOptions {
+            on_conflict: OverwritePolicy::Rename,
+            ..UnpackOptions::default()
+        };
+        let resumed = UnpackOptions {
+            resume: true,
+            ..UnpackOptions::default()
+        };
+
+        for options in [UnpackOptions::default(), moved, resumed] {
+            let result = extract(&dir, &tar, options);
+
+            assert!(matches!(result, Err(Error::InvalidPath(_))));
+            assert!(!dir.join("dst/y").exists());
+
+            fs::remove_dir_all(dir.join("dst")).unwrap();
+            fs::create_dir(dir.join("dst")).unwrap();
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unpack_keeps_links_inside_the_destination() {
+        let dir = test_dir("links-inside");
+        let tar = tar_of(&[
+            ("d/f", EntryType::Regular, "data"),
+            ("d/l", EntryType::Symlink, "../d/f"),
+            ("h", EntryType::Link, "d/f"),
+        ]);
+
+        extract(&dir, &tar, UnpackOptions::default()).unwrap();
+
+        assert_eq!(fs::read_to_string(dir.join("dst/d/l")).unwrap(), "data");
+        assert_eq!(fs::read_to_string(dir.join("dst/h")).unwrap(), "data");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,569 @@
+use crate::{Error, Result};
+use std::path::PathBuf;
+
+/// The app files of a type are opened with.
+// Each platform reads the fields it needs.
+#[cfg_attr(any(windows, target_os = "macos"), allow(dead_code))]
+pub(crate) struct Handler<'a> {
+    // The bundle identifier of the app, e.g. `com.example.app`.
+    pub(crate) identifier: &'a str,
+    // The name of the app.
+    pub(crate) name: &'a str,
+    // The executable of the app, started with the path of the opened file.
+    pub(crate) exe: PathBuf,
+    // The data directory of the user, where the MIME types and desktop entries are installed on Linux.
+    pub(crate) data_dir: PathBuf,
+    // The description of the file type.
+    pub(crate) description: Option<&'a str>,
+    // The MIME type of the files, on Linux.
+    pub(crate) mime_type: Option<&'a str>,
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+pub(crate) use platform::quote_exec;
+
+/// Make the app the handler for the files with the extension, for the current user.
+pub(crate) fn register(extension: &str, handler: &Handler) -> Result<()> {
+    if let Some(mime_type) = handler
+        .mime_type
+        .filter(|mime_type| !is_valid_mime_type(mime_type))
+    {
+        return Err(Error::InvalidMimeType(mime_type.to_string()));
+    }
+
+    platform::register(&normalize(extension)?, handler)
+}
+
+/// Remove what [`register`] set up, returns `false` if the app was not registered for the extension.
+pub(crate) fn unregister(extension: &str, handler: &Handler) -> Result<bool> {
+    platform::unregister(&normalize(extension)?, handler)
+}
+
+// The extension without its leading dot in lowercase, refusing anything that can't be part of a file type name.
+fn normalize(extension: &str) -> Result<String> {
+    let normalized = extension.trim_start_matches('.').to_lowercase();
+
+    let valid = !normalized.is_empty()
+        && normalized
+            .chars()
+            .all(|char| char.is_alphanumeric() || matches!(char, '-' | '_' | '.'));
+
+    match valid {
+        true => Ok(normalized),
+        false => Err(Error::InvalidExtension(extension.to_string())),
+    }
+}
+
+// A MIME type as `type/subtype`, which is written into the desktop entry and the MIME package as is on Linux.
+fn is_valid_mime_type(mime_type: &str) -> bool {
+    let is_token = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || "!#$&^_.+-".contains(char))
+    };
+
+    mime_type
+        .split_once('/')
+        .is_some_and(|(kind, subtype)| is_token(kind) && is_token(subtype))
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::Handler;
+    use crate::Result;
+    use std::{io, iter, ptr};
+    use windows_sys::Win32::{
+        Foundation::{ERROR_FILE_NOT_FOUND, ERROR_SUCCESS, WIN32_ERROR},
+        System::Registry::{
+            RegDeleteKeyValueW, RegDeleteTreeW, RegGetValueW, RegSetKeyValueW, HKEY_CURRENT_USER,
+            REG_SZ, RRF_RT_REG_SZ,
+        },
+        UI::Shell::{SHChangeNotify, SHCNE_ASSOCCHANGED, SHCNF_IDLIST},
+    };
+
+    // The classes of the current user, which take precedence over the ones of the machine and need no administrator rights.
+    const CLASSES: &str = r"Software\Classes";
+
+    pub(super) fn register(extension: &str, handler: &Handler) -> Result<()> {
+        let prog_id = prog_id(extension, handler);
+        let exe = handler.exe.display();
+
+        set_value(&prog_id, None, handler.description.unwrap_or(handler.name))?;
+        set_value(
+            &format!(r"{}\DefaultIcon", prog_id),
+            None,
+            &format!("\"{}\",0", exe),
+        )?;
+        set_value(
+            &format!(r"{}\shell\open\command", prog_id),
+            None,
+            &format!("\"{}\" \"%1\"", exe),
+        )?;
+        set_value(&format!(".{}", extension), None, &prog_id)?;
+        set_value(
+            &format!(r".{}\OpenWithProgids", extension),
+            Some(&prog_id),
+            "",
+        )?;
+
+        notify();
+
+        Ok(())
+    }
+
+    pub(super) fn unregister(extension: &str, handler: &Handler) -> Result<bool> {
+        let prog_id = prog_id(extension, handler);
+        let key = wide(&format!(r"{}\{}", CLASSES, prog_id));
+
+        match unsafe { RegDeleteTreeW(HKEY_CURRENT_USER, key.as_ptr()) } {
+            ERROR_SUCCESS => {}
+            ERROR_FILE_NOT_FOUND => return Ok(false),
+            code => return Err(error(code).into()),
+        }
+
+        let extension_key = format!(".{}", extension);
+
+        delete_value(
+            &format!(r"{}\OpenWithProgids", extension_key),
+            Some(&prog_id),
+        )?;
+
+        // The extension is left to another app that took it over since.
+        if value(&extension_key).as_deref() == Some(prog_id.as_str()) {
+            delete_value(&extension_key, None)?;
+        }
+
+        notify();
+
+        Ok(true)
+    }
+
+    // The programmatic identifier of the file type, e.g. `com.example.app.mydoc`.
+    fn prog_id(extension: &str, handler: &Handler) -> String {
+        format!("{}.{}", handler.identifier, extension)
+    }
+
+    fn wide(value: &str) -> Vec<u16> {
+        value.encode_utf16().chain(iter::once(0)).collect()
+    }
+
+    fn error(code: WIN32_ERROR) -> io::Error {
+        io::Error::from_raw_os_error(code as i32)
+    }
+
+    // Set a string value of a key under the classes, creating the key when missing. `None` is the default value of the key.
+    fn set_value(key: &str, name: Option<&str>, data: &str) -> io::Result<()> {
+        let key = wide(&format!(r"{}\{}", CLASSES, key));
+        let name = name.map(wide);
+        let data = wide(data);
+
+        let code = unsafe {
+            RegSetKeyValueW(
+                HKEY_CURRENT_USER,
+                key.as_ptr(),
+                name.as_ref().map_or(ptr::null(), |name| name.as_ptr()),
+                REG_SZ,
+                data.as_ptr().cast(),
+                (data.len() * 2) as u32,
+            )
+        };
+
+        match code {
+            ERROR_SUCCESS => Ok(()),
+            code => Err(error(code)),
+        }
+    }
+
+    fn delete_value(key: &str, name: Option<&str>) -> io::Result<()> {
+        let key = wide(&format!(r"{}\{}", CLASSES, key));
+        let name = name.map(wide);
+
+        let code = unsafe {
+            RegDeleteKeyValueW(
+                HKEY_CURRENT_USER,
+                key.as_ptr(),
+                name.as_ref().map_or(ptr::null(), |name| name.as_ptr()),
+            )
+        };
+
+        match code {
+            ERROR_SUCCESS | ERROR_FILE_NOT_FOUND => Ok(()),
+            code => Err(error(code)),
+        }
+    }
+
+    // The default value of a key under the classes.
+    fn value(key: &str) -> Option<String> {
+        let key = wide(&format!(r"{}\{}", CLASSES, key));
+        let mut data = vec![0u16; 256];
+        let mut len = (data.len() * 2) as u32;
+
+        let code = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                key.as_ptr(),
+                ptr::null(),
+                RRF_RT_REG_SZ,
+                ptr::null_mut(),
+                data.as_mut_ptr().cast(),
+                &mut len,
+            )
+        };
+
+        if code != ERROR_SUCCESS {
+            return None;
+        }
+
+        let units = &data[..len as usize / 2];
+
+        Some(String::from_utf16_lossy(
+            units.strip_suffix(&[0]).unwrap_or(units),
+        ))
+    }
+
+    // Let Explorer pick up the new icons and handlers without a restart.
+    fn notify() {
+        unsafe {
+            SHChangeNotify(
+                SHCNE_ASSOCCHANGED as i32,
+                SHCNF_IDLIST,
+                ptr::null(),
+                ptr::null(),
+            )
+        };
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::Handler;
+    use crate::Result;
+    use std::{ffi::c_void, io, ptr};
+
+    type CFStringRef = *const c_void;
+
+    const K_CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+    const K_LS_ROLES_ALL: u32 = 0xFFFF_FFFF;
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFStringCreateWithBytes(
+            alloc: *const c_void,
+            bytes: *const u8,
+            num_bytes: isize,
+            encoding: u32,
+            is_external_representation: u8,
+        ) -> CFStringRef;
+        fn CFRelease(cf: *const c_void);
+    }
+
+    #[link(name = "CoreServices", kind = "framework")]
+    extern "C" {
+        static kUTTagClassFilenameExtension: CFStringRef;
+
+        fn UTTypeCreatePreferredIdentifierForTag(
+            tag_class: CFStringRef,
+            tag: CFStringRef,
+            conforming_to: CFStringRef,
+        ) -> CFStringRef;
+        fn LSSetDefaultRoleHandlerForContentType(
+            content_type: CFStringRef,
+            role: u32,
+            handler_bundle_id: CFStringRef,
+        ) -> i32;
+    }
+
+    // An owned Core Foundation string, released when dropped.
+    struct CFString(CFStringRef);
+
+    impl CFString {
+        fn new(value: &str) -> Self {
+            Self(unsafe {
+                CFStringCreateWithBytes(
+                    ptr::null(),
+                    value.as_ptr(),
+                    value.len() as isize,
+                    K_CF_STRING_ENCODING_UTF8,
+                    0,
+                )
+            })
+        }
+    }
+
+    impl Drop for CFString {
+        fn drop(&mut self) {
+            if !self.0.is_null() {
+                unsafe { CFRelease(self.0) };
+            }
+        }
+    }
+
+    // The document types themselves are declared in the `Info.plist` of the app, e.g. with `bundle.fileAssociations` in the Tauri
+    // configuration, Launch Services only picks the default handler among the apps declaring them.
+    pub(super) fn register(extension: &str, handler: &Handler) -> Result<()> {
+        let extension = CFString::new(extension);
+        let bundle_id = CFString::new(handler.identifier);
+
+        let content_type = CFString(unsafe {
+            UTTypeCreatePreferredIdentifierForTag(
+                kUTTagClassFilenameExtension,
+                extension.0,
+                ptr::null(),
+            )
+        });
+
+        let status = unsafe {
+            LSSetDefaultRoleHandlerForContentType(content_type.0, K_LS_ROLES_ALL, bundle_id.0)
+        };
+
+        match status {
+            0 => Ok(()),
+            status => Err(io::Error::other(format!(
+                "Launch Services failed to set the default handler with status {}",
+                status
+            ))
+            .into()),
+        }
+    }
+
+    // Launch Services has no way to remove a default handler, only to replace it with another app.
+    pub(super) fn unregister(_extension: &str, _handler: &Handler) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod platform {
+    use super::Handler;
+    use crate::{xml::escape as escape_xml, Result};
+    use std::{
+        ffi::OsStr,
+        fs, io,
+        path::{Path, PathBuf},
+        process::Command,
+    };
+
+    pub(super) fn register(extension: &str, handler: &Handler) -> Result<()> {
+        let default_mime_type = format!("application/x-{}", extension);
+        let mime_type = handler.mime_type.unwrap_or(&default_mime_type);
+        let package = package_path(extension, handler);
+
+        if let Some(parent) = package.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(
+            &package,
+            format!(
+                concat!(
+                    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                    "<mime-info xmlns=\"http://www.freedesktop.org/standards/shared-mime-info\">\n",
+                    "  <mime-type type=\"{}\">\n",
+                    "    <comment>{}</comment>\n",
+                    "    <glob pattern=\"*.{}\"/>\n",
+                    "  </mime-type>\n",
+                    "</mime-info>\n"
+                ),
+                escape_xml(mime_type),
+                escape_xml(handler.description.unwrap_or(handler.name)),
+                escape_xml(extension),
+            ),
+        )?;
+        run(
+            "update-mime-database",
+            [handler.data_dir.join("mime").as_os_str()],
+        )?;
+
+        let mut mime_types = desktop_mime_types(handler);
+
+        if !mime_types.iter().any(|known| known == mime_type) {
+            mime_types.push(mime_type.to_string());
+        }
+
+        write_desktop_entry(handler, &mime_types)?;
+
+        let desktop_id = desktop_id(handler);
+
+        run(
+            "xdg-mime",
+            [
+                OsStr::new("default"),
+                OsStr::new(&desktop_id),
+                OsStr::new(mime_type),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub(super) fn unregister(extension: &str, handler: &Handler) -> Result<bool> {
+        let package = package_path(extension, handler);
+
+        let Ok(contents) = fs::read_to_string(&package) else {
+            return Ok(false);
+        };
+
+        fs::remove_file(&package)?;
+        run(
+            "update-mime-database",
+            [handler.data_dir.join("mime").as_os_str()],
+        )?;
+
+        let mime_type = contents
+            .split_once("type=\"")
+            .and_then(|(_, rest)| rest.split_once('"'))
+            .map(|(mime_type, _)| unescape_xml(mime_type))
+            .unwrap_or_default();
+        let mime_types = desktop_mime_types(handler)
+            .into_iter()
+            .filter(|known| *known != mime_type)
+            .collect::<Vec<_>>();
+
+        if mime_types.is_empty() {
+            match fs::remove_file(desktop_path(handler)) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+        } else {
+            write_desktop_entry(handler, &mime_types)?;
+        }
+
+        Ok(true)
+    }
+
+    // The MIME type installed for the extension, e.g. `~/.local/share/mime/packages/com.example.app-mydoc.xml`.
+    fn package_path(extension: &str, handler: &Handler) -> PathBuf {
+        handler
+            .data_dir
+            .join("mime")
+            .join("packages")
+            .join(format!("{}-{}.xml", handler.identifier, extension))
+    }
+
+    // A desktop entry of its own, so one installed with the app stays untouched.
+    fn desktop_id(handler: &Handler) -> String {
+        format!("{}.fs-pro.desktop", handler.identifier)
+    }
+
+    fn desktop_path(handler: &Handler) -> PathBuf {
+        handler
+            .data_dir
+            .join("applications")
+            .join(desktop_id(handler))
+    }
+
+    // The MIME types the desktop entry handles so far.
+    fn desktop_mime_types(handler: &Handler) -> Vec<String> {
+        let contents = fs::read_to_string(desktop_path(handler)).unwrap_or_default();
+
+        contents
+            .lines()
+            .find_map(|line| line.strip_prefix("MimeType="))
+            .map(|mime_types| {
+                mime_types
+                    .split(';')
+                    .filter(|mime_type| !mime_type.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // Hidden from the app menus, it's only there to open files with.
+    fn write_desktop_entry(handler: &Handler, mime_types: &[String]) -> io::Result<()> {
+        let path = desktop_path(handler);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(
+            &path,
+            format!(
+                "[Desktop Entry]\nType=Application\nName={}\nExec={} %f\nNoDisplay=true\nMimeType={};\n",
+                handler.name,
+                quote_exec(&handler.exe),
+                mime_types.join(";"),
+            ),
+        )?;
+
+        // Only refreshes the cache some desktops read the handlers from, it's fine when the tool is missing.
+        let _ = run(
+            "update-desktop-database",
+            [handler.data_dir.join("applications").as_os_str()],
+        );
+
+        Ok(())
+    }
+
+    // Quote the executable for the `Exec` key. The desktop entry specification reserves some characters inside quotes, then
+    // escapes backslashes once more as in any string value, and `%` starts a field code.
+    pub(crate) fn quote_exec(exe: &Path) -> String {
+        let mut quoted = String::from("\"");
+
+        for char in exe.to_string_lossy().chars() {
+            if matches!(char, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+
+            quoted.push(char);
+        }
+
+        quoted.push('"');
+        quoted.replace('\\', "\\\\").replace('%', "%%")
+    }
+
+    fn unescape_xml(value: &str) -> String {
+        value
+            .replace("&quot;", "\"")
+            .replace("&gt;", ">")
+            .replace("&lt;", "<")
+            .replace("&amp;", "&")
+    }
+
+    fn run<'a>(program: &str, args: impl IntoIterator<Item = &'a OsStr>) -> io::Result<()> {
+        let output = Command::new(program)
+            .args(args)
+            .output()
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::NotFound => {
+                    io::Error::new(err.kind(), format!("{} is not installed", program))
+                }
+                _ => err,
+            })?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+
+        Err(io::Error::other(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_types_are_type_slash_subtype_tokens() {
+        assert!(is_valid_mime_type("application/x-mydoc"));
+        assert!(is_valid_mime_type("application/vnd.example+json"));
+
+        for invalid in [
+            "",
+            "application",
+            "application/",
+            "/x-mydoc",
+            "application/x/mydoc",
+            "application/x-mydoc\nExec=evil",
+            "application/x mydoc",
+        ] {
+            assert!(!is_valid_mime_type(invalid), "{:?}", invalid);
+        }
+    }
+}
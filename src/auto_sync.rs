@@ -0,0 +1,378 @@
+use crate::{
+    commands::is_ignored, retry::RetryPolicy, unlink, AutoSyncOptions, Error, FsProExt,
+    PathRedaction, Result,
+};
+use filetime::FileTime;
+use ignore::gitignore::Gitignore;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs, io, mem,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use tauri::{AppHandle, Emitter, Runtime};
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AutoSyncEventKind {
+    // A file was copied to the destination, or a directory created there.
+    Copy,
+    // A path that no longer exists in the source was removed from the destination.
+    Remove,
+    // A path could not be synced, it's synced again on its next change.
+    Error,
+}
+
+// An `Error` serialized the same way, events must be cloneable.
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoSyncError {
+    kind: &'static str,
+    message: String,
+}
+
+impl AutoSyncError {
+    fn new(err: Error, redaction: PathRedaction) -> Self {
+        Self {
+            kind: err.kind(),
+            message: err.redacted_message(redaction),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoSyncEvent {
+    id: u32,
+    kind: AutoSyncEventKind,
+    // The path relative to the source and the destination.
+    #[serde(flatten, serialize_with = "crate::commands::serialize_path_entry")]
+    path: PathBuf,
+    // The error of an `error` event.
+    error: Option<AutoSyncError>,
+}
+
+/// The running auto syncs, each one a watcher of its source.
+#[derive(Default)]
+pub(crate) struct AutoSyncs {
+    next_id: AtomicU32,
+    watchers: Mutex<HashMap<u32, RecommendedWatcher>>,
+}
+
+// Keeps a destination directory a copy of a source directory.
+struct Mirror<R: Runtime> {
+    app_handle: AppHandle<R>,
+    id: u32,
+    src: PathBuf,
+    dst: PathBuf,
+    matcher: Option<Gitignore>,
+    delete: bool,
+}
+
+impl<R: Runtime> Mirror<R> {
+    fn emit(&self, kind: AutoSyncEventKind, path: &Path, error: Option<Error>) {
+        let _ = self.app_handle.emit(
+            "fs-pro://auto-sync",
+            AutoSyncEvent {
+                id: self.id,
+                kind,
+                path: path.to_path_buf(),
+                error: error.map(|err| {
+                    AutoSyncError::new(err, self.app_handle.fs_pro().settings.path_redaction)
+                }),
+            },
+        );
+    }
+
+    fn report(&self, rel: &Path, result: Result<Option<AutoSyncEventKind>>) {
+        match result {
+            Ok(Some(kind)) => self.emit(kind, rel, None),
+            Ok(None) => {}
+            Err(err) => self.emit(AutoSyncEventKind::Error, rel, Some(err)),
+        }
+    }
+
+    // Whether the path of the source, or one of its parents, is ignored.
+    fn is_ignored(&self, src: &Path, is_dir: bool) -> bool {
+        self.matcher
+            .as_ref()
+            .is_some_and(|matcher| matcher.matched_path_or_any_parents(src, is_dir).is_ignore())
+    }
+
+    // Bring the destination of a path relative to the source up to date, including everything below it.
+    fn sync(&self, rel: &Path) {
+        let src = self.src.join(rel);
+        let dst = self.dst.join(rel);
+
+        match fs::symlink_metadata(&src) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                if !self.is_ignored(&src, dst.is_dir()) {
+                    self.report(rel, self.remove(&dst));
+                }
+            }
+            Err(err) => self.emit(AutoSyncEventKind::Error, rel, Some(err.into())),
+            Ok(metadata) if self.is_ignored(&src, metadata.is_dir()) => {}
+            Ok(metadata) if metadata.is_dir() => self.sync_tree(rel),
+            Ok(metadata) if metadata.is_file() => {
+                self.report(rel, self.copy_file(&src, &dst, &metadata))
+            }
+            // Symbolic links are not synced.
+            Ok(_) => {}
+        }
+    }
+
+    // Sync a directory relative to the source, then remove what is no longer in the source.
+    fn sync_tree(&self, rel: &Path) {
+        let root = self.src.join(rel);
+        let entries = WalkDir::new(&root).into_iter().filter_entry(|entry| {
+            !is_ignored(
+                self.matcher.as_ref(),
+                entry.path(),
+                entry.file_type().is_dir(),
+            )
+        });
+
+        for entry in entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    let path = err.path().unwrap_or(&root);
+                    let rel = path.strip_prefix(&self.src).unwrap_or(path).to_path_buf();
+                    self.emit(AutoSyncEventKind::Error, &rel, Some(err.into()));
+                    continue;
+                }
+            };
+            let rel = entry.path().strip_prefix(&self.src).unwrap_or(entry.path());
+            let dst = self.dst.join(rel);
+
+            let result = match entry.metadata() {
+                Ok(metadata) if metadata.is_dir() => self.create_dir(&dst),
+                Ok(metadata) if metadata.is_file() => self.copy_file(entry.path(), &dst, &metadata),
+                Ok(_) => Ok(None),
+                Err(err) => Err(err.into()),
+            };
+
+            self.report(rel, result);
+        }
+
+        if !self.delete {
+            return;
+        }
+
+        let mut entries = WalkDir::new(self.dst.join(rel)).min_depth(1).into_iter();
+
+        while let Some(entry) = entries.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let rel = entry.path().strip_prefix(&self.dst).unwrap_or(entry.path());
+            let src = self.src.join(rel);
+            let is_dir = entry.file_type().is_dir();
+
+            // Ignored paths are left alone in the destination too.
+            if src.symlink_metadata().is_ok() || self.is_ignored(&src, is_dir) {
+                continue;
+            }
+
+            if is_dir {
+                entries.skip_current_dir();
+            }
+
+            self.report(rel, self.remove(entry.path()));
+        }
+    }
+
+    fn create_dir(&self, dst: &Path) -> Result<Option<AutoSyncEventKind>> {
+        if dst.is_dir() {
+            return Ok(None);
+        }
+
+        // A file took the place of a directory in the source.
+        if dst.symlink_metadata().is_ok() {
+            fs::remove_file(dst)?;
+        }
+
+        fs::create_dir_all(dst)?;
+
+        Ok(Some(AutoSyncEventKind::Copy))
+    }
+
+    // Copy a file unless the destination has the same size and modification time.
+    fn copy_file(
+        &self,
+        src: &Path,
+        dst: &Path,
+        metadata: &fs::Metadata,
+    ) -> Result<Option<AutoSyncEventKind>> {
+        let modified = FileTime::from_last_modification_time(metadata);
+
+        match dst.symlink_metadata() {
+            Ok(existing) if existing.is_dir() => {
+                unlink::remove_all(dst, &RetryPolicy::default())?;
+            }
+            Ok(existing)
+                if existing.len() == metadata.len()
+                    && FileTime::from_last_modification_time(&existing) == modified =>
+            {
+                return Ok(None);
+            }
+            _ => {}
+        }
+
+        copy_atomic(src, dst, modified)?;
+
+        Ok(Some(AutoSyncEventKind::Copy))
+    }
+
+    fn remove(&self, dst: &Path) -> Result<Option<AutoSyncEventKind>> {
+        if !self.delete || dst.symlink_metadata().is_err() {
+            return Ok(None);
+        }
+
+        unlink::remove_all(dst, &RetryPolicy::default())?;
+
+        Ok(Some(AutoSyncEventKind::Remove))
+    }
+}
+
+/// Copy a file and give the copy the modification time `modified`, creating the parents of the destination.
+///
+/// The file is copied next to the destination first, so the destination never holds half a file.
+pub(crate) fn copy_atomic(src: &Path, dst: &Path, modified: FileTime) -> io::Result<()> {
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let partial = partial_path(dst);
+    let copied = fs::copy(src, &partial)
+        .and_then(|_| filetime::set_file_mtime(&partial, modified))
+        .and_then(|_| fs::rename(&partial, dst));
+
+    if copied.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+
+    copied
+}
+
+// The temporary file `copy_atomic` copies to, e.g. `.report.pdf.fs-pro-sync`.
+fn partial_path(dst: &Path) -> PathBuf {
+    let name = dst.file_name().unwrap_or_default().to_string_lossy();
+
+    dst.with_file_name(format!(".{}.fs-pro-sync", name))
+}
+
+/// Whether the path is a temporary file left behind by an interrupted [`copy_atomic`].
+pub(crate) fn is_partial(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().ends_with(".fs-pro-sync"))
+}
+
+impl AutoSyncs {
+    /// Mirror a source directory into a destination directory, then keep it mirrored until [`AutoSyncs::stop`] is called.
+    pub(crate) fn start<R: Runtime>(
+        &self,
+        app_handle: AppHandle<R>,
+        src: PathBuf,
+        dst: PathBuf,
+        options: Option<AutoSyncOptions>,
+    ) -> Result<u32> {
+        if !src.is_dir() {
+            return Err(Error::NotADirectory(src));
+        }
+
+        let src = dunce::canonicalize(&src)?;
+
+        // Neither may contain the other, the mirror would sync itself or remove its own source.
+        let resolved_dst = dunce::canonicalize(&dst).unwrap_or(dst.clone());
+
+        if resolved_dst.starts_with(&src) || src.starts_with(&resolved_dst) {
+            return Err(Error::InvalidPath(dst));
+        }
+
+        let (ignore, delete, debounce) = match options {
+            Some(opt) => (opt.ignore, opt.delete, opt.debounce),
+            None => (None, None, None),
+        };
+        let matcher = ignore.map(|rules| rules.matcher(&src)).transpose()?;
+        let debounce = Duration::from_millis(debounce.unwrap_or(500));
+
+        fs::create_dir_all(&dst)?;
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = mpsc::channel();
+
+        // Watch before the first sync, so changes made during it are not missed.
+        let mut watcher = notify::recommended_watcher(tx)?;
+        watcher.watch(&src, RecursiveMode::Recursive)?;
+        self.watchers.lock().unwrap().insert(id, watcher);
+
+        let mirror = Mirror {
+            app_handle,
+            id,
+            dst: dunce::canonicalize(&dst)?,
+            src,
+            matcher,
+            delete: delete.unwrap_or(true),
+        };
+
+        // The thread ends once the watcher, and with it the sender, is dropped.
+        thread::spawn(move || {
+            mirror.sync(Path::new(""));
+
+            let mut pending = BTreeSet::new();
+
+            loop {
+                // Wait for a change, then until the source was quiet for the debounce time.
+                let event = if pending.is_empty() {
+                    rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                } else {
+                    rx.recv_timeout(debounce)
+                };
+
+                match event {
+                    Ok(Ok(event)) if event.need_rescan() => {
+                        pending.insert(PathBuf::new());
+                    }
+                    Ok(Ok(event)) => {
+                        pending.extend(event.paths.iter().filter_map(|path| {
+                            path.strip_prefix(&mirror.src).ok().map(Path::to_path_buf)
+                        }));
+                    }
+                    Ok(Err(_)) => {
+                        pending.insert(PathBuf::new());
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        // Parents sort before their children, which are skipped once a parent was synced as a whole.
+                        let mut synced: Vec<PathBuf> = Vec::new();
+
+                        for rel in mem::take(&mut pending) {
+                            if synced.iter().any(|parent| rel.starts_with(parent)) {
+                                continue;
+                            }
+
+                            mirror.sync(&rel);
+                            synced.push(rel);
+                        }
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+
+        Ok(id)
+    }
+
+    /// Stop an auto sync, returns `false` if there is no auto sync with the id.
+    pub(crate) fn stop(&self, id: u32) -> bool {
+        self.watchers.lock().unwrap().remove(&id).is_some()
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{buffer::BufferLimit, cancel::CancelToken, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+// Files are written and read in chunks of this size, or of the buffer limit when it's smaller, the file size is rounded up to
+// whole chunks.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+// Unbuffered reads on Windows need buffers aligned to the sector size, which is at most this.
+const ALIGNMENT: usize = 4096;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BenchmarkMode {
+    // Only measure reading, the file is still written first but not timed.
+    Read,
+    // Only measure writing.
+    Write,
+    // Measure writing, then reading the written file.
+    #[default]
+    ReadWrite,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkReport {
+    // The size of the file written and read in bytes.
+    pub file_size: u64,
+    // The sequential write throughput in bytes per second, `None` when not measured.
+    pub write_bytes_per_second: Option<u64>,
+    // The sequential read throughput in bytes per second, `None` when not measured.
+    pub read_bytes_per_second: Option<u64>,
+}
+
+/// Measure the sequential throughput of the volume of a directory with a temporary file, which is always removed again.
+pub(crate) fn run(
+    dir: &Path,
+    file_size: u64,
+    mode: BenchmarkMode,
+    buffer_limit: BufferLimit,
+    cancel: &CancelToken,
+) -> Result<BenchmarkReport> {
+    if !dir.is_dir() {
+        return Err(Error::NotADirectory(dir.to_path_buf()));
+    }
+
+    // Whole sectors, for the unbuffered reads on Windows.
+    let chunk_size = buffer_limit.size(CHUNK_SIZE) / ALIGNMENT * ALIGNMENT;
+    let chunks = file_size.div_ceil(chunk_size as u64).max(1);
+    let path = temp_path(dir);
+
+    let measured = measure(&path, chunk_size, chunks, mode, cancel);
+    let _ = fs::remove_file(&path);
+    let (write_secs, read_secs) = measured?;
+
+    let file_size = chunks * chunk_size as u64;
+    let throughput = |secs: f64| (file_size as f64 / secs.max(f64::EPSILON)) as u64;
+
+    Ok(BenchmarkReport {
+        file_size,
+        write_bytes_per_second: write_secs.map(throughput),
+        read_bytes_per_second: read_secs.map(throughput),
+    })
+}
+
+// A hidden file that doesn't collide with an earlier run, e.g. `.fs-pro-benchmark-1700000000000`.
+fn temp_path(dir: &Path) -> PathBuf {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+
+    dir.join(format!(".fs-pro-benchmark-{}", millis))
+}
+
+// Write the file, then read it back, returning the seconds each took for the measured modes.
+fn measure(
+    path: &Path,
+    chunk_size: usize,
+    chunks: u64,
+    mode: BenchmarkMode,
+    cancel: &CancelToken,
+) -> Result<(Option<f64>, Option<f64>)> {
+    let mut buffer = vec![0u8; chunk_size + ALIGNMENT];
+    let offset = buffer.as_ptr().align_offset(ALIGNMENT);
+    let chunk = &mut buffer[offset..offset + chunk_size];
+
+    // Random bytes, so file systems that compress or deduplicate can't skip the writes.
+    let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+    for bytes in chunk.chunks_exact_mut(8) {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        bytes.copy_from_slice(&seed.to_le_bytes());
+    }
+
+    let started = Instant::now();
+    let mut file = File::create(path)?;
+
+    for _ in 0..chunks {
+        cancel.check()?;
+        file.write_all(chunk)?;
+    }
+
+    // Include flushing to the device, otherwise only the speed of memory is measured.
+    file.sync_all()?;
+    drop_cache(&file);
+    drop(file);
+
+    let write_secs = started.elapsed().as_secs_f64();
+
+    if mode == BenchmarkMode::Write {
+        return Ok((Some(write_secs), None));
+    }
+
+    let started = Instant::now();
+    let mut file = open_uncached(path)?;
+
+    for _ in 0..chunks {
+        cancel.check()?;
+        file.read_exact(chunk)?;
+    }
+
+    let read_secs = started.elapsed().as_secs_f64();
+
+    match mode {
+        BenchmarkMode::Read => Ok((None, Some(read_secs))),
+        _ => Ok((Some(write_secs), Some(read_secs))),
+    }
+}
+
+// Evict the written file from the page cache, so it's read back from the device.
+#[cfg(target_os = "linux")]
+fn drop_cache(file: &File) {
+    use std::os::unix::io::AsRawFd;
+
+    unsafe {
+        libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn drop_cache(_file: &File) {}
+
+#[cfg(target_os = "macos")]
+fn open_uncached(path: &Path) -> std::io::Result<File> {
+    use std::os::unix::io::AsRawFd;
+
+    let file = File::open(path)?;
+
+    unsafe {
+        libc::fcntl(file.as_raw_fd(), libc::F_NOCACHE, 1);
+    }
+
+    Ok(file)
+}
+
+#[cfg(windows)]
+fn open_uncached(path: &Path) -> std::io::Result<File> {
+    use std::{fs::OpenOptions, os::windows::fs::OpenOptionsExt};
+    use windows_sys::Win32::Storage::FileSystem::FILE_FLAG_NO_BUFFERING;
+
+    OpenOptions::new()
+        .read(true)
+        .custom_flags(FILE_FLAG_NO_BUFFERING)
+        .open(path)
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn open_uncached(path: &Path) -> std::io::Result<File> {
+    File::open(path)
+}
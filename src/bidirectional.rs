@@ -0,0 +1,497 @@
+use crate::{
+    auto_sync::{copy_atomic, is_partial},
+    commands::is_ignored,
+    index::StoredPath,
+    retry::RetryPolicy,
+    unlink, BidirectionalSyncOptions, Error, Result,
+};
+use filetime::FileTime;
+use ignore::gitignore::Gitignore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::{self, create_dir_all, File},
+    io::{self, BufReader, BufWriter, Read},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictStrategy {
+    // Leave both versions untouched and report the conflict, again on every sync until both sides are the same.
+    #[default]
+    Ask,
+    // Keep the version modified last, an edited file wins over a deleted one.
+    NewerWins,
+    // Keep the left version under its name and the right version next to it as `name (conflict <timestamp>).ext`, on both sides.
+    KeepBoth,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    // The path relative to both directories.
+    #[serde(flatten, serialize_with = "crate::commands::serialize_path_entry")]
+    pub path: PathBuf,
+    // When the left version was last modified in milliseconds, `None` if it was deleted.
+    pub left_modified_at: Option<u128>,
+    // When the right version was last modified in milliseconds, `None` if it was deleted.
+    pub right_modified_at: Option<u128>,
+    // Whether the strategy resolved the conflict, `false` with `ConflictStrategy::Ask`.
+    pub resolved: bool,
+    // The path relative to both directories the right version was kept as with `ConflictStrategy::KeepBoth`.
+    #[serde(serialize_with = "crate::commands::serialize_optional_path_entry")]
+    pub kept_as: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    // The paths relative to both directories that were copied from the right directory to the left one.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub copied_to_left: Vec<PathBuf>,
+    // The paths relative to both directories that were copied from the left directory to the right one.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub copied_to_right: Vec<PathBuf>,
+    // The paths relative to both directories that were removed from the left directory, as they were deleted from the right one.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub removed_from_left: Vec<PathBuf>,
+    // The paths relative to both directories that were removed from the right directory, as they were deleted from the left one.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub removed_from_right: Vec<PathBuf>,
+    // The files changed on both sides since the last sync.
+    pub conflicts: Vec<SyncConflict>,
+    // The paths relative to both directories that could not be synced, they're synced again next time.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub failed: Vec<PathBuf>,
+}
+
+// The size and modification time of a file, a file has changed when either differs from the last sync.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Stamp {
+    size: u64,
+    modified_at: u128,
+}
+
+impl Stamp {
+    fn of(metadata: &fs::Metadata) -> Self {
+        Self {
+            size: metadata.len(),
+            modified_at: metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// Both sides of a file as they were after the last sync.
+#[derive(Debug, Clone, Copy)]
+struct Synced {
+    left: Stamp,
+    right: Stamp,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncedFile {
+    path: StoredPath,
+    left: Stamp,
+    right: Stamp,
+}
+
+// The sync state of a pair of directories, kept in the plugin data directory.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StateFile {
+    left: StoredPath,
+    right: StoredPath,
+    files: Vec<SyncedFile>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Side {
+    Left,
+    Right,
+}
+
+// The two directories being synced, and the state and report the sync builds.
+struct Pair<'a> {
+    left: &'a Path,
+    right: &'a Path,
+    strategy: ConflictStrategy,
+    state: HashMap<PathBuf, Synced>,
+    report: SyncReport,
+}
+
+// The files under `root` by their path relative to it, any error aborts the sync as a missing file would count as deleted.
+fn scan(root: &Path, matcher: Option<&Gitignore>) -> Result<HashMap<PathBuf, Stamp>> {
+    let entries = WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| {
+            !is_ignored(matcher, entry.path(), entry.file_type().is_dir())
+                && !is_partial(entry.path())
+        });
+    let mut files = HashMap::new();
+
+    for entry in entries {
+        let entry = entry?;
+
+        // Directories follow the files in them, symbolic links are not synced.
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let rel = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        files.insert(rel.to_path_buf(), Stamp::of(&entry.metadata()?));
+    }
+
+    Ok(files)
+}
+
+fn state_path(state_dir: &Path, left: &Path, right: &Path) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(left.as_os_str().as_encoded_bytes());
+    hasher.update([0]);
+    hasher.update(right.as_os_str().as_encoded_bytes());
+
+    let hash: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    state_dir.join(format!("{}.json", hash))
+}
+
+fn load_state(path: &Path) -> Result<HashMap<PathBuf, Synced>> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let state_file: StateFile = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+
+    Ok(state_file
+        .files
+        .into_iter()
+        .map(|file| {
+            let synced = Synced {
+                left: file.left,
+                right: file.right,
+            };
+
+            (PathBuf::from(file.path), synced)
+        })
+        .collect())
+}
+
+fn save_state(
+    path: &Path,
+    left: &Path,
+    right: &Path,
+    state: &HashMap<PathBuf, Synced>,
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    let state_file = StateFile {
+        left: StoredPath::from(&left.to_path_buf()),
+        right: StoredPath::from(&right.to_path_buf()),
+        files: state
+            .iter()
+            .map(|(path, synced)| SyncedFile {
+                path: StoredPath::from(path),
+                left: synced.left,
+                right: synced.right,
+            })
+            .collect(),
+    };
+
+    // Write to a temporary file first so a crash never leaves a truncated state behind.
+    let tmp_path = path.with_extension("json.tmp");
+    serde_json::to_writer(BufWriter::new(File::create(&tmp_path)?), &state_file)?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+// Compare two files of the same size byte by byte.
+fn same_contents(a: &Path, b: &Path) -> io::Result<bool> {
+    let mut a = BufReader::new(File::open(a)?);
+    let mut b = BufReader::new(File::open(b)?);
+    let mut a_buf = [0; 8192];
+    let mut b_buf = [0; 8192];
+
+    loop {
+        let read = a.read(&mut a_buf)?;
+
+        if read == 0 {
+            return Ok(b.read(&mut b_buf)? == 0);
+        }
+
+        // Both files have the same size, a shorter one fails the read.
+        b.read_exact(&mut b_buf[..read])?;
+
+        if a_buf[..read] != b_buf[..read] {
+            return Ok(false);
+        }
+    }
+}
+
+// Remove the empty directories between `dir` and `root`, a deleted file leaves no empty directories behind.
+fn remove_empty_parents(root: &Path, dir: Option<&Path>) {
+    let mut dir = dir;
+
+    while let Some(path) = dir.filter(|path| path.starts_with(root) && *path != root) {
+        if fs::remove_dir(path).is_err() {
+            break;
+        }
+
+        dir = path.parent();
+    }
+}
+
+impl Pair<'_> {
+    fn root(&self, side: Side) -> &Path {
+        match side {
+            Side::Left => self.left,
+            Side::Right => self.right,
+        }
+    }
+
+    // A path for the right version of a conflicting file that's free on both sides, e.g. `notes (conflict 1700000000000).txt`.
+    fn conflict_path(&self, rel: &Path) -> PathBuf {
+        let stem = rel
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let extension = rel
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_default();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default();
+
+        let mut counter = 1;
+        let mut kept =
+            rel.with_file_name(format!("{} (conflict {}){}", stem, timestamp, extension));
+
+        while self.left.join(&kept).symlink_metadata().is_ok()
+            || self.right.join(&kept).symlink_metadata().is_ok()
+        {
+            counter += 1;
+            kept = rel.with_file_name(format!(
+                "{} (conflict {} {}){}",
+                stem, timestamp, counter, extension
+            ));
+        }
+
+        kept
+    }
+
+    // Make the other side of a path the same as `from`, copying the file or removing it if it no longer exists, and record the result.
+    fn propagate(&mut self, rel: &Path, from: Side) -> io::Result<()> {
+        let (to, to_root) = match from {
+            Side::Left => (Side::Right, self.right),
+            Side::Right => (Side::Left, self.left),
+        };
+        let src = self.root(from).join(rel);
+        let dst = to_root.join(rel);
+
+        match fs::metadata(&src) {
+            Ok(metadata) => {
+                copy_atomic(&src, &dst, FileTime::from_last_modification_time(&metadata))?;
+
+                let copied = Stamp::of(&fs::metadata(&dst)?);
+                let source = Stamp::of(&metadata);
+                let synced = match from {
+                    Side::Left => Synced {
+                        left: source,
+                        right: copied,
+                    },
+                    Side::Right => Synced {
+                        left: copied,
+                        right: source,
+                    },
+                };
+
+                self.state.insert(rel.to_path_buf(), synced);
+
+                match to {
+                    Side::Left => self.report.copied_to_left.push(rel.to_path_buf()),
+                    Side::Right => self.report.copied_to_right.push(rel.to_path_buf()),
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                if dst.symlink_metadata().is_ok() {
+                    unlink::remove_all(&dst, &RetryPolicy::default())?;
+                    remove_empty_parents(to_root, dst.parent());
+
+                    match to {
+                        Side::Left => self.report.removed_from_left.push(rel.to_path_buf()),
+                        Side::Right => self.report.removed_from_right.push(rel.to_path_buf()),
+                    }
+                }
+
+                self.state.remove(rel);
+            }
+            Err(err) => return Err(err),
+        }
+
+        Ok(())
+    }
+
+    fn sync_path(
+        &mut self,
+        rel: &Path,
+        left: Option<Stamp>,
+        right: Option<Stamp>,
+    ) -> io::Result<()> {
+        let synced = self.state.get(rel).copied();
+        let left_changed = left != synced.map(|synced| synced.left);
+        let right_changed = right != synced.map(|synced| synced.right);
+
+        match (left_changed, right_changed) {
+            (false, false) => return Ok(()),
+            (true, false) => return self.propagate(rel, Side::Left),
+            (false, true) => return self.propagate(rel, Side::Right),
+            (true, true) => {}
+        }
+
+        // Both sides changed the same way, e.g. the same file was added to both.
+        match (left, right) {
+            (None, None) => {
+                self.state.remove(rel);
+                return Ok(());
+            }
+            (Some(l), Some(r))
+                if l.size == r.size
+                    && same_contents(&self.left.join(rel), &self.right.join(rel))? =>
+            {
+                self.state
+                    .insert(rel.to_path_buf(), Synced { left: l, right: r });
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        let mut conflict = SyncConflict {
+            path: rel.to_path_buf(),
+            left_modified_at: left.map(|stamp| stamp.modified_at),
+            right_modified_at: right.map(|stamp| stamp.modified_at),
+            resolved: self.strategy != ConflictStrategy::Ask,
+            kept_as: None,
+        };
+
+        match (self.strategy, left, right) {
+            // The state is left as is, so the conflict is reported again until it's resolved.
+            (ConflictStrategy::Ask, _, _) => {}
+            (_, Some(_), None) => self.propagate(rel, Side::Left)?,
+            (_, None, Some(_)) => self.propagate(rel, Side::Right)?,
+            (ConflictStrategy::NewerWins, Some(l), Some(r)) => {
+                let newer = if l.modified_at >= r.modified_at {
+                    Side::Left
+                } else {
+                    Side::Right
+                };
+
+                self.propagate(rel, newer)?;
+            }
+            (ConflictStrategy::KeepBoth, Some(_), Some(_)) => {
+                let kept = self.conflict_path(rel);
+
+                fs::rename(self.right.join(rel), self.right.join(&kept))?;
+                self.propagate(&kept, Side::Right)?;
+                self.propagate(rel, Side::Left)?;
+                conflict.kept_as = Some(kept);
+            }
+            (_, None, None) => {}
+        }
+
+        self.report.conflicts.push(conflict);
+
+        Ok(())
+    }
+}
+
+/// Sync two directories both ways, copying the files changed on one side since the last sync to the other and removing the
+/// files deleted from one side from the other. Files changed on both sides are conflicts, resolved with `options.strategy`.
+pub(crate) fn sync(
+    left: &Path,
+    right: &Path,
+    state_dir: &Path,
+    options: Option<BidirectionalSyncOptions>,
+) -> Result<SyncReport> {
+    for path in [left, right] {
+        if !path.is_dir() {
+            return Err(Error::NotADirectory(path.to_path_buf()));
+        }
+    }
+
+    let left = dunce::canonicalize(left)?;
+    let right = dunce::canonicalize(right)?;
+
+    // Neither may contain the other, the sync would copy files into itself.
+    if left.starts_with(&right) || right.starts_with(&left) {
+        return Err(Error::InvalidPath(right));
+    }
+
+    let (strategy, ignore) = match options {
+        Some(opt) => (opt.strategy, opt.ignore),
+        None => (None, None),
+    };
+    let left_matcher = ignore
+        .as_ref()
+        .map(|rules| rules.matcher(&left))
+        .transpose()?;
+    let right_matcher = ignore
+        .as_ref()
+        .map(|rules| rules.matcher(&right))
+        .transpose()?;
+
+    let left_files = scan(&left, left_matcher.as_ref())?;
+    let right_files = scan(&right, right_matcher.as_ref())?;
+
+    let state_path = state_path(state_dir, &left, &right);
+    let state = load_state(&state_path)?;
+
+    // Files in the state that no longer exist on either side were deleted.
+    let paths: BTreeSet<PathBuf> = left_files
+        .keys()
+        .chain(right_files.keys())
+        .chain(state.keys())
+        .cloned()
+        .collect();
+
+    let mut pair = Pair {
+        left: &left,
+        right: &right,
+        strategy: strategy.unwrap_or_default(),
+        state,
+        report: SyncReport::default(),
+    };
+
+    for rel in paths {
+        let left_stamp = left_files.get(&rel).copied();
+        let right_stamp = right_files.get(&rel).copied();
+
+        // The state of a failed path is kept, so it's synced again next time.
+        if pair.sync_path(&rel, left_stamp, right_stamp).is_err() {
+            pair.report.failed.push(rel);
+        }
+    }
+
+    save_state(&state_path, &left, &right, &pair.state)?;
+
+    Ok(pair.report)
+}
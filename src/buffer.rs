@@ -0,0 +1,26 @@
+// Below this, reading and writing in chunks takes more system calls than it saves memory. Also the sector size unbuffered reads
+// on Windows align to.
+const MIN_BUFFER_BYTES: usize = 4096;
+
+/// The largest buffer the plugin holds at once, set with `Builder::max_buffer_bytes`, no limit by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct BufferLimit(Option<usize>);
+
+impl BufferLimit {
+    pub(crate) fn new(bytes: usize) -> Self {
+        Self(Some(bytes.max(MIN_BUFFER_BYTES)))
+    }
+
+    // The limit in bytes, `None` when there is none.
+    pub(crate) fn max(self) -> Option<usize> {
+        self.0
+    }
+
+    // The size of a buffer that would best be `preferred` bytes, within the limit.
+    pub(crate) fn size(self, preferred: usize) -> usize {
+        match self.0 {
+            Some(max) => preferred.min(max),
+            None => preferred,
+        }
+    }
+}
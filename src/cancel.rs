@@ -0,0 +1,85 @@
+use crate::{Error, Result};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Checked by long-running operations to stop early once [`Operations::cancel`] was called.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Returns [`Error::Cancelled`] once the operation was cancelled.
+    pub(crate) fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
+        Ok(())
+    }
+}
+
+/// The cancellation tokens of the running operations, keyed by the id the frontend chose.
+#[derive(Default)]
+pub(crate) struct Operations {
+    tokens: Arc<Mutex<HashMap<String, CancelToken>>>,
+}
+
+/// Keeps an operation cancellable until it's dropped.
+pub(crate) struct OperationGuard {
+    tokens: Arc<Mutex<HashMap<String, CancelToken>>>,
+    id: Option<String>,
+    token: CancelToken,
+}
+
+impl OperationGuard {
+    pub(crate) fn token(&self) -> &CancelToken {
+        &self.token
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        if let Some(id) = &self.id {
+            self.tokens.lock().unwrap().remove(id);
+        }
+    }
+}
+
+impl Operations {
+    /// Register an operation, operations without an id can't be cancelled.
+    pub(crate) fn start(&self, id: Option<String>) -> OperationGuard {
+        let token = CancelToken::default();
+
+        if let Some(id) = &id {
+            self.tokens
+                .lock()
+                .unwrap()
+                .insert(id.clone(), token.clone());
+        }
+
+        OperationGuard {
+            tokens: self.tokens.clone(),
+            id,
+            token,
+        }
+    }
+
+    /// Cancel a running operation, returns `false` if there is no running operation with the id.
+    pub(crate) fn cancel(&self, id: &str) -> bool {
+        match self.tokens.lock().unwrap().get(id) {
+            Some(token) => {
+                token.0.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{buffer::BufferLimit, Error, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoreMode {
+    // Copy the file into the store, hashing it while it's copied.
+    #[default]
+    Copy,
+    // Hard link the file into the store, copying it when the store is on another file system.
+    Link,
+    // Move the file into the store, removing it when the store already has the content.
+    Move,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredObject {
+    // The SHA-256 digest of the content, in lowercase hex.
+    pub digest: String,
+    // The path of the content inside the store.
+    #[serde(flatten, serialize_with = "crate::commands::serialize_path_entry")]
+    pub path: PathBuf,
+    // Whether the store already had the content, nothing was written then.
+    pub deduplicated: bool,
+}
+
+/// Put the file at `src` into the content-addressed store at `root`, under `ab/cdef…` after its SHA-256 digest.
+///
+/// New content is written to a temporary file in the store first and renamed into place once complete, so an object in the store
+/// is always whole, even when two stores of the same content race.
+pub(crate) fn store(
+    src: &Path,
+    root: &Path,
+    mode: StoreMode,
+    buffer_limit: BufferLimit,
+) -> Result<StoredObject> {
+    if !fs::metadata(src)?.is_file() {
+        return Err(Error::InvalidPath(src.to_path_buf()));
+    }
+
+    fs::create_dir_all(root)?;
+
+    let (digest, staged) = match mode {
+        // Hashed and copied in one pass.
+        StoreMode::Copy => {
+            let staged = staging_path(root);
+
+            match copy_hashed(src, &staged, buffer_limit) {
+                Ok(digest) => (digest, Some(staged)),
+                Err(err) => {
+                    let _ = fs::remove_file(&staged);
+                    return Err(err.into());
+                }
+            }
+        }
+        StoreMode::Link | StoreMode::Move => (hash(src)?, None),
+    };
+
+    let path = object_path(root, &digest);
+    let deduplicated = path.is_file();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    match (staged, mode) {
+        (Some(staged), _) if deduplicated => fs::remove_file(staged)?,
+        (Some(staged), _) => fs::rename(staged, &path)?,
+        (None, StoreMode::Move) if deduplicated => fs::remove_file(src)?,
+        (None, StoreMode::Move) => {
+            if fs::rename(src, &path).is_err() {
+                place_copy(src, root, &path)?;
+                fs::remove_file(src)?;
+            }
+        }
+        (None, _) if deduplicated => {}
+        (None, _) => {
+            if fs::hard_link(src, &path).is_err() {
+                place_copy(src, root, &path)?;
+            }
+        }
+    }
+
+    Ok(StoredObject {
+        digest,
+        path,
+        deduplicated,
+    })
+}
+
+/// The path of the content with `digest` in the store at `root`.
+pub(crate) fn retrieve(root: &Path, digest: &str) -> Result<PathBuf> {
+    let digest = digest.to_ascii_lowercase();
+
+    if digest.len() != 64 || !digest.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(Error::InvalidDigest(digest));
+    }
+
+    let path = object_path(root, &digest);
+
+    match path.is_file() {
+        true => Ok(path),
+        false => Err(Error::NotFound(path)),
+    }
+}
+
+// The objects are spread over 256 directories by the first two characters, so no directory grows too large.
+fn object_path(root: &Path, digest: &str) -> PathBuf {
+    root.join(&digest[..2]).join(&digest[2..])
+}
+
+// A temporary file in the store that doesn't collide with another store, e.g. `.fs-pro-store-1234-1700000000000000000`.
+fn staging_path(root: &Path) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+
+    root.join(format!(".fs-pro-store-{}-{}", process::id(), nanos))
+}
+
+fn hash(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+
+    Ok(hex(&hasher.finalize()))
+}
+
+fn copy_hashed(src: &Path, dst: &Path, buffer_limit: BufferLimit) -> io::Result<String> {
+    let mut src_file = File::open(src)?;
+    let mut dst_file = File::create(dst)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; buffer_limit.size(1024 * 1024)];
+
+    loop {
+        let read = match src_file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        hasher.update(&buffer[..read]);
+        dst_file.write_all(&buffer[..read])?;
+    }
+
+    Ok(hex(&hasher.finalize()))
+}
+
+// Copy a file into place through a temporary file, when it can't be linked or moved across file systems.
+fn place_copy(src: &Path, root: &Path, path: &Path) -> io::Result<()> {
+    let staged = staging_path(root);
+    let placed = fs::copy(src, &staged).and_then(|_| fs::rename(&staged, path));
+
+    if placed.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+
+    placed
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
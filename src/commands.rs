@@ -1,20 +1,64 @@
+use crate::{
+    archive::{
+        self, ArchiveEntry, ArchiveFormat, ArchiveWriter, DecompressReport, NameEncoding,
+        OverwritePolicy, RenamedEntry, UnpackOptions,
+    },
+    association,
+    benchmark::{self, BenchmarkMode, BenchmarkReport},
+    bidirectional::{self, ConflictStrategy, SyncReport},
+    cancel::CancelToken,
+    cas::{self, StoreMode, StoredObject},
+    diagnose::{self, Diagnosis},
+    events,
+    exe_info::{read_exe_info, ExeInfo},
+    file_copy,
+    index::IndexState,
+    launcher::{self, LauncherLocation},
+    link,
+    mount::{self, DiskUsage, Volume},
+    notification,
+    operations::{self, CommitReport, HistoryEntry, Journal, Operation, Plan, UndoReport},
+    progress::RateEstimator,
+    retry::{is_fs_extra_locked, RetryPolicy},
+    shortcut,
+    sparse::{self, Extent},
+    token, trace, unlink,
+    volume::{self, Volumes},
+    workspace::Workspace,
+    Error, FsProExt, IconOverride, Result,
+};
 use file_icon_provider::get_file_icon;
-use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use fs_extra::{
-    dir::{get_size, ls, CopyOptions, DirEntryAttr, DirEntryValue},
-    move_items,
+    copy_items_with_progress,
+    dir::{
+        self as fs_extra_dir, ls, CopyOptions, DirEntryAttr, DirEntryValue, TransitProcessResult,
+    },
+    error::ErrorKind as FsExtraErrorKind,
+    move_items_with_progress, remove_items, TransitProcess,
 };
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use image::{DynamicImage, RgbaImage};
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::{
-    collections::HashSet,
-    fs::{self, create_dir_all, read_dir, File},
-    io::{self},
-    path::PathBuf,
-    time::{SystemTime, UNIX_EPOCH},
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap, HashMap, HashSet},
+    env,
+    ffi::{OsStr, OsString},
+    fs::{self, create_dir_all, read_dir, File, OpenOptions},
+    io,
+    path::{Component, Path, PathBuf, Prefix},
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tauri::{
+    async_runtime, command,
+    ipc::{Channel, JavaScriptChannelId},
+    AppHandle, Emitter, Manager, Runtime, Webview,
 };
-use tar::Archive;
-use tauri::{command, AppHandle, Manager, Runtime};
+use walkdir::WalkDir;
 
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +67,117 @@ pub struct IconOptions {
     pub size: Option<u16>,
     // The path to save the icon, defaults to the default save path.
     pub save_path: Option<PathBuf>,
+    // Multiply the size by the scale factor of the display, so the icon stays sharp on high DPI displays, defaults to `false`.
+    pub hi_dpi: Option<bool>,
+    // The scale factor to multiply the size by, e.g. of the window showing the icon, takes precedence over `hi_dpi`.
+    pub scale_factor: Option<f64>,
+}
+
+#[cfg(feature = "icon-asset")]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IconAsset {
+    // The path of the saved icon.
+    #[serde(flatten, serialize_with = "serialize_path_entry")]
+    pub path: PathBuf,
+    // The asset protocol URL of the icon, ready to be used as an image source.
+    pub url: String,
+}
+
+/// The raw encoding of a path: bytes on Unix, UTF-16 code units on Windows.
+#[cfg(unix)]
+pub type RawOsString = Vec<u8>;
+
+/// The raw encoding of a path: bytes on Unix, UTF-16 code units on Windows.
+#[cfg(windows)]
+pub type RawOsString = Vec<u16>;
+
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathEntry {
+    // The path, with invalid Unicode replaced by `U+FFFD`.
+    pub path: String,
+    // The raw encoding of the path, only present when the path is not valid Unicode.
+    pub raw_path: Option<RawOsString>,
+}
+
+impl From<&Path> for PathEntry {
+    fn from(path: &Path) -> Self {
+        Self {
+            path: path.to_string_lossy().to_string(),
+            raw_path: raw_os_str(path.as_os_str()),
+        }
+    }
+}
+
+impl From<PathEntry> for PathBuf {
+    fn from(entry: PathEntry) -> Self {
+        match entry.raw_path {
+            Some(raw) => PathBuf::from(raw_os_string(raw)),
+            None => PathBuf::from(entry.path),
+        }
+    }
+}
+
+/// A path argument, given as a string or as a [`PathEntry`] returned by another command. The raw encoding of an entry is used when
+/// present, so a path that is not valid Unicode still reaches the right file.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(untagged)]
+pub enum PathInput {
+    // The path as a string.
+    Path(PathBuf),
+    // The path as returned by another command.
+    Entry(PathEntry),
+}
+
+impl From<PathInput> for PathBuf {
+    fn from(input: PathInput) -> Self {
+        match input {
+            PathInput::Path(path) => path,
+            PathInput::Entry(entry) => PathBuf::from(entry),
+        }
+    }
+}
+
+impl From<PathBuf> for PathInput {
+    fn from(path: PathBuf) -> Self {
+        PathInput::Path(path)
+    }
+}
+
+impl From<&Path> for PathInput {
+    fn from(path: &Path) -> Self {
+        PathInput::Path(path.to_path_buf())
+    }
+}
+
+impl From<&str> for PathInput {
+    fn from(path: &str) -> Self {
+        PathInput::Path(PathBuf::from(path))
+    }
+}
+
+// Serialize a path as a `PathEntry` rather than a string, which fails the whole response when the path is not valid Unicode. Also
+// used with `#[serde(flatten)]` to keep a `path` field alongside its `rawPath`.
+pub(crate) fn serialize_path_entry<S: serde::Serializer>(
+    path: &Path,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    PathEntry::from(path).serialize(serializer)
+}
+
+pub(crate) fn serialize_path_entries<S: serde::Serializer>(
+    paths: &[PathBuf],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_seq(paths.iter().map(|path| PathEntry::from(path.as_path())))
+}
+
+pub(crate) fn serialize_optional_path_entry<S: serde::Serializer>(
+    path: &Option<PathBuf>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    path.as_deref().map(PathEntry::from).serialize(serializer)
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -30,19 +185,224 @@ pub struct IconOptions {
 pub struct MetadataOptions {
     // When getting the metadata of a path, if you don't need to calculate the size, you can omit it to save time and return 0 after omitting it, defaults to `false`.
     pub omit_size: Option<bool>,
+    // Return immediately with a `0` size and a `sizeId`, then emit the `fs-pro://size` event with the same id once the size is calculated, defaults to `false`.
+    pub defer_size: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SkipPreset {
+    // Version control directories, such as `.git`.
+    Vcs,
+    // Installed dependencies, such as `node_modules`.
+    Dependencies,
+    // Files and directories created by the operating system, such as `.DS_Store`.
+    OsJunk,
+}
+
+impl SkipPreset {
+    fn names(self) -> &'static [&'static str] {
+        match self {
+            SkipPreset::Vcs => &[".git", ".svn", ".hg", ".bzr"],
+            SkipPreset::Dependencies => &[
+                "node_modules",
+                "bower_components",
+                ".pnpm-store",
+                ".venv",
+                "__pycache__",
+                ".gradle",
+                "Pods",
+            ],
+            SkipPreset::OsJunk => &[
+                ".DS_Store",
+                ".Spotlight-V100",
+                ".Trashes",
+                ".fseventsd",
+                "Thumbs.db",
+                "desktop.ini",
+                "$RECYCLE.BIN",
+                "System Volume Information",
+            ],
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeOptions {
+    // The preset sets of names to skip.
+    pub skip: Option<Vec<SkipPreset>>,
+    // The glob patterns to skip, matched against the path relative to the measured path, e.g. `*.log`.
+    pub excludes: Option<Vec<String>>,
+    // Treat macOS bundles such as `.app` as single files, nothing inside them is skipped, defaults to `false`.
+    pub treat_bundles_as_files: Option<bool>,
+    // An id of your choice to cancel the operation with `cancel_operation`.
+    pub operation_id: Option<String>,
+}
+
+// What to leave out when summing sizes, skipped directories are not descended into.
+#[derive(Default)]
+struct SizeFilter {
+    names: HashSet<&'static str>,
+    globs: GlobSet,
+    treat_bundles_as_files: bool,
+}
+
+impl SizeFilter {
+    fn new(options: Option<SizeOptions>) -> Result<Self> {
+        let Some(options) = options else {
+            return Ok(Self::default());
+        };
+
+        let names = options
+            .skip
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|preset| preset.names().iter().copied())
+            .collect();
+
+        let mut globs = GlobSetBuilder::new();
+
+        for pattern in options.excludes.unwrap_or_default() {
+            globs.add(Glob::new(&pattern)?);
+        }
+
+        Ok(Self {
+            names,
+            globs: globs.build()?,
+            treat_bundles_as_files: options.treat_bundles_as_files.unwrap_or(false),
+        })
+    }
+
+    fn is_skipped(&self, root: &Path, path: &Path) -> bool {
+        if self.treat_bundles_as_files && enclosing_bundle(root, path).is_some() {
+            return false;
+        }
+
+        let is_skipped_name = path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .is_some_and(|name| self.names.contains(name));
+
+        is_skipped_name || self.globs.is_match(path.strip_prefix(root).unwrap_or(path))
+    }
+}
+
+// Directory extensions macOS presents as a single item.
+const BUNDLE_EXTENSIONS: &[&str] = &[
+    "app",
+    "appex",
+    "bundle",
+    "framework",
+    "kext",
+    "plugin",
+    "xpc",
+];
+
+fn is_bundle_name(path: &Path) -> bool {
+    path.extension()
+        .and_then(OsStr::to_str)
+        .is_some_and(|extension| {
+            BUNDLE_EXTENSIONS
+                .iter()
+                .any(|bundle| extension.eq_ignore_ascii_case(bundle))
+        })
+}
+
+fn is_bundle(path: &Path) -> bool {
+    is_bundle_name(path) && path.is_dir()
+}
+
+// The outermost bundle strictly containing `path`, looking only at the components below `root`.
+fn enclosing_bundle(root: &Path, path: &Path) -> Option<PathBuf> {
+    let mut current = root.to_path_buf();
+
+    for component in path.strip_prefix(root).ok()?.parent()?.components() {
+        current.push(component);
+
+        if is_bundle_name(&current) {
+            return Some(current);
+        }
+    }
+
+    None
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeEvent {
+    // The id returned as `size_id` by `metadata`.
+    pub id: u32,
+    // The path whose size was calculated.
+    #[serde(flatten, serialize_with = "serialize_path_entry")]
+    pub path: PathBuf,
+    // The size of the path in bytes.
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileType {
+    File,
+    Dir,
+    Symlink,
+    // A Unix domain socket.
+    Socket,
+    // A named pipe.
+    Fifo,
+    // A block device, such as a disk.
+    Block,
+    // A character device, such as a terminal.
+    Char,
+}
+
+impl From<fs::FileType> for FileType {
+    fn from(file_type: fs::FileType) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+
+            if file_type.is_socket() {
+                return FileType::Socket;
+            }
+            if file_type.is_fifo() {
+                return FileType::Fifo;
+            }
+            if file_type.is_block_device() {
+                return FileType::Block;
+            }
+            if file_type.is_char_device() {
+                return FileType::Char;
+            }
+        }
+
+        if file_type.is_symlink() {
+            FileType::Symlink
+        } else if file_type.is_dir() {
+            FileType::Dir
+        } else {
+            FileType::File
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Metadata {
     // The size of the path in bytes.
     pub size: u64,
+    // The id of the `fs-pro://size` event carrying the size with `defer_size`, `None` otherwise.
+    pub size_id: Option<u32>,
     // The file or directory name of the path.
     pub name: String,
     // The extension name of the path.
     pub extname: String,
     // The full name of the path including extension.
     pub full_name: String,
+    // The raw encoding of the full name, only present when the full name is not valid Unicode.
+    pub raw_full_name: Option<RawOsString>,
+    // The raw encoding of the path, only present when the path is not valid Unicode.
+    pub raw_path: Option<RawOsString>,
     // The parent directory name of the path.
     pub parent_name: String,
     // Whether the path exists.
@@ -53,6 +413,8 @@ pub struct Metadata {
     pub is_dir: bool,
     // Whether the path is a symbolic link.
     pub is_symlink: bool,
+    // The type of the path itself, symbolic links are not followed. Sockets, named pipes and devices are neither files nor directories and shouldn't be read like files.
+    pub file_type: FileType,
     // Whether the path is an absolute path.
     pub is_absolute: bool,
     // Whether the path is a relative path.
@@ -65,527 +427,6219 @@ pub struct Metadata {
     pub modified_at: u128,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirEntry {
+    #[serde(flatten)]
+    pub entry: PathEntry,
+    // The file name, with invalid Unicode replaced by `U+FFFD`.
+    pub name: String,
+    // Whether the entry is a directory.
+    pub is_dir: bool,
+    // Whether the entry is a file.
+    pub is_file: bool,
+    // Whether the entry is a symbolic link.
+    pub is_symlink: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroppedPath {
+    #[serde(flatten)]
+    pub entry: PathEntry,
+    // The file name, with invalid Unicode replaced by `U+FFFD`.
+    pub name: String,
+    // Whether the path is a directory.
+    pub is_dir: bool,
+    // Whether the path is a file.
+    pub is_file: bool,
+    // The size of files in bytes, `0` for directories.
+    pub size: u64,
+    // The modification time in milliseconds since the Unix epoch.
+    pub modified_at: u128,
+}
+
 #[derive(Debug, serde::Deserialize)]
-pub struct CompressOptions {
-    // The name of the file or directory to be compressed.
-    pub includes: Option<Vec<String>>,
-    // The name of the file or directory not to be compressed.
-    pub excludes: Option<Vec<String>>,
+#[serde(rename_all = "camelCase")]
+pub struct ReadDirStreamOptions {
+    // The number of entries sent per message, defaults to `100`.
+    pub batch_size: Option<usize>,
+    // The minimum time between two messages in milliseconds, a batch grows past the batch size in the meantime.
+    pub min_interval_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathStat {
+    // Whether the path exists.
+    pub exists: bool,
+    // Whether the path is a file.
+    pub is_file: bool,
+    // Whether the path is a directory.
+    pub is_dir: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PrefixKind {
+    // `\\?\prefix`
+    Verbatim,
+    // `\\?\UNC\server\share`
+    VerbatimUnc,
+    // `\\?\C:`
+    VerbatimDisk,
+    // `\\.\device`
+    DeviceNs,
+    // `\\server\share`
+    Unc,
+    // `C:`
+    Disk,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedPath {
+    // The kind of the Windows path prefix, if any.
+    pub prefix_kind: Option<PrefixKind>,
+    // The drive letter, e.g. `C`.
+    pub drive: Option<String>,
+    // The UNC server name.
+    pub host: Option<String>,
+    // The UNC share name.
+    pub share: Option<String>,
+    // The verbatim or device prefix that is neither a drive nor a UNC share.
+    pub device: Option<String>,
+    // Whether the path has a root, e.g. `/` or `\`.
+    pub has_root: bool,
+    // Whether the path is an absolute path.
+    pub is_absolute: bool,
+    // The directory components between the prefix and the file name.
+    pub directories: Vec<String>,
+    // The full name including extension of the last component.
+    pub file_name: Option<String>,
+    // The name of the last component without extension.
+    pub name: Option<String>,
+    // The extension name of the last component.
+    pub extname: Option<String>,
 }
 
 #[derive(Debug, serde::Deserialize)]
-pub struct TransferOptions {
-    // The name of the file or directory to be moved.
-    pub includes: Option<Vec<String>>,
-    // The name of the file or directory not to be moved.
-    pub excludes: Option<Vec<String>>,
+#[serde(rename_all = "camelCase")]
+pub struct RecentChangesOptions {
+    // Only return files modified after this time, in milliseconds since the Unix epoch, defaults to `0`.
+    pub since: Option<u64>,
+    // The maximum number of files to return, defaults to `100`.
+    pub limit: Option<usize>,
 }
 
-/// Check if a path exists.
-///
-/// # Arguments
-/// - `path`: Specify the path.
-///
-/// # Example
-/// ```
-/// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::is_exist;
-///
-/// let path = PathBuf::from("/path/to/file.txt");
-/// let exists = is_exist(path).await;
-/// println!("{}", exists); // true
-/// ```
-#[command]
-pub async fn is_exist(path: PathBuf) -> bool {
-    path.exists()
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentChange {
+    #[serde(flatten)]
+    pub entry: PathEntry,
+    // The modification time in milliseconds since the Unix epoch.
+    pub modified_at: u128,
 }
 
-/// Check if a path is a file.
-///
-/// # Arguments
-/// - `path`: Specify the path.
-///
-/// # Example
-/// ```
-/// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::is_file;
-///
-/// let path = PathBuf::from("/path/to/file.txt");
-/// let is_file = is_file(path).await;
-/// println!("{}", is_file); // true
-/// ```
-#[command]
-pub async fn is_file(path: PathBuf) -> bool {
-    path.is_file()
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LargestOptions {
+    // The maximum number of files and directories to return, defaults to `10`.
+    pub count: Option<usize>,
+    // Only return files and directories of at least this size in bytes, defaults to `0`.
+    pub min_size: Option<u64>,
+    // Treat macOS bundles such as `.app` as single files, nothing inside them is returned, defaults to `false`.
+    pub treat_bundles_as_files: Option<bool>,
 }
 
-/// Check if a path is a directory.
-///
-/// # Arguments
-/// - `path`: Specify the path.
-///
-/// # Example
-/// ```
-/// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::is_dir;
-///
-/// let path = PathBuf::from("/path/to/directory");
-/// let is_dir = is_dir(path).await;
-/// println!("{}", is_dir); // true
-/// ```
-#[command]
-pub async fn is_dir(path: PathBuf) -> bool {
-    path.is_dir()
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LargestEntry {
+    #[serde(flatten)]
+    pub entry: PathEntry,
+    // The size in bytes, including everything inside for directories.
+    pub size: u64,
+    // Whether the path is a directory.
+    pub is_dir: bool,
 }
 
-/// Get the size of the path, or 0 if it does not exist.
-///
-/// # Arguments
-/// - `path`: Specify the path.
-///
-/// # Example
-/// ```
-/// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::size;
-///
-/// let path = PathBuf::from("/path/to/file.txt");
-/// let size = size(path).await;
-/// println!("{}", size); // 1024
-/// ```
-#[command]
-pub async fn size(path: PathBuf) -> u64 {
-    get_size(path).unwrap_or(0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileCategory {
+    Image,
+    Video,
+    Audio,
+    Document,
+    Archive,
+    Code,
+    Application,
+    Other,
 }
 
-/// Get the name of the path.
-///
-/// # Arguments
-/// - `path`: Specify the path.
-///
-/// # Example
-/// ```
-/// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::name;
-///
-/// let path = PathBuf::from("/path/to/file.txt");
-/// let name = name(path).await;
-/// println!("{}", name); // "file"
-/// ```
-#[command]
-pub async fn name(path: PathBuf) -> String {
-    path.file_stem()
-        .map(|name| name.to_string_lossy().to_string())
-        .unwrap_or_default()
+impl FileCategory {
+    // Categorize by the lowercased extension name, without the leading dot.
+    fn from_extname(extname: &str) -> Self {
+        match extname {
+            "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp" | "svg" | "ico" | "tif" | "tiff"
+            | "heic" | "heif" | "avif" | "raw" | "cr2" | "nef" | "psd" => FileCategory::Image,
+            "mp4" | "mkv" | "mov" | "avi" | "wmv" | "flv" | "webm" | "m4v" | "mpg" | "mpeg"
+            | "3gp" => FileCategory::Video,
+            "mp3" | "wav" | "flac" | "aac" | "ogg" | "m4a" | "wma" | "opus" | "aiff" => {
+                FileCategory::Audio
+            }
+            "pdf" | "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" | "odt" | "ods" | "odp"
+            | "rtf" | "txt" | "md" | "csv" | "epub" | "pages" | "numbers" | "key" => {
+                FileCategory::Document
+            }
+            "zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "zst" | "7z" | "rar" | "dmg" | "iso" => {
+                FileCategory::Archive
+            }
+            "rs" | "js" | "ts" | "jsx" | "tsx" | "py" | "java" | "c" | "h" | "cpp" | "hpp"
+            | "cs" | "go" | "rb" | "php" | "swift" | "kt" | "html" | "css" | "json" | "toml"
+            | "yaml" | "yml" | "xml" | "sh" => FileCategory::Code,
+            "exe" | "msi" | "app" | "apk" | "deb" | "rpm" | "appimage" => FileCategory::Application,
+            _ => FileCategory::Other,
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeStatsOptions {
+    // Treat macOS bundles such as `.app` as single files, counted once with the size of everything inside, defaults to `false`.
+    pub treat_bundles_as_files: Option<bool>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeStat {
+    // The number of files.
+    pub count: u64,
+    // The total size of the files in bytes.
+    pub size: u64,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeStats {
+    // The files grouped by category.
+    pub categories: BTreeMap<FileCategory, TypeStat>,
+    // The files grouped by lowercased extension name without the leading dot, files without one are grouped under `""`.
+    pub extensions: BTreeMap<String, TypeStat>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeReport {
+    // The size of the readable part of the path in bytes.
+    pub size: u64,
+    // The paths that could not be read because of missing permissions.
+    #[serde(serialize_with = "serialize_path_entries")]
+    pub inaccessible: Vec<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressReport {
+    // The path of the written archive, the first volume when it's split.
+    #[serde(flatten, serialize_with = "serialize_path_entry")]
+    pub path: PathBuf,
+    // The paths of all volumes in order when the archive is split, empty otherwise.
+    #[serde(serialize_with = "serialize_path_entries")]
+    pub volumes: Vec<PathBuf>,
+    // The paths that were left out of the archive because of missing permissions.
+    #[serde(serialize_with = "serialize_path_entries")]
+    pub inaccessible: Vec<PathBuf>,
+    // The path of the manifest written next to the archive with `writeManifest`.
+    #[serde(serialize_with = "serialize_optional_path_entry")]
+    pub manifest_path: Option<PathBuf>,
+    // What would be compressed with `dryRun`, nothing is written then.
+    pub plan: Option<DryRunPlan>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferReport {
+    // The paths that were not moved or copied because of missing permissions, or the links that could not be followed.
+    #[serde(serialize_with = "serialize_path_entries")]
+    pub inaccessible: Vec<PathBuf>,
+    // The paths that were locked by another process at first and had to be retried.
+    #[serde(serialize_with = "serialize_path_entries")]
+    pub retried: Vec<PathBuf>,
+    // The paths of the existing files the items left out with `OverwritePolicy::Skip` would have replaced.
+    #[serde(serialize_with = "serialize_path_entries")]
+    pub skipped: Vec<PathBuf>,
+    // The items moved or copied under another name with `OverwritePolicy::Rename`.
+    pub renamed: Vec<RenamedEntry>,
+    // What would be moved or copied with `dryRun`, nothing is changed then.
+    pub plan: Option<DryRunPlan>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunPlan {
+    // The files and directories that would be moved, copied or compressed, in order.
+    pub items: Vec<PlannedItem>,
+    // The total size of the items in bytes.
+    pub total_bytes: u64,
+    // The existing paths the items would collide with, files in the destination or entries in the archive appended to.
+    #[serde(serialize_with = "serialize_path_entries")]
+    pub collisions: Vec<PathBuf>,
+}
+
+impl DryRunPlan {
+    fn new(items: Vec<PlannedItem>, collisions: Vec<PathBuf>) -> Self {
+        Self {
+            total_bytes: items.iter().map(|item| item.size).sum(),
+            items,
+            collisions,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedItem {
+    // The path of the file or directory on disk.
+    #[serde(serialize_with = "serialize_path_entry")]
+    pub src_path: PathBuf,
+    // The path it would be moved or copied to, or its path inside the archive.
+    #[serde(serialize_with = "serialize_path_entry")]
+    pub dst_path: PathBuf,
+    // The size in bytes, of everything in it for a directory that is moved or copied and `0` for a directory entry of an archive.
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferProgress {
+    // The number of bytes moved or copied so far.
+    pub copied_bytes: u64,
+    // The total number of bytes to move or copy.
+    pub total_bytes: u64,
+    // The name of the file being moved or copied.
+    pub current_file: String,
+    // The recent throughput in bytes per second, smoothed over the last seconds.
+    pub bytes_per_second: u64,
+    // The estimated time remaining in milliseconds at the recent throughput, `None` as long as nothing is moving.
+    pub remaining_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecompressProgress {
+    // The number of entries extracted so far.
+    pub extracted_entries: u64,
+    // The total number of entries to extract.
+    pub total_entries: u64,
+    // The uncompressed size of the entries extracted so far in bytes.
+    pub extracted_bytes: u64,
+    // The total uncompressed size of the entries to extract in bytes.
+    pub total_bytes: u64,
+    // The path of the entry being extracted, relative to the destination path.
+    #[serde(serialize_with = "serialize_path_entry")]
+    pub current_entry: PathBuf,
+    // The recent throughput in uncompressed bytes per second, smoothed over the last seconds.
+    pub bytes_per_second: u64,
+    // The estimated time remaining in milliseconds at the recent throughput, `None` as long as nothing is extracted.
+    pub remaining_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveReport {
+    // The paths that were locked by another process at first and had to be retried.
+    #[serde(serialize_with = "serialize_path_entries")]
+    pub retried: Vec<PathBuf>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryOptions {
+    // The number of retries after the first attempt, defaults to `5`.
+    pub attempts: Option<u32>,
+    // The delay before the first retry in milliseconds, doubled for every following retry, defaults to `50`.
+    pub delay: Option<u64>,
+}
+
+impl RetryOptions {
+    fn policy(options: Option<Self>) -> RetryPolicy {
+        options
+            .map(|opt| RetryPolicy::new(opt.attempts, opt.delay))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveOptions {
+    // How deleting files locked by another process is retried, only on Windows.
+    pub retry: Option<RetryOptions>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitOptions {
+    // An id of your choice to undo the operations with `undo`, defaults to a generated one.
+    pub operation_id: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IgnoreRules {
+    // The path of a gitignore-style file, e.g. `.exportignore`.
+    Path(PathBuf),
+    // The contents of a gitignore-style file.
+    Contents(String),
+}
+
+impl IgnoreRules {
+    // Build a matcher for the paths under `root`, patterns are relative to `root` like in a `.gitignore` placed there.
+    pub(crate) fn matcher(&self, root: &Path) -> Result<Gitignore> {
+        let mut builder = GitignoreBuilder::new(root);
+
+        match self {
+            IgnoreRules::Path(path) => {
+                if let Some(err) = builder.add(path) {
+                    return Err(err.into());
+                }
+            }
+            IgnoreRules::Contents(contents) => {
+                for line in contents.lines() {
+                    builder.add_line(None, line)?;
+                }
+            }
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+pub(crate) fn is_ignored(matcher: Option<&Gitignore>, path: &Path, is_dir: bool) -> bool {
+    matcher.is_some_and(|matcher| matcher.matched(path, is_dir).is_ignore())
+}
+
+// The `minSize`, `maxSize`, `modifiedAfter` and `modifiedBefore` options of compress and transfer, which only apply to files.
+#[derive(Debug, Default, Clone, Copy)]
+struct FileLimits {
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    // In milliseconds since the Unix epoch, both exclusive.
+    modified_after: Option<u64>,
+    modified_before: Option<u64>,
+}
+
+impl FileLimits {
+    fn is_set(&self) -> bool {
+        self.min_size.is_some()
+            || self.max_size.is_some()
+            || self.modified_after.is_some()
+            || self.modified_before.is_some()
+    }
+
+    // Whether a file is within the limits. A file that can't be read is kept, to be reported as inaccessible where it's used.
+    fn contains(&self, path: &Path) -> bool {
+        if !self.is_set() {
+            return true;
+        }
+
+        let Ok(metadata) = fs::metadata(path) else {
+            return true;
+        };
+        let modified_at = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_millis());
+
+        self.min_size.map_or(true, |min| metadata.len() >= min)
+            && self.max_size.map_or(true, |max| metadata.len() <= max)
+            && self
+                .modified_after
+                .map_or(true, |after| modified_at > u128::from(after))
+            && self
+                .modified_before
+                .map_or(true, |before| modified_at < u128::from(before))
+    }
+}
+
+// The `includes`, `excludes` and `ignore` options of compress and transfer along with the file limits. The globs are matched against
+// the path relative to the source path, so a plain name only matches a top-level entry while `*.log` matches at any depth.
+struct EntryFilter {
+    root: PathBuf,
+    includes: Option<GlobSet>,
+    excludes: GlobSet,
+    matcher: Option<Gitignore>,
+    limits: FileLimits,
+    keep_dirs: bool,
+}
+
+impl EntryFilter {
+    fn new(
+        root: &Path,
+        includes: &[String],
+        excludes: &[String],
+        ignore: Option<&IgnoreRules>,
+        limits: FileLimits,
+    ) -> Result<Self> {
+        let globs = |patterns: &[String]| -> Result<GlobSet> {
+            let mut globs = GlobSetBuilder::new();
+
+            for pattern in patterns {
+                // The paths are matched without a leading `./` or separators around them, e.g. `./src/config/` is `src/config`.
+                let pattern = pattern.trim_start_matches("./").trim_matches('/');
+
+                globs.add(Glob::new(pattern)?);
+            }
+
+            Ok(globs.build()?)
+        };
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            includes: (!includes.is_empty())
+                .then(|| globs(includes))
+                .transpose()?,
+            excludes: globs(excludes)?,
+            matcher: ignore.map(|rules| rules.matcher(root)).transpose()?,
+            limits,
+            keep_dirs: false,
+        })
+    }
+
+    // Keep every directory that is not skipped, whatever the includes and limits.
+    fn keep_dirs(self, keep_dirs: bool) -> Self {
+        Self { keep_dirs, ..self }
+    }
+
+    fn relative<'a>(&self, path: &'a Path) -> &'a Path {
+        path.strip_prefix(&self.root).unwrap_or(path)
+    }
+
+    // Whether an entry is left out by the excludes or the ignore rules, together with everything below it.
+    fn is_skipped(&self, path: &Path, is_dir: bool) -> bool {
+        self.excludes.is_match(self.relative(path))
+            || is_ignored(self.matcher.as_ref(), path, is_dir)
+    }
+
+    // Whether an entry is selected by the includes, itself or through one of its parent directories. Directories that are not can
+    // still contain entries that are.
+    fn is_included(&self, path: &Path) -> bool {
+        let Some(includes) = &self.includes else {
+            return true;
+        };
+
+        self.relative(path)
+            .ancestors()
+            .any(|ancestor| !ancestor.as_os_str().is_empty() && includes.is_match(ancestor))
+    }
+
+    // Whether an entry that is not skipped is kept for itself: a file selected by the includes and within the limits, or a directory
+    // selected by the includes while there are no limits, or any with `keep_dirs`. Directories that are not kept are still added for
+    // the entries in them.
+    fn keeps(&self, path: &Path, is_dir: bool) -> bool {
+        match is_dir {
+            true => self.keep_dirs || (self.is_included(path) && !self.limits.is_set()),
+            false => self.is_included(path) && self.limits.contains(path),
+        }
+    }
+
+    // Whether nothing below an included directory is left out, so it can be handled as a whole.
+    fn keeps_all(&self, dir: &Path) -> bool {
+        if !self.is_included(dir) {
+            return false;
+        }
+
+        if self.excludes.is_empty() && self.matcher.is_none() && !self.limits.is_set() {
+            return true;
+        }
+
+        !WalkDir::new(dir)
+            .min_depth(1)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .any(|entry| {
+                let is_dir = entry.file_type().is_dir();
+
+                self.is_skipped(entry.path(), is_dir)
+                    || (!is_dir && !self.limits.contains(entry.path()))
+            })
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchFileOptions {
+    // Keep the watch when the volume of the file is unmounted and resume it once the volume is mounted again, defaults to `false`.
+    pub auto_resume: Option<bool>,
+    // The minimum time between two `modify` events in milliseconds, the changes in the meantime are sent as one.
+    pub min_interval_ms: Option<u64>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoSyncOptions {
+    // Gitignore-style rules of the paths not to be synced, matched relative to the source path.
+    pub ignore: Option<IgnoreRules>,
+    // Remove the paths that no longer exist in the source from the destination, defaults to `true`.
+    pub delete: Option<bool>,
+    // How long the source must be left unchanged before the changes are synced in milliseconds, defaults to `500`.
+    pub debounce: Option<u64>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkOptions {
+    // The size of the temporary file in bytes, rounded up to whole MiB, defaults to 128 MiB.
+    pub file_size: Option<u64>,
+    // Whether to measure reading, writing or both, defaults to `BenchmarkMode::ReadWrite`.
+    pub mode: Option<BenchmarkMode>,
+    // An id of your choice to cancel the operation with `cancel_operation`.
+    pub operation_id: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BidirectionalSyncOptions {
+    // How files changed on both sides since the last sync are resolved, defaults to `ConflictStrategy::Ask`.
+    pub strategy: Option<ConflictStrategy>,
+    // Gitignore-style rules of the paths not to be synced, matched relative to each directory.
+    pub ignore: Option<IgnoreRules>,
+}
+
+/// A system notification shown once an operation finished or failed, not when it's cancelled, through `notify-send` on Linux,
+/// `osascript` on macOS and a PowerShell toast on Windows, which only appears for apps installed with a Start Menu shortcut of their
+/// identifier as Tauri installers create. One that can't be shown emits the `fs-pro://notification-error` event.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationOptions {
+    // The title once the operation succeeded, defaults to `{name}`.
+    pub title: Option<String>,
+    // The text once the operation succeeded, defaults to `Completed`.
+    pub body: Option<String>,
+    // The title once the operation failed, defaults to `title`.
+    pub failure_title: Option<String>,
+    // The text once the operation failed, defaults to `{error}`.
+    pub failure_body: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressOptions {
+    // The glob patterns of the paths to be compressed, matched against the path relative to the source path.
+    pub includes: Option<Vec<String>>,
+    // The glob patterns of the paths not to be compressed, matched against the path relative to the source path.
+    pub excludes: Option<Vec<String>>,
+    // Gitignore-style rules of the paths not to be compressed, matched relative to the source path.
+    pub ignore: Option<IgnoreRules>,
+    // Only compress files of at least this many bytes.
+    pub min_size: Option<u64>,
+    // Only compress files of at most this many bytes.
+    pub max_size: Option<u64>,
+    // Only compress files modified after this time, in milliseconds since the Unix epoch.
+    pub modified_after: Option<u64>,
+    // Only compress files modified before this time, in milliseconds since the Unix epoch.
+    pub modified_before: Option<u64>,
+    // Store every directory that isn't excluded, even when the includes or the limits keep nothing in it, defaults to `false`.
+    pub include_empty_dirs: Option<bool>,
+    // Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+    pub overwrite: Option<bool>,
+    // Append the current timestamp in milliseconds to the destination file name, defaults to `false`.
+    pub append_timestamp: Option<bool>,
+    // An id of your choice to cancel the operation with `cancel_operation`.
+    pub operation_id: Option<String>,
+    // The format of the archive, `tar.gz`, `tar.zst`, `tar.xz`, `tar.bz2` or `zip`, defaults to `tar.gz`.
+    pub format: Option<ArchiveFormat>,
+    // The compression level, within the levels of the format.
+    pub level: Option<u32>,
+    // Add the entries to an existing archive at the destination path instead of replacing it, defaults to `false`.
+    pub append: Option<bool>,
+    // Split the archive into volumes of this many bytes, named after the destination path with `.001`, `.002` and so on.
+    pub volume_size: Option<u64>,
+    // Leave out modified times, owners and groups so identical trees give byte-identical archives, defaults to `false`.
+    pub deterministic: Option<bool>,
+    // Store symbolic links as links instead of the files and directories they point to, defaults to `false`.
+    pub preserve_symlinks: Option<bool>,
+    // Return what would be compressed, with the sizes and collisions, without writing anything, defaults to `false`.
+    pub dry_run: Option<bool>,
+    // Write a JSON manifest of the entries with their sizes and SHA-256 hashes next to the archive, defaults to `false`.
+    pub write_manifest: Option<bool>,
+    // Show a system notification once the archive is written or the compression failed.
+    pub notify: Option<NotificationOptions>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressManyOptions {
+    #[serde(flatten)]
+    pub compress: CompressOptions,
+    // The paths of the sources inside the archive keyed by source path, defaults to the name of each source.
+    pub names: Option<HashMap<PathBuf, PathBuf>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressManifestOptions {
+    // Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+    pub overwrite: Option<bool>,
+    // Append the current timestamp in milliseconds to the destination file name, defaults to `false`.
+    pub append_timestamp: Option<bool>,
+    // An id of your choice to cancel the operation with `cancel_operation`.
+    pub operation_id: Option<String>,
+    // The format of the archive, `tar.gz`, `tar.zst`, `tar.xz`, `tar.bz2` or `zip`, defaults to `tar.gz`.
+    pub format: Option<ArchiveFormat>,
+    // The compression level, within the levels of the format.
+    pub level: Option<u32>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    // The path of the file or directory on disk.
+    pub src: PathBuf,
+    // The relative path of the entry inside the archive.
+    pub archive_path: PathBuf,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+pub enum Manifest {
+    // The path of a JSON file containing the entries.
+    Path(PathBuf),
+    // The entries themselves.
+    Entries(Vec<ManifestEntry>),
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneMetadataOptions {
+    // Also copy the extended attributes, only supported on Unix, defaults to `false`.
+    pub xattrs: Option<bool>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "by", rename_all = "camelCase")]
+pub enum OrganizeRule {
+    // Files with one of the extension names, or any extension name when omitted, go into `folder`, defaults to the extension name.
+    Extension {
+        extensions: Option<Vec<String>>,
+        folder: Option<String>,
+    },
+    // Files go into a folder named after their local modification date, `YYYY`, `MM` and `DD` are replaced, e.g. `YYYY/MM`.
+    Date {
+        pattern: String,
+    },
+    // Files whose name matches the regex go into `folder`, which may refer to capture groups, e.g. `$1`.
+    Regex {
+        pattern: String,
+        folder: String,
+    },
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizeOptions {
+    // Only return the plan without moving anything, defaults to `false`.
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizeMove {
+    // The current path of the file.
+    #[serde(serialize_with = "serialize_path_entry")]
+    pub src: PathBuf,
+    // The path the file is moved to.
+    #[serde(serialize_with = "serialize_path_entry")]
+    pub dst: PathBuf,
+    // Whether the destination is already taken, such files are left in place.
+    pub conflict: bool,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOptions {
+    // Skip files whose content already exists anywhere under the destination directory, defaults to `false`.
+    pub skip_if_identical_exists: Option<bool>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileAssociationOptions {
+    // The description of the file type, e.g. `My Document`, defaults to the app name.
+    pub description: Option<String>,
+    // The MIME type of the files on Linux, defaults to `application/x-<extension>`.
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherOptions {
+    // The name shown for the launcher, defaults to the name of the file or directory.
+    pub name: Option<String>,
+    // The icon of the launcher, an `.ico` file on Windows, an image file or an icon theme name on Linux.
+    pub icon: Option<PathBuf>,
+    // Where the launcher is created, `menu` or `desktop`, defaults to `menu`.
+    pub location: Option<LauncherLocation>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreCasOptions {
+    // How the file is put into the store, `copy`, `link` or `move`, defaults to `copy`.
+    pub mode: Option<StoreMode>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedFile {
+    // The path of the imported file.
+    #[serde(serialize_with = "serialize_path_entry")]
+    pub src: PathBuf,
+    // The path of the copy, renamed to `name (1).ext` and so on when the name is taken.
+    #[serde(serialize_with = "serialize_path_entry")]
+    pub dst: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DedupedFile {
+    // The path of the skipped file.
+    #[serde(serialize_with = "serialize_path_entry")]
+    pub src: PathBuf,
+    // The path of the file with the same content under the destination directory.
+    #[serde(serialize_with = "serialize_path_entry")]
+    pub existing: PathBuf,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    // The files that were copied.
+    pub imported: Vec<ImportedFile>,
+    // The files that were skipped because their content already exists.
+    pub deduped: Vec<DedupedFile>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct BackupOptions {
+    // The directory to store the backups in, defaults to the directory of the path.
+    pub dir: Option<PathBuf>,
+    // The number of backups to keep, the oldest ones are removed first, defaults to keeping all.
+    pub keep: Option<usize>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecompressOptions {
+    // Glob patterns of the entries to extract, matched against their path inside the archive, the entries inside a matched directory are extracted too.
+    pub entries: Option<Vec<String>>,
+    // Move existing destination files to the system trash before overwriting them, defaults to `false`.
+    pub backup_to_trash: Option<bool>,
+    // An id of your choice to cancel the operation with `cancel_operation`.
+    pub operation_id: Option<String>,
+    // Restore the setuid, setgid and sticky bits of tar entries too, defaults to `false`.
+    pub preserve_permissions: Option<bool>,
+    // Restore the owners and groups of tar entries, defaults to `false`.
+    pub preserve_ownership: Option<bool>,
+    // Extract the entries and links leading outside the destination path instead of refusing them, only for trusted archives, defaults to `false`.
+    pub allow_external_paths: Option<bool>,
+    // The number of leading components to remove from the names of the entries, as `tar --strip-components` does, defaults to `0`.
+    pub strip_components: Option<u32>,
+    // What happens to the existing files where entries go, `overwrite`, `skip`, `error` or `rename`, defaults to `overwrite`.
+    pub on_conflict: Option<OverwritePolicy>,
+    // Leave out the files an earlier extraction into the same destination completed before it failed, defaults to `false`.
+    pub resume: Option<bool>,
+    // The minimum time between two progress updates in milliseconds, defaults to `100`.
+    pub on_progress_interval_ms: Option<u64>,
+    // Show a system notification once the archive is extracted or the extraction failed.
+    pub notify: Option<NotificationOptions>,
+    // How the names of zip entries not flagged as UTF-8 are decoded, `auto`, `cp437`, `gbk` or `shift-jis`, defaults to `auto`.
+    pub encoding: Option<NameEncoding>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveEntriesOptions {
+    // How the names of zip entries not flagged as UTF-8 are decoded, `auto`, `cp437`, `gbk` or `shift-jis`, defaults to `auto`.
+    pub encoding: Option<NameEncoding>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertArchiveOptions {
+    // The format of the new archive, `tar.gz`, `tar.zst`, `tar.xz`, `tar.bz2` or `zip`, defaults to `tar.gz`.
+    pub format: Option<ArchiveFormat>,
+    // The compression level, within the levels of the format.
+    pub level: Option<u32>,
+    // Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+    pub overwrite: Option<bool>,
+    // An id of your choice to cancel the operation with `cancel_operation`.
+    pub operation_id: Option<String>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GzipOptions {
+    // The compression level, from `0` to `9`, defaults to `6`.
+    pub level: Option<u32>,
+    // Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+    pub overwrite: Option<bool>,
+    // An id of your choice to cancel the operation with `cancel_operation`.
+    pub operation_id: Option<String>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GunzipOptions {
+    // Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+    pub overwrite: Option<bool>,
+    // An id of your choice to cancel the operation with `cancel_operation`.
+    pub operation_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BaseDir {
+    // The data directory of the app, e.g. `~/Library/Application Support/<identifier>` on macOS.
+    AppData,
+    // The local data directory of the app, which doesn't roam with the user on Windows.
+    AppLocalData,
+    // The config directory of the app.
+    AppConfig,
+    // The cache directory of the app.
+    AppCache,
+    // The log directory of the app.
+    AppLog,
+    // The home directory of the user.
+    Home,
+    // The desktop directory of the user.
+    Desktop,
+    // The documents directory of the user.
+    Document,
+    // The downloads directory of the user.
+    Download,
+    // The pictures directory of the user.
+    Picture,
+    // The music directory of the user.
+    Audio,
+    // The videos directory of the user.
+    Video,
+    // The temporary directory of the system.
+    Temp,
+    // The resources bundled with the app.
+    Resource,
+}
+
+impl BaseDir {
+    fn resolve<R: Runtime>(self, app_handle: &AppHandle<R>) -> Result<PathBuf> {
+        let path = app_handle.path();
+        let dir = match self {
+            BaseDir::AppData => path.app_data_dir(),
+            BaseDir::AppLocalData => path.app_local_data_dir(),
+            BaseDir::AppConfig => path.app_config_dir(),
+            BaseDir::AppCache => path.app_cache_dir(),
+            BaseDir::AppLog => path.app_log_dir(),
+            BaseDir::Home => path.home_dir(),
+            BaseDir::Desktop => path.desktop_dir(),
+            BaseDir::Document => path.document_dir(),
+            BaseDir::Download => path.download_dir(),
+            BaseDir::Picture => path.picture_dir(),
+            BaseDir::Audio => path.audio_dir(),
+            BaseDir::Video => path.video_dir(),
+            BaseDir::Temp => path.temp_dir(),
+            BaseDir::Resource => path.resource_dir(),
+        };
+
+        Ok(dir?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymlinkPolicy {
+    // Move or copy what the link points to in its place, the link itself is removed from the source when moving.
+    Follow,
+    // Recreate the link with the same target, a relative target stays relative.
+    Preserve,
+    // Leave the link in the source, it's neither moved nor copied.
+    Skip,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferOptions {
+    // The glob patterns of the paths to be moved, matched against the path relative to the source path.
+    pub includes: Option<Vec<String>>,
+    // The glob patterns of the paths not to be moved, matched against the path relative to the source path.
+    pub excludes: Option<Vec<String>>,
+    // Gitignore-style rules of the paths not to be moved, matched relative to the source path. Ignored entries stay in the source.
+    pub ignore: Option<IgnoreRules>,
+    // Only move files of at least this many bytes.
+    pub min_size: Option<u64>,
+    // Only move files of at most this many bytes.
+    pub max_size: Option<u64>,
+    // Only move files modified after this time, in milliseconds since the Unix epoch.
+    pub modified_after: Option<u64>,
+    // Only move files modified before this time, in milliseconds since the Unix epoch.
+    pub modified_before: Option<u64>,
+    // Move existing destination files to the system trash before overwriting them, defaults to `false`.
+    pub backup_to_trash: Option<bool>,
+    // Snapshot existing destination files into numbered backups before overwriting them.
+    pub backup: Option<BackupOptions>,
+    // Treat macOS bundles such as `.app` as single files, a bundle is never filtered inside or left half moved, defaults to `false`.
+    pub treat_bundles_as_files: Option<bool>,
+    // How moving or copying items locked by another process is retried, only on Windows.
+    pub retry: Option<RetryOptions>,
+    // An id of your choice to cancel the operation with `cancel_operation`.
+    pub operation_id: Option<String>,
+    // What happens to the existing files where items go, `overwrite`, `skip`, `error` or `rename`, defaults to `overwrite`.
+    pub on_conflict: Option<OverwritePolicy>,
+    // Return what would be moved or copied, with the sizes and collisions, without touching anything, defaults to `false`.
+    pub dry_run: Option<bool>,
+    // Put back what was moved or copied when the operation fails or is cancelled part way, defaults to `false`.
+    pub rollback: Option<bool>,
+    // Give the copies the access and modification times of the originals, defaults to `false`.
+    pub preserve_timestamps: Option<bool>,
+    // Give the copies the permissions of the originals, the modes on Unix, defaults to `false`.
+    pub preserve_permissions: Option<bool>,
+    // How the symbolic links inside the source are handled, `follow`, `preserve` or `skip`. Left as the system handles them when unset.
+    pub symlinks: Option<SymlinkPolicy>,
+    // The minimum time between two progress updates in milliseconds, defaults to `100`.
+    pub on_progress_interval_ms: Option<u64>,
+    // Show a system notification once the items are moved or copied, or the operation failed.
+    pub notify: Option<NotificationOptions>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyFileOptions {
+    // Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+    pub overwrite: Option<bool>,
+    // The minimum time between two progress updates in milliseconds, defaults to `100`.
+    pub on_progress_interval_ms: Option<u64>,
+    // An id of your choice to cancel the operation with `cancel_operation`.
+    pub operation_id: Option<String>,
+    // Show a system notification once the file is copied or the copy failed.
+    pub notify: Option<NotificationOptions>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct IndexQueryOptions {
+    // The maximum number of paths to return, defaults to `100`.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexStatus {
+    // The state of the index.
+    pub status: IndexState,
+    // The indexed directory.
+    pub dir: Option<PathEntry>,
+    // The number of indexed paths.
+    pub count: usize,
+    // The time the index was built in milliseconds.
+    pub built_at: u128,
+    // The paths that could not be read, with everything below them left out of the index.
+    #[serde(serialize_with = "serialize_path_entries")]
+    pub inaccessible: Vec<PathBuf>,
+    // The error message of the last failed build.
+    pub error: Option<String>,
+}
+
+// The raw encoding of `value`, or `None` when it is valid Unicode and its string form is already lossless.
+pub(crate) fn raw_os_str(value: &OsStr) -> Option<RawOsString> {
+    if value.to_str().is_some() {
+        return None;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        Some(value.as_bytes().to_vec())
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+
+        Some(value.encode_wide().collect())
+    }
+}
+
+// The path a raw encoding from `raw_os_str` stands for.
+pub(crate) fn raw_os_string(raw: RawOsString) -> OsString {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+
+        OsString::from_vec(raw)
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStringExt;
+
+        OsString::from_wide(&raw)
+    }
+}
+
+/// Check if a path exists.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::is_exist;
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let exists = is_exist(path.into()).await;
+/// println!("{}", exists); // true
+/// ```
+#[command]
+pub async fn is_exist(path: PathInput) -> bool {
+    let path = PathBuf::from(path);
+
+    path.exists()
+}
+
+/// Check if a path is a file.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::is_file;
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let is_file = is_file(path.into()).await;
+/// println!("{}", is_file); // true
+/// ```
+#[command]
+pub async fn is_file(path: PathInput) -> bool {
+    let path = PathBuf::from(path);
+
+    path.is_file()
+}
+
+/// Check if a path is a directory.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::is_dir;
+///
+/// let path = PathBuf::from("/path/to/directory");
+/// let is_dir = is_dir(path.into()).await;
+/// println!("{}", is_dir); // true
+/// ```
+#[command]
+pub async fn is_dir(path: PathInput) -> bool {
+    let path = PathBuf::from(path);
+
+    path.is_dir()
+}
+
+/// Check the existence and type of many paths in one call.
+///
+/// # Arguments
+/// - `paths`: Specify the paths.
+///
+/// # Returns
+/// The stats in the same order as the paths.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::{stat_many, PathInput};
+///
+/// let paths = vec![PathInput::from("/path/to/file.txt"), PathInput::from("/path/to/directory")];
+/// let stats = stat_many(paths).await;
+/// println!("{:?}", stats);
+/// ```
+#[command]
+pub async fn stat_many(paths: Vec<PathInput>) -> Vec<PathStat> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+
+    paths
+        .iter()
+        .map(|path| {
+            // A single `metadata` call answers all three questions.
+            let metadata = fs::metadata(path).ok();
+
+            PathStat {
+                exists: metadata.is_some(),
+                is_file: metadata.as_ref().is_some_and(|metadata| metadata.is_file()),
+                is_dir: metadata.as_ref().is_some_and(|metadata| metadata.is_dir()),
+            }
+        })
+        .collect()
+}
+
+/// Parse a path into structured components using the path rules of the current platform.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::parse_path;
+///
+/// let path = PathBuf::from(r"\\server\share\docs\file.txt");
+/// let parsed = parse_path(path.into()).await;
+/// println!("{:?}", parsed); // host: "server", share: "share", directories: ["docs"], ...
+/// ```
+#[command]
+pub async fn parse_path(path: PathInput) -> ParsedPath {
+    let path = PathBuf::from(path);
+
+    let to_string = |value: &std::ffi::OsStr| value.to_string_lossy().to_string();
+
+    let mut parsed = ParsedPath {
+        has_root: path.has_root(),
+        is_absolute: path.is_absolute(),
+        file_name: path.file_name().map(to_string),
+        name: path.file_stem().map(to_string),
+        extname: path.extension().map(to_string),
+        ..Default::default()
+    };
+
+    for component in path.components() {
+        match component {
+            Component::Prefix(prefix) => match prefix.kind() {
+                Prefix::Verbatim(device) => {
+                    parsed.prefix_kind = Some(PrefixKind::Verbatim);
+                    parsed.device = Some(to_string(device));
+                }
+                Prefix::VerbatimUNC(host, share) => {
+                    parsed.prefix_kind = Some(PrefixKind::VerbatimUnc);
+                    parsed.host = Some(to_string(host));
+                    parsed.share = Some(to_string(share));
+                }
+                Prefix::VerbatimDisk(drive) => {
+                    parsed.prefix_kind = Some(PrefixKind::VerbatimDisk);
+                    parsed.drive = Some((drive as char).to_string());
+                }
+                Prefix::DeviceNS(device) => {
+                    parsed.prefix_kind = Some(PrefixKind::DeviceNs);
+                    parsed.device = Some(to_string(device));
+                }
+                Prefix::UNC(host, share) => {
+                    parsed.prefix_kind = Some(PrefixKind::Unc);
+                    parsed.host = Some(to_string(host));
+                    parsed.share = Some(to_string(share));
+                }
+                Prefix::Disk(drive) => {
+                    parsed.prefix_kind = Some(PrefixKind::Disk);
+                    parsed.drive = Some((drive as char).to_string());
+                }
+            },
+            Component::RootDir => {}
+            Component::CurDir => parsed.directories.push(".".to_string()),
+            Component::ParentDir => parsed.directories.push("..".to_string()),
+            Component::Normal(name) => parsed.directories.push(to_string(name)),
+        }
+    }
+
+    // The last normal component is the file name, not a directory.
+    if parsed.file_name.is_some() {
+        parsed.directories.pop();
+    }
+
+    parsed
+}
+
+/// Read the entries of a directory and send them over a channel in batches as they are read, instead of returning them all at once.
+///
+/// # Arguments
+/// - `path`: Specify the path of the directory.
+/// - `on_entries`: Specify the channel that receives the batches of entries.
+/// - `options.batchSize`: The number of entries sent per message, defaults to `100`. A batch is sent early rather than growing past the limit set with [`crate::Builder::max_buffer_bytes`].
+/// - `options.minIntervalMs`: The minimum time between two messages in milliseconds, no limit by default or the interval set with [`crate::Builder::min_event_interval`]. In the meantime a batch grows past the batch size, or the listing waits when it reached the buffer limit. The last batch is sent as soon as the directory is read.
+///
+/// # Returns
+/// - `Ok(usize)`: The total number of entries, once all of them were sent.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri::ipc::Channel;
+/// use tauri_plugin_fs_pro::read_dir_stream;
+///
+/// let path = PathBuf::from("/path/to/directory");
+/// let on_entries = Channel::new(|body| Ok(()));
+/// let count = read_dir_stream(app.handle().clone(), path.into(), on_entries, None).await?;
+/// println!("{}", count);
+/// ```
+#[command]
+pub async fn read_dir_stream<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    on_entries: Channel<Vec<DirEntry>>,
+    options: Option<ReadDirStreamOptions>,
+) -> Result<usize> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    let (batch_size, interval) = match options {
+        Some(opt) => (opt.batch_size, opt.min_interval_ms),
+        None => (None, None),
+    };
+    let batch_size = batch_size.unwrap_or(100).max(1);
+    let interval = events::interval(&app_handle.fs_pro().settings, interval);
+    let max_batch_bytes = app_handle.fs_pro().settings.buffer_limit.max();
+
+    // Waiting for the interval blocks the thread, so the listing runs off the async runtime.
+    async_runtime::spawn_blocking(move || {
+        send_dir_entries(&path, &on_entries, batch_size, max_batch_bytes, interval)
+    })
+    .await?
+}
+
+// Send the entries of the directory in batches, returns their number once all of them were sent.
+fn send_dir_entries(
+    path: &Path,
+    on_entries: &Channel<Vec<DirEntry>>,
+    batch_size: usize,
+    max_batch_bytes: Option<usize>,
+    interval: Duration,
+) -> Result<usize> {
+    let mut last_sent: Option<Instant> = None;
+
+    let mut batch = Vec::with_capacity(batch_size);
+    // The size of the batch as it's sent, only counted with a buffer limit.
+    let mut batch_bytes = 0;
+    let mut count = 0;
+
+    for entry in read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        let dir_entry = DirEntry {
+            entry: PathEntry::from(path.as_path()),
+            name: entry.file_name().to_string_lossy().to_string(),
+            is_dir: path.is_dir(),
+            is_file: path.is_file(),
+            is_symlink: file_type.is_symlink(),
+        };
+
+        if let Some(max_batch_bytes) = max_batch_bytes {
+            let entry_bytes = serde_json::to_vec(&dir_entry).map_or(0, |json| json.len());
+
+            if !batch.is_empty() && batch_bytes + entry_bytes > max_batch_bytes {
+                // The batch can't grow any further, so it waits for the interval instead.
+                if let Some(last_sent) = last_sent {
+                    thread::sleep(interval.saturating_sub(last_sent.elapsed()));
+                }
+
+                count += batch.len();
+                last_sent = Some(Instant::now());
+                on_entries.send(std::mem::replace(
+                    &mut batch,
+                    Vec::with_capacity(batch_size),
+                ))?;
+                batch_bytes = 0;
+            }
+
+            batch_bytes += entry_bytes;
+        }
+
+        batch.push(dir_entry);
+
+        if batch.len() >= batch_size && last_sent.map_or(true, |last| last.elapsed() >= interval) {
+            count += batch.len();
+            last_sent = Some(Instant::now());
+            on_entries.send(std::mem::replace(
+                &mut batch,
+                Vec::with_capacity(batch_size),
+            ))?;
+            batch_bytes = 0;
+        }
+    }
+
+    if !batch.is_empty() {
+        count += batch.len();
+        on_entries.send(batch)?;
+    }
+
+    Ok(count)
+}
+
+/// Turn the paths or `file://` URIs of a drag and drop event into existing, canonical paths with basic metadata.
+///
+/// Symbolic links and Windows shortcuts (`.lnk`) are resolved to their targets. Paths that don't exist are left out and duplicates are removed.
+///
+/// # Arguments
+/// - `paths`: Specify the dropped paths or `file://` URIs.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::resolve_dropped;
+///
+/// let paths = vec!["file:///path/to/file%20name.txt".to_string()];
+/// let dropped = resolve_dropped(paths).await;
+/// println!("{:?}", dropped);
+/// ```
+#[command]
+pub async fn resolve_dropped(paths: Vec<String>) -> Vec<DroppedPath> {
+    let mut seen = HashSet::new();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let path = if path.starts_with("file:") {
+                url::Url::parse(&path).ok()?.to_file_path().ok()?
+            } else {
+                PathBuf::from(path)
+            };
+
+            let is_shortcut = path
+                .extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("lnk"));
+
+            let path = match cfg!(windows) && is_shortcut {
+                true => shortcut::lnk_target(&path).unwrap_or(path),
+                false => path,
+            };
+
+            let path = dunce::canonicalize(path).ok()?;
+
+            if !seen.insert(path.clone()) {
+                return None;
+            }
+
+            let metadata = fs::metadata(&path).ok()?;
+
+            Some(DroppedPath {
+                entry: PathEntry::from(path.as_path()),
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                is_dir: metadata.is_dir(),
+                is_file: metadata.is_file(),
+                size: if metadata.is_file() {
+                    metadata.len()
+                } else {
+                    0
+                },
+                modified_at: system_time_to_unix_millis(metadata.modified()),
+            })
+        })
+        .collect()
+}
+
+/// Get the size of the path, or 0 if it does not exist.
+///
+/// Symbolic links are not followed, the path itself included, and count for their own size. Paths that can't be read are left out,
+/// [`size_report`] lists them.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+/// - `options.skip`: The preset sets of names to skip: `vcs`, `dependencies` and `osJunk`.
+/// - `options.excludes`: The glob patterns to skip, matched against the path relative to `path`, e.g. `*.log`.
+/// - `options.treatBundlesAsFiles`: Treat macOS bundles such as `.app` as single files, nothing inside them is skipped, defaults to `false`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`].
+///
+/// # Returns
+/// - `Ok(u64)`: The size in bytes.
+/// - `Err(Error)`: The error if a glob pattern is invalid or the operation was cancelled.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::size;
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let size = size(app.handle().clone(), path.into(), None).await?;
+/// println!("{}", size); // 1024
+/// ```
+#[command]
+pub async fn size<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    options: Option<SizeOptions>,
+) -> Result<u64> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    Ok(measure(&app_handle, "size", &path, options)?.0)
+}
+
+// The size of `path` and the paths that couldn't be read, for both `size` and `size_report`.
+fn measure<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    command: &'static str,
+    path: &Path,
+    mut options: Option<SizeOptions>,
+) -> Result<(u64, Vec<PathBuf>)> {
+    let operation_id = options.as_mut().and_then(|opt| opt.operation_id.take());
+    let mut trace = trace::command(
+        &app_handle.fs_pro().settings,
+        command,
+        path,
+        operation_id.as_deref(),
+    );
+    let operation = app_handle.fs_pro().operations.start(operation_id);
+    let filter = SizeFilter::new(options)?;
+
+    let (size, inaccessible) = walk_filtered_size(path, &filter, operation.token());
+    operation.token().check()?;
+
+    trace.bytes(size);
+    trace.succeeded();
+
+    Ok((size, inaccessible))
+}
+
+fn is_permission_denied(err: Option<&io::Error>) -> bool {
+    err.is_some_and(|err| err.kind() == io::ErrorKind::PermissionDenied)
+}
+
+// Sum the sizes of everything under `path` without following symlinks, collecting the paths that can't be read.
+pub(crate) fn walk_size(path: &Path) -> (u64, Vec<PathBuf>) {
+    walk_filtered_size(path, &SizeFilter::default(), &CancelToken::default())
+}
+
+// Sum up the sizes of the files below `path`, stopping early when cancelled.
+fn walk_filtered_size(
+    path: &Path,
+    filter: &SizeFilter,
+    cancel: &CancelToken,
+) -> (u64, Vec<PathBuf>) {
+    let mut size = 0;
+    let mut inaccessible = Vec::new();
+
+    let walker = WalkDir::new(path)
+        .follow_root_links(false)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !filter.is_skipped(path, entry.path()));
+
+    for entry in walker {
+        if cancel.is_cancelled() {
+            break;
+        }
+
+        match entry {
+            Ok(entry) => {
+                if entry.file_type().is_dir() {
+                    continue;
+                }
+
+                match entry.metadata() {
+                    Ok(metadata) => size += metadata.len(),
+                    Err(err) if is_permission_denied(err.io_error()) => {
+                        inaccessible.push(entry.into_path());
+                    }
+                    Err(_) => {}
+                }
+            }
+            Err(err) if is_permission_denied(err.io_error()) => {
+                inaccessible.push(err.path().unwrap_or(path).to_path_buf());
+            }
+            Err(_) => {}
+        }
+    }
+
+    (size, inaccessible)
+}
+
+/// Get the size of the path as [`size`] does, along with the paths that could not be read because of missing permissions.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+/// - `options`: The same options as [`size`].
+///
+/// # Returns
+/// - `Ok(SizeReport)`: The size in bytes and the paths that could not be read.
+/// - `Err(Error)`: The error if a glob pattern is invalid or the operation was cancelled.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::size_report;
+///
+/// let path = PathBuf::from("/path/to/directory");
+/// let report = size_report(app.handle().clone(), path.into(), None).await?;
+/// println!("{:?}", report.inaccessible);
+/// ```
+#[command]
+pub async fn size_report<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    options: Option<SizeOptions>,
+) -> Result<SizeReport> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+    let (size, inaccessible) = measure(&app_handle, "size_report", &path, options)?;
+
+    Ok(SizeReport { size, inaccessible })
+}
+
+/// Get the files in a directory, recursively, that were modified after a point in time, most recently modified first.
+///
+/// # Arguments
+/// - `dir`: Specify the directory.
+/// - `options.since`: Only return files modified after this time, in milliseconds since the Unix epoch, defaults to `0`.
+/// - `options.limit`: The maximum number of files to return, defaults to `100`.
+///
+/// # Returns
+/// - `Ok(Vec<RecentChange>)`: The modified files along with their modification time.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::recent_changes;
+///
+/// let dir = PathBuf::from("/path/to/directory");
+/// let changes = recent_changes(app.handle().clone(), dir.into(), None).await?;
+/// println!("{:?}", changes);
+/// ```
+#[command]
+pub async fn recent_changes<R: Runtime>(
+    app_handle: AppHandle<R>,
+    dir: PathInput,
+    options: Option<RecentChangesOptions>,
+) -> Result<Vec<RecentChange>> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let dir = PathBuf::from(dir);
+
+    if !dir.is_dir() {
+        return Err(Error::NotADirectory(dir));
+    }
+
+    let since = options.as_ref().and_then(|opt| opt.since).unwrap_or(0) as u128;
+    let limit = options.and_then(|opt| opt.limit).unwrap_or(100);
+
+    let mut changes: Vec<RecentChange> = WalkDir::new(&dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let modified_at = system_time_to_unix_millis(entry.metadata().ok()?.modified());
+
+            (modified_at > since).then(|| RecentChange {
+                entry: PathEntry::from(entry.path()),
+                modified_at,
+            })
+        })
+        .collect();
+
+    changes.sort_unstable_by_key(|change| Reverse(change.modified_at));
+    changes.truncate(limit);
+
+    Ok(changes)
+}
+
+/// Get the largest files and directories inside a directory, largest first, in a single walk.
+///
+/// # Arguments
+/// - `dir`: Specify the directory.
+/// - `options.count`: The maximum number of files and directories to return, defaults to `10`.
+/// - `options.minSize`: Only return files and directories of at least this size in bytes, defaults to `0`.
+/// - `options.treatBundlesAsFiles`: Treat macOS bundles such as `.app` as single files, nothing inside them is returned, defaults to `false`.
+///
+/// # Returns
+/// - `Ok(Vec<LargestEntry>)`: The largest files and directories along with their size.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::largest;
+///
+/// let dir = PathBuf::from("/path/to/directory");
+/// let entries = largest(app.handle().clone(), dir.into(), None).await?;
+/// println!("{:?}", entries);
+/// ```
+#[command]
+pub async fn largest<R: Runtime>(
+    app_handle: AppHandle<R>,
+    dir: PathInput,
+    options: Option<LargestOptions>,
+) -> Result<Vec<LargestEntry>> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let dir = PathBuf::from(dir);
+
+    let mut trace = trace::command(&app_handle.fs_pro().settings, "largest", &dir, None);
+
+    if !dir.is_dir() {
+        return Err(Error::NotADirectory(dir));
+    }
+
+    let count = options.as_ref().and_then(|opt| opt.count).unwrap_or(10);
+    let min_size = options.as_ref().and_then(|opt| opt.min_size).unwrap_or(0);
+    let treat_bundles_as_files = options
+        .and_then(|opt| opt.treat_bundles_as_files)
+        .unwrap_or(false);
+
+    // The sizes of the directories whose contents are still being walked.
+    let mut dir_sizes: HashMap<PathBuf, u64> = HashMap::new();
+    // The `count` largest entries so far, smallest on top so it can be evicted.
+    let mut heap = BinaryHeap::new();
+
+    // Walking contents first means a directory is only visited once its size is complete.
+    for entry in WalkDir::new(&dir)
+        .min_depth(1)
+        .contents_first(true)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+    {
+        let is_dir = entry.file_type().is_dir();
+
+        let size = if is_dir {
+            dir_sizes.remove(entry.path()).unwrap_or(0)
+        } else {
+            entry.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+        };
+
+        if let Some(parent) = entry.path().parent() {
+            *dir_sizes.entry(parent.to_path_buf()).or_default() += size;
+        }
+
+        if size < min_size || !(is_dir || entry.file_type().is_file()) {
+            continue;
+        }
+
+        if treat_bundles_as_files && enclosing_bundle(&dir, entry.path()).is_some() {
+            continue;
+        }
+
+        heap.push(Reverse((size, is_dir, entry.into_path())));
+
+        if heap.len() > count {
+            heap.pop();
+        }
+    }
+
+    trace.bytes(dir_sizes.values().sum());
+    trace.succeeded();
+
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((size, is_dir, path))| LargestEntry {
+            entry: PathEntry::from(path.as_path()),
+            size,
+            is_dir,
+        })
+        .collect())
+}
+
+/// Get the number and total size of the files in a directory, recursively, grouped by category and by extension name.
+///
+/// # Arguments
+/// - `dir`: Specify the directory.
+/// - `options.treatBundlesAsFiles`: Treat macOS bundles such as `.app` as single files, counted once with the size of everything inside, defaults to `false`.
+///
+/// # Returns
+/// - `Ok(TypeStats)`: The files grouped by category and by extension name.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::type_stats;
+///
+/// let dir = PathBuf::from("/path/to/directory");
+/// let stats = type_stats(app.handle().clone(), dir.into(), None).await?;
+/// println!("{:?}", stats.categories);
+/// ```
+#[command]
+pub async fn type_stats<R: Runtime>(
+    app_handle: AppHandle<R>,
+    dir: PathInput,
+    options: Option<TypeStatsOptions>,
+) -> Result<TypeStats> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let dir = PathBuf::from(dir);
+
+    let mut trace = trace::command(&app_handle.fs_pro().settings, "type_stats", &dir, None);
+    let treat_bundles_as_files = options
+        .and_then(|opt| opt.treat_bundles_as_files)
+        .unwrap_or(false);
+
+    if !dir.is_dir() {
+        return Err(Error::NotADirectory(dir));
+    }
+
+    let mut stats = TypeStats::default();
+    let mut walker = WalkDir::new(&dir).min_depth(1).into_iter();
+
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+
+        let size =
+            if treat_bundles_as_files && entry.file_type().is_dir() && is_bundle_name(entry.path())
+            {
+                walker.skip_current_dir();
+                walk_size(entry.path()).0
+            } else if entry.file_type().is_file() {
+                entry.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+            } else {
+                continue;
+            };
+
+        let extname = entry
+            .path()
+            .extension()
+            .map(|extname| extname.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        for stat in [
+            stats
+                .categories
+                .entry(FileCategory::from_extname(&extname))
+                .or_default(),
+            stats.extensions.entry(extname).or_default(),
+        ] {
+            stat.count += 1;
+            stat.size += size;
+        }
+    }
+
+    trace.bytes(stats.categories.values().map(|stat| stat.size).sum());
+    trace.succeeded();
+
+    Ok(stats)
+}
+
+/// Get the name of the path.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::name;
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let name = name(path.into()).await;
+/// println!("{}", name); // "file"
+/// ```
+#[command]
+pub async fn name(path: PathInput) -> String {
+    let path = PathBuf::from(path);
+
+    path.file_stem()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Get the extension name of a path.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::extname;
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let ext = extname(path.into()).await;
+/// println!("{}", ext); // "txt"
+/// ```
+#[command]
+pub async fn extname(path: PathInput) -> String {
+    let path = PathBuf::from(path);
+
+    path.extension()
+        .map(|extname| extname.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Get the full name of a file or directory including extension.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::full_name;
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let full_name = full_name(path.into()).await;
+/// println!("{}", full_name); // "file.txt"
+/// ```
+#[command]
+pub async fn full_name(path: PathInput) -> String {
+    let path = PathBuf::from(path);
+
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Get the parent directory name of a path.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+/// - `level`: Specify the level of the parent directory, defaults to `1`.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::parent_name;
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let parent = parent_name(app.handle().clone(), path.into(), None).await;
+/// println!("{}", parent.unwrap()); // "to"
+/// ```
+#[command]
+pub async fn parent_name<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    level: Option<u8>,
+) -> Result<String> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    let mut current = path;
+    for _ in 0..level.unwrap_or(1) {
+        if let Some(parent) = current.parent() {
+            current = parent.to_path_buf();
+        } else {
+            return Ok(String::default());
+        }
+    }
+    Ok(full_name(current.into()).await)
+}
+
+async fn get_icon_name(path: PathBuf) -> Result<String> {
+    let is_dir = is_dir(path.clone().into()).await;
+    let name = name(path.clone().into()).await;
+    // File type associations ignore the case of the extension, so `.PDF` and `.pdf` share an icon.
+    let extname = extname(path.clone().into()).await.to_lowercase();
+    let full_name = full_name(path.clone().into()).await;
+
+    let is_mac_app = cfg!(target_os = "macos") && extname.eq(&"app");
+    let is_win_app = cfg!(target_os = "windows") && extname.eq(&"exe");
+
+    if is_mac_app || is_win_app {
+        return Ok(full_name);
+    }
+
+    if is_dir {
+        return Ok("__TAURI_PLUGIN_FS_PRO_DIRECTORY__".to_string());
+    }
+
+    if extname.is_empty() {
+        return Ok(name);
+    }
+
+    Ok(extname)
+}
+
+/// Get the default save icon path.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::get_default_save_icon_path;
+///
+/// let path = get_default_save_icon_path(app.handle()).await?;
+/// println!("{}", path);
+/// ```
+#[command]
+pub async fn get_default_save_icon_path<R: Runtime>(app_handle: AppHandle<R>) -> Result<PathBuf> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    app_handle.fs_pro().icon_dir()
+}
+
+/// Get the icon of a path.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+/// - `options.size`: Specify the size of the icon, defaults to `32`.
+/// - `options.savePath`: Specify the path to save the icon, defaults to the default save path.
+/// - `options.hiDpi`: Multiply the size by the highest scale factor of the connected monitors, e.g. a `64` pixels icon for a size of `32` on a 200% display, so it isn't blurry on high DPI displays, defaults to `false`. Scaled icons are saved apart from the others.
+/// - `options.scaleFactor`: Multiply the size by this scale factor instead, e.g. of the window showing the icon.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::{icon, IconOptions};
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let icon_path = icon(app.handle(), path.into(), None).await?;
+/// println!("{}", icon_path);
+///
+/// let path = PathBuf::from("/path/to/photo.jpg");
+/// let options = IconOptions {
+///     size: Some(32),
+///     save_path: None,
+///     hi_dpi: Some(true),
+///     scale_factor: None,
+/// };
+/// let icon_path = icon(app.handle(), path.into(), Some(options)).await?;
+/// ```
+#[command]
+pub async fn icon<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    options: Option<IconOptions>,
+) -> Result<PathBuf> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    let size = options.as_ref().and_then(|opt| opt.size).unwrap_or(32);
+    let scale = icon_scale(&app_handle, options.as_ref())?;
+    let default_save_path = get_default_save_icon_path(app_handle.clone()).await?;
+    let save_path = options
+        .and_then(|opt| opt.save_path)
+        .unwrap_or(default_save_path);
+
+    if !path.is_dir() {
+        let extname = extname(path.clone().into()).await;
+
+        if let Some(icon_path) = override_icon(&app_handle, &extname, &save_path)? {
+            return Ok(icon_path);
+        }
+    }
+
+    let icon_name = get_icon_name(path.clone()).await?;
+
+    save_icon(&path, &icon_name, size, scale, &save_path)
+}
+
+// The custom icon registered for the extension name through the plugin builder, if any.
+fn override_icon<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    extname: &str,
+    save_dir: &Path,
+) -> Result<Option<PathBuf>> {
+    if extname.is_empty() {
+        return Ok(None);
+    }
+
+    match app_handle.fs_pro().icon_override(extname) {
+        None => Ok(None),
+        Some(IconOverride::Path(path)) => Ok(Some(path.clone())),
+        Some(IconOverride::Bytes(bytes)) => {
+            let save_path = save_dir.join(icon_file_name(&format!(
+                "__TAURI_PLUGIN_FS_PRO_OVERRIDE__{}",
+                extname.trim_start_matches('.').to_lowercase()
+            )));
+
+            if !save_path.exists() {
+                let image = image::load_from_memory(bytes)?;
+
+                create_dir_all(save_dir)?;
+                image.save(&save_path)?;
+            }
+
+            Ok(Some(save_path))
+        }
+    }
+}
+
+/// Get the icon of a path along with its asset protocol URL, the icon is added to the asset protocol scope.
+///
+/// Needs the `icon-asset` feature, which turns on the asset protocol of Tauri. The CSP of the app must allow the asset protocol for
+/// images, e.g. `img-src 'self' asset: http://asset.localhost`.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+/// - `options.size`: Specify the size of the icon, defaults to `32`.
+/// - `options.savePath`: Specify the path to save the icon, defaults to the default save path.
+/// - `options.hiDpi`: Multiply the size by the scale factor of the window of the webview, see [`icon`].
+/// - `options.scaleFactor`: Multiply the size by this scale factor instead.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::icon_asset;
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let icon = icon_asset(webview, path.into(), None).await?;
+/// println!("{}", icon.url);
+/// ```
+#[cfg(feature = "icon-asset")]
+#[command]
+pub async fn icon_asset<R: Runtime>(
+    webview: Webview<R>,
+    path: PathInput,
+    mut options: Option<IconOptions>,
+) -> Result<IconAsset> {
+    let _redacting = webview.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    // The icon is shown in this window, so its scale factor is used rather than the highest one of the monitors.
+    if let Some(options) = options.as_mut().filter(|opt| opt.hi_dpi == Some(true)) {
+        options.scale_factor = options
+            .scale_factor
+            .or_else(|| webview.window().scale_factor().ok());
+    }
+
+    let path = icon(webview.app_handle().clone(), path.into(), options).await?;
+
+    webview.asset_protocol_scope().allow_file(&path)?;
+    let url = webview.convert_file_src(&path, None)?;
+
+    Ok(IconAsset { path, url })
+}
+
+// The file name an icon is saved under, e.g. `pdf-1a2b3c4d.png`. The name is followed by a hash of it, so names that only differ
+// by case or unicode normalization never share a file on file systems that fold them together, as on macOS and Windows.
+fn icon_file_name(icon_name: &str) -> String {
+    let readable: String = icon_name
+        .chars()
+        .map(|char| match char {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => char,
+            _ => '_',
+        })
+        .collect();
+    let hash = Sha256::digest(icon_name.as_bytes());
+
+    format!(
+        "{}-{:02x}{:02x}{:02x}{:02x}.png",
+        readable, hash[0], hash[1], hash[2], hash[3]
+    )
+}
+
+// The scale factor the size of an icon is multiplied by, given in the options or detected with `hiDpi`.
+fn icon_scale<R: Runtime>(app_handle: &AppHandle<R>, options: Option<&IconOptions>) -> Result<f64> {
+    let Some(options) = options else {
+        return Ok(1.0);
+    };
+
+    match options.scale_factor {
+        Some(scale) if scale.is_finite() && scale > 0.0 => Ok(scale),
+        Some(scale) => Err(Error::Icon(format!("Invalid scale factor: {}", scale))),
+        // The highest scale factor of the connected monitors, so the icon stays sharp on any of them.
+        None if options.hi_dpi == Some(true) => Ok(app_handle
+            .available_monitors()
+            .unwrap_or_default()
+            .iter()
+            .map(|monitor| monitor.scale_factor())
+            .fold(1.0, f64::max)),
+        None => Ok(1.0),
+    }
+}
+
+// Save the system icon of `path` in `save_dir` at `size` times `scale` pixels, reusing an icon that was saved before.
+fn save_icon(
+    path: &Path,
+    icon_name: &str,
+    size: u16,
+    scale: f64,
+    save_dir: &Path,
+) -> Result<PathBuf> {
+    // Scaled icons are saved apart from the others, e.g. as `pdf@2x`.
+    let icon_name = match scale == 1.0 {
+        true => icon_name.to_string(),
+        false => format!("{}@{}x", icon_name, scale),
+    };
+    let save_path = save_dir.join(icon_file_name(&icon_name));
+
+    if save_path.exists() {
+        return Ok(save_path);
+    }
+
+    let size = (f64::from(size) * scale)
+        .round()
+        .clamp(1.0, f64::from(u16::MAX)) as u16;
+    let icon = get_file_icon(path, size).map_err(|err| Error::Icon(err.to_string()))?;
+
+    let image = RgbaImage::from_raw(icon.width, icon.height, icon.pixels)
+        .map(DynamicImage::ImageRgba8)
+        .ok_or_else(|| Error::Icon("Failed to convert Icon to Image".to_string()))?;
+
+    create_dir_all(save_dir)?;
+
+    image.save(&save_path)?;
+
+    Ok(save_path)
+}
+
+/// Get the system icon associated with an extension name, without requiring an existing file.
+///
+/// # Arguments
+/// - `extname`: Specify the extension name, with or without the leading dot, case-insensitive.
+/// - `options.size`: Specify the size of the icon, defaults to `32`.
+/// - `options.savePath`: Specify the path to save the icon, defaults to the default save path.
+/// - `options.hiDpi`: Multiply the size by the highest scale factor of the connected monitors, see [`icon`].
+/// - `options.scaleFactor`: Multiply the size by this scale factor instead.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::icon_for_extension;
+///
+/// let icon_path = icon_for_extension(app.handle(), "pdf".to_string(), None).await?;
+/// println!("{}", icon_path.display());
+/// ```
+#[command]
+pub async fn icon_for_extension<R: Runtime>(
+    app_handle: AppHandle<R>,
+    extname: String,
+    options: Option<IconOptions>,
+) -> Result<PathBuf> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let extname = extname.trim_start_matches('.').to_lowercase();
+
+    if extname.is_empty() || extname.contains(['/', '\\']) {
+        return Err(Error::InvalidExtension(extname));
+    }
+
+    let size = options.as_ref().and_then(|opt| opt.size).unwrap_or(32);
+    let scale = icon_scale(&app_handle, options.as_ref())?;
+    let default_save_path = get_default_save_icon_path(app_handle.clone()).await?;
+    let save_path = options
+        .and_then(|opt| opt.save_path)
+        .unwrap_or(default_save_path);
+
+    if let Some(icon_path) = override_icon(&app_handle, &extname, &save_path)? {
+        return Ok(icon_path);
+    }
+
+    let placeholder = create_placeholder(&app_handle, &extname)?;
+    let result = save_icon(&placeholder, &extname, size, scale, &save_path);
+
+    let _ = fs::remove_file(&placeholder);
+
+    result
+}
+
+// The system resolves icons from the file type association, so an empty placeholder file is enough. Each call creates its own in
+// the cache directory of the app, which other users can't write to, and never opens an existing file.
+fn create_placeholder<R: Runtime>(app_handle: &AppHandle<R>, extname: &str) -> Result<PathBuf> {
+    static NEXT_PLACEHOLDER: AtomicU64 = AtomicU64::new(0);
+
+    let dir = app_handle.path().app_cache_dir()?.join("placeholders");
+    create_dir_all(&dir)?;
+
+    let id = NEXT_PLACEHOLDER.fetch_add(1, Ordering::Relaxed);
+    let placeholder = dir.join(format!("{}-{}.{}", std::process::id(), id, extname));
+
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&placeholder)?;
+
+    Ok(placeholder)
+}
+
+/// Get the product name, version, company and description of an executable, from the version resources on Windows and the `Info.plist` of app bundles on macOS.
+///
+/// Fields that are missing, and all fields on other platforms, are `None`.
+///
+/// # Arguments
+/// - `path`: Specify the path of the executable or app bundle.
+///
+/// # Returns
+/// - `Ok(ExeInfo)`: The product metadata of the executable.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::exe_info;
+///
+/// let path = PathBuf::from("C:\\Program Files\\App\\app.exe");
+/// let info = exe_info(app.handle().clone(), path.into()).await?;
+/// println!("{:?}", info.product_name);
+/// ```
+#[command]
+pub async fn exe_info<R: Runtime>(app_handle: AppHandle<R>, path: PathInput) -> Result<ExeInfo> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    read_exe_info(&path)
+}
+
+fn system_time_to_unix_millis(time: io::Result<SystemTime>) -> u128 {
+    match time {
+        Ok(system_time) => system_time
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default(),
+        Err(_) => 0,
+    }
+}
+
+/// Get the metadata of the path.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+/// - `options.omitSize`: When getting the metadata of a path, if you don't need to calculate the size, you can omit it to save time and return 0 after omitting it, defaults to `false`.
+/// - `options.deferSize`: Return immediately with a `0` size and a `sizeId`, then emit the `fs-pro://size` event with the same id once the size is calculated, defaults to `false`.
+///
+/// # Returns
+/// - `Ok(Metadata)`: The metadata of the path.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::metadata;
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let metadata = metadata(app.handle(), path.into(), None).await?;
+/// println!("{:?}", metadata);
+/// ```
+#[command]
+pub async fn metadata<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    options: Option<MetadataOptions>,
+) -> Result<Metadata> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    let omit_size = options
+        .as_ref()
+        .and_then(|opt| opt.omit_size)
+        .unwrap_or(false);
+    let defer_size = options.and_then(|opt| opt.defer_size).unwrap_or(false) && !omit_size;
+
+    let size = if omit_size || defer_size {
+        0
+    } else {
+        walk_size(&path).0
+    };
+    let name = name(path.clone().into()).await;
+    let extname = extname(path.clone().into()).await;
+    let full_name = full_name(path.clone().into()).await;
+    let parent_name = parent_name(app_handle.clone(), path.clone().into(), Some(1)).await?;
+    let raw_full_name = path.file_name().and_then(raw_os_str);
+    let raw_path = raw_os_str(path.as_os_str());
+
+    let is_dir = path.is_dir();
+    let is_file = path.is_file();
+    let is_exist = path.exists();
+    let is_symlink = path.is_symlink();
+    let is_absolute = path.is_absolute();
+    let is_relative = path.is_relative();
+    let file_type = FileType::from(fs::symlink_metadata(&path)?.file_type());
+
+    let metadata = fs::metadata(&path)?;
+    let accessed_at = system_time_to_unix_millis(metadata.accessed());
+    let created_at = system_time_to_unix_millis(metadata.created());
+    let modified_at = system_time_to_unix_millis(metadata.modified());
+
+    // Only once the path is known to exist, so a failed call emits nothing.
+    let size_id = defer_size.then(|| {
+        static NEXT_SIZE_ID: AtomicU32 = AtomicU32::new(0);
+
+        let id = NEXT_SIZE_ID.fetch_add(1, Ordering::Relaxed);
+
+        thread::spawn(move || {
+            let size = walk_size(&path).0;
+            let _ = app_handle.emit("fs-pro://size", SizeEvent { id, path, size });
+        });
+
+        id
+    });
+
+    Ok(Metadata {
+        size,
+        size_id,
+        name,
+        extname,
+        full_name,
+        raw_full_name,
+        raw_path,
+        parent_name,
+        is_dir,
+        is_file,
+        is_exist,
+        is_symlink,
+        file_type,
+        is_absolute,
+        is_relative,
+        accessed_at,
+        created_at,
+        modified_at,
+    })
+}
+
+// Archive extensions made of several parts, which must stay together when inserting a timestamp.
+const ARCHIVE_EXTENSIONS: &[&str] = &[".tar.gz", ".tar.zst", ".tar.xz", ".tar.bz2"];
+
+// Insert the current timestamp before the extension, e.g. `backup.tar.gz` -> `backup-1700000000000.tar.gz`.
+fn with_timestamp(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let extension = ARCHIVE_EXTENSIONS
+        .iter()
+        .find(|extension| file_name.ends_with(*extension))
+        .map(|extension| extension.to_string())
+        .or_else(|| {
+            path.extension()
+                .map(|extension| format!(".{}", extension.to_string_lossy()))
+        })
+        .unwrap_or_default();
+
+    let stem = &file_name[..file_name.len() - extension.len()];
+    let timestamp = system_time_to_unix_millis(Ok(SystemTime::now()));
+
+    path.with_file_name(format!("{}-{}{}", stem, timestamp, extension))
+}
+
+// Append a directory tree to the archive under `name`, skipping ignored entries and collecting the entries that can't be read.
+// Symbolic links inside the tree are stored as links unless `follow_links` is set.
+fn append_dir_tree(
+    archive: &mut ArchiveWriter,
+    name: &Path,
+    path: &Path,
+    filter: &EntryFilter,
+    follow_links: bool,
+    cancel: &CancelToken,
+    inaccessible: &mut Vec<PathBuf>,
+) -> Result<()> {
+    // Sorted, so the same tree always gives the same archive.
+    let entries = WalkDir::new(path)
+        .follow_links(follow_links)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !filter.is_skipped(entry.path(), entry.file_type().is_dir()));
+
+    // The directories that are not included themselves, with their depth, added once an entry below them is.
+    let mut pending: Vec<(usize, PathBuf, PathBuf)> = Vec::new();
+
+    for entry in entries {
+        cancel.check()?;
+
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) if is_permission_denied(err.io_error()) => {
+                inaccessible.push(err.path().unwrap_or(path).to_path_buf());
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let relative = entry.path().strip_prefix(path).unwrap_or(entry.path());
+        let archive_name = if relative.as_os_str().is_empty() {
+            name.to_path_buf()
+        } else {
+            name.join(relative)
+        };
+        let is_dir = entry.file_type().is_dir();
+
+        // Drop the pending directories the walk has left.
+        pending.retain(|(depth, ..)| *depth < entry.depth());
+
+        if !filter.keeps(entry.path(), is_dir) {
+            if is_dir {
+                pending.push((entry.depth(), archive_name, entry.into_path()));
+            }
+
+            continue;
+        }
+
+        for (_, dir_name, dir) in pending.drain(..) {
+            match archive.append_dir(&dir_name, &dir) {
+                Ok(()) => {}
+                Err(err) if is_permission_denied(Some(&err)) => inaccessible.push(dir),
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let result = if is_dir {
+            archive.append_dir(&archive_name, entry.path())
+        } else if entry.file_type().is_symlink() {
+            archive.append_symlink(&archive_name, entry.path())
+        } else {
+            archive.append_file(&archive_name, entry.path())
+        };
+
+        match result {
+            Ok(()) => {}
+            Err(err) if is_permission_denied(Some(&err)) => inaccessible.push(entry.into_path()),
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(())
+}
+
+// Resolve where an archive is written, refusing to replace an existing file unless `overwrite` is set.
+fn archive_path(dst_path: PathBuf, overwrite: bool, append_timestamp: bool) -> Result<PathBuf> {
+    let dst_path = if append_timestamp {
+        with_timestamp(&dst_path)
+    } else {
+        dst_path
+    };
+
+    if !overwrite && dst_path.exists() {
+        return Err(Error::AlreadyExists(dst_path));
+    }
+
+    Ok(dst_path)
+}
+
+/// Compress the source path into a tar.gz, tar.zst, tar.xz, tar.bz2 or zip file to the destination path.
+///
+/// The contents of a source directory are stored at the root of the archive, a source file is stored under its name and the
+/// filter options don't apply to it. Directories are stored as explicit entries, so empty ones survive a round trip through
+/// [`decompress`], but with `options.includes` or the size and time options only those of the entries kept are, unless
+/// `options.includeEmptyDirs` is set. The entries are stored sorted by name.
+///
+/// # Arguments
+/// - `src_path`: Specify the source path, a directory or a single file.
+/// - `dst_path`: Specify the destination path.
+/// - `options.includes`: The glob patterns of the paths to be compressed, matched against the path relative to the source path, e.g. `src`, `src/config/settings.json` or `**/*.rs`, with or without a leading `./`. Directories are searched for matching entries.
+/// - `options.excludes`: The glob patterns of the paths not to be compressed, matched against the path relative to the source path, e.g. `*.log` or `target`.
+/// - `options.ignore`: Gitignore-style rules of the paths not to be compressed, matched relative to the source path, either `IgnoreRules::Path` of a file such as `.exportignore` or `IgnoreRules::Contents`.
+/// - `options.minSize`: Only compress files of at least this many bytes.
+/// - `options.maxSize`: Only compress files of at most this many bytes.
+/// - `options.modifiedAfter`: Only compress files modified after this time, in milliseconds since the Unix epoch.
+/// - `options.modifiedBefore`: Only compress files modified before this time, in milliseconds since the Unix epoch. With any of the size and time options, directories are only stored for the files compressed in them.
+/// - `options.includeEmptyDirs`: Store every directory that isn't excluded, see [`compress`].
+/// - `options.includeEmptyDirs`: Store every directory that isn't excluded, even when `options.includes` or the size and time options keep nothing in it, defaults to `false`.
+/// - `options.overwrite`: Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`. The archive is written next to it and only replaces it once complete, so a failed compression leaves it intact.
+/// - `options.appendTimestamp`: Append the current timestamp in milliseconds to the destination file name, defaults to `false`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`], the partial archive is removed.
+/// - `options.format`: The format of the archive, `ArchiveFormat::TarGz`, `ArchiveFormat::TarZst`, `ArchiveFormat::TarXz` with the `xz` feature, `ArchiveFormat::TarBz2` with the `bzip2` feature or `ArchiveFormat::Zip`, defaults to `ArchiveFormat::TarGz`.
+/// - `options.level`: The compression level, from `0` to `9` defaulting to `6` for `ArchiveFormat::TarGz`, `ArchiveFormat::Zip` and `ArchiveFormat::TarXz`, from `1` to `22` defaulting to `3` for `ArchiveFormat::TarZst` and from `1` to `9` defaulting to `6` for `ArchiveFormat::TarBz2`. Higher levels are smaller but slower, `0` stores without compressing.
+/// - `options.append`: Add the entries to an existing archive at the destination path instead of replacing it, in its own format detected from its contents, defaults to `false`. The archive is rewritten next to it and replaced once complete. An entry already in a zip file can't be added again, a tar file keeps both and the later one wins when extracting.
+/// - `options.volumeSize`: Split the archive into volumes of this many bytes, e.g. for services with an upload limit, named after the destination path with `.001`, `.002` and so on. [`decompress`] reads the set back from its first volume. A split archive is always written anew, `options.append` doesn't apply to it.
+/// - `options.deterministic`: Leave out modified times, owners and groups so identical trees give byte-identical archives, e.g. to hash them for cache keys, defaults to `false`. Tar entries get a fixed modified time and the permissions `0o755` or `0o644`, zip entries the earliest time zip files can store.
+/// - `options.preserveSymlinks`: Store the symbolic links inside the source directories as links instead of the files and directories they point to, defaults to `false`. A link is stored with its target as it is, relative or absolute. Tar entries always carry the permissions, owners and groups of the files, zip entries their permissions.
+/// - `options.writeManifest`: Write a JSON manifest next to the archive, named after it with `.manifest.json`, e.g. for an updater to compare archives without opening them, defaults to `false`. It's read back from the written archive and holds `entryCount`, `totalSize` and the `entries` in order with their `path`, `size`, `fileType`, `modifiedAt` and `sha256`, the lowercase hex SHA-256 hash of the data of files and `null` for other entries.
+/// - `options.dryRun`: Only plan the compression without writing anything, defaults to `false`. The report holds the `plan` instead, with the `items` that would be stored, each with its `srcPath`, its `dstPath` inside the archive and its `size`, the `totalBytes` of their files before compression and the `collisions`: the destination file that would be replaced, or the entries of the archive appended to that would be stored again.
+/// - `options.notify`: Show a system notification once the archive is written or failed, see [`NotificationOptions`]. The texts are templates of `{name}`, `{srcPath}`, `{dstPath}`, `{error}` and the fields of the report, arrays by their length, e.g. `Exported {name} to {path}`.
+///
+/// # Returns
+/// - `Ok(CompressReport)`: The path of the written archive, or of its first volume along with the paths of all volumes when split, the paths left out because of missing permissions, the path of the manifest when written and the plan of a dry run.
+/// - `Err(Error)`: The error on failure, of kind `invalidCompressionLevel` when the level is out of range for the format.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::compress;
+///
+/// let src_path = PathBuf::from("/path/to/source");
+/// let dst_path = PathBuf::from("/path/to/destination.tar.gz");
+/// compress(app.handle().clone(), src_path.into(), dst_path.into(), None).await?;
+/// ```
+#[command]
+pub async fn compress<R: Runtime>(
+    app_handle: AppHandle<R>,
+    src_path: PathInput,
+    dst_path: PathInput,
+    mut options: Option<CompressOptions>,
+) -> Result<CompressReport> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let src_path = PathBuf::from(src_path);
+    let dst_path = PathBuf::from(dst_path);
+
+    let notify = options.as_mut().and_then(|opt| opt.notify.take());
+    let result = compress_sources(
+        app_handle.clone(),
+        "compress",
+        vec![(src_path.clone(), None)],
+        dst_path.clone(),
+        options,
+    )
+    .await;
+
+    notification::finished(&app_handle, notify.as_ref(), &src_path, &dst_path, &result);
+
+    result
+}
+
+/// Compress several files and directories from different locations into one tar.gz, tar.zst, tar.xz, tar.bz2 or zip file.
+///
+/// Each source is stored under its name at the root of the archive, or under the name given in `options.names`, e.g. a config file
+/// and a data directory next to each other. Takes the same options as [`compress`], the filter options are matched relative to each
+/// source directory.
+///
+/// # Arguments
+/// - `src_paths`: Specify the source paths, directories or files.
+/// - `dst_path`: Specify the destination path.
+/// - `options.names`: The paths of the sources inside the archive keyed by source path, e.g. `backup/config.json`, defaults to the name of each source.
+/// - `options.includes`: The glob patterns of the paths to be compressed, matched against the path relative to each source directory.
+/// - `options.excludes`: The glob patterns of the paths not to be compressed, matched against the path relative to each source directory.
+/// - `options.ignore`: Gitignore-style rules of the paths not to be compressed, matched relative to each source directory.
+/// - `options.minSize`: Only compress files of at least this many bytes.
+/// - `options.maxSize`: Only compress files of at most this many bytes.
+/// - `options.modifiedAfter`: Only compress files modified after this time, in milliseconds since the Unix epoch.
+/// - `options.modifiedBefore`: Only compress files modified before this time, in milliseconds since the Unix epoch. With any of the size and time options, directories are only stored for the files compressed in them.
+/// - `options.overwrite`: Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`. The archive is written next to it and only replaces it once complete, so a failed compression leaves it intact.
+/// - `options.appendTimestamp`: Append the current timestamp in milliseconds to the destination file name, defaults to `false`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`], the partial archive is removed.
+/// - `options.format`: The format of the archive, see [`compress`], defaults to `ArchiveFormat::TarGz`.
+/// - `options.level`: The compression level, within the levels of the format, see [`compress`].
+/// - `options.append`: Add the entries to an existing archive at the destination path instead of replacing it, see [`compress`].
+/// - `options.volumeSize`: Split the archive into volumes of this many bytes, see [`compress`].
+/// - `options.deterministic`: Leave out modified times, owners and groups so identical trees give byte-identical archives, see [`compress`].
+/// - `options.preserveSymlinks`: Store the symbolic links inside the source directories as links, see [`compress`].
+/// - `options.writeManifest`: Write a JSON manifest of the entries with their sizes and hashes next to the archive, see [`compress`].
+/// - `options.dryRun`: Only return the planned entries with their sizes and the collisions without writing anything, see [`compress`].
+/// - `options.notify`: Show a system notification once finished, see [`compress`].
+///
+/// # Returns
+/// - `Ok(CompressReport)`: The path of the written archive, the paths left out because of missing permissions, the path of the manifest when written and the plan of a dry run.
+/// - `Err(Error)`: The error on failure, of kind `alreadyExists` when two sources have the same name inside the archive and `invalidPath` when a name is not a relative path.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::{compress_many, PathInput};
+///
+/// let src_paths = vec![PathInput::from("/etc/app/config.json"), PathInput::from("/var/lib/app/data")];
+/// let dst_path = PathBuf::from("/path/to/backup.zip");
+/// compress_many(app.handle().clone(), src_paths, dst_path.into(), None).await?;
+/// ```
+#[command]
+pub async fn compress_many<R: Runtime>(
+    app_handle: AppHandle<R>,
+    src_paths: Vec<PathInput>,
+    dst_path: PathInput,
+    mut options: Option<CompressManyOptions>,
+) -> Result<CompressReport> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let src_paths: Vec<PathBuf> = src_paths.into_iter().map(PathBuf::from).collect();
+    let dst_path = PathBuf::from(dst_path);
+
+    let notify = options.as_mut().and_then(|opt| opt.compress.notify.take());
+    // The notification is about the first source.
+    let src_path = src_paths.first().cloned().unwrap_or_default();
+    let result = compress_named(app_handle.clone(), src_paths, dst_path.clone(), options).await;
+
+    notification::finished(&app_handle, notify.as_ref(), &src_path, &dst_path, &result);
+
+    result
+}
+
+// Write the sources into an archive under their names, checking them all before anything is written.
+async fn compress_named<R: Runtime>(
+    app_handle: AppHandle<R>,
+    src_paths: Vec<PathBuf>,
+    dst_path: PathBuf,
+    options: Option<CompressManyOptions>,
+) -> Result<CompressReport> {
+    let (options, mut names) = match options {
+        Some(opt) => (Some(opt.compress), opt.names.unwrap_or_default()),
+        None => (None, HashMap::new()),
+    };
+    let mut taken = HashSet::new();
+    let mut sources = Vec::new();
+
+    // Check every source up front so a wrong name doesn't leave a partial archive behind.
+    for src_path in src_paths {
+        if fs::symlink_metadata(&src_path).is_err() {
+            return Err(Error::NotFound(src_path));
+        }
+
+        let name = match names.remove(&src_path) {
+            Some(name) => name,
+            None => PathBuf::from(full_name(src_path.clone().into()).await),
+        };
+
+        if !is_archive_relative(&name) {
+            return Err(Error::InvalidPath(name));
+        }
+
+        if !taken.insert(name.clone()) {
+            return Err(Error::AlreadyExists(name));
+        }
+
+        sources.push((src_path, Some(name)));
+    }
+
+    compress_sources(app_handle, "compress_many", sources, dst_path, options).await
+}
+
+// Write the sources into an archive, each under its name or, without one, a directory's contents at the root of the archive.
+async fn compress_sources<R: Runtime>(
+    app_handle: AppHandle<R>,
+    command: &'static str,
+    sources: Vec<(PathBuf, Option<PathBuf>)>,
+    dst_path: PathBuf,
+    options: Option<CompressOptions>,
+) -> Result<CompressReport> {
+    let options = options.unwrap_or(CompressOptions {
+        includes: Some(vec![]),
+        excludes: Some(vec![]),
+        ignore: None,
+        min_size: None,
+        max_size: None,
+        modified_after: None,
+        modified_before: None,
+        include_empty_dirs: None,
+        overwrite: Some(true),
+        append_timestamp: Some(false),
+        operation_id: None,
+        format: None,
+        level: None,
+        append: None,
+        volume_size: None,
+        deterministic: None,
+        preserve_symlinks: None,
+        dry_run: None,
+        write_manifest: None,
+        notify: None,
+    });
+    let includes = options.includes.unwrap_or(vec![]);
+    let excludes = options.excludes.unwrap_or(vec![]);
+    let limits = FileLimits {
+        min_size: options.min_size,
+        max_size: options.max_size,
+        modified_after: options.modified_after,
+        modified_before: options.modified_before,
+    };
+    let filters = sources
+        .iter()
+        .map(|(src_path, _)| {
+            EntryFilter::new(
+                src_path,
+                &includes,
+                &excludes,
+                options.ignore.as_ref(),
+                limits,
+            )
+            .map(|filter| filter.keep_dirs(options.include_empty_dirs.unwrap_or(false)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let overwrite = options.overwrite.unwrap_or(true);
+    let append_timestamp = options.append_timestamp.unwrap_or(false);
+    let follow_links = !options.preserve_symlinks.unwrap_or(false);
+    let traced_path = sources.first().map(|(src_path, _)| src_path.as_path());
+    let mut trace = trace::command(
+        &app_handle.fs_pro().settings,
+        command,
+        traced_path.unwrap_or(Path::new("")),
+        options.operation_id.as_deref(),
+    )
+    .dst_path(&dst_path);
+    let operation = app_handle.fs_pro().operations.start(options.operation_id);
+    let format = options.format.unwrap_or_default();
+    let volume_size = options.volume_size;
+    // A split archive is always written anew.
+    let appending = options.append.unwrap_or(false) && dst_path.is_file() && volume_size.is_none();
+    // When appending, the level is checked against the format of the existing archive instead.
+    let level = match appending {
+        true => None,
+        false => Some(format.level(options.level)?),
+    };
+
+    let dry_run = options.dry_run.unwrap_or(false);
+
+    // A dry run reports an existing destination as a collision rather than failing.
+    let dst_path = if appending {
+        dst_path
+    } else {
+        archive_path(dst_path, overwrite || dry_run, append_timestamp)?
+    };
+
+    if dry_run {
+        let mut archive = ArchiveWriter::DryRun(Vec::new());
+        let mut inaccessible = Vec::new();
+
+        for ((src_path, name), filter) in sources.iter().zip(&filters) {
+            append_source(
+                &mut archive,
+                src_path,
+                name.as_deref(),
+                filter,
+                follow_links,
+                operation.token(),
+                &mut inaccessible,
+            )
+            .await?;
+        }
+
+        let ArchiveWriter::DryRun(items) = archive else {
+            unreachable!("a dry run only plans the entries");
+        };
+        let written_path = match volume_size {
+            Some(_) => volume::volume_path(&dst_path, 1),
+            None => dst_path.clone(),
+        };
+
+        // The entries of an existing archive are only replaced when appending to a tar file, by a later entry of the same name.
+        let collisions = if appending {
+            let existing = archive::entries(&dst_path, NameEncoding::default())?
+                .into_iter()
+                .filter(|entry| entry.file_type != FileType::Dir)
+                .map(|entry| entry.path)
+                .collect::<HashSet<_>>();
+
+            items
+                .iter()
+                .filter(|item| existing.contains(&item.dst_path))
+                .map(|item| item.dst_path.clone())
+                .collect()
+        } else if written_path.exists() {
+            vec![written_path]
+        } else {
+            Vec::new()
+        };
+
+        trace.succeeded();
+
+        return Ok(CompressReport {
+            path: dst_path,
+            volumes: Vec::new(),
+            inaccessible,
+            manifest_path: None,
+            plan: Some(DryRunPlan::new(items, collisions)),
+        });
+    }
+
+    if volume_size.is_some() && !overwrite && volume::volume_path(&dst_path, 1).exists() {
+        return Err(Error::AlreadyExists(volume::volume_path(&dst_path, 1)));
+    }
+
+    // The archive is written next to the destination and only moved in place once complete, so an existing archive, or the one
+    // appended to, stays intact when anything fails.
+    let written_path = {
+        let name = full_name(dst_path.clone().into()).await;
+        let suffix = if appending { "append" } else { "compress" };
+
+        dst_path.with_file_name(format!(".{}.fs-pro-{}", name, suffix))
+    };
+    let mut inaccessible = Vec::new();
+
+    let written: Result<()> = async {
+        let written_file = Volumes::create(&written_path, volume_size)?;
+        let mut archive = match level {
+            Some(level) => ArchiveWriter::new(written_file, format, level)?,
+            None => archive::reopen(&dst_path, written_file, options.level)?,
+        };
+
+        if options.deterministic.unwrap_or(false) {
+            archive.set_deterministic();
+        }
+
+        for ((src_path, name), filter) in sources.iter().zip(&filters) {
+            append_source(
+                &mut archive,
+                src_path,
+                name.as_deref(),
+                filter,
+                follow_links,
+                operation.token(),
+                &mut inaccessible,
+            )
+            .await?;
+        }
+
+        archive.finish()?;
+        volume::rename(&written_path, &dst_path)?;
+
+        Ok(())
+    }
+    .await;
+
+    // Don't leave a partial archive behind.
+    if written.is_err() {
+        let _ = fs::remove_file(&written_path);
+        let _ = volume::remove(&written_path);
+    }
+
+    written?;
+
+    let volumes = match volume_size {
+        Some(_) => volume::paths(&dst_path),
+        None => Vec::new(),
+    };
+
+    let written_paths = match volumes.is_empty() {
+        true => std::slice::from_ref(&dst_path),
+        false => &volumes,
+    };
+
+    trace.bytes(
+        written_paths
+            .iter()
+            .filter_map(|path| fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum(),
+    );
+
+    // Read back from the written archive, so the manifest describes exactly what it holds.
+    let manifest_path = match options.write_manifest.unwrap_or(false) {
+        true => {
+            let mut name = dst_path.clone().into_os_string();
+            name.push(".manifest.json");
+
+            let manifest = archive::manifest(&dst_path)?;
+            fs::write(&name, serde_json::to_vec_pretty(&manifest)?)?;
+
+            Some(PathBuf::from(name))
+        }
+        false => None,
+    };
+
+    trace.succeeded();
+
+    Ok(CompressReport {
+        path: volumes.first().cloned().unwrap_or(dst_path),
+        volumes,
+        inaccessible,
+        manifest_path,
+        plan: None,
+    })
+}
+
+// Append a source to the archive under `name`. A file without a name is stored under its own name and the filter doesn't apply to
+// it, a directory without one has its contents stored at the root of the archive. The source itself is always followed when it's a
+// symbolic link.
+async fn append_source(
+    archive: &mut ArchiveWriter,
+    src_path: &Path,
+    name: Option<&Path>,
+    filter: &EntryFilter,
+    follow_links: bool,
+    cancel: &CancelToken,
+    inaccessible: &mut Vec<PathBuf>,
+) -> Result<()> {
+    if src_path.is_file() {
+        let full_name = full_name(src_path.into()).await;
+
+        return Ok(archive.append_file(name.unwrap_or(Path::new(&full_name)), src_path)?);
+    }
+
+    if let Some(name) = name {
+        return append_dir_tree(
+            archive,
+            name,
+            src_path,
+            filter,
+            follow_links,
+            cancel,
+            inaccessible,
+        );
+    }
+
+    let mut paths = read_dir(src_path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    paths.sort();
+
+    for path in paths {
+        cancel.check()?;
+
+        let is_symlink = !follow_links && path.is_symlink();
+        let is_file = is_symlink || path.is_file();
+        let full_name = full_name(path.clone().into()).await;
+
+        if filter.is_skipped(&path, !is_file) {
+            continue;
+        }
+
+        if is_file {
+            if !filter.keeps(&path, false) {
+                continue;
+            }
+
+            let result = match is_symlink {
+                true => archive.append_symlink(Path::new(&full_name), &path),
+                false => archive.append_file(Path::new(&full_name), &path),
+            };
+
+            match result {
+                Ok(()) => {}
+                Err(err) if is_permission_denied(Some(&err)) => inaccessible.push(path),
+                Err(err) => return Err(err.into()),
+            }
+        } else {
+            append_dir_tree(
+                archive,
+                Path::new(&full_name),
+                &path,
+                filter,
+                follow_links,
+                cancel,
+                inaccessible,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+// Whether a path inside an archive is relative and stays inside it.
+fn is_archive_relative(path: &Path) -> bool {
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Compress an explicit list of files and directories into a tar.gz, tar.zst, tar.xz, tar.bz2 or zip file, without walking any directory.
+///
+/// Directories are added as empty entries, list their contents in the manifest to include them.
+///
+/// # Arguments
+/// - `manifest`: Specify the path of a JSON file containing the entries, or the entries themselves. Each entry is an object of `src`, the path on disk, and `archivePath`, the relative path inside the archive.
+/// - `dst_path`: Specify the destination path.
+/// - `options.overwrite`: Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+/// - `options.appendTimestamp`: Append the current timestamp in milliseconds to the destination file name, defaults to `false`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`], the partial archive is removed.
+/// - `options.format`: The format of the archive, see [`compress`], defaults to `ArchiveFormat::TarGz`.
+/// - `options.level`: The compression level, within the levels of the format, see [`compress`].
+///
+/// # Returns
+/// - `Ok(CompressReport)`: The path of the written archive and the paths left out because of missing permissions.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::{compress_manifest, Manifest};
+///
+/// let manifest = Manifest::Path(PathBuf::from("/path/to/manifest.json"));
+/// let dst_path = PathBuf::from("/path/to/destination.tar.gz");
+/// compress_manifest(app.handle().clone(), manifest, dst_path.into(), None).await?;
+/// ```
+#[command]
+pub async fn compress_manifest<R: Runtime>(
+    app_handle: AppHandle<R>,
+    manifest: Manifest,
+    dst_path: PathInput,
+    options: Option<CompressManifestOptions>,
+) -> Result<CompressReport> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let dst_path = PathBuf::from(dst_path);
+
+    let options = options.unwrap_or(CompressManifestOptions {
+        overwrite: Some(true),
+        append_timestamp: Some(false),
+        operation_id: None,
+        format: None,
+        level: None,
+    });
+    let overwrite = options.overwrite.unwrap_or(true);
+    let append_timestamp = options.append_timestamp.unwrap_or(false);
+    let format = options.format.unwrap_or_default();
+    let level = format.level(options.level)?;
+
+    let entries = match manifest {
+        Manifest::Entries(entries) => entries,
+        Manifest::Path(path) => serde_json::from_reader(io::BufReader::new(File::open(path)?))?,
+    };
+
+    // Check every entry up front so an invalid manifest doesn't leave a partial archive behind.
+    for entry in &entries {
+        if !is_archive_relative(&entry.archive_path) {
+            return Err(Error::InvalidPath(entry.archive_path.clone()));
+        }
+    }
+
+    let dst_path = archive_path(dst_path, overwrite, append_timestamp)?;
+    let operation = app_handle.fs_pro().operations.start(options.operation_id);
+
+    // Written next to the destination and moved in place once complete, as with `compress`.
+    let written_path = {
+        let name = full_name(dst_path.clone().into()).await;
+
+        dst_path.with_file_name(format!(".{}.fs-pro-compress", name))
+    };
+    let mut inaccessible = Vec::new();
+
+    let written: Result<()> = (|| {
+        let written_file = File::create(&written_path)?;
+        let mut archive = ArchiveWriter::new(written_file.into(), format, level)?;
+
+        for entry in entries {
+            operation.token().check()?;
+
+            let result = if entry.src.is_dir() {
+                archive.append_dir(&entry.archive_path, &entry.src)
+            } else {
+                archive.append_file(&entry.archive_path, &entry.src)
+            };
+
+            match result {
+                Ok(()) => {}
+                Err(err) if is_permission_denied(Some(&err)) => inaccessible.push(entry.src),
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        archive.finish()?;
+        volume::rename(&written_path, &dst_path)?;
+
+        Ok(())
+    })();
+
+    // Don't leave a partial archive behind.
+    if written.is_err() {
+        let _ = fs::remove_file(&written_path);
+    }
+
+    written?;
+
+    Ok(CompressReport {
+        path: dst_path,
+        volumes: Vec::new(),
+        inaccessible,
+        manifest_path: None,
+        plan: None,
+    })
+}
+
+/// Decompress the archive from the source path to the destination path.
+///
+/// The format is detected from the contents rather than the extension: a tar file, compressed with gzip, zstd, xz or bzip2 or not at all, or a zip file.
+/// tar.xz files need the `xz` feature and tar.bz2 files the `bzip2` feature, both off by default.
+///
+/// An archive split into volumes is reassembled while reading, given by its first volume such as `backup.tar.gz.001`, or by its name
+/// without the number.
+///
+/// Directory entries are recreated even when they are empty, and symbolic links as links on Linux and macOS.
+///
+/// Entries leading outside the destination path are refused, protecting against crafted archives ("zip slip"): absolute paths,
+/// paths through `..` or through a link extracted before, and links pointing outside. The extraction stops at the first one, the
+/// entries extracted before it are kept.
+///
+/// With `on_progress`, the entries to extract are counted first to report the progress against, reading a compressed tar file
+/// through an extra time, a zip file only has its central directory read. The progress is updated as each entry starts, by the
+/// entries and their uncompressed size.
+///
+/// # Arguments
+/// - `src_path`: Specify the source path.
+/// - `dst_path`: Specify the destination path.
+/// - `options.entries`: Glob patterns of the entries to extract, matched against their path inside the archive as listed by [`archive_entries`] without a leading `./`, e.g. `assets/*.png`. The entries inside a matched directory are extracted too. Extracts all entries when omitted.
+/// - `options.backupToTrash`: Move existing destination files to the system trash before overwriting them, defaults to `false`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`], the entries extracted so far are kept.
+/// - `options.preservePermissions`: Restore the setuid, setgid and sticky bits of tar entries too, defaults to `false`. The other permission bits are always restored, zip entries only carry those.
+/// - `options.preserveOwnership`: Restore the owners and groups of tar entries, defaults to `false`. Changing them takes the privileges to do so, such as running as root, the extraction fails otherwise.
+/// - `options.allowExternalPaths`: Extract the entries and links leading outside the destination path as they are instead of refusing them, defaults to `false`. Only for archives from a trusted source, such as backups of the system the app created itself.
+/// - `options.stripComponents`: The number of leading components to remove from the names of the entries, as `tar --strip-components` does, defaults to `0`. With `1`, `foo-1.2.3/src/lib.rs` is extracted to `src/lib.rs` in the destination path, and the entries with no components left, such as `foo-1.2.3/` itself, are skipped. `options.entries` still matches the full names.
+/// - `options.onConflict`: What happens to a file that exists before the extraction where an entry goes, defaults to `OverwritePolicy::Overwrite`. `OverwritePolicy::Skip` keeps the file and leaves the entry out, `OverwritePolicy::Error` stops the extraction with an `alreadyExists` error, keeping the entries extracted before, and `OverwritePolicy::Rename` keeps the file and extracts the entry next to it as `name (1).ext`. Existing directories are merged into either way.
+/// - `options.resume`: Continue an extraction into the same destination that failed part way, such as when the disk filled up or the app quit, defaults to `false`. The files it completed are left as they are rather than extracted again: files of the same size and modified time for tar entries, of the same size and CRC-32 for zip entries. A compressed tar file is still read through up to where it failed, a zip file is not. The other existing files are handled after `options.onConflict`.
+/// - `options.onProgressIntervalMs`: The minimum time between two progress updates in milliseconds, defaults to `100` or the interval set with [`crate::Builder::min_event_interval`].
+/// - `options.notify`: Show a system notification once finished, see [`compress`].
+/// - `options.encoding`: How the names of zip entries not flagged as UTF-8 are decoded, defaults to `NameEncoding::Auto`. Zip files made by Windows or older tools store them in the code page of the system, `NameEncoding::Auto` detects GBK, Shift-JIS, Big5, EUC-KR and EUC-JP from the names and decodes them as CP437, the encoding of the zip format, otherwise. Give the encoding when the names still come out garbled.
+/// - `on_progress`: Specify the channel that receives `DecompressProgress` updates.
+///
+/// # Returns
+/// - `Ok(DecompressReport)`: The archive was extracted, with the number of entries written, the size of the files written, the top-level directories in the destination path they were written to, such as `foo-1.2.3` to open afterwards, and the paths of the existing files entries were skipped or renamed for.
+/// - `Err(Error)`: The error on failure, of kind `unsupportedArchive` when the format is not recognized, `notWritable` when the destination is on read-only media, `invalidPath` when an entry leads outside the destination path, or `alreadyExists` when a file exists where an entry goes with `OverwritePolicy::Error`.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::decompress;
+///
+/// let src_path = PathBuf::from("/path/to/source.tar.gz");
+/// let dst_path = PathBuf::from("/path/to/destination");
+/// let report = decompress(webview, src_path.into(), dst_path.into(), None, None).await?;
+/// println!("{:?}", report.root_dirs);
+/// ```
+#[command]
+pub async fn decompress<R: Runtime>(
+    webview: Webview<R>,
+    src_path: PathInput,
+    dst_path: PathInput,
+    mut options: Option<DecompressOptions>,
+    on_progress: Option<JavaScriptChannelId>,
+) -> Result<DecompressReport> {
+    let _redacting = webview.fs_pro().redacting();
+
+    let src_path = PathBuf::from(src_path);
+    let dst_path = PathBuf::from(dst_path);
+
+    let notify = options.as_mut().and_then(|opt| opt.notify.take());
+    let app_handle = webview.app_handle().clone();
+    let result = extract(
+        webview,
+        src_path.clone(),
+        dst_path.clone(),
+        options,
+        on_progress,
+    )
+    .await;
+
+    notification::finished(&app_handle, notify.as_ref(), &src_path, &dst_path, &result);
+
+    result
+}
+
+// Extract the archive, reporting the progress over the channel.
+async fn extract<R: Runtime>(
+    webview: Webview<R>,
+    src_path: PathBuf,
+    dst_path: PathBuf,
+    options: Option<DecompressOptions>,
+    on_progress: Option<JavaScriptChannelId>,
+) -> Result<DecompressReport> {
+    let on_progress: Option<Channel<DecompressProgress>> =
+        on_progress.map(|id| id.channel_on(webview.clone()));
+    let buffer_limit = webview.fs_pro().settings.buffer_limit;
+    let (entries, interval, operation_id, unpack_options) = match options {
+        Some(opt) => (
+            opt.entries,
+            opt.on_progress_interval_ms,
+            opt.operation_id,
+            UnpackOptions {
+                backup_to_trash: opt.backup_to_trash.unwrap_or(false),
+                preserve_permissions: opt.preserve_permissions.unwrap_or(false),
+                preserve_ownership: opt.preserve_ownership.unwrap_or(false),
+                allow_external_paths: opt.allow_external_paths.unwrap_or(false),
+                strip_components: opt.strip_components.unwrap_or(0),
+                on_conflict: opt.on_conflict.unwrap_or_default(),
+                resume: opt.resume.unwrap_or(false),
+                name_encoding: opt.encoding.unwrap_or_default(),
+                buffer_limit,
+            },
+        ),
+        None => (
+            None,
+            None,
+            None,
+            UnpackOptions {
+                buffer_limit,
+                ..UnpackOptions::default()
+            },
+        ),
+    };
+    let interval = events::progress_interval(&webview.fs_pro().settings, interval);
+    let selection = entries
+        .map(|patterns| {
+            let mut globs = GlobSetBuilder::new();
+
+            for pattern in patterns {
+                globs.add(Glob::new(&pattern)?);
+            }
+
+            globs.build()
+        })
+        .transpose()?;
+    let mut trace = trace::command(
+        &webview.fs_pro().settings,
+        "decompress",
+        &src_path,
+        operation_id.as_deref(),
+    )
+    .dst_path(&dst_path);
+    let operation = webview.fs_pro().operations.start(operation_id);
+
+    ensure_writable(&dst_path)?;
+    create_dir_all(dst_path.clone())?;
+
+    let (total_entries, total_bytes) = match on_progress {
+        Some(_) => archive::totals(
+            &src_path,
+            selection.as_ref(),
+            unpack_options.strip_components,
+            unpack_options.name_encoding,
+        )?,
+        None => (0, 0),
+    };
+    let mut last_progress: Option<Instant> = None;
+    let mut rate = RateEstimator::new();
+
+    let mut send_progress =
+        |extracted_entries: u64, extracted_bytes: u64, current_entry: &Path, force: bool| {
+            let Some(on_progress) = &on_progress else {
+                return;
+            };
+
+            if !force && last_progress.is_some_and(|last| last.elapsed() < interval) {
+                return;
+            }
+
+            last_progress = Some(Instant::now());
+
+            let (bytes_per_second, remaining_ms) = rate.update(extracted_bytes, total_bytes);
+
+            let _ = on_progress.send(DecompressProgress {
+                extracted_entries,
+                total_entries,
+                extracted_bytes,
+                total_bytes,
+                current_entry: current_entry.to_path_buf(),
+                bytes_per_second,
+                remaining_ms,
+            });
+        };
+
+    let mut extracted_entries = 0;
+    let mut extracted_bytes = 0;
+    let mut current_entry = PathBuf::new();
+
+    let report = archive::unpack(
+        &src_path,
+        &dst_path,
+        selection.as_ref(),
+        unpack_options,
+        operation.token(),
+        |name, size| {
+            // Entries the count leaves out, such as tar metadata, don't take the progress past the totals.
+            send_progress(
+                extracted_entries.min(total_entries),
+                extracted_bytes.min(total_bytes),
+                name,
+                false,
+            );
+
+            extracted_entries += 1;
+            extracted_bytes += size;
+            current_entry = name.to_path_buf();
+        },
+    )?;
+
+    send_progress(total_entries, total_bytes, &current_entry, true);
+
+    trace.bytes(report.bytes_written);
+    trace.succeeded();
+
+    Ok(report)
+}
+
+/// List the entries of an archive without extracting it, nothing is written to disk.
+///
+/// The format is detected from the contents like [`decompress`] does. The entries of a compressed tar file are read through once,
+/// the entries of a zip file are read from its central directory.
+///
+/// # Arguments
+/// - `path`: Specify the archive path.
+/// - `options.encoding`: How the names of zip entries not flagged as UTF-8 are decoded, defaults to `NameEncoding::Auto`, as [`decompress`] does.
+///
+/// # Returns
+/// - `Ok(Vec<ArchiveEntry>)`: The entries in the order they are stored, with their path inside the archive, uncompressed size, type and modified time.
+/// - `Err(Error)`: The error on failure, of kind `unsupportedArchive` when the format is not recognized.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::archive_entries;
+///
+/// let path = PathBuf::from("/path/to/archive.zip");
+/// let entries = archive_entries(app.handle().clone(), path.into(), None).await?;
+/// println!("{:?}", entries);
+/// ```
+#[command]
+pub async fn archive_entries<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    options: Option<ArchiveEntriesOptions>,
+) -> Result<Vec<ArchiveEntry>> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    let name_encoding = options.and_then(|opt| opt.encoding).unwrap_or_default();
+    let mut trace = trace::command(
+        &app_handle.fs_pro().settings,
+        "archive_entries",
+        &path,
+        None,
+    );
+    let entries = archive::entries(&path, name_encoding)?;
+
+    trace.bytes(entries.iter().map(|entry| entry.size).sum());
+    trace.succeeded();
+
+    Ok(entries)
+}
+
+/// Convert an archive to another format, e.g. a tar.gz file to a zip file, without extracting it to disk.
+///
+/// The source format is detected from the contents like [`decompress`] does. The entries are streamed from one archive into the
+/// other with their permissions and modified times, hard links, devices and named pipes of tar files are left out. The new archive
+/// is written next to the destination and renamed once complete, so the destination may be the source itself.
+///
+/// # Arguments
+/// - `src_path`: Specify the source archive path.
+/// - `dst_path`: Specify the destination path.
+/// - `options.format`: The format of the new archive, see [`compress`], defaults to `ArchiveFormat::TarGz`.
+/// - `options.level`: The compression level, within the levels of the format, see [`compress`].
+/// - `options.overwrite`: Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`], the partial archive is removed.
+///
+/// # Returns
+/// - `Ok(())`: The archive was converted.
+/// - `Err(Error)`: The error on failure, of kind `unsupportedArchive` when the source format is not recognized, or `invalidCompressionLevel` when the level is out of range for the format.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::{convert_archive, ArchiveFormat, ConvertArchiveOptions};
+///
+/// let src_path = PathBuf::from("/path/to/archive.tar.gz");
+/// let dst_path = PathBuf::from("/path/to/archive.zip");
+/// let options = ConvertArchiveOptions {
+///     format: Some(ArchiveFormat::Zip),
+///     ..Default::default()
+/// };
+/// convert_archive(app.handle().clone(), src_path.into(), dst_path.into(), Some(options)).await?;
+/// ```
+#[command]
+pub async fn convert_archive<R: Runtime>(
+    app_handle: AppHandle<R>,
+    src_path: PathInput,
+    dst_path: PathInput,
+    options: Option<ConvertArchiveOptions>,
+) -> Result<()> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let src_path = PathBuf::from(src_path);
+    let dst_path = PathBuf::from(dst_path);
+
+    let options = options.unwrap_or_default();
+    let mut trace = trace::command(
+        &app_handle.fs_pro().settings,
+        "convert_archive",
+        &src_path,
+        options.operation_id.as_deref(),
+    )
+    .dst_path(&dst_path);
+    let operation = app_handle.fs_pro().operations.start(options.operation_id);
+    let format = options.format.unwrap_or_default();
+    let level = format.level(options.level)?;
+
+    if !src_path.is_file() {
+        return Err(Error::NotFound(src_path));
+    }
+
+    let dst_path = archive_path(dst_path, options.overwrite.unwrap_or(true), false)?;
+    let name = full_name(dst_path.clone().into()).await;
+    let written_path = dst_path.with_file_name(format!(".{}.fs-pro-convert", name));
+
+    let written: Result<()> = (|| {
+        let mut archive = ArchiveWriter::new(File::create(&written_path)?.into(), format, level)?;
+
+        archive::convert(&src_path, &mut archive, operation.token())?;
+        archive.finish()?;
+
+        Ok(fs::rename(&written_path, &dst_path)?)
+    })();
+
+    if written.is_err() {
+        let _ = fs::remove_file(&written_path);
+    }
+
+    written?;
+
+    if let Ok(metadata) = fs::metadata(&dst_path) {
+        trace.bytes(metadata.len());
+    }
+    trace.succeeded();
+
+    Ok(())
+}
+
+// Queue a directory to be moved into `dst_dir` as a whole, or, when the filter leaves out entries in it, with `split_existing` when
+// it exists there already or with `split_links` when there are links in it, recreate it there and queue the entries that are kept
+// instead, so the others stay behind. Such links are queued on their own rather than followed.
+fn split_filtered(
+    filter: &EntryFilter,
+    dir: &Path,
+    dst_dir: &Path,
+    split_existing: bool,
+    split_links: bool,
+    items: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<()> {
+    let is_split = |name: &OsStr| {
+        !filter.keeps_all(dir)
+            || split_existing && dst_dir.join(name).is_dir()
+            || split_links && has_link(dir)
+    };
+    let Some(name) = dir.file_name().filter(|name| is_split(name)) else {
+        items.push((dir.to_path_buf(), dst_dir.to_path_buf()));
+        return Ok(());
+    };
+
+    // Only recreated when something is kept in it, once its items are moved.
+    let target = dst_dir.join(name);
+
+    for entry in read_dir(dir)? {
+        let path = entry?.path();
+        let is_dir = !(split_links && path.is_symlink()) && path.is_dir();
+
+        if filter.is_skipped(&path, is_dir) {
+            continue;
+        }
+
+        if is_dir {
+            split_filtered(filter, &path, &target, split_existing, split_links, items)?;
+        } else if filter.keeps(&path, false) {
+            items.push((path, target.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+// Recreate a link at `target`, replacing an existing file or link there like moving a file over it would.
+fn copy_link(src: &Path, target: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(target) {
+        Ok(metadata) if metadata.is_dir() => return Err(io::ErrorKind::AlreadyExists.into()),
+        Ok(_) => link::remove(target)?,
+        Err(_) => {}
+    }
+
+    link::copy(src, target)
+}
+
+// Whether there is a symbolic link anywhere below a directory.
+fn has_link(dir: &Path) -> bool {
+    WalkDir::new(dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .any(|entry| entry.path_is_symlink())
+}
+
+// The times and permissions of an item and of everything in it by their path relative to it, taken before the item is copied,
+// which reads it and may update its access times. Links are left out, they don't have their own on every platform.
+fn metadata_snapshot(item: &Path) -> Vec<(PathBuf, fs::Metadata)> {
+    WalkDir::new(item)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.path_is_symlink())
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(item).ok()?.to_path_buf();
+
+            Some((relative, entry.metadata().ok()?))
+        })
+        .collect()
+}
+
+// Restore a snapshot on the copy of the item at `target`, the deepest entries first so a read-only directory doesn't keep the
+// entries in it from being changed. File systems without them, such as FAT, keep their own.
+fn restore_metadata(
+    snapshot: &[(PathBuf, fs::Metadata)],
+    target: &Path,
+    timestamps: bool,
+    permissions: bool,
+) {
+    for (relative, metadata) in snapshot.iter().rev() {
+        let path = match relative.as_os_str().is_empty() {
+            true => target.to_path_buf(),
+            false => target.join(relative),
+        };
+
+        if timestamps {
+            let _ = filetime::set_file_times(
+                &path,
+                filetime::FileTime::from_last_access_time(metadata),
+                filetime::FileTime::from_last_modification_time(metadata),
+            );
+        }
+
+        if permissions {
+            let _ = fs::set_permissions(&path, metadata.permissions());
+        }
+    }
+}
+
+// Move or copy an item into `dst_dir`, or to `renamed_to` when it's kept next to an existing one of the same name, which the
+// `fs_extra` items functions can't do.
+fn transfer_item<F>(
+    item: &Path,
+    dst_dir: &Path,
+    renamed_to: Option<&Path>,
+    options: &CopyOptions,
+    keep_source: bool,
+    mut progress: F,
+) -> fs_extra::error::Result<()>
+where
+    F: FnMut(TransitProcess) -> TransitProcessResult,
+{
+    let Some(renamed_to) = renamed_to else {
+        return match keep_source {
+            true => copy_items_with_progress(&[item], dst_dir, options, progress),
+            false => move_items_with_progress(&[item], dst_dir, options, progress),
+        }
+        .map(|_| ());
+    };
+
+    if item.is_dir() {
+        // `renamed_to` doesn't exist yet, so the contents are moved into it rather than into a directory inside it.
+        let options = CopyOptions {
+            copy_inside: true,
+            ..options.clone()
+        };
+        let progress = |process: fs_extra_dir::TransitProcess| {
+            progress(TransitProcess {
+                copied_bytes: process.copied_bytes,
+                total_bytes: process.total_bytes,
+                file_bytes_copied: process.file_bytes_copied,
+                file_total_bytes: process.file_total_bytes,
+                file_name: process.file_name,
+                dir_name: String::new(),
+                state: process.state,
+            })
+        };
+
+        return match keep_source {
+            true => fs_extra_dir::copy_with_progress(item, renamed_to, &options, progress),
+            false => fs_extra_dir::move_dir_with_progress(item, renamed_to, &options, progress),
+        }
+        .map(|_| ());
+    }
+
+    let file_options = fs_extra::file::CopyOptions {
+        overwrite: false,
+        skip_exist: false,
+        buffer_size: options.buffer_size,
+    };
+    let file_name = item
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let progress = |process: fs_extra::file::TransitProcess| {
+        progress(TransitProcess {
+            copied_bytes: process.copied_bytes,
+            total_bytes: process.total_bytes,
+            file_bytes_copied: process.copied_bytes,
+            file_total_bytes: process.total_bytes,
+            file_name: file_name.clone(),
+            dir_name: String::new(),
+            state: fs_extra_dir::TransitState::Normal,
+        });
+    };
+
+    match keep_source {
+        true => fs_extra::file::copy_with_progress(item, renamed_to, &file_options, progress),
+        false => fs_extra::file::move_file_with_progress(item, renamed_to, &file_options, progress),
+    }
+    .map(|_| ())
+}
+
+/// Move the source path to the destination path.
+///
+/// The contents of a source directory are moved into the destination directory, while a source file is moved into it on its own,
+/// e.g. a download into a folder of the user. The filter options only apply inside a source directory.
+///
+/// Moving to another volume, such as another drive on Windows, copies each item with everything in it before removing it from the
+/// source, so an item that fails part way is left whole in the source.
+///
+/// # Arguments
+/// - `src_path`: Specify the source path, a directory or a file.
+/// - `dst_path`: Specify the destination directory path.
+/// - `options.includes`: The glob patterns of the paths to be moved, matched against the path relative to the source path, e.g. `src`, `src/config/settings.json` or `**/*.rs`, with or without a leading `./`. Directories are searched for matching entries.
+/// - `options.excludes`: The glob patterns of the paths not to be moved, matched against the path relative to the source path, e.g. `*.log` or `target`.
+/// - `options.ignore`: Gitignore-style rules of the paths not to be moved, matched relative to the source path, either `IgnoreRules::Path` of a file such as `.exportignore` or `IgnoreRules::Contents`. Ignored entries stay in the source.
+/// - `options.minSize`: Only move files of at least this many bytes.
+/// - `options.maxSize`: Only move files of at most this many bytes.
+/// - `options.modifiedAfter`: Only move files modified after this time, in milliseconds since the Unix epoch.
+/// - `options.modifiedBefore`: Only move files modified before this time, in milliseconds since the Unix epoch. The files outside the size and time limits stay in the source.
+/// - `options.backupToTrash`: Move existing destination files to the system trash before overwriting them, defaults to `false`.
+/// - `options.backup`: Snapshot existing destination files into numbered backups before overwriting them, see [`backup`].
+/// - `options.onConflict`: What happens to an existing file where an item goes, defaults to `OverwritePolicy::Overwrite`. `OverwritePolicy::Skip` keeps the file and leaves the item in the source, listed in `skipped` of the report, `OverwritePolicy::Error` returns an `alreadyExists` error before anything is moved, and `OverwritePolicy::Rename` keeps the file and moves the item next to it as `name (1).ext`, listed in `renamed`. Existing directories are merged into either way, with the conflicts resolved file by file inside them. Backups are only made of overwritten files.
+/// - `options.dryRun`: Only plan the move without touching the source or the destination, defaults to `false`. The includes, excludes and other filters and `options.onConflict` are evaluated as for the move, `skipped` and `renamed` of the report are filled in and `plan` holds the `items` that would be moved, each with its `srcPath`, its `dstPath` and its `size`, their `totalBytes` and the `collisions`, the existing paths where items go. An `OverwritePolicy::Error` conflict is listed in the collisions rather than returned.
+/// - `options.rollback`: Put everything back when the move fails or is cancelled part way, so the source and the destination are left as they were, defaults to `false`. The items moved so far are moved back, including what reached the destination of the item that failed, the directories created for them are removed and overwritten files are moved aside until every item is moved, then put back. The part of an item listed in `inaccessible` that was moved is put back as well. The error of the move is returned, or an error of kind `notRolledBack` with the number of paths that could not be restored. Backups made with `options.backup` are kept, while the files moved to the trash with `options.backupToTrash` are put back.
+/// - `options.preserveTimestamps`: Give the items that are copied rather than renamed, when moving to another volume or a device, the access and modification times of the originals, down to every entry inside them, defaults to `false`. The creation time is left untouched like with [`clone_metadata`].
+/// - `options.preservePermissions`: Give them the permissions of the originals as well, the modes on Unix and the read-only attribute on Windows, defaults to `false`. File systems without them, such as FAT, keep their own.
+/// - `options.symlinks`: How the symbolic links inside the source directory, or a source file that is a link, are handled. `SymlinkPolicy::Follow` moves a copy of what a link points to in its place and removes the link, a link that is broken or points to a directory containing it is listed in `inaccessible` instead. `SymlinkPolicy::Preserve` recreates a link with the same target, so a relative link still points to the moved entry next to it. `SymlinkPolicy::Skip` leaves links in the source like excluded entries. A directory with links in it is recreated in the destination and its entries moved one by one. When unset, a link is moved as it is when the item holding it is renamed and replaced by what it points to when it's copied, such as to another volume. The source path itself and bundles moved as a whole are always taken as they are.
+/// - `options.treatBundlesAsFiles`: Treat macOS bundles such as `.app` as single files, a bundle is never filtered inside or left half moved and a bundle source is moved into the destination as a whole, defaults to `false`.
+/// - `options.retry.attempts`: The number of retries of an item locked by another process after the first attempt, only on Windows, defaults to `5`.
+/// - `options.retry.delay`: The delay before the first retry in milliseconds, doubled for every following retry, defaults to `50`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`], the item in progress is still moved completely.
+/// - `options.onProgressIntervalMs`: The minimum time between two progress updates in milliseconds, defaults to `100` or the interval set with [`crate::Builder::min_event_interval`]. The last update is always sent.
+/// - `options.notify`: Show a system notification once finished, see [`compress`].
+/// - `on_progress`: Specify the channel that receives `TransferProgress` updates, at most every `options.onProgressIntervalMs`.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::transfer;
+///
+/// let src_path = PathBuf::from("/path/to/source");
+/// let dst_path = PathBuf::from("/path/to/destination");
+/// let report = transfer(webview, src_path.into(), dst_path.into(), None, None).await?;
+/// println!("{:?}", report.inaccessible);
+///
+/// let src_path = PathBuf::from("/Users/xxx/Downloads/report.pdf");
+/// let dst_path = PathBuf::from("/Users/xxx/Documents");
+/// transfer(webview, src_path.into(), dst_path.into(), None, None).await?;
+///
+/// let src_path = PathBuf::from("/path/to/import");
+/// let dst_path = PathBuf::from("/path/to/library");
+/// let options = TransferOptions {
+///     on_conflict: Some(OverwritePolicy::Rename),
+///     ..Default::default()
+/// };
+/// let report = transfer(webview, src_path.into(), dst_path.into(), Some(options), None).await?;
+/// println!("{:?}", report.renamed);
+///
+/// let src_path = PathBuf::from("/path/to/project");
+/// let dst_path = PathBuf::from("/Volumes/Backup/project");
+/// let options = TransferOptions {
+///     rollback: Some(true),
+///     ..Default::default()
+/// };
+/// transfer(webview, src_path.into(), dst_path.into(), Some(options), None).await?;
+///
+/// let src_path = PathBuf::from("/path/to/node-project");
+/// let dst_path = PathBuf::from("/Volumes/External/projects");
+/// let options = TransferOptions {
+///     symlinks: Some(SymlinkPolicy::Preserve),
+///     ..Default::default()
+/// };
+/// transfer(webview, src_path.into(), dst_path.into(), Some(options), None).await?;
+/// ```
+#[command]
+pub async fn transfer<R: Runtime>(
+    webview: Webview<R>,
+    src_path: PathInput,
+    dst_path: PathInput,
+    mut options: Option<TransferOptions>,
+    on_progress: Option<JavaScriptChannelId>,
+) -> Result<TransferReport> {
+    let _redacting = webview.fs_pro().redacting();
+
+    let src_path = PathBuf::from(src_path);
+    let dst_path = PathBuf::from(dst_path);
+
+    let notify = options.as_mut().and_then(|opt| opt.notify.take());
+    let app_handle = webview.app_handle().clone();
+    let result = transfer_items(
+        webview,
+        src_path.clone(),
+        dst_path.clone(),
+        options,
+        on_progress,
+        false,
+    )
+    .await;
+
+    notification::finished(&app_handle, notify.as_ref(), &src_path, &dst_path, &result);
+
+    result
+}
+
+/// Copy the source path to the destination path, leaving the source untouched.
+///
+/// Takes the same options as [`transfer`].
+///
+/// # Arguments
+/// - `src_path`: Specify the source path.
+/// - `dst_path`: Specify the destination path.
+/// - `options.includes`: The glob patterns of the paths to be copied, matched against the path relative to the source path, e.g. `src`, `src/config/settings.json` or `**/*.rs`, with or without a leading `./`. Directories are searched for matching entries.
+/// - `options.excludes`: The glob patterns of the paths not to be copied, matched against the path relative to the source path, e.g. `*.log` or `target`.
+/// - `options.ignore`: Gitignore-style rules of the paths not to be copied, matched relative to the source path, either `IgnoreRules::Path` of a file such as `.exportignore` or `IgnoreRules::Contents`.
+/// - `options.minSize`: Only copy files of at least this many bytes.
+/// - `options.maxSize`: Only copy files of at most this many bytes.
+/// - `options.modifiedAfter`: Only copy files modified after this time, in milliseconds since the Unix epoch.
+/// - `options.modifiedBefore`: Only copy files modified before this time, in milliseconds since the Unix epoch. The size and time limits only apply to files.
+/// - `options.backupToTrash`: Move existing destination files to the system trash before overwriting them, defaults to `false`.
+/// - `options.backup`: Snapshot existing destination files into numbered backups before overwriting them, see [`backup`].
+/// - `options.onConflict`: What happens to an existing file where an item goes, like [`transfer`] does, defaults to `OverwritePolicy::Overwrite`.
+/// - `options.dryRun`: Only return the planned items with their sizes and the collisions without copying anything, like [`transfer`] does.
+/// - `options.rollback`: Remove the copies made so far and put back the overwritten files when the copy fails or is cancelled part way, like [`transfer`] does, defaults to `false`.
+/// - `options.preserveTimestamps`: Give the copies the access and modification times of the originals, see [`transfer`].
+/// - `options.preservePermissions`: Give the copies the permissions of the originals, see [`transfer`].
+/// - `options.symlinks`: How the symbolic links inside the source are handled, `SymlinkPolicy::Follow` copies what they point to, `SymlinkPolicy::Preserve` recreates them and `SymlinkPolicy::Skip` leaves them out, like [`transfer`] does. Links are copied as what they point to when unset.
+/// - `options.treatBundlesAsFiles`: Treat macOS bundles such as `.app` as single files, a bundle is never filtered inside or left half copied and a bundle source is copied into the destination as a whole, defaults to `false`.
+/// - `options.retry.attempts`: The number of retries of an item locked by another process after the first attempt, only on Windows, defaults to `5`.
+/// - `options.retry.delay`: The delay before the first retry in milliseconds, doubled for every following retry, defaults to `50`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`], the item in progress is still copied completely.
+/// - `options.onProgressIntervalMs`: The minimum time between two progress updates in milliseconds, see [`transfer`].
+/// - `options.notify`: Show a system notification once finished, see [`compress`].
+/// - `on_progress`: Specify the channel that receives `TransferProgress` updates, at most every `options.onProgressIntervalMs`.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::copy;
+///
+/// let src_path = PathBuf::from("/path/to/source");
+/// let dst_path = PathBuf::from("/path/to/backup");
+/// let report = copy(webview, src_path.into(), dst_path.into(), None, None).await?;
+/// println!("{:?}", report.inaccessible);
+/// ```
+#[command]
+pub async fn copy<R: Runtime>(
+    webview: Webview<R>,
+    src_path: PathInput,
+    dst_path: PathInput,
+    mut options: Option<TransferOptions>,
+    on_progress: Option<JavaScriptChannelId>,
+) -> Result<TransferReport> {
+    let _redacting = webview.fs_pro().redacting();
+
+    let src_path = PathBuf::from(src_path);
+    let dst_path = PathBuf::from(dst_path);
+
+    let notify = options.as_mut().and_then(|opt| opt.notify.take());
+    let app_handle = webview.app_handle().clone();
+    let result = transfer_items(
+        webview,
+        src_path.clone(),
+        dst_path.clone(),
+        options,
+        on_progress,
+        true,
+    )
+    .await;
+
+    notification::finished(&app_handle, notify.as_ref(), &src_path, &dst_path, &result);
+
+    result
+}
+
+/// Copy a single file to the destination file path, optimized for large files.
+///
+/// Unlike [`copy`], which copies into a destination directory, the file is copied in large chunks, using the fast copy of the
+/// system where available, and can be cancelled in the middle. It's written next to the destination first and renamed once complete,
+/// so a failed or cancelled copy leaves no partial file behind and an existing destination untouched.
+///
+/// # Arguments
+/// - `src_path`: Specify the source file path.
+/// - `dst_path`: Specify the destination file path.
+/// - `options.overwrite`: Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+/// - `options.onProgressIntervalMs`: The minimum time between two progress updates in milliseconds, defaults to `100` or the interval set with [`crate::Builder::min_event_interval`].
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`].
+/// - `options.notify`: Show a system notification once finished, see [`compress`].
+/// - `on_progress`: Specify the channel that receives `TransferProgress` updates.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::copy_file;
+///
+/// let src_path = PathBuf::from("/path/to/video.mkv");
+/// let dst_path = PathBuf::from("/Volumes/External/video.mkv");
+/// copy_file(webview, src_path.into(), dst_path.into(), None, None).await?;
+/// ```
+#[command]
+pub async fn copy_file<R: Runtime>(
+    webview: Webview<R>,
+    src_path: PathInput,
+    dst_path: PathInput,
+    mut options: Option<CopyFileOptions>,
+    on_progress: Option<JavaScriptChannelId>,
+) -> Result<()> {
+    let _redacting = webview.fs_pro().redacting();
+
+    let src_path = PathBuf::from(src_path);
+    let dst_path = PathBuf::from(dst_path);
+
+    let notify = options.as_mut().and_then(|opt| opt.notify.take());
+    let app_handle = webview.app_handle().clone();
+    let result = copy_single_file(
+        webview,
+        src_path.clone(),
+        dst_path.clone(),
+        options,
+        on_progress,
+    )
+    .await;
+
+    notification::finished(&app_handle, notify.as_ref(), &src_path, &dst_path, &result);
+
+    result
+}
+
+// Copy the file in chunks, reporting the progress over the channel.
+async fn copy_single_file<R: Runtime>(
+    webview: Webview<R>,
+    src_path: PathBuf,
+    dst_path: PathBuf,
+    options: Option<CopyFileOptions>,
+    on_progress: Option<JavaScriptChannelId>,
+) -> Result<()> {
+    let on_progress: Option<Channel<TransferProgress>> =
+        on_progress.map(|id| id.channel_on(webview.clone()));
+    let (overwrite, interval, operation_id) = match options {
+        Some(opt) => (opt.overwrite, opt.on_progress_interval_ms, opt.operation_id),
+        None => (None, None, None),
+    };
+    let interval = events::progress_interval(&webview.fs_pro().settings, interval);
+    let mut trace = trace::command(
+        &webview.fs_pro().settings,
+        "copy_file",
+        &src_path,
+        operation_id.as_deref(),
+    )
+    .dst_path(&dst_path);
+    let operation = webview.fs_pro().operations.start(operation_id);
+
+    if !overwrite.unwrap_or(true) && dst_path.symlink_metadata().is_ok() {
+        return Err(Error::AlreadyExists(dst_path));
+    }
+
+    let total_bytes = fs::metadata(&src_path)?.len();
+    let current_file = full_name(src_path.clone().into()).await;
+    let mut last_progress: Option<Instant> = None;
+    let mut rate = RateEstimator::new();
+
+    let mut send_progress = |copied_bytes: u64, force: bool| {
+        let Some(on_progress) = &on_progress else {
+            return;
+        };
+
+        if !force && last_progress.is_some_and(|last| last.elapsed() < interval) {
+            return;
+        }
+
+        last_progress = Some(Instant::now());
+
+        let (bytes_per_second, remaining_ms) = rate.update(copied_bytes, total_bytes);
+
+        let _ = on_progress.send(TransferProgress {
+            copied_bytes,
+            total_bytes,
+            current_file: current_file.clone(),
+            bytes_per_second,
+            remaining_ms,
+        });
+    };
+
+    file_copy::copy(
+        &src_path,
+        &dst_path,
+        webview.fs_pro().settings.buffer_limit,
+        operation.token(),
+        |copied_bytes| send_progress(copied_bytes, false),
+    )?;
+
+    send_progress(total_bytes, true);
+
+    trace.bytes(total_bytes);
+    trace.succeeded();
+
+    Ok(())
+}
+
+/// Cancel a running operation that was started with an `operationId`, the operation then fails with a `cancelled` error.
+///
+/// # Arguments
+/// - `id`: Specify the id passed as `operationId`.
+///
+/// # Returns
+/// - `bool`: Whether an operation with the id was running.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::cancel_operation;
+///
+/// let cancelled = cancel_operation(app.handle().clone(), "export".to_string()).await;
+/// println!("{}", cancelled); // true
+/// ```
+#[command]
+pub async fn cancel_operation<R: Runtime>(app_handle: AppHandle<R>, id: String) -> bool {
+    app_handle.fs_pro().operations.cancel(&id)
+}
+
+// Move, or copy when `keep_source` is set, the contents of the source path into the destination path.
+async fn transfer_items<R: Runtime>(
+    webview: Webview<R>,
+    src_path: PathBuf,
+    dst_path: PathBuf,
+    options: Option<TransferOptions>,
+    on_progress: Option<JavaScriptChannelId>,
+    keep_source: bool,
+) -> Result<TransferReport> {
+    let fs_pro = webview.fs_pro();
+    let on_progress: Option<Channel<TransferProgress>> =
+        on_progress.map(|id| id.channel_on(webview.clone()));
+    let options = options.unwrap_or(TransferOptions {
+        includes: Some(vec![]),
+        excludes: Some(vec![]),
+        ignore: None,
+        min_size: None,
+        max_size: None,
+        modified_after: None,
+        modified_before: None,
+        backup_to_trash: Some(false),
+        backup: None,
+        treat_bundles_as_files: Some(false),
+        retry: None,
+        operation_id: None,
+        on_conflict: None,
+        dry_run: None,
+        rollback: None,
+        preserve_timestamps: None,
+        preserve_permissions: None,
+        symlinks: None,
+        on_progress_interval_ms: None,
+        notify: None,
+    });
+    let filter = EntryFilter::new(
+        &src_path,
+        &options.includes.unwrap_or(vec![]),
+        &options.excludes.unwrap_or(vec![]),
+        options.ignore.as_ref(),
+        FileLimits {
+            min_size: options.min_size,
+            max_size: options.max_size,
+            modified_after: options.modified_after,
+            modified_before: options.modified_before,
+        },
+    )?;
+    let backup_to_trash = options.backup_to_trash.unwrap_or(false);
+    let backup_options = options.backup;
+    let treat_bundles_as_files = options.treat_bundles_as_files.unwrap_or(false);
+    let retry_policy = RetryOptions::policy(options.retry);
+    let on_conflict = options.on_conflict.unwrap_or_default();
+    let dry_run = options.dry_run.unwrap_or(false);
+    let rollback = options.rollback.unwrap_or(false);
+    let preserve_timestamps = options.preserve_timestamps.unwrap_or(false);
+    let preserve_permissions = options.preserve_permissions.unwrap_or(false);
+    let symlinks = options.symlinks;
+    let progress_interval =
+        events::progress_interval(&fs_pro.settings, options.on_progress_interval_ms);
+    // Existing directories are merged into, so the conflicts are found file by file unless files are overwritten, and always to
+    // list the collisions of a dry run, to back up, trash or roll back the overwritten files one by one.
+    let split_existing = on_conflict != OverwritePolicy::Overwrite
+        || dry_run
+        || rollback
+        || backup_to_trash
+        || backup_options.is_some();
+    let command = if keep_source { "copy" } else { "transfer" };
+    let mut trace = trace::command(
+        &fs_pro.settings,
+        command,
+        &src_path,
+        options.operation_id.as_deref(),
+    )
+    .dst_path(&dst_path);
+    let operation = fs_pro.operations.start(options.operation_id);
+
+    if !dry_run {
+        ensure_writable(&dst_path)?;
+    }
+
+    // A bundle source is moved into the destination as a whole instead of having its contents filtered, and so is a file source,
+    // such as a download, which the filter doesn't apply to either.
+    let is_src_bundle = treat_bundles_as_files && is_bundle(&src_path);
+    let is_src_whole = is_src_bundle || src_path.is_file();
+
+    let src_items = if is_src_whole {
+        vec![src_path.clone()]
+    } else {
+        let mut config = HashSet::new();
+        config.insert(DirEntryAttr::Path);
+
+        ls(&src_path, &config)?
+            .items
+            .into_iter()
+            .filter_map(|item| match item.get(&DirEntryAttr::Path) {
+                Some(DirEntryValue::String(path)) => Some(PathBuf::from(path)),
+                _ => None,
+            })
+            .collect()
+    };
+
+    let mut from_items = Vec::new();
+
+    for path in src_items {
+        // With a policy for them, links are items of their own instead of being moved as what they point to.
+        let is_dir = !(symlinks.is_some() && path.is_symlink()) && path.is_dir();
+
+        if !is_src_whole && filter.is_skipped(&path, is_dir) {
+            continue;
+        }
+
+        if !is_src_whole && !is_dir && !filter.keeps(&path, false) {
+            continue;
+        }
+
+        let is_whole_bundle = is_src_bundle || (treat_bundles_as_files && is_bundle(&path));
+
+        if !is_whole_bundle && is_dir {
+            split_filtered(
+                &filter,
+                &path,
+                &dst_path,
+                split_existing,
+                symlinks.is_some(),
+                &mut from_items,
+            )?;
+        } else {
+            from_items.push((path, dst_path.clone()));
+        }
+    }
+
+    let mut skipped = Vec::new();
+    let mut renamed = Vec::new();
+    let mut collisions = Vec::new();
+    let mut resolved_items = Vec::with_capacity(from_items.len());
+
+    // The conflicts are resolved before anything is moved, so an `OverwritePolicy::Error` conflict leaves everything in place.
+    for (item, item_dst) in from_items {
+        if symlinks == Some(SymlinkPolicy::Skip) && item.is_symlink() {
+            continue;
+        }
+
+        let target = item.file_name().map(|name| item_dst.join(name));
+        let existing = target.filter(|target| fs::symlink_metadata(target).is_ok());
+
+        if let Some(existing) = &existing {
+            collisions.push(existing.clone());
+        }
+
+        let renamed_to = match (existing, on_conflict) {
+            (None, _) | (Some(_), OverwritePolicy::Overwrite) => None,
+            (Some(target), OverwritePolicy::Skip) => {
+                skipped.push(target);
+                continue;
+            }
+            (Some(target), OverwritePolicy::Error) if !dry_run => {
+                return Err(Error::AlreadyExists(target))
+            }
+            // A dry run lists the item with its collision instead.
+            (Some(_), OverwritePolicy::Error) => None,
+            (Some(target), OverwritePolicy::Rename) => {
+                let renamed_to = available_path(&item_dst, item.file_name().unwrap_or_default());
+
+                renamed.push(RenamedEntry {
+                    path: target,
+                    renamed_to: renamed_to.clone(),
+                });
+
+                Some(renamed_to)
+            }
+        };
+
+        resolved_items.push((item, item_dst, renamed_to));
+    }
+
+    if dry_run {
+        let items = resolved_items
+            .into_iter()
+            .map(|(item, item_dst, renamed_to)| PlannedItem {
+                dst_path: renamed_to
+                    .unwrap_or_else(|| item_dst.join(item.file_name().unwrap_or_default())),
+                size: walk_size(&item).0,
+                src_path: item,
+            })
+            .collect();
+
+        trace.succeeded();
+
+        return Ok(TransferReport {
+            inaccessible: Vec::new(),
+            retried: Vec::new(),
+            skipped,
+            renamed,
+            plan: Some(DryRunPlan::new(items, collisions)),
+        });
+    }
+
+    let mut journal = Journal::default();
+    journal.create_dir_all(&dst_path)?;
+
+    let options = CopyOptions {
+        overwrite: on_conflict == OverwritePolicy::Overwrite,
+        skip_exist: false,
+        buffer_size: 64000,
+        copy_inside: false,
+        content_only: false,
+        depth: 0,
+    };
+
+    // gvfs and MTP mounts handle renames poorly and choke on large writes, so always copy in small chunks and delete afterwards.
+    let device = [&src_path, &dst_path]
+        .into_iter()
+        .filter_map(|path| mount::mount_point_of(path))
+        .find(|mount| mount.is_device());
+
+    let device_options = CopyOptions {
+        buffer_size: 16 * 1024,
+        ..options.clone()
+    };
+
+    let mut inaccessible = Vec::new();
+    let mut retried = Vec::new();
+
+    // Sizes are only needed to report progress.
+    let item_bytes: Vec<u64> = resolved_items
+        .iter()
+        .map(|(item, _, _)| match on_progress {
+            Some(_) => walk_size(item).0,
+            None => 0,
+        })
+        .collect();
+    let total_bytes = item_bytes.iter().sum();
+    let mut done_bytes = 0;
+    let mut last_progress: Option<Instant> = None;
+    let mut rate = RateEstimator::new();
+
+    let mut send_progress = |copied_bytes: u64, current_file: &str, force: bool| {
+        let Some(on_progress) = &on_progress else {
+            return;
+        };
+
+        if !force && last_progress.is_some_and(|last| last.elapsed() < progress_interval) {
+            return;
+        }
+
+        last_progress = Some(Instant::now());
+
+        let (bytes_per_second, remaining_ms) = rate.update(copied_bytes, total_bytes);
+
+        let _ = on_progress.send(TransferProgress {
+            copied_bytes,
+            total_bytes,
+            current_file: current_file.to_string(),
+            bytes_per_second,
+            remaining_ms,
+        });
+    };
+
+    // Move the items one by one so a single unreadable item doesn't abort the others.
+    let result = (|| -> Result<()> {
+        for ((item, item_dst, renamed_to), item_bytes) in resolved_items.into_iter().zip(item_bytes)
+        {
+            // An item is always moved as a whole, so cancelling takes effect before the next one.
+            operation.token().check()?;
+
+            // Links are copied, with `SymlinkPolicy::Follow` as what they point to, and removed from the source afterwards.
+            let link_policy = symlinks.filter(|_| item.is_symlink());
+
+            if link_policy == Some(SymlinkPolicy::Follow) && !link::is_followable(&item) {
+                inaccessible.push(item);
+                continue;
+            }
+
+            journal.create_dir_all(&item_dst)?;
+
+            let target = renamed_to
+                .clone()
+                .or_else(|| item.file_name().map(|name| item_dst.join(name)));
+            let mut item_journal = Journal::default();
+
+            // Only what is overwritten is backed up, then it goes to the trash or, to roll back, is only removed once every item
+            // is moved.
+            if let Some(target) = target
+                .as_ref()
+                .filter(|target| target.symlink_metadata().is_ok())
+            {
+                if let Some(backup_options) = &backup_options {
+                    backup_path(target, backup_options)?;
+                }
+
+                match (backup_to_trash, rollback) {
+                    (true, true) => item_journal.trash(target)?,
+                    (true, false) => trash::delete(target)?,
+                    (false, true) => item_journal.stage(target)?,
+                    (false, false) => {}
+                }
+            }
+
+            if let Some(target) = target.as_ref().filter(|_| rollback) {
+                match keep_source || link_policy.is_some() {
+                    true => item_journal.copying(target),
+                    false => item_journal.moving(&item, target),
+                }
+            }
+
+            let is_new_bundle = treat_bundles_as_files
+                && is_bundle(&item)
+                && target.as_ref().is_some_and(|target| !target.exists());
+
+            let copy_options = if device.is_some() {
+                &device_options
+            } else {
+                &options
+            };
+
+            // Moving between volumes copies every file anyway, fs_extra would remove each one from the source as soon as it's copied.
+            let is_cross_volume = !keep_source
+                && matches!(
+                    (mount::volume_id(&item), mount::volume_id(&item_dst)),
+                    (Some(src), Some(dst)) if src != dst
+                );
+            let is_copied = keep_source
+                || device.is_some()
+                || is_cross_volume
+                || link_policy == Some(SymlinkPolicy::Follow);
+            let snapshot = match is_copied && (preserve_timestamps || preserve_permissions) {
+                true => Some(metadata_snapshot(&item)),
+                false => None,
+            };
+
+            let (mut result, was_retried) = retry_policy.run(is_fs_extra_locked, || {
+                let progress = |process: TransitProcess| {
+                    send_progress(done_bytes + process.copied_bytes, &process.file_name, false);
+                    TransitProcessResult::ContinueOrAbort
+                };
+
+                let renamed_to = renamed_to.as_deref();
+
+                if link_policy == Some(SymlinkPolicy::Preserve) {
+                    return match &target {
+                        Some(target) => copy_link(&item, target).map_err(Into::into),
+                        None => Ok(()),
+                    };
+                }
+
+                match (
+                    device.is_some() || is_cross_volume,
+                    keep_source || link_policy.is_some(),
+                ) {
+                    (_, true) => {
+                        transfer_item(&item, &item_dst, renamed_to, copy_options, true, progress)
+                    }
+                    (true, false) => {
+                        transfer_item(&item, &item_dst, renamed_to, copy_options, true, progress)
+                            .and_then(|_| remove_items(&[&item]))
+                    }
+                    (false, false) => {
+                        transfer_item(&item, &item_dst, renamed_to, &options, false, progress)
+                    }
+                }
+            });
+
+            done_bytes += item_bytes;
+
+            if was_retried {
+                retried.push(item.clone());
+            }
+
+            // Don't leave a partial copy of a bundle behind, the source is only removed once the copy is complete.
+            if is_new_bundle && result.is_err() && item.exists() {
+                if let Some(target) = &target {
+                    if let Err(err) = remove_items(&[target]) {
+                        result = Err(err);
+                    }
+                }
+            }
+
+            // The link is only removed once what replaces it is complete, moved aside instead until every item is moved to roll back.
+            if result.is_ok() && link_policy.is_some() && !keep_source {
+                result = match rollback {
+                    true => item_journal.stage(&item),
+                    false => link::remove(&item),
+                }
+                .map_err(Into::into);
+            }
+
+            match result {
+                Ok(()) => {
+                    if let (Some(snapshot), Some(target)) = (&snapshot, &target) {
+                        restore_metadata(
+                            snapshot,
+                            target,
+                            preserve_timestamps,
+                            preserve_permissions,
+                        );
+                    }
+
+                    journal.append(item_journal)
+                }
+                Err(err) if matches!(err.kind, FsExtraErrorKind::PermissionDenied) => {
+                    // The part of the item that was moved is put back, the item is reported instead.
+                    item_journal.rollback();
+                    inaccessible.push(item)
+                }
+                Err(err) => {
+                    journal.append(item_journal);
+
+                    if let Some(device) = &device {
+                        let disconnected = matches!(&err.kind, FsExtraErrorKind::Io(err) if mount::is_disconnected(err));
+
+                        if disconnected || !mount::is_mounted(&device.path) {
+                            return Err(Error::DeviceDisconnected(device.path.clone()));
+                        }
+                    }
+
+                    return Err(err.into());
+                }
+            }
+        }
+
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        if !rollback {
+            return Err(err);
+        }
+
+        let paths = journal.rollback();
+
+        return Err(match paths.is_empty() {
+            true => err,
+            false => Error::NotRolledBack {
+                error: Box::new(err),
+                paths,
+            },
+        });
+    }
+
+    journal.finish();
+
+    send_progress(total_bytes, "", true);
+
+    // Sizes are only measured when progress is reported.
+    if on_progress.is_some() {
+        trace.bytes(total_bytes);
+    }
+    trace.succeeded();
+
+    Ok(TransferReport {
+        inaccessible,
+        retried,
+        skipped,
+        renamed,
+        plan: None,
+    })
+}
+
+/// Permanently remove a file or a directory with everything in it, without following symbolic links.
+///
+/// Deep trees such as `node_modules` are deleted in parallel. On Windows, paths beyond the 260 character limit are supported, read-only
+/// files are deleted too, and deletions briefly blocked by other processes such as antivirus scanners are retried.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+/// - `options.retry.attempts`: The number of retries of a locked file after the first attempt, only on Windows, defaults to `5`.
+/// - `options.retry.delay`: The delay before the first retry in milliseconds, doubled for every following retry, defaults to `50`.
+///
+/// # Returns
+/// - `Ok(RemoveReport)`: The paths that had to be retried.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::remove;
+///
+/// let path = PathBuf::from("/path/to/node_modules");
+/// let report = remove(app.handle().clone(), path.into(), None).await?;
+/// println!("{:?}", report.retried);
+/// ```
+#[command]
+pub async fn remove<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    options: Option<RemoveOptions>,
+) -> Result<RemoveReport> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    if fs::symlink_metadata(&path).is_err() {
+        return Err(Error::NotFound(path));
+    }
+
+    let mut trace = trace::command(&app_handle.fs_pro().settings, "remove", &path, None);
+    let retry_policy = RetryOptions::policy(options.and_then(|opt| opt.retry));
+    let retried = unlink::remove_all(&path, &retry_policy)?;
+
+    trace.succeeded();
+
+    Ok(RemoveReport { retried })
+}
+
+/// Check a list of operations against the disk and against each other without changing anything, for an "Apply changes" workflow.
+///
+/// Each operation is checked against the state the operations before it leave behind: sources must exist, destinations must not,
+/// their parents must, and the copies, or moves to another volume, must fit in the free space and, on file systems with a fixed
+/// number of inodes such as ext4, in the free inodes.
+///
+/// # Arguments
+/// - `operations`: Specify the operations, each one of `Operation::Copy`, `Operation::Move`, `Operation::Rename`, `Operation::Delete` or `Operation::Mkdir`. Paths must be absolute.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::{plan_operations, Operation};
+///
+/// let operations = vec![
+///     Operation::Mkdir { path: PathBuf::from("/path/to/archive") },
+///     Operation::Move { src: PathBuf::from("/path/to/old.txt"), dst: PathBuf::from("/path/to/archive/old.txt") },
+/// ];
+/// let plan = plan_operations(app.handle().clone(), operations).await;
+/// println!("{:?}", plan.issues);
+/// ```
+#[command]
+pub async fn plan_operations<R: Runtime>(
+    app_handle: AppHandle<R>,
+    operations: Vec<Operation>,
+) -> Plan {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    operations::plan(&operations)
+}
+
+/// Apply a list of operations in order, after checking them like [`plan_operations`].
+///
+/// When an operation fails, the ones applied before it are reverted, last first, on a best-effort basis. Deleted paths are kept
+/// next to where they were until every operation succeeded, then they are moved to the system trash. Where there is no trash,
+/// such as on some network shares, they are kept under their hidden staged names instead and listed in `not_trashed`, to remove
+/// once the operations don't need to be undone anymore.
+///
+/// Committed operations are added to the undo history, see [`undo`].
+///
+/// # Arguments
+/// - `operations`: Specify the operations, see [`plan_operations`].
+/// - `options.operationId`: An id of your choice to undo the operations with [`undo`], defaults to a generated one.
+///
+/// # Returns
+/// - `Ok(CommitReport)`: Whether every operation was applied and the id to undo them with, otherwise the failed operation, its error and the paths that could not be restored.
+/// - `Err(Error)`: The error when the operations have issues.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::{commit_operations, Operation};
+///
+/// let operations = vec![Operation::Delete { path: PathBuf::from("/path/to/file.txt") }];
+/// let report = commit_operations(app.handle().clone(), operations, None).await?;
+/// println!("{}", report.committed);
+/// ```
+#[command]
+pub async fn commit_operations<R: Runtime>(
+    app_handle: AppHandle<R>,
+    operations: Vec<Operation>,
+    options: Option<CommitOptions>,
+) -> Result<CommitReport> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let operation_id = options.and_then(|opt| opt.operation_id);
+
+    operations::commit(&operations, operation_id, &app_handle.fs_pro().history)
+}
+
+/// Revert operations committed with [`commit_operations`], last first, and remove them from the undo history.
+///
+/// Copies and created directories are removed, moves and renames are moved back and deleted paths are restored from the
+/// system trash. A path is not restored when another file took its place in the meantime, and directories are only removed
+/// when they are empty. On macOS, only deleted paths of the startup volume can be restored.
+///
+/// # Arguments
+/// - `operation_id`: Specify the id of the committed operations, defaults to the most recently committed ones.
+///
+/// # Returns
+/// - `Ok(UndoReport)`: Whether every operation was reverted and the paths that could not be restored.
+/// - `Err(Error)`: The error when there are no such operations in the undo history.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::undo;
+///
+/// let report = undo(app.handle().clone(), None).await?;
+/// println!("{:?}", report.not_undone);
+/// ```
+#[command]
+pub async fn undo<R: Runtime>(
+    app_handle: AppHandle<R>,
+    operation_id: Option<String>,
+) -> Result<UndoReport> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    app_handle.fs_pro().history.undo(operation_id.as_deref())
+}
+
+/// Get the committed operations that can be undone, most recent first. Up to 50 are kept.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::undo_history;
+///
+/// let history = undo_history(app.handle().clone()).await;
+/// println!("{:?}", history);
+/// ```
+#[command]
+pub async fn undo_history<R: Runtime>(app_handle: AppHandle<R>) -> Vec<HistoryEntry> {
+    app_handle.fs_pro().history.entries()
+}
+
+// Find the numbers of the existing `name.~N~` backups in `dir`.
+fn backup_numbers(dir: &Path, full_name: &str) -> Result<Vec<u64>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let prefix = format!("{}.~", full_name);
+    let mut numbers = Vec::new();
+
+    for entry in read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+
+        if let Some(number) = file_name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix('~'))
+            .and_then(|number| number.parse::<u64>().ok())
+        {
+            numbers.push(number);
+        }
+    }
+
+    numbers.sort_unstable();
+
+    Ok(numbers)
+}
+
+fn backup_path(path: &Path, options: &BackupOptions) -> Result<PathBuf> {
+    let full_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| Error::InvalidPath(path.to_path_buf()))?;
+
+    let dir = match &options.dir {
+        Some(dir) => dir.clone(),
+        None => path.parent().map(Path::to_path_buf).unwrap_or_default(),
+    };
+
+    create_dir_all(&dir)?;
+
+    let mut numbers = backup_numbers(&dir, &full_name)?;
+    let next = numbers.last().map(|number| number + 1).unwrap_or(1);
+    let backup = dir.join(format!("{}.~{}~", full_name, next));
+
+    if path.is_dir() {
+        let options = CopyOptions {
+            copy_inside: true,
+            ..CopyOptions::new()
+        };
+
+        fs_extra_dir::copy(path, &backup, &options)?;
+    } else {
+        fs::copy(path, &backup)?;
+    }
+
+    numbers.push(next);
+
+    if let Some(keep) = options.keep {
+        let excess = numbers.len().saturating_sub(keep.max(1));
+
+        for number in &numbers[..excess] {
+            let old = dir.join(format!("{}.~{}~", full_name, number));
+
+            unlink::remove_all(&old, &RetryPolicy::default())?;
+        }
+    }
+
+    Ok(backup)
+}
+
+/// Snapshot the path into a numbered backup (`file.txt.~1~`, `file.txt.~2~`, ...), removing the oldest backups beyond `keep`.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+/// - `options.dir`: The directory to store the backups in, defaults to the directory of the path.
+/// - `options.keep`: The number of backups to keep, the oldest ones are removed first, defaults to keeping all.
+///
+/// # Returns
+/// - `Ok(PathBuf)`: The path of the new backup.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::backup;
+///
+/// let path = PathBuf::from("/path/to/file.txt");
+/// let backup_path = backup(app.handle().clone(), path.into(), None).await?;
+/// println!("{}", backup_path.display()); // "/path/to/file.txt.~1~"
+/// ```
+#[command]
+pub async fn backup<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    options: Option<BackupOptions>,
+) -> Result<PathBuf> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    if !path.exists() {
+        return Err(Error::NotFound(path));
+    }
+
+    let mut trace = trace::command(&app_handle.fs_pro().settings, "backup", &path, None);
+    let backup = backup_path(&path, &options.unwrap_or_default())?;
+
+    trace.succeeded();
+
+    Ok(backup)
+}
+
+// The folder a file goes into under the first matching rule, relative to the organized directory.
+fn organize_folder(rules: &[(OrganizeRule, Option<regex::Regex>)], path: &Path) -> Option<PathBuf> {
+    let file_name = path.file_name()?.to_string_lossy();
+    let extname = path
+        .extension()
+        .map(|extname| extname.to_string_lossy().to_lowercase());
+
+    rules.iter().find_map(|(rule, regex)| match rule {
+        OrganizeRule::Extension { extensions, folder } => {
+            let extname = extname.as_ref()?;
+
+            let is_match = extensions.as_ref().map_or(true, |extensions| {
+                extensions.iter().any(|extension| {
+                    extension
+                        .trim_start_matches('.')
+                        .eq_ignore_ascii_case(extname)
+                })
+            });
+
+            is_match.then(|| PathBuf::from(folder.as_ref().unwrap_or(extname)))
+        }
+        OrganizeRule::Date { pattern } => {
+            let modified = fs::metadata(path).ok()?.modified().ok()?;
+            let date = chrono::DateTime::<chrono::Local>::from(modified);
+
+            let folder = pattern
+                .replace("YYYY", &date.format("%Y").to_string())
+                .replace("MM", &date.format("%m").to_string())
+                .replace("DD", &date.format("%d").to_string());
+
+            Some(PathBuf::from(folder))
+        }
+        OrganizeRule::Regex { folder, .. } => {
+            let captures = regex.as_ref()?.captures(&file_name)?;
+            let mut expanded = String::new();
+            captures.expand(folder, &mut expanded);
+
+            Some(PathBuf::from(expanded))
+        }
+    })
+}
+
+/// Move the files of a directory into subfolders by rules, the first matching rule wins and unmatched files stay in place.
+///
+/// # Arguments
+/// - `dir`: Specify the directory, only the files directly inside it are organized.
+/// - `rules`: Specify the rules, each one is an object with `by` set to `extension`, `date` or `regex`.
+/// - `options.dryRun`: Only return the plan without moving anything, defaults to `false`.
+///
+/// # Returns
+/// - `Ok(Vec<OrganizeMove>)`: The planned or performed moves, conflicting ones are left in place.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::{organize, OrganizeOptions, OrganizeRule};
+///
+/// let dir = PathBuf::from("/path/to/downloads");
+/// let rules = vec![OrganizeRule::Date { pattern: "YYYY/MM".to_string() }];
+/// let plan = organize(app.handle().clone(), dir.into(), rules, Some(OrganizeOptions { dry_run: Some(true) })).await?;
+/// println!("{:?}", plan);
+/// ```
+#[command]
+pub async fn organize<R: Runtime>(
+    app_handle: AppHandle<R>,
+    dir: PathInput,
+    rules: Vec<OrganizeRule>,
+    options: Option<OrganizeOptions>,
+) -> Result<Vec<OrganizeMove>> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let dir = PathBuf::from(dir);
+
+    let dry_run = options.and_then(|opt| opt.dry_run).unwrap_or(false);
+
+    if !dir.is_dir() {
+        return Err(Error::NotADirectory(dir));
+    }
+
+    let rules = rules
+        .into_iter()
+        .map(|rule| {
+            let regex = match &rule {
+                OrganizeRule::Regex { pattern, .. } => Some(regex::Regex::new(pattern)?),
+                _ => None,
+            };
+
+            Ok((rule, regex))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut planned = HashSet::new();
+    let mut moves = Vec::new();
+
+    for entry in read_dir(&dir)? {
+        let src = entry?.path();
+
+        if !src.is_file() {
+            continue;
+        }
+
+        let Some(folder) = organize_folder(&rules, &src) else {
+            continue;
+        };
+
+        // Folders come from user input and file names, they must stay inside the directory.
+        let is_relative = folder
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+
+        if !is_relative {
+            return Err(Error::InvalidPath(folder));
+        }
+
+        let Some(file_name) = src.file_name() else {
+            continue;
+        };
+
+        let dst = dir.join(folder).join(file_name);
+
+        if dst == src {
+            continue;
+        }
+
+        let conflict = dst.exists() || !planned.insert(dst.clone());
+
+        moves.push(OrganizeMove { src, dst, conflict });
+    }
+
+    if !dry_run {
+        for organize_move in moves.iter().filter(|organize_move| !organize_move.conflict) {
+            if let Some(parent) = organize_move.dst.parent() {
+                create_dir_all(parent)?;
+            }
+
+            fs::rename(&organize_move.src, &organize_move.dst)?;
+        }
+    }
+
+    Ok(moves)
+}
+
+// A file under the import destination and its hash, computed the first time it's compared.
+type HashedFile = (PathBuf, Option<Vec<u8>>);
+
+fn hash_file(path: &Path) -> io::Result<Vec<u8>> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+
+    Ok(hasher.finalize().to_vec())
+}
+
+// A path in `dir` named after `file_name` that isn't taken yet, e.g. `photo (1).jpg`.
+pub(crate) fn available_path(dir: &Path, file_name: &OsStr) -> PathBuf {
+    let path = dir.join(file_name);
+
+    if !path.exists() {
+        return path;
+    }
+
+    let file_name = Path::new(file_name);
+    let stem = file_name
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = file_name
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+
+    (1..)
+        .map(|number| dir.join(format!("{} ({}){}", stem, number, extension)))
+        .find(|path| !path.exists())
+        .unwrap_or(path)
+}
+
+/// Copy files into a directory, optionally skipping the ones whose content already exists anywhere under it.
+///
+/// Existing files are only hashed when their size matches one of the imported files.
+///
+/// # Arguments
+/// - `files`: Specify the paths of the files to import.
+/// - `dst_dir`: Specify the destination directory.
+/// - `options.skipIfIdenticalExists`: Skip files whose content already exists anywhere under the destination directory, defaults to `false`.
+///
+/// # Returns
+/// - `Ok(ImportReport)`: The imported files and the skipped duplicates.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::{import_files, ImportOptions, PathInput};
+///
+/// let files = vec![PathInput::from("/path/to/camera/IMG_0001.jpg")];
+/// let dst_dir = PathBuf::from("/path/to/photos");
+/// let options = ImportOptions { skip_if_identical_exists: Some(true) };
+/// let report = import_files(app.handle().clone(), files, dst_dir.into(), Some(options)).await?;
+/// println!("{:?}", report.deduped);
+/// ```
+#[command]
+pub async fn import_files<R: Runtime>(
+    app_handle: AppHandle<R>,
+    files: Vec<PathInput>,
+    dst_dir: PathInput,
+    options: Option<ImportOptions>,
+) -> Result<ImportReport> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let files: Vec<PathBuf> = files.into_iter().map(PathBuf::from).collect();
+    let dst_dir = PathBuf::from(dst_dir);
+
+    let skip_if_identical_exists = options
+        .and_then(|opt| opt.skip_if_identical_exists)
+        .unwrap_or(false);
+
+    create_dir_all(&dst_dir)?;
+
+    // The hashes of the files under the destination, keyed by size so most files never need hashing.
+    let mut existing: HashMap<u64, Vec<HashedFile>> = HashMap::new();
+
+    if skip_if_identical_exists {
+        let sizes = files
+            .iter()
+            .filter_map(|path| fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .collect::<HashSet<_>>();
+
+        for entry in WalkDir::new(&dst_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+        {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+
+            if sizes.contains(&metadata.len()) {
+                existing
+                    .entry(metadata.len())
+                    .or_default()
+                    .push((entry.into_path(), None));
+            }
+        }
+    }
+
+    let mut report = ImportReport::default();
+
+    for src in files {
+        let Some(file_name) = src.file_name() else {
+            return Err(Error::InvalidPath(src));
+        };
+
+        let len = fs::metadata(&src)?.len();
+
+        if skip_if_identical_exists {
+            let hash = hash_file(&src)?;
+            let candidates = existing.entry(len).or_default();
+
+            let mut duplicate = None;
+
+            for (path, candidate_hash) in candidates.iter_mut() {
+                if candidate_hash.is_none() {
+                    *candidate_hash = hash_file(path).ok();
+                }
+
+                if candidate_hash.as_ref() == Some(&hash) {
+                    duplicate = Some(path.clone());
+                    break;
+                }
+            }
+
+            if let Some(existing) = duplicate {
+                report.deduped.push(DedupedFile { src, existing });
+                continue;
+            }
+
+            let dst = available_path(&dst_dir, file_name);
+            fs::copy(&src, &dst)?;
+
+            // Later files in the same import are deduplicated against this one as well.
+            candidates.push((dst.clone(), Some(hash)));
+            report.imported.push(ImportedFile { src, dst });
+        } else {
+            let dst = available_path(&dst_dir, file_name);
+            fs::copy(&src, &dst)?;
+
+            report.imported.push(ImportedFile { src, dst });
+        }
+    }
+
+    Ok(report)
+}
+
+/// Copy the access and modification times, the permissions and optionally the extended attributes from one path to another.
+///
+/// The creation time is left untouched, as most platforms don't allow changing it.
+///
+/// # Arguments
+/// - `src_path`: Specify the path to copy the metadata from.
+/// - `dst_path`: Specify the path to copy the metadata to.
+/// - `options.xattrs`: Also copy the extended attributes, only supported on Unix, defaults to `false`.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::clone_metadata;
+///
+/// let src_path = PathBuf::from("/path/to/original.txt");
+/// let dst_path = PathBuf::from("/path/to/regenerated.txt");
+/// clone_metadata(app.handle().clone(), src_path.into(), dst_path.into(), None).await?;
+/// ```
+#[command]
+pub async fn clone_metadata<R: Runtime>(
+    app_handle: AppHandle<R>,
+    src_path: PathInput,
+    dst_path: PathInput,
+    options: Option<CloneMetadataOptions>,
+) -> Result<()> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let src_path = PathBuf::from(src_path);
+    let dst_path = PathBuf::from(dst_path);
+
+    let xattrs = options.and_then(|opt| opt.xattrs).unwrap_or(false);
+
+    let metadata = fs::metadata(&src_path)?;
+
+    if !dst_path.exists() {
+        return Err(Error::NotFound(dst_path));
+    }
+
+    #[cfg(unix)]
+    if xattrs {
+        for name in xattr::list(&src_path)? {
+            if let Some(value) = xattr::get(&src_path, &name)? {
+                xattr::set(&dst_path, &name, &value)?;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = xattrs;
+
+    filetime::set_file_times(
+        &dst_path,
+        filetime::FileTime::from_last_access_time(&metadata),
+        filetime::FileTime::from_last_modification_time(&metadata),
+    )?;
+
+    // Permissions go last, a read-only destination could refuse the other changes.
+    fs::set_permissions(&dst_path, metadata.permissions())?;
+
+    Ok(())
+}
+
+/// Build the filename index of a directory in the background, replacing the previous index.
+///
+/// The directory is watched from then on, so the index follows the entries created, removed and renamed in it, a second after the
+/// changes settled. An index loaded from disk when the app starts is watched as well, changes made while the app wasn't running
+/// are only picked up by the next build. The paths that could not be read are listed in `inaccessible` of [`index_status`].
+///
+/// # Arguments
+/// - `dir`: Specify the directory to index.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::index_build;
+///
+/// let dir = PathBuf::from("/path/to/directory");
+/// index_build(app.handle(), dir.into()).await?;
+/// ```
+#[command]
+pub async fn index_build<R: Runtime>(app_handle: AppHandle<R>, dir: PathInput) -> Result<()> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let dir = PathBuf::from(dir);
+
+    app_handle.fs_pro().index_build(dir)
+}
+
+/// Query the filename index for paths whose name contains the text, ignoring case.
+///
+/// # Arguments
+/// - `text`: Specify the text to search for.
+/// - `options.limit`: The maximum number of paths to return, defaults to `100`.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::index_query;
+///
+/// let paths = index_query(app.handle(), "report".to_string(), None).await;
+/// println!("{:?}", paths);
+/// ```
+#[command]
+pub async fn index_query<R: Runtime>(
+    app_handle: AppHandle<R>,
+    text: String,
+    options: Option<IndexQueryOptions>,
+) -> Vec<PathEntry> {
+    let limit = options.and_then(|opt| opt.limit).unwrap_or(100);
+
+    app_handle.fs_pro().index_query(&text, limit)
+}
+
+/// Get the status of the filename index.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::index_status;
+///
+/// let status = index_status(app.handle()).await;
+/// println!("{:?}", status);
+/// ```
+#[command]
+pub async fn index_status<R: Runtime>(app_handle: AppHandle<R>) -> IndexStatus {
+    app_handle.fs_pro().index_status()
+}
+
+/// Watch a single file for changes, without watching its parent directory.
+///
+/// Emits `fs-pro://watch-file` events with the metadata of the file after each change. The watcher stops by itself once the file is removed or renamed.
+/// Files replaced by an editor saving atomically keep being watched and are reported as modified.
+///
+/// When the volume of the file is unmounted, such as an external drive being ejected, a `lost` event is emitted instead of `remove`.
+///
+/// # Arguments
+/// - `path`: Specify the path of the file.
+/// - `options.autoResume`: Keep the watch after a `lost` event and resume it with a `resume` event once the volume is mounted again, defaults to `false`. If the file is gone by then, a `remove` event ends the watch.
+/// - `options.minIntervalMs`: The minimum time between two `modify` events in milliseconds, no limit by default or the interval set with [`crate::Builder::min_event_interval`]. The changes in the meantime are sent as one event once the interval passed, with the metadata of the file by then. `remove` and `rename` events are never held back.
+///
+/// # Returns
+/// - `Ok(u32)`: The id of the watcher.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::watch_file;
+///
+/// let id = watch_file(app.handle(), PathBuf::from("/path/to/config.json").into(), None).await?;
+/// println!("{}", id);
+/// ```
+#[command]
+pub async fn watch_file<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    options: Option<WatchFileOptions>,
+) -> Result<u32> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    app_handle.fs_pro().watch_file(path, options)
+}
+
+/// Stop watching a file.
+///
+/// # Arguments
+/// - `id`: Specify the id of the watcher.
+///
+/// # Returns
+/// - `bool`: `false` if there is no watcher with the id.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::unwatch_file;
+///
+/// let stopped = unwatch_file(app.handle(), id).await;
+/// println!("{}", stopped);
+/// ```
+#[command]
+pub async fn unwatch_file<R: Runtime>(app_handle: AppHandle<R>, id: u32) -> bool {
+    app_handle.fs_pro().unwatch_file(id)
+}
+
+/// Mirror the source directory into the destination directory, then keep it mirrored as the source changes.
+///
+/// Files are copied when their size or modification time differ, through a temporary file so the destination never holds half a
+/// file. Symbolic links are not synced. Emits `fs-pro://auto-sync` events for every copied, removed or failed path.
+///
+/// # Arguments
+/// - `src_path`: Specify the source directory.
+/// - `dst_path`: Specify the destination directory, created if it doesn't exist. Neither directory may contain the other.
+/// - `options.ignore`: Gitignore-style rules of the paths not to be synced, matched relative to the source path, either `IgnoreRules::Path` of a file such as `.syncignore` or `IgnoreRules::Contents`. Ignored paths in the destination are left alone.
+/// - `options.delete`: Remove the paths that no longer exist in the source from the destination, defaults to `true`.
+/// - `options.debounce`: How long the source must be left unchanged before the changes are synced in milliseconds, defaults to `500`.
+///
+/// # Returns
+/// - `Ok(u32)`: The id of the auto sync.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::auto_sync_start;
+///
+/// let src_path = PathBuf::from("/path/to/documents");
+/// let dst_path = PathBuf::from("/path/to/backup");
+/// let id = auto_sync_start(app.handle().clone(), src_path.into(), dst_path.into(), None).await?;
+/// println!("{}", id);
+/// ```
+#[command]
+pub async fn auto_sync_start<R: Runtime>(
+    app_handle: AppHandle<R>,
+    src_path: PathInput,
+    dst_path: PathInput,
+    options: Option<AutoSyncOptions>,
+) -> Result<u32> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let src_path = PathBuf::from(src_path);
+    let dst_path = PathBuf::from(dst_path);
+
+    app_handle
+        .fs_pro()
+        .auto_sync_start(src_path, dst_path, options)
+}
+
+/// Stop an auto sync, changes that are not synced yet are left out.
+///
+/// # Arguments
+/// - `id`: Specify the id of the auto sync.
+///
+/// # Returns
+/// - `bool`: `false` if there is no auto sync with the id.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::auto_sync_stop;
+///
+/// let stopped = auto_sync_stop(app.handle().clone(), id).await;
+/// println!("{}", stopped);
+/// ```
+#[command]
+pub async fn auto_sync_stop<R: Runtime>(app_handle: AppHandle<R>, id: u32) -> bool {
+    app_handle.fs_pro().auto_sync_stop(id)
+}
+
+/// Sync two directories both ways: files added, changed or deleted on one side since the last sync are copied to or removed from
+/// the other side.
+///
+/// The size and modification time of every synced file is kept in the plugin data directory, a file changed on both sides since
+/// is a conflict and is never overwritten silently. The first sync of two directories treats files that exist on both sides with
+/// different contents as conflicts. Only files are synced, symbolic links and empty directories are not.
+///
+/// # Arguments
+/// - `left_path`: Specify the left directory.
+/// - `right_path`: Specify the right directory. Neither directory may contain the other.
+/// - `options.strategy`: How conflicts are resolved, defaults to `ConflictStrategy::Ask`.
+///   - `ConflictStrategy::Ask`: Leave both versions untouched and report the conflict, it's reported on every sync until both sides are the same.
+///   - `ConflictStrategy::NewerWins`: Keep the version modified last, an edited file wins over a deleted one.
+///   - `ConflictStrategy::KeepBoth`: Keep the left version under its name and the right version next to it as `name (conflict <timestamp>).ext`, on both sides. An edited file wins over a deleted one.
+/// - `options.ignore`: Gitignore-style rules of the paths not to be synced, matched relative to each directory, either `IgnoreRules::Path` of a file such as `.syncignore` or `IgnoreRules::Contents`.
+///
+/// # Returns
+/// - `Ok(SyncReport)`: The copied and removed paths, the conflicts and the paths that could not be synced, relative to both directories. Failed paths are synced again next time.
+/// - `Err(Error)`: The error on failure, nothing is changed when a directory can't be read.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::{bidirectional_sync, BidirectionalSyncOptions, ConflictStrategy};
+///
+/// let left_path = PathBuf::from("/path/to/documents");
+/// let right_path = PathBuf::from("/Volumes/USB/documents");
+/// let options = BidirectionalSyncOptions {
+///     strategy: Some(ConflictStrategy::KeepBoth),
+///     ignore: None,
+/// };
+/// let report = bidirectional_sync(app.handle().clone(), left_path.into(), right_path.into(), Some(options)).await?;
+/// println!("{:?}", report.conflicts);
+/// ```
+#[command]
+pub async fn bidirectional_sync<R: Runtime>(
+    app_handle: AppHandle<R>,
+    left_path: PathInput,
+    right_path: PathInput,
+    options: Option<BidirectionalSyncOptions>,
+) -> Result<SyncReport> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let left_path = PathBuf::from(left_path);
+    let right_path = PathBuf::from(right_path);
+
+    let mut trace = trace::command(
+        &app_handle.fs_pro().settings,
+        "bidirectional_sync",
+        &left_path,
+        None,
+    )
+    .dst_path(&right_path);
+    let state_dir = app_handle.fs_pro().data_dir()?.join("sync");
+    let report = bidirectional::sync(&left_path, &right_path, &state_dir, options)?;
+
+    trace.succeeded();
+
+    Ok(report)
+}
+
+/// Get the mounted volumes, such as disks, USB drives and network shares.
+///
+/// Call [`watch_volumes`] and listen for the `fs-pro://volume` event to learn about volumes mounted or unmounted later on instead of polling.
+///
+/// # Returns
+/// - `Vec<Volume>`: The mount path, name and file system of each volume.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::volumes;
+///
+/// let volumes = volumes().await;
+/// println!("{:?}", volumes);
+/// ```
+#[command]
+pub async fn volumes() -> Vec<Volume> {
+    mount::volumes()
+}
+
+/// Start emitting `fs-pro://volume` events as volumes are mounted or unmounted, until [`unwatch_volumes`] is called as many times.
+///
+/// Changes are noticed within a second. On Linux the volumes are only listed again when the mount table changes, elsewhere they are listed every second while anything watches them.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::watch_volumes;
+///
+/// watch_volumes(app.handle()).await;
+/// ```
+#[command]
+pub async fn watch_volumes<R: Runtime>(app_handle: AppHandle<R>) {
+    app_handle.fs_pro().watch_volumes()
+}
+
+/// Stop emitting `fs-pro://volume` events for one earlier call to [`watch_volumes`].
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::unwatch_volumes;
+///
+/// unwatch_volumes(app.handle()).await;
+/// ```
+#[command]
+pub async fn unwatch_volumes<R: Runtime>(app_handle: AppHandle<R>) {
+    app_handle.fs_pro().unwatch_volumes()
+}
+
+/// Measure the sequential read and write throughput of the volume of a directory.
+///
+/// A temporary file is written to the directory, flushed to the device and read back, bypassing the cache of the system where
+/// possible. It's removed afterwards, also on failure or cancellation.
+///
+/// # Arguments
+/// - `path`: Specify a directory on the volume to measure.
+/// - `options.fileSize`: The size of the temporary file in bytes, rounded up to whole MiB, defaults to 128 MiB. Larger files give more reliable results. It's written and read in chunks of 1 MiB, or of the limit set with [`crate::Builder::max_buffer_bytes`] when smaller.
+/// - `options.mode`: Whether to measure `BenchmarkMode::Read`, `BenchmarkMode::Write` or `BenchmarkMode::ReadWrite`, defaults to `BenchmarkMode::ReadWrite`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`].
+///
+/// # Returns
+/// - `Ok(BenchmarkReport)`: The size of the file and the measured throughput in bytes per second.
+/// - `Err(Error)`: The error on failure, e.g. when the volume is full.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::benchmark;
+///
+/// let path = PathBuf::from("/Volumes/External");
+/// let report = benchmark(app.handle().clone(), path.into(), None).await?;
+/// println!("{:?}", report.write_bytes_per_second);
+/// ```
+#[command]
+pub async fn benchmark<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    options: Option<BenchmarkOptions>,
+) -> Result<BenchmarkReport> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    let (file_size, mode, operation_id) = match options {
+        Some(opt) => (opt.file_size, opt.mode, opt.operation_id),
+        None => (None, None, None),
+    };
+    let mut trace = trace::command(
+        &app_handle.fs_pro().settings,
+        "benchmark",
+        &path,
+        operation_id.as_deref(),
+    );
+    let operation = app_handle.fs_pro().operations.start(operation_id);
+
+    let report = benchmark::run(
+        &path,
+        file_size.unwrap_or(128 * 1024 * 1024),
+        mode.unwrap_or_default(),
+        app_handle.fs_pro().settings.buffer_limit,
+        operation.token(),
+    )?;
+
+    trace.bytes(report.file_size);
+    trace.succeeded();
+
+    Ok(report)
+}
+
+/// Get the size and free space of the volume of a path, including the free inodes on file systems with a fixed number of them.
+///
+/// Each file or directory takes an inode, so extracting or copying many small files can fail on a volume with plenty of free space
+/// left, e.g. on ext4.
+///
+/// # Arguments
+/// - `path`: Specify an existing path on the volume.
+///
+/// # Returns
+/// - `Ok(DiskUsage)`: The total and available bytes and inodes, the inodes are `None` on file systems such as btrfs, FAT or NTFS.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::disk_usage;
+///
+/// let usage = disk_usage(app.handle().clone(), PathBuf::from("/home/user").into()).await?;
+/// println!("{:?}", usage.available_inodes);
+/// ```
+#[command]
+pub async fn disk_usage<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+) -> Result<DiskUsage> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    if fs::symlink_metadata(&path).is_err() {
+        return Err(Error::NotFound(path));
+    }
+
+    Ok(mount::disk_usage(&path)?)
+}
+
+/// Check whether a path can be written to, by creating and removing a hidden probe file rather than trusting the permissions alone.
+///
+/// An existing file is checked by opening it for writing, a path that doesn't exist yet through its nearest existing ancestor.
+/// Read-only mounts and write-protected media, e.g. a locked SD card or a mounted disk image, are reported as not writable.
+///
+/// # Arguments
+/// - `path`: Specify the path.
+///
+/// # Returns
+/// - `Ok(bool)`: Whether the path can be written to.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::is_writable;
+///
+/// let writable = is_writable(app.handle().clone(), PathBuf::from("/Volumes/Camera/DCIM").into()).await?;
+/// println!("{}", writable); // false
+/// ```
+#[command]
+pub async fn is_writable<R: Runtime>(app_handle: AppHandle<R>, path: PathInput) -> Result<bool> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    Ok(mount::is_writable(&path)?)
+}
+
+// Fail before anything is written when the destination is read-only, instead of halfway through.
+fn ensure_writable(path: &Path) -> Result<()> {
+    if !mount::is_writable(path)? {
+        return Err(Error::NotWritable(path.to_path_buf()));
+    }
+
+    Ok(())
+}
+
+/// Create an empty scratch directory for a multi-step job, e.g. download, extract, transform and move.
+///
+/// The workspace is removed with [`workspace_dispose`], or with everything in it once the app exits. Workspaces left behind by a
+/// crash are removed the next time the app starts.
+///
+/// # Returns
+/// - `Ok(Workspace)`: The id and the path of the workspace, inside the app cache directory.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::workspace_create;
+///
+/// let workspace = workspace_create(app.handle().clone()).await?;
+/// println!("{}", workspace.path.display());
+/// ```
+#[command]
+pub async fn workspace_create<R: Runtime>(app_handle: AppHandle<R>) -> Result<Workspace> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    app_handle.fs_pro().workspace_create()
+}
+
+/// Remove a workspace created with [`workspace_create`] with everything in it.
+///
+/// # Arguments
+/// - `id`: Specify the id of the workspace.
+///
+/// # Returns
+/// - `Ok(bool)`: Whether there was a workspace with the id.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::workspace_dispose;
+///
+/// let disposed = workspace_dispose(app.handle().clone(), workspace.id).await?;
+/// println!("{}", disposed); // true
+/// ```
+#[command]
+pub async fn workspace_dispose<R: Runtime>(app_handle: AppHandle<R>, id: u32) -> Result<bool> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    app_handle.fs_pro().workspace_dispose(id)
+}
+
+/// Put a file into a content-addressed store, under `ab/cdef…` in the store directory after the SHA-256 digest of its content.
+///
+/// Identical content is stored once, storing it again only returns the existing object. New content is written next to its place
+/// in the store and renamed once complete, so the store never holds half a file.
+///
+/// # Arguments
+/// - `src_path`: Specify the path of the file to store.
+/// - `cas_root`: Specify the directory of the store, created when missing.
+/// - `options.mode`: How the file is put into the store, defaults to `StoreMode::Copy`:
+///   - `StoreMode::Copy`: Copy the file, hashing it in the same pass.
+///   - `StoreMode::Link`: Hard link the file, copying it when the store is on another file system. Changing the file in place afterwards changes the stored object too.
+///   - `StoreMode::Move`: Move the file, removing it when the store already has the content.
+///
+/// # Returns
+/// - `Ok(StoredObject)`: The digest in lowercase hex, the path inside the store and whether the store already had the content.
+/// - `Err(Error)`: The error on failure, of kind `invalidPath` when the source is not a file.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::store_cas;
+///
+/// let src_path = PathBuf::from("/path/to/texture.png");
+/// let cas_root = PathBuf::from("/path/to/assets");
+/// let object = store_cas(app.handle().clone(), src_path.into(), cas_root.into(), None).await?;
+/// println!("{}", object.digest);
+/// ```
+#[command]
+pub async fn store_cas<R: Runtime>(
+    app_handle: AppHandle<R>,
+    src_path: PathInput,
+    cas_root: PathInput,
+    options: Option<StoreCasOptions>,
+) -> Result<StoredObject> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let src_path = PathBuf::from(src_path);
+    let cas_root = PathBuf::from(cas_root);
+
+    let mode = options.and_then(|opt| opt.mode).unwrap_or_default();
+    let mut trace = trace::command(&app_handle.fs_pro().settings, "store_cas", &src_path, None)
+        .dst_path(&cas_root);
+    let len = fs::metadata(&src_path)?.len();
+    let object = cas::store(
+        &src_path,
+        &cas_root,
+        mode,
+        app_handle.fs_pro().settings.buffer_limit,
+    )?;
+
+    trace.bytes(len);
+    trace.succeeded();
+
+    Ok(object)
+}
+
+/// Get the path of the content with a digest in a content-addressed store written by [`store_cas`].
+///
+/// # Arguments
+/// - `cas_root`: Specify the directory of the store.
+/// - `digest`: Specify the SHA-256 digest of the content in hex.
+///
+/// # Returns
+/// - `Ok(PathBuf)`: The path of the content inside the store.
+/// - `Err(Error)`: The error on failure, of kind `notFound` when the store doesn't have the content, or `invalidDigest` when the digest is not 64 hex characters.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::retrieve_cas;
+///
+/// let cas_root = PathBuf::from("/path/to/assets");
+/// let path = retrieve_cas(app.handle().clone(), cas_root.into(), object.digest).await?;
+/// println!("{}", path.display());
+/// ```
+#[command]
+pub async fn retrieve_cas<R: Runtime>(
+    app_handle: AppHandle<R>,
+    cas_root: PathInput,
+    digest: String,
+) -> Result<PathBuf> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let cas_root = PathBuf::from(cas_root);
+
+    cas::retrieve(&cas_root, &digest)
+}
+
+// The running app as the handler of a file type.
+fn file_handler<'a, R: Runtime>(
+    app_handle: &'a AppHandle<R>,
+    options: Option<&'a FileAssociationOptions>,
+) -> Result<association::Handler<'a>> {
+    Ok(association::Handler {
+        identifier: &app_handle.config().identifier,
+        name: &app_handle.package_info().name,
+        exe: std::env::current_exe()?,
+        data_dir: app_handle.path().data_dir()?,
+        description: options.and_then(|opt| opt.description.as_deref()),
+        mime_type: options.and_then(|opt| opt.mime_type.as_deref()),
+    })
+}
+
+/// Make the app the handler of a file extension for the current user, so the files open in it on double-click.
+///
+/// - Windows: Registers a file type named after the app identifier and the extension under `HKEY_CURRENT_USER\Software\Classes`.
+///   A default app the user picked in the Windows settings still takes precedence.
+/// - macOS: Sets the app as the default handler with Launch Services. The extension has to be declared in the `Info.plist` of the
+///   app, e.g. with `bundle.fileAssociations` in the Tauri configuration, and the app must be installed.
+/// - Linux: Installs a MIME type for the extension and a hidden desktop entry of the app, then makes it the default with `xdg-mime`.
+///
+/// The opened file is passed as an argument to the app on Windows and Linux, and through `RunEvent::Opened` on macOS.
+///
+/// # Arguments
+/// - `extension`: Specify the extension, with or without a leading dot, e.g. `mydoc`.
+/// - `options.description`: The description of the file type shown in the file manager, e.g. `My Document`, defaults to the app name.
+/// - `options.mimeType`: The MIME type of the files on Linux as `type/subtype`, e.g. `application/x-mydoc`, defaults to `application/x-<extension>`.
+///
+/// # Returns
+/// - `Ok(())`: The app is the handler of the extension.
+/// - `Err(Error)`: The error on failure, of kind `invalidExtension` when the extension contains anything but letters, digits, `-`, `_` and `.`, or `invalidMimeType` when the MIME type is not `type/subtype` made of letters, digits and `!#$&^_.+-`.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::register_file_association;
+///
+/// register_file_association(app.handle().clone(), "mydoc".into(), None).await?;
+/// ```
+#[command]
+pub async fn register_file_association<R: Runtime>(
+    app_handle: AppHandle<R>,
+    extension: String,
+    options: Option<FileAssociationOptions>,
+) -> Result<()> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let handler = file_handler(&app_handle, options.as_ref())?;
+
+    association::register(&extension, &handler)
+}
+
+/// Remove what [`register_file_association`] set up for an extension.
+///
+/// Another app the extension was handled by before is not restored, the system picks one or asks. On macOS Launch Services has no
+/// way to remove a default handler, nothing is changed and `false` is returned, choose another app in Finder instead.
+///
+/// # Arguments
+/// - `extension`: Specify the extension, with or without a leading dot, e.g. `mydoc`.
+///
+/// # Returns
+/// - `Ok(bool)`: Whether the app was registered for the extension.
+/// - `Err(Error)`: The error on failure.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_fs_pro::unregister_file_association;
+///
+/// let unregistered = unregister_file_association(app.handle().clone(), "mydoc".into()).await?;
+/// println!("{}", unregistered); // true
+/// ```
+#[command]
+pub async fn unregister_file_association<R: Runtime>(
+    app_handle: AppHandle<R>,
+    extension: String,
+) -> Result<bool> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let handler = file_handler(&app_handle, None)?;
+
+    association::unregister(&extension, &handler)
+}
+
+/// Create a launcher that opens a file or directory with its default app, replacing an existing launcher of the same name.
+///
+/// - Windows: A shortcut (`.lnk`) in the Start Menu programs of the current user or on the desktop.
+/// - macOS: An alias in `~/Applications` or on the desktop, the same Finder creates with Make Alias. It follows the target when
+///   moved and shows its icon, `options.icon` is not used.
+/// - Linux: A desktop entry in `~/.local/share/applications`, which the app menus pick up, or on the desktop. It opens the target
+///   with `xdg-open`. Some desktops ask to trust an entry on the desktop before starting it the first time.
+///
+/// # Arguments
+/// - `path`: Specify the path of the file or directory to open.
+/// - `options.name`: The name shown for the launcher, also the name of its file, defaults to the name of the file or directory.
+/// - `options.icon`: The icon of the launcher, an `.ico` file on Windows, an image file or the name of an icon of the theme on Linux. Defaults to the icon of the target on Windows and the generic file or folder icon on Linux.
+/// - `options.location`: Where the launcher is created, `LauncherLocation::Menu` or `LauncherLocation::Desktop`, defaults to `LauncherLocation::Menu`.
+///
+/// # Returns
+/// - `Ok(PathBuf)`: The path of the launcher.
+/// - `Err(Error)`: The error on failure, of kind `notFound` when the path doesn't exist and `invalidPath` when the name contains a path separator or another character file names can't hold, or the path or the icon contains a control character such as a line break.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::create_launcher;
+///
+/// let path = PathBuf::from("/path/to/projects");
+/// let launcher = create_launcher(app.handle().clone(), path.into(), None).await?;
+/// println!("{}", launcher.display()); // "/home/user/.local/share/applications/projects.desktop"
+/// ```
+#[command]
+pub async fn create_launcher<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    options: Option<LauncherOptions>,
+) -> Result<PathBuf> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    if !path.exists() {
+        return Err(Error::NotFound(path));
+    }
+
+    let target = dunce::canonicalize(&path)?;
+    let (name, icon, location) = match options {
+        Some(opt) => (opt.name, opt.icon, opt.location.unwrap_or_default()),
+        None => (None, None, LauncherLocation::default()),
+    };
+    let name = match name {
+        Some(name) => name,
+        None => full_name(target.clone().into()).await,
+    };
+    let paths = app_handle.path();
+    let dir = match location {
+        LauncherLocation::Menu => launcher::menu_dir(&paths.data_dir()?, &paths.home_dir()?),
+        LauncherLocation::Desktop => paths.desktop_dir()?,
+    };
+
+    launcher::create(&launcher::Launcher {
+        target: &target,
+        name: &name,
+        icon: icon.as_deref(),
+        dir,
+    })
 }
 
-/// Get the extension name of a path.
+/// Deallocate a range of a file, the range reads back as zeros and the size of the file stays the same. Freeing the stale parts of
+/// a large cache file gives their disk space back without rewriting the rest.
+///
+/// - Windows: The file is marked as sparse and the range zeroed, NTFS and ReFS give back the whole clusters inside the range.
+/// - macOS: APFS gives back the whole blocks inside the range, the partial blocks at its edges are zeroed.
+/// - Linux: Most file systems give back the whole blocks inside the range, the partial blocks at its edges are zeroed.
 ///
 /// # Arguments
-/// - `path`: Specify the path.
+/// - `path`: Specify the path of the file.
+/// - `offset`: Specify the offset of the range in bytes.
+/// - `len`: Specify the length of the range in bytes, a range past the end of the file stops at the end.
+///
+/// # Returns
+/// - `Ok(())`: The range was deallocated.
+/// - `Err(Error)`: The error on failure, of kind `notFound` when the file doesn't exist and `io` when the file system doesn't support holes.
 ///
 /// # Example
 /// ```
 /// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::extname;
+/// use tauri_plugin_fs_pro::punch_hole;
 ///
-/// let path = PathBuf::from("/path/to/file.txt");
-/// let ext = extname(path).await;
-/// println!("{}", ext); // "txt"
+/// let path = PathBuf::from("/path/to/cache.bin");
+/// punch_hole(app.handle().clone(), path.into(), 4096, 1024 * 1024).await?;
 /// ```
 #[command]
-pub async fn extname(path: PathBuf) -> String {
-    path.extension()
-        .map(|extname| extname.to_string_lossy().to_string())
-        .unwrap_or_default()
+pub async fn punch_hole<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: PathInput,
+    offset: u64,
+    len: u64,
+) -> Result<()> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    if !path.exists() {
+        return Err(Error::NotFound(path));
+    }
+
+    sparse::punch_hole(&path, offset, len)
 }
 
-/// Get the full name of a file or directory including extension.
+/// Get the data and hole extents of a file, in order and covering the whole file.
+///
+/// - Windows: The allocated ranges of a sparse file, other files are a single data extent.
+/// - macOS: The extents APFS keeps track of, other file systems give a single data extent.
+/// - Linux: The extents the file system keeps track of, to the block, some report a hole written with zeros as data.
 ///
 /// # Arguments
-/// - `path`: Specify the path.
+/// - `path`: Specify the path of the file.
+///
+/// # Returns
+/// - `Ok(Vec<Extent>)`: The extents of the file, each with its offset, length and whether it's data or a hole.
+/// - `Err(Error)`: The error on failure, of kind `notFound` when the file doesn't exist.
 ///
 /// # Example
 /// ```
 /// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::full_name;
+/// use tauri_plugin_fs_pro::{extents, ExtentKind};
 ///
-/// let path = PathBuf::from("/path/to/file.txt");
-/// let full_name = full_name(path).await;
-/// println!("{}", full_name); // "file.txt"
+/// let path = PathBuf::from("/path/to/cache.bin");
+/// let holes = extents(app.handle().clone(), path.into()).await?.into_iter().filter(|extent| extent.kind == ExtentKind::Hole);
+/// println!("{}", holes.map(|extent| extent.len).sum::<u64>()); // 1048576
 /// ```
 #[command]
-pub async fn full_name(path: PathBuf) -> String {
-    path.file_name()
-        .map(|name| name.to_string_lossy().to_string())
-        .unwrap_or_default()
+pub async fn extents<R: Runtime>(app_handle: AppHandle<R>, path: PathInput) -> Result<Vec<Extent>> {
+    let _redacting = app_handle.fs_pro().redacting();
+
+    let path = PathBuf::from(path);
+
+    if !path.exists() {
+        return Err(Error::NotFound(path));
+    }
+
+    sparse::extents(&path)
 }
 
-/// Get the parent directory name of a path.
+/// Get a compact token of the state of a file or directory, which changes when it's modified, resized or replaced by another file.
+/// Check it later with [`has_changed`] to know whether what was derived from the file, such as a cached preview, is still valid,
+/// without reading the file again.
+///
+/// The token hashes the modification time, the size and the identity of the file, so writing a new file and renaming it over the
+/// old one changes it even when the time and size come out the same.
+///
+/// - Windows: The identity is the volume serial number and the file index.
+/// - macOS: The identity is the device and inode, along with the inode change time.
+/// - Linux: The identity is the device and inode, along with the inode change time.
 ///
 /// # Arguments
-/// - `path`: Specify the path.
-/// - `level`: Specify the level of the parent directory, defaults to `1`.
+/// - `path`: Specify the path of the file or directory.
+///
+/// # Returns
+/// - `Ok(String)`: The token, 16 hex characters. It's only meant to be compared with a token of the same path on the same machine.
+/// - `Err(Error)`: The error on failure, of kind `notFound` when the path doesn't exist.
 ///
 /// # Example
 /// ```
 /// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::parent_name;
+/// use tauri_plugin_fs_pro::change_token;
 ///
-/// let path = PathBuf::from("/path/to/file.txt");
-/// let parent = parent_name(path, None).await;
-/// println!("{}", parent.unwrap()); // "to"
+/// let path = PathBuf::from("/path/to/photo.jpg");
+/// let token = change_token(app.handle().clone(), path.into()).await?;
+/// println!("{}", token); // "3f2a9c0d41b7e6a8"
 /// ```
 #[command]
-pub async fn parent_name(path: PathBuf, level: Option<u8>) -> Result<String, String> {
-    let mut current = path;
-    for _ in 0..level.unwrap_or(1) {
-        if let Some(parent) = current.parent() {
-            current = parent.to_path_buf();
-        } else {
-            return Ok(String::default());
-        }
-    }
-    Ok(full_name(current).await)
-}
-
-async fn get_icon_name(path: PathBuf) -> Result<String, String> {
-    let is_dir = is_dir(path.clone()).await;
-    let name = name(path.clone()).await;
-    let extname = extname(path.clone()).await;
-    let full_name = full_name(path.clone()).await;
-
-    let is_mac_app = cfg!(target_os = "macos") && extname.eq(&"app");
-    let is_win_app = cfg!(target_os = "windows") && extname.eq(&"exe");
-
-    if is_mac_app || is_win_app {
-        return Ok(full_name);
-    }
+pub async fn change_token<R: Runtime>(app_handle: AppHandle<R>, path: PathInput) -> Result<String> {
+    let _redacting = app_handle.fs_pro().redacting();
 
-    if is_dir {
-        return Ok("__TAURI_PLUGIN_FS_PRO_DIRECTORY__".to_string());
-    }
+    let path = PathBuf::from(path);
 
-    if extname.is_empty() {
-        return Ok(name);
+    if !path.exists() {
+        return Err(Error::NotFound(path));
     }
 
-    return Ok(extname);
+    token::compute(&path)
 }
 
-/// Get the default save icon path.
+/// Check whether a file or directory has changed since a token was got from [`change_token`].
+///
+/// # Arguments
+/// - `path`: Specify the path of the file or directory.
+/// - `token`: Specify the token got from [`change_token`] for the path.
+///
+/// # Returns
+/// - `Ok(bool)`: Whether the file or directory has changed, `true` when it no longer exists.
+/// - `Err(Error)`: The error on failure.
 ///
 /// # Example
 /// ```
-/// use tauri_plugin_fs_pro::get_default_save_icon_path;
+/// use std::path::PathBuf;
+/// use tauri_plugin_fs_pro::has_changed;
 ///
-/// let path = get_default_save_icon_path(app.handle()).await?;
-/// println!("{}", path);
+/// let path = PathBuf::from("/path/to/photo.jpg");
+/// let changed = has_changed(app.handle().clone(), path.into(), token).await?;
+/// println!("{}", changed); // false
 /// ```
 #[command]
-pub async fn get_default_save_icon_path<R: Runtime>(
+pub async fn has_changed<R: Runtime>(
     app_handle: AppHandle<R>,
-) -> Result<PathBuf, String> {
-    let save_path = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|err| err.to_string())?
-        .join("tauri-plugin-fs-pro")
-        .join("icons");
+    path: PathInput,
+    token: String,
+) -> Result<bool> {
+    let _redacting = app_handle.fs_pro().redacting();
 
-    Ok(save_path)
+    let path = PathBuf::from(path);
+
+    token::has_changed(&path, &token)
 }
 
-/// Get the icon of a path.
+/// Compress a single file with gzip, without a tar archive around it, e.g. `data.json` to `data.json.gz`, as the `gzip` tool does.
+///
+/// The name and modified time of the file are stored in the gzip header. The compressed file is written next to the destination
+/// and renamed once complete.
 ///
 /// # Arguments
-/// - `path`: Specify the path.
-/// - `options.size`: Specify the size of the icon, defaults to `32`.
-/// - `options.savePath`: Specify the path to save the icon, defaults to the default save path.
+/// - `src_path`: Specify the path of the file to compress.
+/// - `dst_path`: Specify the destination path.
+/// - `options.level`: The compression level, from `0` to `9`, defaults to `6`. Higher levels are smaller but slower, `0` stores without compressing.
+/// - `options.overwrite`: Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`], the partial file is removed.
+///
+/// # Returns
+/// - `Ok(())`: The file was compressed.
+/// - `Err(Error)`: The error on failure, of kind `notFound` when the source is not a file, `alreadyExists` when the destination exists and `options.overwrite` is `false`, or `invalidCompressionLevel` when the level is out of range.
 ///
 /// # Example
 /// ```
 /// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::icon;
+/// use tauri_plugin_fs_pro::gzip;
 ///
-/// let path = PathBuf::from("/path/to/file.txt");
-/// let icon_path = icon(app.handle(), path, None).await?;
-/// println!("{}", icon_path);
+/// let src_path = PathBuf::from("/path/to/data.json");
+/// let dst_path = PathBuf::from("/path/to/data.json.gz");
+/// gzip(app.handle().clone(), src_path.into(), dst_path.into(), None).await?;
 /// ```
 #[command]
-pub async fn icon<R: Runtime>(
+pub async fn gzip<R: Runtime>(
     app_handle: AppHandle<R>,
-    path: PathBuf,
-    options: Option<IconOptions>,
-) -> Result<PathBuf, String> {
-    let size = options.as_ref().and_then(|opt| opt.size).unwrap_or(32);
-    let default_save_path = get_default_save_icon_path(app_handle).await?;
-    let save_path = options
-        .and_then(|opt| opt.save_path)
-        .unwrap_or(default_save_path);
+    src_path: PathInput,
+    dst_path: PathInput,
+    options: Option<GzipOptions>,
+) -> Result<()> {
+    let _redacting = app_handle.fs_pro().redacting();
 
-    let icon_name = get_icon_name(path.clone()).await?;
+    let src_path = PathBuf::from(src_path);
+    let dst_path = PathBuf::from(dst_path);
 
-    let save_path = save_path.join(format!("{}.png", icon_name));
+    let options = options.unwrap_or_default();
+    let mut trace = trace::command(
+        &app_handle.fs_pro().settings,
+        "gzip",
+        &src_path,
+        options.operation_id.as_deref(),
+    )
+    .dst_path(&dst_path);
+    let operation = app_handle.fs_pro().operations.start(options.operation_id);
+    let level = ArchiveFormat::TarGz.level(options.level)?;
 
-    if save_path.exists() {
-        return Ok(save_path);
+    if !src_path.is_file() {
+        return Err(Error::NotFound(src_path));
     }
 
-    let icon = get_file_icon(path, size).map_err(|err| err.to_string())?;
+    let dst_path = archive_path(dst_path, options.overwrite.unwrap_or(true), false)?;
+    let name = full_name(dst_path.clone().into()).await;
+    let written_path = dst_path.with_file_name(format!(".{}.fs-pro-gzip", name));
 
-    let image = RgbaImage::from_raw(icon.width, icon.height, icon.pixels)
-        .map(DynamicImage::ImageRgba8)
-        .ok_or_else(|| "Failed to convert Icon to Image".to_string())?;
+    let written: Result<()> = (|| {
+        archive::gzip(
+            &src_path,
+            File::create(&written_path)?,
+            level,
+            app_handle.fs_pro().settings.buffer_limit,
+            operation.token(),
+        )?;
 
-    create_dir_all(&save_path).map_err(|err| err.to_string())?;
+        Ok(fs::rename(&written_path, &dst_path)?)
+    })();
 
-    image.save(&save_path).map_err(|err| err.to_string())?;
+    if written.is_err() {
+        let _ = fs::remove_file(&written_path);
+    }
 
-    Ok(save_path)
-}
+    written?;
 
-fn system_time_to_unix_millis(time: io::Result<SystemTime>) -> u128 {
-    match time {
-        Ok(system_time) => system_time
-            .duration_since(UNIX_EPOCH)
-            .map(|duration| duration.as_millis())
-            .unwrap_or_default(),
-        Err(_) => 0,
+    if let Ok(metadata) = fs::metadata(&dst_path) {
+        trace.bytes(metadata.len());
     }
+    trace.succeeded();
+
+    Ok(())
 }
 
-/// Get the metadata of the path.
+/// Decompress a gzip file holding a single file rather than a tar archive, e.g. `data.json.gz` to `data.json`, as the `gunzip`
+/// tool does.
+///
+/// A file of several gzip members, as concatenating gzip files or `pigz` writes, is decompressed whole. The decompressed file is
+/// written next to the destination and renamed once complete. A tar.gz file decompresses to its tar archive, use [`decompress`] to
+/// extract it.
 ///
 /// # Arguments
-/// - `path`: Specify the path.
-/// - `options.omitSize`: When getting the metadata of a path, if you don't need to calculate the size, you can omit it to save time and return 0 after omitting it, defaults to `false`.
+/// - `src_path`: Specify the path of the gzip file.
+/// - `dst_path`: Specify the destination path.
+/// - `options.overwrite`: Whether to overwrite an existing destination file, returns an error instead when `false`, defaults to `true`.
+/// - `options.operationId`: An id of your choice to cancel the operation with [`cancel_operation`], the partial file is removed.
 ///
 /// # Returns
-/// - `Ok(Metadata)`: The metadata of the path.
-/// - `Err(String)`: An error message string on failure.
+/// - `Ok(())`: The file was decompressed.
+/// - `Err(Error)`: The error on failure, of kind `notFound` when the source is not a file, `alreadyExists` when the destination exists and `options.overwrite` is `false`, or `unsupportedArchive` when the source is not a gzip file.
 ///
 /// # Example
 /// ```
 /// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::metadata;
+/// use tauri_plugin_fs_pro::gunzip;
 ///
-/// let path = PathBuf::from("/path/to/file.txt");
-/// let metadata = metadata(path, None).await?;
-/// println!("{:?}", metadata);
+/// let src_path = PathBuf::from("/path/to/data.json.gz");
+/// let dst_path = PathBuf::from("/path/to/data.json");
+/// gunzip(app.handle().clone(), src_path.into(), dst_path.into(), None).await?;
 /// ```
 #[command]
-pub async fn metadata(path: PathBuf, options: Option<MetadataOptions>) -> Result<Metadata, String> {
-    let omit_size = options.and_then(|opt| opt.omit_size).unwrap_or(false);
+pub async fn gunzip<R: Runtime>(
+    app_handle: AppHandle<R>,
+    src_path: PathInput,
+    dst_path: PathInput,
+    options: Option<GunzipOptions>,
+) -> Result<()> {
+    let _redacting = app_handle.fs_pro().redacting();
 
-    let size = if omit_size {
-        0
-    } else {
-        size(path.clone()).await
-    };
-    let name = name(path.clone()).await;
-    let extname = extname(path.clone()).await;
-    let full_name = full_name(path.clone()).await;
-    let parent_name = parent_name(path.clone(), Some(1)).await?;
+    let src_path = PathBuf::from(src_path);
+    let dst_path = PathBuf::from(dst_path);
 
-    let is_dir = path.is_dir();
-    let is_file = path.is_file();
-    let is_exist = path.exists();
-    let is_symlink = path.is_symlink();
-    let is_absolute = path.is_absolute();
-    let is_relative = path.is_relative();
+    let options = options.unwrap_or_default();
+    let mut trace = trace::command(
+        &app_handle.fs_pro().settings,
+        "gunzip",
+        &src_path,
+        options.operation_id.as_deref(),
+    )
+    .dst_path(&dst_path);
+    let operation = app_handle.fs_pro().operations.start(options.operation_id);
 
-    let metadata = fs::metadata(path).map_err(|err| err.to_string())?;
-    let accessed_at = system_time_to_unix_millis(metadata.accessed());
-    let created_at = system_time_to_unix_millis(metadata.created());
-    let modified_at = system_time_to_unix_millis(metadata.modified());
+    if !src_path.is_file() {
+        return Err(Error::NotFound(src_path));
+    }
 
-    Ok(Metadata {
-        size,
-        name,
-        extname,
-        full_name,
-        parent_name,
-        is_dir,
-        is_file,
-        is_exist,
-        is_symlink,
-        is_absolute,
-        is_relative,
-        accessed_at,
-        created_at,
-        modified_at,
-    })
+    let dst_path = archive_path(dst_path, options.overwrite.unwrap_or(true), false)?;
+    let name = full_name(dst_path.clone().into()).await;
+    let written_path = dst_path.with_file_name(format!(".{}.fs-pro-gunzip", name));
+
+    let written: Result<()> = (|| {
+        archive::gunzip(
+            &src_path,
+            File::create(&written_path)?,
+            app_handle.fs_pro().settings.buffer_limit,
+            operation.token(),
+        )?;
+
+        Ok(fs::rename(&written_path, &dst_path)?)
+    })();
+
+    if written.is_err() {
+        let _ = fs::remove_file(&written_path);
+    }
+
+    written?;
+
+    if let Ok(metadata) = fs::metadata(&src_path) {
+        trace.bytes(metadata.len());
+    }
+    trace.succeeded();
+
+    Ok(())
 }
 
-/// Compress the source path into a tar.gz file to the destination path.
-///
-/// # Arguments
-/// - `src_path`: Specify the source path.
-/// - `dst_path`: Specify the destination path.
-/// - `options.includes`: The name of the file or directory to be compressed.
-/// - `options.excludes`: The name of the file or directory not to be compressed.
+/// Get the current working directory of the app process, which relative paths are resolved against.
 ///
 /// # Example
 /// ```
-/// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::compress;
+/// use tauri_plugin_fs_pro::cwd;
 ///
-/// let src_path = PathBuf::from("/path/to/source");
-/// let dst_path = PathBuf::from("/path/to/destination.tar.gz");
-/// compress(src_path, dst_path, None).await?;
+/// let path = cwd(app.handle().clone()).await?;
+/// println!("{}", path.display());
 /// ```
 #[command]
-pub async fn compress(
-    src_path: PathBuf,
-    dst_path: PathBuf,
-    options: Option<CompressOptions>,
-) -> Result<(), String> {
-    let options = options.unwrap_or(CompressOptions {
-        includes: Some(vec![]),
-        excludes: Some(vec![]),
-    });
-    let includes = options.includes.unwrap_or(vec![]);
-    let excludes = options.excludes.unwrap_or(vec![]);
-
-    let dst_file = File::create(dst_path.clone()).map_err(|err| err.to_string())?;
-    let enc = GzEncoder::new(dst_file, Compression::default());
-    let mut tar = tar::Builder::new(enc);
-
-    for entry in read_dir(&src_path).map_err(|err| err.to_string())? {
-        let path = entry.map_err(|err| err.to_string())?.path();
-        let is_file = path.is_file();
-        let full_name = full_name(path.clone()).await;
-
-        if excludes.iter().any(|name| &full_name == name) {
-            continue;
-        }
-
-        if !includes.is_empty() && !includes.iter().any(|name| &full_name == name) {
-            continue;
-        }
-
-        if is_file {
-            let file = &mut File::open(path.clone()).map_err(|err| err.to_string())?;
-
-            tar.append_file(full_name, file)
-                .map_err(|err| err.to_string())?;
-        } else {
-            tar.append_dir_all(full_name, path.clone())
-                .map_err(|err| err.to_string())?;
-        }
-    }
+pub async fn cwd<R: Runtime>(app_handle: AppHandle<R>) -> Result<PathBuf> {
+    let _redacting = app_handle.fs_pro().redacting();
 
-    tar.finish().map_err(|err| err.to_string())?;
-
-    Ok(())
+    Ok(env::current_dir()?)
 }
 
-/// Decompress the tar.gz file from the source path to the destination path.
+/// Resolve a path inside a well-known directory, e.g. the app data or downloads directory, with the separators of the current
+/// platform.
 ///
 /// # Arguments
-/// - `src_path`: Specify the source path.
-/// - `dst_path`: Specify the destination path.
+/// - `base`: Specify the directory, such as `BaseDir::AppData`, `BaseDir::Download` or `BaseDir::Desktop`.
+/// - `segments`: Specify the names to join to it in order, each may hold several names separated by `/`, e.g. `logs/today.txt`.
+///
+/// # Returns
+/// - `Ok(PathBuf)`: The absolute path, which doesn't have to exist.
+/// - `Err(Error)`: The error on failure, of kind `invalidPath` when a segment is absolute or leads outside the directory with `..`.
 ///
 /// # Example
 /// ```
-/// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::decompress;
+/// use tauri_plugin_fs_pro::{resolve_app_path, BaseDir};
 ///
-/// let src_path = PathBuf::from("/path/to/source.tar.gz");
-/// let dst_path = PathBuf::from("/path/to/destination");
-/// decompress(src_path, dst_path).await?;
+/// let segments = vec!["backups".to_string(), "latest.tar.gz".to_string()];
+/// let path = resolve_app_path(app.handle().clone(), BaseDir::AppData, segments).await?;
+/// println!("{}", path.display());
 /// ```
 #[command]
-pub async fn decompress(src_path: PathBuf, dst_path: PathBuf) -> Result<(), String> {
-    create_dir_all(dst_path.clone()).map_err(|err| err.to_string())?;
-
-    let src_file = File::open(src_path).map_err(|err| err.to_string())?;
-    let decoder = GzDecoder::new(src_file);
-    let mut archive = Archive::new(decoder);
-
-    for entry in archive.entries().map_err(|err| err.to_string())? {
-        let mut entry = entry.map_err(|err| err.to_string())?;
-        let path = entry.path().map_err(|err| err.to_string())?.to_path_buf();
+pub async fn resolve_app_path<R: Runtime>(
+    app_handle: AppHandle<R>,
+    base: BaseDir,
+    segments: Vec<String>,
+) -> Result<PathBuf> {
+    let _redacting = app_handle.fs_pro().redacting();
 
-        #[cfg(target_os = "windows")]
-        let path = std::path::Path::new(&path.to_string_lossy().replace("\\", "/")).to_path_buf();
+    let mut path = base.resolve(&app_handle)?;
 
-        entry
-            .unpack(dst_path.join(path))
-            .map_err(|err| err.to_string())?;
+    for segment in &segments {
+        for component in Path::new(segment).components() {
+            match component {
+                Component::Normal(name) => path.push(name),
+                Component::CurDir => {}
+                _ => return Err(Error::InvalidPath(PathBuf::from(segment))),
+            }
+        }
     }
 
-    Ok(())
+    Ok(path)
 }
 
-/// Move the source path to the destination path.
+/// Gather everything the plugin can find out about a path into one report, e.g. to attach to a support request.
+///
+/// Every check is run even when others fail, the failed ones are listed in `problems` with their errors.
 ///
 /// # Arguments
-/// - `src_path`: Specify the source path.
-/// - `dst_path`: Specify the destination path.
-/// - `options.includes`: The name of the file or directory to be moved.
-/// - `options.excludes`: The name of the file or directory not to be moved.
+/// - `path`: Specify the path, which doesn't have to exist.
+///
+/// # Returns
+/// - `Diagnosis`: The metadata of the path as [`metadata`] returns it, the symbolic links followed from it and its canonical path, the volume and its free space, whether it can be read and written with its permission bits and owner on Unix, and whether the asset protocol scope allows it and the plugin rejects it as relative.
 ///
 /// # Example
 /// ```
 /// use std::path::PathBuf;
-/// use tauri_plugin_fs_pro::transfer;
+/// use tauri_plugin_fs_pro::diagnose;
 ///
-/// let src_path = PathBuf::from("/path/to/source");
-/// let dst_path = PathBuf::from("/path/to/destination");
-/// transfer(src_path, dst_path, None).await?;
+/// let path = PathBuf::from("/Users/xxx/Documents/report.pdf");
+/// let diagnosis = diagnose(app.handle().clone(), path.into()).await;
+/// println!("{}", serde_json::to_string_pretty(&diagnosis)?);
 /// ```
 #[command]
-pub async fn transfer(
-    src_path: PathBuf,
-    dst_path: PathBuf,
-    options: Option<TransferOptions>,
-) -> Result<(), String> {
-    let options = options.unwrap_or(TransferOptions {
-        includes: Some(vec![]),
-        excludes: Some(vec![]),
-    });
-    let includes = options.includes.unwrap_or(vec![]);
-    let excludes = options.excludes.unwrap_or(vec![]);
+pub async fn diagnose<R: Runtime>(app_handle: AppHandle<R>, path: PathInput) -> Diagnosis {
+    let _redacting = app_handle.fs_pro().redacting();
 
-    create_dir_all(dst_path.clone()).map_err(|err| err.to_string())?;
+    let path = PathBuf::from(path);
 
-    let mut config = HashSet::new();
-    config.insert(DirEntryAttr::Path);
+    let metadata = metadata(app_handle.clone(), path.clone().into(), None).await;
 
-    let ls_result = ls(&src_path, &config).map_err(|err| err.to_string())?;
+    diagnose::run(&app_handle, &path, metadata)
+}
 
-    let mut from_items = Vec::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    for item in ls_result.items {
-        if let Some(path) = item.get(&DirEntryAttr::Path) {
-            if let &DirEntryValue::String(ref path) = path {
-                let path = PathBuf::from(path);
-                let full_name = full_name(path.clone()).await;
+    #[test]
+    fn path_input_accepts_strings_and_entries() {
+        let path: PathInput = serde_json::from_str(r#""/path/to/file.txt""#).unwrap();
+        assert_eq!(PathBuf::from(path), PathBuf::from("/path/to/file.txt"));
 
-                if excludes.iter().any(|name| &full_name == name) {
-                    continue;
-                }
+        let entry: PathInput =
+            serde_json::from_str(r#"{ "path": "/path/to/file.txt", "rawPath": null }"#).unwrap();
+        assert_eq!(PathBuf::from(entry), PathBuf::from("/path/to/file.txt"));
+    }
 
-                if !includes.is_empty() && !includes.iter().any(|name| &full_name == name) {
-                    continue;
-                }
+    #[cfg(unix)]
+    #[test]
+    fn paths_that_are_not_valid_unicode_round_trip() {
+        use std::os::unix::ffi::OsStringExt;
 
-                from_items.push(path);
-            }
-        }
+        let path = PathBuf::from(OsString::from_vec(b"/path/to/caf\xe9.txt".to_vec()));
+        let report = SizeReport {
+            size: 0,
+            inaccessible: vec![path.clone()],
+        };
+
+        let value = serde_json::to_value(&report).unwrap();
+        let entry = value["inaccessible"][0].clone();
+        assert_eq!(entry["path"], "/path/to/caf\u{FFFD}.txt");
+
+        let input: PathInput = serde_json::from_value(entry).unwrap();
+        assert_eq!(PathBuf::from(input), path);
     }
 
-    let options = CopyOptions {
-        overwrite: true,
-        skip_exist: false,
-        buffer_size: 64000,
-        copy_inside: false,
-        content_only: false,
-        depth: 0,
-    };
+    #[cfg(unix)]
+    #[test]
+    fn size_counts_symlinks_for_their_own_size() {
+        let dir = std::env::temp_dir().join(format!("fs-pro-size-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("data")).unwrap();
+        fs::write(dir.join("data/file.txt"), [0; 1000]).unwrap();
+        std::os::unix::fs::symlink(dir.join("data"), dir.join("link")).unwrap();
 
-    move_items(&from_items, &dst_path, &options).map_err(|err| err.to_string())?;
+        let link_size = fs::symlink_metadata(dir.join("link")).unwrap().len();
+        assert_eq!(walk_size(&dir.join("data")).0, 1000);
+        assert_eq!(walk_size(&dir.join("link")).0, link_size);
+        assert_eq!(walk_size(&dir).0, 1000 + link_size);
+        assert_eq!(walk_size(&dir.join("missing")).0, 0);
 
-    Ok(())
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compress_keeps_empty_dirs_only_when_asked() {
+        let dir = std::env::temp_dir().join(format!("fs-pro-empty-dirs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/empty")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+
+        let stored = |keep_dirs: bool| {
+            let includes = ["*.rs".to_string()];
+            let filter = EntryFilter::new(&dir, &includes, &[], None, FileLimits::default())
+                .unwrap()
+                .keep_dirs(keep_dirs);
+            let mut archive = ArchiveWriter::DryRun(Vec::new());
+            let cancel = CancelToken::default();
+
+            append_dir_tree(
+                &mut archive,
+                Path::new("src"),
+                &dir.join("src"),
+                &filter,
+                true,
+                &cancel,
+                &mut Vec::new(),
+            )
+            .unwrap();
+
+            let ArchiveWriter::DryRun(items) = archive else {
+                unreachable!();
+            };
+
+            items
+                .into_iter()
+                .map(|item| item.dst_path)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(stored(false), [Path::new("src"), Path::new("src/lib.rs")]);
+        assert_eq!(
+            stored(true),
+            [
+                Path::new("src"),
+                Path::new("src/empty"),
+                Path::new("src/lib.rs")
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,228 @@
+use crate::{
+    auto_sync::AutoSyncs,
+    cancel::Operations,
+    error::Redacting,
+    index::{self, Index, IndexState},
+    mount::VolumeMonitor,
+    operations::History,
+    watch::Watchers,
+    workspace::{self, Workspace, Workspaces},
+    AutoSyncOptions, Error, FsProExt, IndexStatus, PathEntry, Result, Settings, WatchFileOptions,
+};
+use std::{collections::HashMap, path::PathBuf, thread};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// A custom icon used for an extension name instead of the system one.
+#[derive(Debug, Clone)]
+pub enum IconOverride {
+    /// An image file, returned as is.
+    Path(PathBuf),
+    /// The encoded bytes of an image, saved as a PNG file in the icon directory the first time it's requested.
+    Bytes(Vec<u8>),
+}
+
+/// Access to the fs-pro plugin state from Rust.
+pub struct FsPro<R: Runtime> {
+    app_handle: AppHandle<R>,
+    pub(crate) index: Index,
+    pub(crate) watchers: Watchers,
+    pub(crate) volume_monitor: VolumeMonitor,
+    pub(crate) operations: Operations,
+    pub(crate) history: History,
+    auto_syncs: AutoSyncs,
+    pub(crate) workspaces: Workspaces,
+    // Keyed by lowercased extension name without the leading dot.
+    icon_overrides: HashMap<String, IconOverride>,
+    pub(crate) settings: Settings,
+}
+
+impl<R: Runtime> FsPro<R> {
+    pub(crate) fn new(
+        app_handle: AppHandle<R>,
+        icon_overrides: HashMap<String, IconOverride>,
+        settings: Settings,
+    ) -> Self {
+        Self {
+            app_handle,
+            index: Index::default(),
+            watchers: Watchers::default(),
+            volume_monitor: VolumeMonitor::default(),
+            operations: Operations::default(),
+            history: History::default(),
+            auto_syncs: AutoSyncs::default(),
+            workspaces: Workspaces::default(),
+            icon_overrides,
+            settings,
+        }
+    }
+
+    /// Redact the errors of the command holding the returned value, see [`Redacting`].
+    pub(crate) fn redacting(&self) -> Redacting {
+        self.settings.path_redaction.redacting()
+    }
+
+    /// The directory where the plugin keeps its data, inside the app data directory.
+    pub fn data_dir(&self) -> Result<PathBuf> {
+        let data_dir = self
+            .app_handle
+            .path()
+            .app_data_dir()?
+            .join("tauri-plugin-fs-pro");
+
+        Ok(data_dir)
+    }
+
+    /// The directory where icons are saved by default.
+    pub fn icon_dir(&self) -> Result<PathBuf> {
+        Ok(self.data_dir()?.join("icons"))
+    }
+
+    /// The directory where workspaces are created, inside the app cache directory.
+    pub fn workspace_dir(&self) -> Result<PathBuf> {
+        let workspace_dir = self
+            .app_handle
+            .path()
+            .app_cache_dir()?
+            .join("tauri-plugin-fs-pro")
+            .join("workspaces");
+
+        Ok(workspace_dir)
+    }
+
+    /// Create an empty scratch directory, removed with [`FsPro::workspace_dispose`] or once the app exits.
+    pub fn workspace_create(&self) -> Result<Workspace> {
+        self.workspaces.create(&self.workspace_dir()?)
+    }
+
+    /// Remove a workspace with everything in it, returns `false` if there is no workspace with the id.
+    pub fn workspace_dispose(&self, id: u32) -> Result<bool> {
+        self.workspaces.dispose(id)
+    }
+
+    /// Remove the workspaces left behind by earlier runs of the app that didn't exit cleanly.
+    pub(crate) fn workspace_recover(&self) {
+        if let Ok(workspace_dir) = self.workspace_dir() {
+            workspace::recover(&workspace_dir);
+        }
+    }
+
+    /// The custom icon registered for an extension name, if any.
+    pub fn icon_override(&self, extname: &str) -> Option<&IconOverride> {
+        self.icon_overrides
+            .get(&extname.trim_start_matches('.').to_lowercase())
+    }
+
+    /// Build the filename index of a directory in the background, replacing the previous index, and keep it up to date with the
+    /// changes to the directory.
+    pub fn index_build(&self, dir: PathBuf) -> Result<()> {
+        if !dir.is_dir() {
+            return Err(Error::NotADirectory(dir));
+        }
+
+        {
+            let mut state = self.index.state.lock().unwrap();
+
+            if *state == IndexState::Building {
+                return Err(Error::IndexBuilding);
+            }
+
+            *state = IndexState::Building;
+        }
+
+        let app_handle = self.app_handle.clone();
+
+        thread::spawn(move || {
+            let fs_pro = app_handle.fs_pro();
+            let result = index::build(&app_handle, dir);
+
+            let mut state = fs_pro.index.state.lock().unwrap();
+
+            match result {
+                Ok(()) => *state = IndexState::Ready,
+                Err(err) => {
+                    fs_pro.index.data.lock().unwrap().error =
+                        Some(err.redacted_message(fs_pro.settings.path_redaction));
+                    *state = IndexState::Failed;
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Query the filename index for at most `limit` paths whose name contains the text, ignoring case.
+    pub fn index_query(&self, text: &str, limit: usize) -> Vec<PathEntry> {
+        let text = text.to_lowercase();
+
+        if text.is_empty() {
+            return vec![];
+        }
+
+        let data = self.index.data.lock().unwrap();
+
+        data.names
+            .iter()
+            .zip(data.entries.iter())
+            .filter(|(name, _)| name.contains(&text))
+            .take(limit)
+            .map(|(_, path)| PathEntry::from(path.as_path()))
+            .collect()
+    }
+
+    /// Watch a single file for changes, returns the id of the watcher.
+    ///
+    /// Emits `fs-pro://watch-file` events until the watcher is stopped, or the file is removed, renamed or its volume unmounted.
+    pub fn watch_file(&self, path: PathBuf, options: Option<WatchFileOptions>) -> Result<u32> {
+        self.watchers
+            .watch_file(self.app_handle.clone(), path, options)
+    }
+
+    /// Stop watching a file, returns `false` if there is no watcher with the id.
+    pub fn unwatch_file(&self, id: u32) -> bool {
+        self.watchers.unwatch(id)
+    }
+
+    /// Start emitting `fs-pro://volume` events as volumes are mounted or unmounted, until [`FsPro::unwatch_volumes`] is called as
+    /// many times.
+    pub fn watch_volumes(&self) {
+        self.volume_monitor.listen(&self.app_handle)
+    }
+
+    /// Stop emitting `fs-pro://volume` events for one earlier call to [`FsPro::watch_volumes`].
+    pub fn unwatch_volumes(&self) {
+        self.volume_monitor.unlisten()
+    }
+
+    /// Keep a destination directory mirrored to a source directory, returns the id of the auto sync.
+    ///
+    /// Emits `fs-pro://auto-sync` events until the auto sync is stopped.
+    pub fn auto_sync_start(
+        &self,
+        src_path: PathBuf,
+        dst_path: PathBuf,
+        options: Option<AutoSyncOptions>,
+    ) -> Result<u32> {
+        self.auto_syncs
+            .start(self.app_handle.clone(), src_path, dst_path, options)
+    }
+
+    /// Stop an auto sync, returns `false` if there is no auto sync with the id.
+    pub fn auto_sync_stop(&self, id: u32) -> bool {
+        self.auto_syncs.stop(id)
+    }
+
+    /// The status of the filename index.
+    pub fn index_status(&self) -> IndexStatus {
+        let status = *self.index.state.lock().unwrap();
+        let data = self.index.data.lock().unwrap();
+
+        IndexStatus {
+            status,
+            dir: data.dir.as_deref().map(PathEntry::from),
+            count: data.entries.len(),
+            built_at: data.built_at,
+            inaccessible: data.inaccessible.clone(),
+            error: data.error.clone(),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    commands::Metadata,
+    mount::{self, DiskUsage, Volume},
+    Error, FsProExt, Result,
+};
+use serde::Serialize;
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+use tauri::{AppHandle, Runtime};
+
+// Links are followed at most this many times, as the system does before giving up with `ELOOP`.
+const MAX_LINKS: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosisCheck {
+    Metadata,
+    Links,
+    DiskUsage,
+    Read,
+    Write,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosisProblem {
+    // The check that failed.
+    pub check: DiagnosisCheck,
+    pub error: Error,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathAccess {
+    // Whether the file can be opened or the directory listed.
+    pub readable: bool,
+    // Whether the path can be written to, probed with a file like `is_writable` does.
+    pub writable: bool,
+    // Whether the path is marked read-only, without any write permission on Unix.
+    pub read_only: bool,
+    // The permission bits on Unix, e.g. `0o644`.
+    pub mode: Option<u32>,
+    // The id of the user owning the path on Unix.
+    pub uid: Option<u32>,
+    // The id of the group owning the path on Unix.
+    pub gid: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathScope {
+    // Whether the asset protocol scope of the app allows the path, e.g. to show it with `convertFileSrc`, always `false` without the
+    // `icon-asset` feature.
+    pub asset_protocol: bool,
+    // Whether the commands reject the path as relative, see [`crate::Builder::require_absolute_paths`].
+    pub rejected_as_relative: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnosis {
+    #[serde(flatten, serialize_with = "crate::commands::serialize_path_entry")]
+    pub path: PathBuf,
+    // Whether the path exists, a broken symbolic link exists as well.
+    pub exists: bool,
+    // The metadata of the path, `None` when it could not be read.
+    pub metadata: Option<Metadata>,
+    // The targets of the symbolic links followed from the path in order, as they are stored.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub links: Vec<PathBuf>,
+    // The path with every symbolic link, `.` and `..` resolved, `None` when it doesn't exist or a link is broken.
+    #[serde(serialize_with = "crate::commands::serialize_optional_path_entry")]
+    pub canonical_path: Option<PathBuf>,
+    // The volume of the path, or of its nearest existing parent.
+    pub volume: Option<Volume>,
+    // The size and free space of that volume.
+    pub disk_usage: Option<DiskUsage>,
+    pub access: PathAccess,
+    pub scope: PathScope,
+    // The checks that failed with their errors, the other fields keep what could be found out.
+    pub problems: Vec<DiagnosisProblem>,
+}
+
+/// Gather everything the plugin can find out about a path, a check that fails is listed in the problems instead of failing the
+/// diagnosis. The metadata is read by the `metadata` command beforehand.
+pub(crate) fn run<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    path: &Path,
+    metadata: Result<Metadata>,
+) -> Diagnosis {
+    let mut problems = Vec::new();
+    let exists = fs::symlink_metadata(path).is_ok();
+
+    // Nothing but its volume is known about a path that doesn't exist.
+    let (metadata, links, readable) = match exists {
+        true => (
+            checked(&mut problems, DiagnosisCheck::Metadata, metadata),
+            checked(&mut problems, DiagnosisCheck::Links, follow_links(path)).unwrap_or_default(),
+            checked(&mut problems, DiagnosisCheck::Read, read(path)).is_some(),
+        ),
+        false => (None, Vec::new(), false),
+    };
+
+    let existing = path.ancestors().find(|ancestor| ancestor.exists());
+    let volume = existing.and_then(mount::volume_of);
+    let disk_usage = existing.and_then(|existing| {
+        checked(
+            &mut problems,
+            DiagnosisCheck::DiskUsage,
+            mount::disk_usage(existing),
+        )
+    });
+    let writable = checked(
+        &mut problems,
+        DiagnosisCheck::Write,
+        mount::is_writable(path),
+    )
+    .unwrap_or(false);
+
+    let (read_only, mode, uid, gid) = match fs::metadata(path) {
+        Ok(metadata) => {
+            let (mode, uid, gid) = owner_and_mode(&metadata);
+            (metadata.permissions().readonly(), mode, uid, gid)
+        }
+        Err(_) => (false, None, None, None),
+    };
+
+    Diagnosis {
+        path: path.to_path_buf(),
+        exists,
+        metadata,
+        links,
+        canonical_path: dunce::canonicalize(path).ok(),
+        volume,
+        disk_usage,
+        access: PathAccess {
+            readable,
+            writable,
+            read_only,
+            mode,
+            uid,
+            gid,
+        },
+        scope: PathScope {
+            asset_protocol: is_asset_allowed(app_handle, path),
+            rejected_as_relative: app_handle.fs_pro().settings.require_absolute_paths
+                && path.is_relative(),
+        },
+        problems,
+    }
+}
+
+#[cfg(feature = "icon-asset")]
+fn is_asset_allowed<R: Runtime>(app_handle: &AppHandle<R>, path: &Path) -> bool {
+    use tauri::Manager;
+
+    app_handle.asset_protocol_scope().is_allowed(path)
+}
+
+// The asset protocol is off without the feature, so it allows nothing.
+#[cfg(not(feature = "icon-asset"))]
+fn is_asset_allowed<R: Runtime>(_app_handle: &AppHandle<R>, _path: &Path) -> bool {
+    false
+}
+
+fn checked<T, E: Into<Error>>(
+    problems: &mut Vec<DiagnosisProblem>,
+    check: DiagnosisCheck,
+    result: std::result::Result<T, E>,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(err) => {
+            problems.push(DiagnosisProblem {
+                check,
+                error: err.into(),
+            });
+            None
+        }
+    }
+}
+
+// The chain of links from the path up to the first target that is not a link, or doesn't exist.
+fn follow_links(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut links = Vec::new();
+    let mut current = path.to_path_buf();
+
+    while fs::symlink_metadata(&current).is_ok_and(|metadata| metadata.is_symlink()) {
+        if links.len() == MAX_LINKS {
+            return Err(io::Error::other("Too many levels of symbolic links"));
+        }
+
+        let target = fs::read_link(&current)?;
+
+        // A relative target is relative to the directory of the link.
+        current = match current.parent() {
+            Some(parent) => parent.join(&target),
+            None => target.clone(),
+        };
+        links.push(target);
+    }
+
+    Ok(links)
+}
+
+fn read(path: &Path) -> io::Result<()> {
+    match path.is_dir() {
+        true => fs::read_dir(path).map(drop),
+        false => File::open(path).map(drop),
+    }
+}
+
+#[cfg(unix)]
+fn owner_and_mode(metadata: &fs::Metadata) -> (Option<u32>, Option<u32>, Option<u32>) {
+    use std::os::unix::fs::MetadataExt;
+
+    (
+        Some(metadata.mode() & 0o7777),
+        Some(metadata.uid()),
+        Some(metadata.gid()),
+    )
+}
+
+#[cfg(not(unix))]
+fn owner_and_mode(_metadata: &fs::Metadata) -> (Option<u32>, Option<u32>, Option<u32>) {
+    (None, None, None)
+}
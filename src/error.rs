@@ -0,0 +1,307 @@
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::{
+    cell::Cell,
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+thread_local! {
+    // The redaction of the app whose command last returned on this thread, see `Redacting`.
+    static PATH_REDACTION: Cell<PathRedaction> = const { Cell::new(PathRedaction::None) };
+}
+
+/// How paths are written in the error messages sent to the frontend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PathRedaction {
+    /// Keep paths as they are.
+    #[default]
+    None,
+    /// Replace paths with `<path>`.
+    Redact,
+    /// Replace each path component with a short, stable hash, so errors about the same path can still be grouped.
+    Hash,
+}
+
+/// Redacts the errors serialized on the thread a command returns on, with the redaction of its app.
+///
+/// Tauri serializes the result of a command right after it returns, on the same thread, so a command holding this until it
+/// returns has its errors redacted, including those in its result such as the issues of a plan.
+pub(crate) struct Redacting(PathRedaction);
+
+impl Drop for Redacting {
+    fn drop(&mut self) {
+        PATH_REDACTION.with(|redaction| redaction.set(self.0));
+    }
+}
+
+impl PathRedaction {
+    pub(crate) fn redacting(self) -> Redacting {
+        Redacting(self)
+    }
+
+    pub(crate) fn apply(self, path: &Path) -> String {
+        match self {
+            PathRedaction::None => path.display().to_string(),
+            PathRedaction::Redact => "<path>".to_string(),
+            PathRedaction::Hash => {
+                let mut redacted = PathBuf::new();
+
+                for component in path.components() {
+                    match component {
+                        Component::Normal(name) => {
+                            redacted.push(format!("{:08x}", fnv1a(name.as_encoded_bytes())))
+                        }
+                        component => redacted.push(component),
+                    }
+                }
+
+                redacted.display().to_string()
+            }
+        }
+    }
+
+    // Paths embedded by other libraries can only be found by their separators.
+    fn apply_to_token(self, token: &str) -> String {
+        let start = token.find(|c: char| !matches!(c, '"' | '\'' | '(' | '`'));
+        let end = token.rfind(|c: char| !matches!(c, '"' | '\'' | ')' | '`' | ',' | ':' | '.'));
+
+        let (Some(start), Some(end)) = (start, end) else {
+            return token.to_string();
+        };
+
+        if start > end {
+            return token.to_string();
+        }
+
+        let inner = &token[start..=end];
+
+        if !inner.contains(['/', '\\']) {
+            return token.to_string();
+        }
+
+        format!(
+            "{}{}{}",
+            &token[..start],
+            self.apply(Path::new(inner)),
+            &token[end + 1..]
+        )
+    }
+}
+
+// 32-bit FNV-1a, stable across builds and platforms unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ *byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    FsExtra(#[from] fs_extra::error::Error),
+    #[error(transparent)]
+    Walk(#[from] walkdir::Error),
+    #[error(transparent)]
+    Trash(#[from] trash::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+    #[error(transparent)]
+    Tauri(#[from] tauri::Error),
+    #[error(transparent)]
+    Watch(#[from] notify::Error),
+    #[error(transparent)]
+    Glob(#[from] globset::Error),
+    #[error(transparent)]
+    Regex(#[from] regex::Error),
+    #[error(transparent)]
+    Ignore(#[from] ignore::Error),
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+    #[error("Failed to get the icon: {0}")]
+    Icon(String),
+    #[error("Path does not exist: {}", .0.display())]
+    NotFound(PathBuf),
+    #[error("Destination already exists: {}", .0.display())]
+    AlreadyExists(PathBuf),
+    #[error("Not a directory: {}", .0.display())]
+    NotADirectory(PathBuf),
+    #[error("Invalid path: {}", .0.display())]
+    InvalidPath(PathBuf),
+    #[error("Relative paths are not allowed, expected an absolute path: {}", .0.display())]
+    RelativePath(PathBuf),
+    #[error("Invalid extension name: {0}")]
+    InvalidExtension(String),
+    #[error("Invalid MIME type, expected type/subtype: {0}")]
+    InvalidMimeType(String),
+    #[error("An index is already being built")]
+    IndexBuilding,
+    #[error("The device mounted at {} was disconnected", .0.display())]
+    DeviceDisconnected(PathBuf),
+    #[error("The operations have {0} issues, check them with plan_operations")]
+    InvalidPlan(usize),
+    #[error("The operation was cancelled")]
+    Cancelled,
+    #[error("There are no committed operations to undo")]
+    NothingToUndo,
+    #[error("Unsupported archive format: {}", .0.display())]
+    UnsupportedArchive(PathBuf),
+    #[error("The destination is not writable: {}", .0.display())]
+    NotWritable(PathBuf),
+    #[error("Invalid digest: {0}")]
+    InvalidDigest(String),
+    #[error("{error}, and {} paths could not be rolled back", .paths.len())]
+    NotRolledBack {
+        error: Box<Error>,
+        paths: Vec<PathBuf>,
+    },
+    #[error("Compression level {level} is out of range for {format}, expected {min} to {max}")]
+    InvalidCompressionLevel {
+        format: &'static str,
+        level: u32,
+        min: u32,
+        max: u32,
+    },
+}
+
+impl Error {
+    /// A stable, machine-readable identifier of the error.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Io(err) => io_kind(err),
+            Error::FsExtra(err) => match &err.kind {
+                fs_extra::error::ErrorKind::NotFound => "notFound",
+                fs_extra::error::ErrorKind::PermissionDenied => "permissionDenied",
+                fs_extra::error::ErrorKind::AlreadyExists => "alreadyExists",
+                fs_extra::error::ErrorKind::Io(err) => io_kind(err),
+                _ => "io",
+            },
+            Error::Walk(err) => err.io_error().map(io_kind).unwrap_or("io"),
+            Error::Trash(_) => "trash",
+            Error::Json(_) => "json",
+            Error::Image(_) => "image",
+            Error::Tauri(_) => "tauri",
+            Error::Glob(_) => "invalidGlob",
+            Error::Regex(_) => "invalidRegex",
+            Error::Ignore(err) => err.io_error().map(io_kind).unwrap_or("invalidIgnore"),
+            Error::Zip(err) => match err {
+                zip::result::ZipError::Io(err) => io_kind(err),
+                _ => "invalidArchive",
+            },
+            Error::Watch(err) => match &err.kind {
+                notify::ErrorKind::Io(err) => io_kind(err),
+                notify::ErrorKind::PathNotFound => "notFound",
+                _ => "watch",
+            },
+            Error::Icon(_) => "icon",
+            Error::NotFound(_) => "notFound",
+            Error::AlreadyExists(_) => "alreadyExists",
+            Error::NotADirectory(_) => "notADirectory",
+            Error::InvalidPath(_) => "invalidPath",
+            Error::RelativePath(_) => "relativePath",
+            Error::InvalidExtension(_) => "invalidExtension",
+            Error::InvalidMimeType(_) => "invalidMimeType",
+            Error::IndexBuilding => "indexBuilding",
+            Error::DeviceDisconnected(_) => "deviceDisconnected",
+            Error::InvalidPlan(_) => "invalidPlan",
+            Error::Cancelled => "cancelled",
+            Error::NothingToUndo => "nothingToUndo",
+            Error::UnsupportedArchive(_) => "unsupportedArchive",
+            Error::InvalidCompressionLevel { .. } => "invalidCompressionLevel",
+            Error::NotWritable(_) => "notWritable",
+            Error::InvalidDigest(_) => "invalidDigest",
+            Error::NotRolledBack { .. } => "notRolledBack",
+        }
+    }
+
+    /// The path the error is about, if the error carries one.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Error::NotFound(path)
+            | Error::AlreadyExists(path)
+            | Error::NotADirectory(path)
+            | Error::InvalidPath(path)
+            | Error::RelativePath(path)
+            | Error::DeviceDisconnected(path)
+            | Error::UnsupportedArchive(path)
+            | Error::NotWritable(path) => Some(path),
+            Error::NotRolledBack { paths, .. } => paths.first().map(PathBuf::as_path),
+            Error::Walk(err) => err.path(),
+            Error::Watch(err) => err.paths.first().map(PathBuf::as_path),
+            _ => None,
+        }
+    }
+
+    /// The error message, with paths redacted as configured with [`crate::Builder::path_redaction`] for the app whose command
+    /// last returned on this thread.
+    pub fn message(&self) -> String {
+        self.redacted_message(PATH_REDACTION.with(Cell::get))
+    }
+
+    // The error message with paths redacted as given, for the errors sent from other threads than the ones of the commands.
+    pub(crate) fn redacted_message(&self, redaction: PathRedaction) -> String {
+        let message = self.to_string();
+
+        if redaction == PathRedaction::None {
+            return message;
+        }
+
+        if let Some(path) = self.path() {
+            let display = path.display().to_string();
+
+            if message.contains(&display) {
+                return message.replace(&display, &redaction.apply(path));
+            }
+        }
+
+        message
+            .split(' ')
+            .map(|token| redaction.apply_to_token(token))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn io_kind(err: &io::Error) -> &'static str {
+    match err.kind() {
+        io::ErrorKind::NotFound => "notFound",
+        io::ErrorKind::PermissionDenied => "permissionDenied",
+        io::ErrorKind::AlreadyExists => "alreadyExists",
+        _ => "io",
+    }
+}
+
+impl Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut error = serializer.serialize_struct("Error", 2)?;
+        error.serialize_field("kind", self.kind())?;
+        error.serialize_field("message", &self.message())?;
+        error.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_are_redacted_once_a_command_returns() {
+        let error = Error::NotFound(PathBuf::from("/path/to/file.txt"));
+        assert_eq!(error.message(), "Path does not exist: /path/to/file.txt");
+
+        drop(PathRedaction::Redact.redacting());
+        assert_eq!(error.message(), "Path does not exist: <path>");
+
+        // Another app's command returning on the same thread uses its own redaction.
+        drop(PathRedaction::None.redacting());
+        assert_eq!(error.message(), "Path does not exist: /path/to/file.txt");
+    }
+}
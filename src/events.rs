@@ -0,0 +1,21 @@
+use crate::Settings;
+use std::time::Duration;
+
+// How often progress is reported unless set otherwise.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+// The minimum time between two progress updates, `ms` from the options of the command or the one set with
+// `Builder::min_event_interval` for its app, `100` milliseconds otherwise.
+pub(crate) fn progress_interval(settings: &Settings, ms: Option<u64>) -> Duration {
+    ms.map(Duration::from_millis)
+        .or(settings.min_event_interval)
+        .unwrap_or(PROGRESS_INTERVAL)
+}
+
+// The minimum time between two events of a watch or two batches of a stream, like `progress_interval` but without a limit by
+// default.
+pub(crate) fn interval(settings: &Settings, ms: Option<u64>) -> Duration {
+    ms.map(Duration::from_millis)
+        .or(settings.min_event_interval)
+        .unwrap_or_default()
+}
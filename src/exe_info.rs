@@ -0,0 +1,148 @@
+use crate::{Error, Result};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExeInfo {
+    // The product name, `CFBundleDisplayName` or `CFBundleName` for app bundles.
+    pub product_name: Option<String>,
+    // The product version, `CFBundleShortVersionString` for app bundles.
+    pub product_version: Option<String>,
+    // The file version, `CFBundleVersion` for app bundles.
+    pub file_version: Option<String>,
+    // The company name, not available for app bundles.
+    pub company_name: Option<String>,
+    // The file description, `CFBundleGetInfoString` for app bundles.
+    pub description: Option<String>,
+    // The copyright notice, `NSHumanReadableCopyright` for app bundles.
+    pub copyright: Option<String>,
+}
+
+/// Read the version resources of a Windows executable or the `Info.plist` of a macOS app bundle.
+pub(crate) fn read_exe_info(path: &Path) -> Result<ExeInfo> {
+    if !path.exists() {
+        return Err(Error::NotFound(path.to_path_buf()));
+    }
+
+    read(path)
+}
+
+#[cfg(windows)]
+fn read(path: &Path) -> Result<ExeInfo> {
+    use std::{
+        ffi::{c_void, OsStr},
+        io,
+        os::windows::ffi::OsStrExt,
+        ptr, slice,
+    };
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW,
+    };
+
+    fn wide(value: &OsStr) -> Vec<u16> {
+        value.encode_wide().chain(Some(0)).collect()
+    }
+
+    let file_name = wide(path.as_os_str());
+    let len = unsafe { GetFileVersionInfoSizeW(file_name.as_ptr(), ptr::null_mut()) };
+
+    // Executables without version resources are not an error, there is just nothing to report.
+    if len == 0 {
+        return Ok(ExeInfo::default());
+    }
+
+    let mut data = vec![0u8; len as usize];
+
+    if unsafe { GetFileVersionInfoW(file_name.as_ptr(), 0, len, data.as_mut_ptr().cast()) } == 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+
+    let query = |sub_block: &str| -> Option<(*const c_void, u32)> {
+        let sub_block = wide(OsStr::new(sub_block));
+        let mut buffer = ptr::null_mut();
+        let mut buffer_len = 0;
+
+        let found = unsafe {
+            VerQueryValueW(
+                data.as_ptr().cast(),
+                sub_block.as_ptr(),
+                &mut buffer,
+                &mut buffer_len,
+            )
+        };
+
+        (found != 0 && !buffer.is_null() && buffer_len > 0)
+            .then_some((buffer as *const c_void, buffer_len))
+    };
+
+    // The string table of the first language and code page, US English Unicode when there is none.
+    let translation = query("\\VarFileInfo\\Translation")
+        .filter(|(_, len)| *len >= 4)
+        .map(|(buffer, _)| {
+            let pair = unsafe { slice::from_raw_parts(buffer as *const u16, 2) };
+
+            format!("{:04x}{:04x}", pair[0], pair[1])
+        })
+        .unwrap_or_else(|| "040904b0".to_string());
+
+    let string = |name: &str| {
+        query(&format!("\\StringFileInfo\\{}\\{}", translation, name))
+            .map(|(buffer, len)| {
+                // The length of string values is in UTF-16 code units.
+                let value = unsafe { slice::from_raw_parts(buffer as *const u16, len as usize) };
+
+                String::from_utf16_lossy(value)
+                    .trim_end_matches('\0')
+                    .to_string()
+            })
+            .filter(|value| !value.is_empty())
+    };
+
+    Ok(ExeInfo {
+        product_name: string("ProductName"),
+        product_version: string("ProductVersion"),
+        file_version: string("FileVersion"),
+        company_name: string("CompanyName"),
+        description: string("FileDescription"),
+        copyright: string("LegalCopyright"),
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn read(path: &Path) -> Result<ExeInfo> {
+    use std::io;
+
+    let info_path = path.join("Contents").join("Info.plist");
+
+    if !info_path.is_file() {
+        return Ok(ExeInfo::default());
+    }
+
+    let info = plist::Value::from_file(info_path)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    let Some(info) = info.as_dictionary() else {
+        return Ok(ExeInfo::default());
+    };
+
+    let string = |key: &str| {
+        info.get(key)
+            .and_then(plist::Value::as_string)
+            .map(|value| value.to_string())
+    };
+
+    Ok(ExeInfo {
+        product_name: string("CFBundleDisplayName").or_else(|| string("CFBundleName")),
+        product_version: string("CFBundleShortVersionString"),
+        file_version: string("CFBundleVersion"),
+        company_name: None,
+        description: string("CFBundleGetInfoString"),
+        copyright: string("NSHumanReadableCopyright"),
+    })
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn read(_path: &Path) -> Result<ExeInfo> {
+    Ok(ExeInfo::default())
+}
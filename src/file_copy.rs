@@ -0,0 +1,154 @@
+use crate::{buffer::BufferLimit, cancel::CancelToken, Error, Result};
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+
+// Cancellation is checked and progress reported after every chunk of this size, or of the buffer limit when it's smaller.
+const CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Copy a single file to `dst`, calling `progress` with the number of bytes copied so far after every chunk.
+///
+/// The file is copied next to the destination first and renamed once complete, so a failed or cancelled copy never leaves half a
+/// file behind and an existing destination stays untouched until then. Uses `copy_file_range` on Linux and clones the file on APFS.
+pub(crate) fn copy(
+    src: &Path,
+    dst: &Path,
+    buffer_limit: BufferLimit,
+    cancel: &CancelToken,
+    mut progress: impl FnMut(u64),
+) -> Result<()> {
+    let metadata = fs::metadata(src)?;
+
+    if !metadata.is_file() {
+        return Err(Error::InvalidPath(src.to_path_buf()));
+    }
+
+    let partial = partial_path(dst);
+    let copied = copy_to(
+        src,
+        &partial,
+        &metadata,
+        buffer_limit,
+        cancel,
+        &mut progress,
+    )
+    .and_then(|()| Ok(fs::rename(&partial, dst)?));
+
+    if copied.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+
+    copied
+}
+
+// The temporary file the copy is written to, e.g. `.movie.mkv.fs-pro-copy`.
+fn partial_path(dst: &Path) -> PathBuf {
+    let name = dst.file_name().unwrap_or_default().to_string_lossy();
+
+    dst.with_file_name(format!(".{}.fs-pro-copy", name))
+}
+
+fn copy_to(
+    src: &Path,
+    partial: &Path,
+    metadata: &fs::Metadata,
+    buffer_limit: BufferLimit,
+    cancel: &CancelToken,
+    progress: &mut impl FnMut(u64),
+) -> Result<()> {
+    if clone(src, partial) {
+        progress(metadata.len());
+        return Ok(());
+    }
+
+    let mut src_file = File::open(src)?;
+    let mut dst_file = File::create(partial)?;
+    let chunk_size = buffer_limit.size(CHUNK_SIZE);
+    let mut buffer = Vec::new();
+    let mut copied = 0;
+
+    loop {
+        cancel.check()?;
+
+        let chunk = copy_chunk(&mut src_file, &mut dst_file, &mut buffer, chunk_size)?;
+
+        if chunk == 0 {
+            break;
+        }
+
+        copied += chunk;
+        progress(copied);
+    }
+
+    dst_file.set_permissions(metadata.permissions())?;
+
+    Ok(())
+}
+
+// Copy up to `chunk_size` bytes, returns `0` at the end of the source. The standard library copies between files with
+// `copy_file_range` on Linux, within the kernel and without a buffer.
+#[cfg(target_os = "linux")]
+fn copy_chunk(
+    src: &mut File,
+    dst: &mut File,
+    _buffer: &mut Vec<u8>,
+    chunk_size: usize,
+) -> io::Result<u64> {
+    use std::io::Read;
+
+    io::copy(&mut Read::by_ref(src).take(chunk_size as u64), dst)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn copy_chunk(
+    src: &mut File,
+    dst: &mut File,
+    buffer: &mut Vec<u8>,
+    chunk_size: usize,
+) -> io::Result<u64> {
+    use std::io::{Read, Write};
+
+    // A large buffer, the default one of `io::copy` takes many more system calls for a big file.
+    buffer.resize(chunk_size, 0);
+
+    let mut filled = 0;
+
+    while filled < buffer.len() {
+        match src.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    dst.write_all(&buffer[..filled])?;
+
+    Ok(filled as u64)
+}
+
+// Clone the file on file systems with copy-on-write such as APFS, which takes no time and no space. Returns `false` when the file
+// system can't, it's copied instead then.
+#[cfg(target_os = "macos")]
+fn clone(src: &Path, dst: &Path) -> bool {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    // A partial file left behind by a crash would make cloning fail.
+    let _ = fs::remove_file(dst);
+
+    let (Ok(src), Ok(dst)) = (
+        CString::new(src.as_os_str().as_bytes()),
+        CString::new(dst.as_os_str().as_bytes()),
+    ) else {
+        return false;
+    };
+
+    unsafe { libc::clonefile(src.as_ptr(), dst.as_ptr(), 0) == 0 }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn clone(_src: &Path, _dst: &Path) -> bool {
+    false
+}
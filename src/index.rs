@@ -0,0 +1,350 @@
+use crate::{
+    commands::{raw_os_str, raw_os_string},
+    FsPro, FsProExt, RawOsString, Result,
+};
+use notify::{event::ModifyKind, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashSet},
+    fs::{self, create_dir_all, File},
+    io::{BufReader, BufWriter},
+    mem,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Mutex,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tauri::{AppHandle, Runtime};
+use walkdir::WalkDir;
+
+// The changes are applied once the directory was quiet for this long, so a large copy into it is read once.
+const DEBOUNCE: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IndexState {
+    // No index has been built or loaded yet.
+    #[default]
+    Idle,
+    // The index is being built in the background.
+    Building,
+    // The index is ready to be queried.
+    Ready,
+    // The last build failed.
+    Failed,
+}
+
+// Paths that are not valid Unicode are stored by their raw encoding so they survive a reload.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum StoredPath {
+    Str(String),
+    Raw(RawOsString),
+}
+
+impl From<&PathBuf> for StoredPath {
+    fn from(path: &PathBuf) -> Self {
+        match raw_os_str(path.as_os_str()) {
+            Some(raw) => StoredPath::Raw(raw),
+            None => StoredPath::Str(path.to_string_lossy().to_string()),
+        }
+    }
+}
+
+impl From<StoredPath> for PathBuf {
+    fn from(path: StoredPath) -> Self {
+        match path {
+            StoredPath::Str(path) => PathBuf::from(path),
+            StoredPath::Raw(raw) => PathBuf::from(raw_os_string(raw)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexFile {
+    dir: StoredPath,
+    built_at: u128,
+    entries: Vec<StoredPath>,
+    #[serde(default)]
+    inaccessible: Vec<StoredPath>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct IndexData {
+    pub dir: Option<PathBuf>,
+    pub built_at: u128,
+    pub entries: Vec<PathBuf>,
+    // Lowercased file names, aligned with `entries`, so queries don't allocate per entry.
+    pub names: Vec<String>,
+    // The paths that could not be read, with everything below them left out.
+    pub inaccessible: Vec<PathBuf>,
+    pub error: Option<String>,
+}
+
+impl IndexData {
+    fn index_file(&self) -> Option<IndexFile> {
+        Some(IndexFile {
+            dir: StoredPath::from(self.dir.as_ref()?),
+            built_at: self.built_at,
+            entries: self.entries.iter().map(StoredPath::from).collect(),
+            inaccessible: self.inaccessible.iter().map(StoredPath::from).collect(),
+        })
+    }
+}
+
+/// The filename index managed by the plugin.
+#[derive(Debug, Default)]
+pub(crate) struct Index {
+    pub(crate) state: Mutex<IndexState>,
+    pub(crate) data: Mutex<IndexData>,
+    // Keeps the index up to date while it's ready, replaced by the next build.
+    watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
+fn lowercase_names(entries: &[PathBuf]) -> Vec<String> {
+    entries
+        .iter()
+        .map(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_lowercase())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn index_file_path<R: Runtime>(fs_pro: &FsPro<R>) -> Result<PathBuf> {
+    Ok(fs_pro.data_dir()?.join("index.json"))
+}
+
+fn save<R: Runtime>(fs_pro: &FsPro<R>, index_file: &IndexFile) -> Result<()> {
+    let path = index_file_path(fs_pro)?;
+
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    // Write to a temporary file first so a crash never leaves a truncated index behind.
+    let tmp_path = path.with_extension("json.tmp");
+    let file = File::create(&tmp_path)?;
+    serde_json::to_writer(BufWriter::new(file), index_file)?;
+    fs::rename(&tmp_path, &path)?;
+
+    Ok(())
+}
+
+// Walk `path` from `min_depth`, collecting the paths that could not be read instead of the entries below them.
+fn walk(
+    path: &Path,
+    min_depth: usize,
+    entries: &mut Vec<PathBuf>,
+    inaccessible: &mut Vec<PathBuf>,
+) {
+    for entry in WalkDir::new(path).min_depth(min_depth) {
+        match entry {
+            Ok(entry) => entries.push(entry.into_path()),
+            Err(err) => inaccessible.push(err.path().unwrap_or(path).to_path_buf()),
+        }
+    }
+}
+
+/// Load the persisted index from disk, if there is one, and keep it up to date from then on.
+pub(crate) fn load<R: Runtime>(app_handle: &AppHandle<R>) -> Result<()> {
+    let fs_pro = app_handle.fs_pro();
+    let path = index_file_path(fs_pro)?;
+
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let file = File::open(path)?;
+    let index_file: IndexFile = serde_json::from_reader(BufReader::new(file))?;
+
+    let index = &fs_pro.index;
+    let mut state = index.state.lock().unwrap();
+
+    // A build started while we were reading from disk takes precedence.
+    if *state != IndexState::Idle {
+        return Ok(());
+    }
+
+    let entries: Vec<PathBuf> = index_file.entries.into_iter().map(PathBuf::from).collect();
+    let dir: PathBuf = index_file.dir.into();
+
+    let mut data = index.data.lock().unwrap();
+    data.names = lowercase_names(&entries);
+    data.dir = Some(dir.clone());
+    data.built_at = index_file.built_at;
+    data.entries = entries;
+    data.inaccessible = index_file
+        .inaccessible
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    data.error = None;
+    *state = IndexState::Ready;
+
+    drop(data);
+    drop(state);
+
+    watch(app_handle, dir)
+}
+
+/// Walk `dir`, replace the in-memory index and persist it to disk. The index is then updated with the changes to the directory.
+pub(crate) fn build<R: Runtime>(app_handle: &AppHandle<R>, dir: PathBuf) -> Result<()> {
+    let fs_pro = app_handle.fs_pro();
+    let mut entries = Vec::new();
+    let mut inaccessible = Vec::new();
+
+    // Changes made while walking are picked up by the watcher, so none are missed.
+    let watcher = watcher(app_handle, &dir)?;
+    walk(&dir, 1, &mut entries, &mut inaccessible);
+
+    let built_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+
+    let data = IndexData {
+        names: lowercase_names(&entries),
+        dir: Some(dir),
+        built_at,
+        entries,
+        inaccessible,
+        error: None,
+    };
+
+    if let Some(index_file) = data.index_file() {
+        save(fs_pro, &index_file)?;
+    }
+
+    *fs_pro.index.data.lock().unwrap() = data;
+    *fs_pro.index.watcher.lock().unwrap() = Some(watcher);
+
+    Ok(())
+}
+
+fn watch<R: Runtime>(app_handle: &AppHandle<R>, dir: PathBuf) -> Result<()> {
+    let watcher = watcher(app_handle, &dir)?;
+    *app_handle.fs_pro().index.watcher.lock().unwrap() = Some(watcher);
+
+    Ok(())
+}
+
+// Watch the indexed directory and apply the changes to the index once it was quiet for a while. The thread ends once the watcher,
+// and with it the sender, is dropped.
+fn watcher<R: Runtime>(app_handle: &AppHandle<R>, dir: &Path) -> Result<RecommendedWatcher> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(dir, RecursiveMode::Recursive)?;
+
+    let app_handle = app_handle.clone();
+    let dir = dir.to_path_buf();
+
+    thread::spawn(move || {
+        let mut pending = BTreeSet::new();
+
+        loop {
+            let event = if pending.is_empty() {
+                rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                rx.recv_timeout(DEBOUNCE)
+            };
+
+            match event {
+                // Events were dropped, so the whole directory is read again.
+                Ok(Ok(event)) if event.need_rescan() => {
+                    pending.insert(dir.clone());
+                }
+                // Only entries appearing, disappearing or renamed change the index.
+                Ok(Ok(event)) => {
+                    if matches!(
+                        event.kind,
+                        EventKind::Any
+                            | EventKind::Create(_)
+                            | EventKind::Remove(_)
+                            | EventKind::Modify(ModifyKind::Name(_) | ModifyKind::Any)
+                    ) {
+                        pending.extend(event.paths);
+                    }
+                }
+                Ok(Err(_)) => {
+                    pending.insert(dir.clone());
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let _ = refresh(&app_handle, &dir, mem::take(&mut pending));
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    Ok(watcher)
+}
+
+// Read the changed paths and everything below them again, replacing what the index held for them, and persist the index.
+fn refresh<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    dir: &Path,
+    pending: BTreeSet<PathBuf>,
+) -> Result<()> {
+    // Parents sort before their children, which are read with them.
+    let mut roots: Vec<PathBuf> = Vec::new();
+
+    for path in pending {
+        if path.starts_with(dir) && !roots.iter().any(|root| path.starts_with(root)) {
+            roots.push(path);
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut inaccessible = Vec::new();
+
+    for root in &roots {
+        // A removed path is only dropped from the index, and the indexed directory itself is not an entry.
+        if root.symlink_metadata().is_ok() {
+            walk(
+                root,
+                usize::from(root == dir),
+                &mut entries,
+                &mut inaccessible,
+            );
+        }
+    }
+
+    let fs_pro = app_handle.fs_pro();
+    let roots: HashSet<PathBuf> = roots.into_iter().collect();
+    let is_changed = |path: &Path| path.ancestors().any(|ancestor| roots.contains(ancestor));
+
+    let index_file = {
+        let mut data = fs_pro.index.data.lock().unwrap();
+
+        // The index was rebuilt for another directory in the meantime.
+        if data.dir.as_deref() != Some(dir) {
+            return Ok(());
+        }
+
+        let (mut kept, mut names): (Vec<PathBuf>, Vec<String>) = mem::take(&mut data.entries)
+            .into_iter()
+            .zip(mem::take(&mut data.names))
+            .filter(|(path, _)| !is_changed(path))
+            .unzip();
+
+        names.extend(lowercase_names(&entries));
+        kept.extend(entries);
+        data.entries = kept;
+        data.names = names;
+        data.inaccessible.retain(|path| !is_changed(path));
+        data.inaccessible.extend(inaccessible);
+
+        data.index_file()
+    };
+
+    match index_file {
+        Some(index_file) => save(fs_pro, &index_file),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,380 @@
+use crate::{Error, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LauncherLocation {
+    // The Start Menu on Windows, the app menu on Linux and `~/Applications` on macOS.
+    #[default]
+    Menu,
+    // The desktop of the user.
+    Desktop,
+}
+
+/// A launcher to create for a file or directory.
+// The icon is not used on macOS, an alias shows the icon of its target.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+pub(crate) struct Launcher<'a> {
+    // The absolute path of the file or directory it opens.
+    pub(crate) target: &'a Path,
+    // The name shown for the launcher, also the name of its file.
+    pub(crate) name: &'a str,
+    // The icon of the launcher, an `.ico` file on Windows, an image file or an icon theme name on Linux.
+    pub(crate) icon: Option<&'a Path>,
+    // The directory the launcher is created in.
+    pub(crate) dir: PathBuf,
+}
+
+/// Create a launcher for a file or directory, replacing an existing one of the same name, returns the path of the launcher.
+pub(crate) fn create(launcher: &Launcher) -> Result<PathBuf> {
+    if !is_valid_name(launcher.name) {
+        return Err(Error::InvalidPath(PathBuf::from(launcher.name)));
+    }
+
+    // Desktop entries hold one key per line, a line break in a value would add keys of its own.
+    for path in [Some(launcher.target), launcher.icon].into_iter().flatten() {
+        if path.to_string_lossy().chars().any(char::is_control) {
+            return Err(Error::InvalidPath(path.to_path_buf()));
+        }
+    }
+
+    std::fs::create_dir_all(&launcher.dir)?;
+
+    platform::create(launcher)
+}
+
+/// The directory of the app menu of the current user.
+pub(crate) fn menu_dir(data_dir: &Path, home_dir: &Path) -> PathBuf {
+    platform::menu_dir(data_dir, home_dir)
+}
+
+// The name becomes a file name, so it can't hold a path nor the characters Windows reserves.
+fn is_valid_name(name: &str) -> bool {
+    !name.trim().is_empty()
+        && !matches!(name, "." | "..")
+        && !name.chars().any(|char| {
+            char.is_control()
+                || matches!(char, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+        })
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::Launcher;
+    use crate::Result;
+    use std::{
+        ffi::c_void,
+        io, iter,
+        os::windows::ffi::OsStrExt,
+        path::{Path, PathBuf},
+        ptr,
+    };
+    use windows_sys::{
+        core::{GUID, HRESULT},
+        Win32::{
+            System::Com::{
+                CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER,
+                COINIT_APARTMENTTHREADED,
+            },
+            UI::Shell::ShellLink,
+        },
+    };
+
+    const IID_ISHELLLINKW: GUID = GUID::from_u128(0x000214f9_0000_0000_c000_000000000046);
+    const IID_IPERSISTFILE: GUID = GUID::from_u128(0x0000010b_0000_0000_c000_000000000046);
+
+    // windows-sys has no COM interfaces, so the vtables are laid out by hand up to the methods used.
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct IUnknownVtbl {
+        query_interface:
+            unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+        add_ref: unsafe extern "system" fn(*mut c_void) -> u32,
+        release: unsafe extern "system" fn(*mut c_void) -> u32,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct IShellLinkWVtbl {
+        unknown: IUnknownVtbl,
+        get_path: usize,
+        get_id_list: usize,
+        set_id_list: usize,
+        get_description: usize,
+        set_description: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+        get_working_directory: usize,
+        set_working_directory: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+        get_arguments: usize,
+        set_arguments: usize,
+        get_hotkey: usize,
+        set_hotkey: usize,
+        get_show_cmd: usize,
+        set_show_cmd: usize,
+        get_icon_location: usize,
+        set_icon_location: unsafe extern "system" fn(*mut c_void, *const u16, i32) -> HRESULT,
+        set_relative_path: usize,
+        resolve: usize,
+        set_path: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct IPersistFileVtbl {
+        unknown: IUnknownVtbl,
+        get_class_id: usize,
+        is_dirty: usize,
+        load: usize,
+        save: unsafe extern "system" fn(*mut c_void, *const u16, i32) -> HRESULT,
+    }
+
+    // An interface pointer of a COM object, released when dropped.
+    struct Interface(*mut c_void);
+
+    impl Interface {
+        unsafe fn vtbl<T>(&self) -> &T {
+            &**(self.0 as *const *const T)
+        }
+    }
+
+    impl Drop for Interface {
+        fn drop(&mut self) {
+            unsafe { (self.vtbl::<IUnknownVtbl>().release)(self.0) };
+        }
+    }
+
+    pub(super) fn create(launcher: &Launcher) -> Result<PathBuf> {
+        let path = launcher.dir.join(format!("{}.lnk", launcher.name));
+
+        // A thread already initialized for another threading model can use the object all the same.
+        let initialized =
+            unsafe { CoInitializeEx(ptr::null(), COINIT_APARTMENTTHREADED as u32) } >= 0;
+        let saved = save(launcher, &path);
+
+        if initialized {
+            unsafe { CoUninitialize() };
+        }
+
+        saved?;
+
+        Ok(path)
+    }
+
+    // The programs of the Start Menu of the current user, `%APPDATA%\Microsoft\Windows\Start Menu\Programs`.
+    pub(super) fn menu_dir(data_dir: &Path, _home_dir: &Path) -> PathBuf {
+        data_dir
+            .join("Microsoft")
+            .join("Windows")
+            .join("Start Menu")
+            .join("Programs")
+    }
+
+    fn save(launcher: &Launcher, path: &Path) -> io::Result<()> {
+        let mut link = ptr::null_mut();
+
+        check(unsafe {
+            CoCreateInstance(
+                &ShellLink,
+                ptr::null_mut(),
+                CLSCTX_INPROC_SERVER,
+                &IID_ISHELLLINKW,
+                &mut link,
+            )
+        })?;
+
+        let link = Interface(link);
+        let shell_link = unsafe { link.vtbl::<IShellLinkWVtbl>() };
+
+        check(unsafe { (shell_link.set_path)(link.0, wide(launcher.target).as_ptr()) })?;
+        check(unsafe { (shell_link.set_description)(link.0, wide(launcher.name).as_ptr()) })?;
+
+        if let Some(dir) = launcher.target.parent() {
+            check(unsafe { (shell_link.set_working_directory)(link.0, wide(dir).as_ptr()) })?;
+        }
+
+        if let Some(icon) = launcher.icon {
+            check(unsafe { (shell_link.set_icon_location)(link.0, wide(icon).as_ptr(), 0) })?;
+        }
+
+        let mut file = ptr::null_mut();
+
+        check(unsafe {
+            (shell_link.unknown.query_interface)(link.0, &IID_IPERSISTFILE, &mut file)
+        })?;
+
+        let file = Interface(file);
+
+        check(unsafe { (file.vtbl::<IPersistFileVtbl>().save)(file.0, wide(path).as_ptr(), 1) })
+    }
+
+    fn wide(value: impl AsRef<std::ffi::OsStr>) -> Vec<u16> {
+        value.as_ref().encode_wide().chain(iter::once(0)).collect()
+    }
+
+    fn check(result: HRESULT) -> io::Result<()> {
+        match result {
+            0.. => Ok(()),
+            code => Err(io::Error::from_raw_os_error(code)),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::Launcher;
+    use crate::Result;
+    use std::{
+        ffi::c_void,
+        io,
+        os::unix::ffi::OsStrExt,
+        path::{Path, PathBuf},
+        ptr,
+    };
+
+    type CFTypeRef = *const c_void;
+
+    const K_CF_URL_BOOKMARK_CREATION_SUITABLE_FOR_BOOKMARK_FILE: usize = 1 << 10;
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFURLCreateFromFileSystemRepresentation(
+            alloc: CFTypeRef,
+            buffer: *const u8,
+            buffer_len: isize,
+            is_directory: u8,
+        ) -> CFTypeRef;
+        fn CFURLCreateBookmarkData(
+            alloc: CFTypeRef,
+            url: CFTypeRef,
+            options: usize,
+            resource_properties_to_include: CFTypeRef,
+            relative_to_url: CFTypeRef,
+            error: *mut CFTypeRef,
+        ) -> CFTypeRef;
+        fn CFURLWriteBookmarkDataToFile(
+            bookmark: CFTypeRef,
+            file_url: CFTypeRef,
+            options: usize,
+            error: *mut CFTypeRef,
+        ) -> u8;
+        fn CFRelease(cf: CFTypeRef);
+    }
+
+    // An owned Core Foundation object, released when dropped.
+    struct CFObject(CFTypeRef);
+
+    impl CFObject {
+        fn url(path: &Path, is_dir: bool) -> io::Result<Self> {
+            let bytes = path.as_os_str().as_bytes();
+            let url = unsafe {
+                CFURLCreateFromFileSystemRepresentation(
+                    ptr::null(),
+                    bytes.as_ptr(),
+                    bytes.len() as isize,
+                    is_dir as u8,
+                )
+            };
+
+            match url.is_null() {
+                true => Err(io::Error::other(format!(
+                    "Failed to create a URL for {}",
+                    path.display()
+                ))),
+                false => Ok(Self(url)),
+            }
+        }
+    }
+
+    impl Drop for CFObject {
+        fn drop(&mut self) {
+            if !self.0.is_null() {
+                unsafe { CFRelease(self.0) };
+            }
+        }
+    }
+
+    // An alias file, the same Finder creates with Make Alias, which keeps working when the target is moved or renamed.
+    pub(super) fn create(launcher: &Launcher) -> Result<PathBuf> {
+        let path = launcher.dir.join(launcher.name);
+        let target = CFObject::url(launcher.target, launcher.target.is_dir())?;
+        let alias = CFObject::url(&path, false)?;
+
+        let bookmark = CFObject(unsafe {
+            CFURLCreateBookmarkData(
+                ptr::null(),
+                target.0,
+                K_CF_URL_BOOKMARK_CREATION_SUITABLE_FOR_BOOKMARK_FILE,
+                ptr::null(),
+                ptr::null(),
+                ptr::null_mut(),
+            )
+        });
+
+        if bookmark.0.is_null() {
+            return Err(io::Error::other(format!(
+                "Failed to create an alias of {}",
+                launcher.target.display()
+            ))
+            .into());
+        }
+
+        let written =
+            unsafe { CFURLWriteBookmarkDataToFile(bookmark.0, alias.0, 0, ptr::null_mut()) };
+
+        match written {
+            0 => Err(
+                io::Error::other(format!("Failed to write the alias {}", path.display())).into(),
+            ),
+            _ => Ok(path),
+        }
+    }
+
+    pub(super) fn menu_dir(_data_dir: &Path, home_dir: &Path) -> PathBuf {
+        home_dir.join("Applications")
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod platform {
+    use super::Launcher;
+    use crate::{association::quote_exec, Result};
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    // A desktop entry opening the target with its default app. Desktops only start entries on the desktop that are executable.
+    pub(super) fn create(launcher: &Launcher) -> Result<PathBuf> {
+        let path = launcher.dir.join(format!("{}.desktop", launcher.name));
+        // Names of the freedesktop icon naming specification, themes provide them.
+        let icon = match launcher.icon {
+            Some(icon) => icon.to_string_lossy().replace('\\', "\\\\"),
+            None if launcher.target.is_dir() => "folder".to_string(),
+            None => "text-x-generic".to_string(),
+        };
+
+        fs::write(
+            &path,
+            format!(
+                "[Desktop Entry]\nType=Application\nName={}\nExec=xdg-open {}\nIcon={}\n",
+                launcher.name,
+                quote_exec(launcher.target),
+                icon,
+            ),
+        )?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+        }
+
+        Ok(path)
+    }
+
+    // `~/.local/share/applications`, where the app menus pick up the entries of the user.
+    pub(super) fn menu_dir(data_dir: &Path, _home_dir: &Path) -> PathBuf {
+        data_dir.join("applications")
+    }
+}
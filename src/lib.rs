@@ -1,29 +1,259 @@
+use buffer::BufferLimit;
+use std::{collections::HashMap, thread, time::Duration};
 use tauri::{
-    plugin::{Builder, TauriPlugin},
-    Runtime,
+    plugin::{Builder as PluginBuilder, TauriPlugin},
+    Manager, RunEvent, Runtime,
 };
+use tracing::level_filters::LevelFilter;
 
+mod archive;
+mod association;
+mod auto_sync;
+mod benchmark;
+mod bidirectional;
+mod buffer;
+mod cancel;
+mod cas;
 mod commands;
+mod desktop;
+mod diagnose;
+mod error;
+mod events;
+mod exe_info;
+mod file_copy;
+mod index;
+mod launcher;
+mod link;
+mod mount;
+mod notification;
+mod operations;
+mod progress;
+mod retry;
+mod shortcut;
+mod sparse;
+mod strict;
+mod token;
+mod trace;
+mod unlink;
+mod volume;
+mod watch;
+mod workspace;
+// Only Linux and Windows write XML files.
+#[cfg(not(target_os = "macos"))]
+mod xml;
 
+pub use archive::{
+    ArchiveEntry, ArchiveFormat, ArchiveManifest, ArchiveManifestEntry, DecompressReport,
+    NameEncoding, OverwritePolicy, RenamedEntry,
+};
+pub use auto_sync::{AutoSyncError, AutoSyncEvent, AutoSyncEventKind};
+pub use benchmark::{BenchmarkMode, BenchmarkReport};
+pub use bidirectional::{ConflictStrategy, SyncConflict, SyncReport};
+pub use cas::{StoreMode, StoredObject};
 pub use commands::*;
+pub use desktop::{FsPro, IconOverride};
+pub use diagnose::{Diagnosis, DiagnosisCheck, DiagnosisProblem, PathAccess, PathScope};
+pub use error::{Error, PathRedaction, Result};
+pub use exe_info::ExeInfo;
+pub use index::IndexState;
+pub use launcher::LauncherLocation;
+pub use mount::{DiskUsage, Volume, VolumeEvent, VolumeEventKind};
+pub use notification::NotificationError;
+pub use operations::{
+    CommitReport, HistoryEntry, Operation, Plan, PlanIssue, PlanIssueKind, UndoReport,
+};
+pub use sparse::{Extent, ExtentKind};
+pub use watch::{WatchFileEvent, WatchFileEventKind};
+pub use workspace::Workspace;
+
+/// Extensions to [`tauri::App`], [`tauri::AppHandle`] and [`tauri::Window`] to access the fs-pro APIs.
+pub trait FsProExt<R: Runtime> {
+    fn fs_pro(&self) -> &FsPro<R>;
+}
+
+impl<R: Runtime, T: Manager<R>> FsProExt<R> for T {
+    fn fs_pro(&self) -> &FsPro<R> {
+        self.state::<FsPro<R>>().inner()
+    }
+}
+
+// The settings of a `Builder`, kept in the state of its app so each app uses its own.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Settings {
+    pub(crate) path_redaction: PathRedaction,
+    pub(crate) buffer_limit: BufferLimit,
+    pub(crate) require_absolute_paths: bool,
+    pub(crate) min_event_interval: Option<Duration>,
+    pub(crate) tracing_level: Option<LevelFilter>,
+}
+
+/// Configures the fs-pro plugin.
+#[derive(Debug, Default)]
+pub struct Builder {
+    settings: Settings,
+    icon_overrides: HashMap<String, IconOverride>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Redact or hash the paths in the error messages sent to the frontend, defaults to [`PathRedaction::None`].
+    pub fn path_redaction(mut self, path_redaction: PathRedaction) -> Self {
+        self.settings.path_redaction = path_redaction;
+        self
+    }
+
+    /// Use a custom icon for an extension name, e.g. `myproj`, in the `icon` commands instead of the system one.
+    pub fn icon_override(mut self, extname: impl Into<String>, icon: IconOverride) -> Self {
+        let extname = extname.into().trim_start_matches('.').to_lowercase();
+
+        self.icon_overrides.insert(extname, icon);
+        self
+    }
+
+    /// The level of the `tracing` spans and events of the plugin, defaults to `DEBUG`, `OFF` turns them off.
+    ///
+    /// Every command is recorded with its paths and operation id, long-running ones such as `size`, `compress` or `transfer` with a
+    /// span that also records their duration and the bytes involved. All of them use the target `tauri_plugin_fs_pro`, paths are
+    /// redacted like with [`Builder::path_redaction`].
+    pub fn tracing_level(mut self, level: LevelFilter) -> Self {
+        self.settings.tracing_level = Some(level);
+        self
+    }
+
+    /// The largest buffer the plugin holds at once, in bytes, no limit by default. Files are copied, compressed, hashed and
+    /// benchmarked in chunks of at most this size, and the batches of `read_dir_stream` are sent before they grow past it.
+    ///
+    /// Chunks are `1 MiB` by default, `8 MiB` to copy a file, a smaller limit takes more system calls for the same work. Limits
+    /// below `4 KiB` are raised to it. The working memory of the compressors is set by the compression level rather than this.
+    pub fn max_buffer_bytes(mut self, bytes: usize) -> Self {
+        self.settings.buffer_limit = BufferLimit::new(bytes);
+        self
+    }
+
+    /// Reject the commands called from the frontend with a relative path, with an error of kind `relativePath`, defaults to
+    /// `false`. Relative paths resolve against the working directory of the process, which is rarely the directory meant.
+    ///
+    /// The path arguments of each command are checked before it runs, including those in its options such as
+    /// `options.ignore.path`, while `file://` URIs count as absolute. Values that are not always paths, such as the icon of
+    /// `create_launcher`, are left to the command.
+    pub fn require_absolute_paths(mut self, require: bool) -> Self {
+        self.settings.require_absolute_paths = require;
+        self
+    }
+
+    /// The minimum time between two events sent to the frontend, so a flood of them doesn't freeze the UI on slower machines.
+    ///
+    /// It's the default of `onProgressIntervalMs` of the commands reporting progress, `100` milliseconds otherwise, and of
+    /// `minIntervalMs` of `watch_file` and `read_dir_stream`, which send every event as it comes otherwise. Options given to a
+    /// command take precedence.
+    pub fn min_event_interval(mut self, interval: Duration) -> Self {
+        self.settings.min_event_interval = Some(interval);
+        self
+    }
+
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
+        PluginBuilder::new("fs-pro")
+            .invoke_handler(trace::handler(strict::handler(tauri::generate_handler![
+                commands::is_exist,
+                commands::is_dir,
+                commands::is_file,
+                commands::size,
+                commands::name,
+                commands::full_name,
+                commands::extname,
+                commands::parent_name,
+                commands::get_default_save_icon_path,
+                commands::icon,
+                commands::metadata,
+                commands::compress,
+                commands::decompress,
+                commands::transfer,
+                commands::backup,
+                commands::index_build,
+                commands::index_query,
+                commands::index_status,
+                commands::stat_many,
+                commands::parse_path,
+                commands::size_report,
+                commands::icon_for_extension,
+                commands::watch_file,
+                commands::unwatch_file,
+                commands::recent_changes,
+                commands::largest,
+                commands::type_stats,
+                commands::compress_manifest,
+                #[cfg(feature = "icon-asset")]
+                commands::icon_asset,
+                commands::clone_metadata,
+                commands::organize,
+                commands::import_files,
+                commands::read_dir_stream,
+                commands::exe_info,
+                commands::resolve_dropped,
+                commands::remove,
+                commands::copy,
+                commands::plan_operations,
+                commands::commit_operations,
+                commands::cancel_operation,
+                commands::undo,
+                commands::undo_history,
+                commands::auto_sync_start,
+                commands::auto_sync_stop,
+                commands::bidirectional_sync,
+                commands::archive_entries,
+                commands::volumes,
+                commands::benchmark,
+                commands::copy_file,
+                commands::disk_usage,
+                commands::is_writable,
+                commands::compress_many,
+                commands::workspace_create,
+                commands::workspace_dispose,
+                commands::convert_archive,
+                commands::store_cas,
+                commands::retrieve_cas,
+                commands::register_file_association,
+                commands::unregister_file_association,
+                commands::create_launcher,
+                commands::punch_hole,
+                commands::extents,
+                commands::change_token,
+                commands::has_changed,
+                commands::gzip,
+                commands::gunzip,
+                commands::cwd,
+                commands::resolve_app_path,
+                commands::diagnose,
+                commands::watch_volumes,
+                commands::unwatch_volumes
+            ])))
+            .setup(move |app_handle, _api| {
+                app_handle.manage(FsPro::new(
+                    app_handle.clone(),
+                    self.icon_overrides,
+                    self.settings,
+                ));
+
+                let app_handle = app_handle.clone();
+                thread::spawn(move || {
+                    app_handle.fs_pro().workspace_recover();
+                    let _ = index::load(&app_handle);
+                });
+
+                Ok(())
+            })
+            .on_event(|app_handle, event| {
+                if let RunEvent::Exit = event {
+                    app_handle.fs_pro().workspaces.dispose_all();
+                }
+            })
+            .build()
+    }
+}
 
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    Builder::new("fs-pro")
-        .invoke_handler(tauri::generate_handler![
-            commands::is_exist,
-            commands::is_dir,
-            commands::is_file,
-            commands::size,
-            commands::name,
-            commands::full_name,
-            commands::extname,
-            commands::parent_name,
-            commands::get_default_save_icon_path,
-            commands::icon,
-            commands::metadata,
-            commands::compress,
-            commands::decompress,
-            commands::transfer
-        ])
-        .build()
+    Builder::new().build()
 }
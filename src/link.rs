@@ -0,0 +1,49 @@
+use std::{fs, io, path::Path};
+
+/// Recreate the symbolic link at `src` as `dst`, with the same target as it is stored, so a relative target stays relative to the
+/// link.
+pub(crate) fn copy(src: &Path, dst: &Path) -> io::Result<()> {
+    symlink(src, &fs::read_link(src)?, dst)
+}
+
+#[cfg(unix)]
+fn symlink(_src: &Path, target: &Path, dst: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, dst)
+}
+
+// Windows tells links to directories apart from links to files, so the link is created as the same kind.
+#[cfg(windows)]
+fn symlink(src: &Path, target: &Path, dst: &Path) -> io::Result<()> {
+    use std::os::windows::fs::FileTypeExt;
+
+    match fs::symlink_metadata(src)?.file_type().is_symlink_dir() {
+        true => std::os::windows::fs::symlink_dir(target, dst),
+        false => std::os::windows::fs::symlink_file(target, dst),
+    }
+}
+
+/// Remove the symbolic link at `path`, leaving what it points to untouched.
+pub(crate) fn remove(path: &Path) -> io::Result<()> {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::FileTypeExt;
+
+        if fs::symlink_metadata(path)?.file_type().is_symlink_dir() {
+            return fs::remove_dir(path);
+        }
+    }
+
+    fs::remove_file(path)
+}
+
+/// Whether the link at `path` can be followed, which a broken link or a link to a directory containing it can't.
+pub(crate) fn is_followable(path: &Path) -> bool {
+    let Ok(target) = dunce::canonicalize(path) else {
+        return false;
+    };
+
+    !path
+        .parent()
+        .and_then(|parent| dunce::canonicalize(parent).ok())
+        .is_some_and(|parent| parent.starts_with(&target))
+}
@@ -0,0 +1,603 @@
+use crate::FsProExt;
+use serde::Serialize;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::Duration,
+};
+use tauri::{AppHandle, Emitter, Runtime};
+
+// Only read on Linux, the only platform the mount table is parsed on.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub(crate) struct MountPoint {
+    pub device: String,
+    pub path: PathBuf,
+    pub fs_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Volume {
+    // The path the volume is mounted at, e.g. `/Volumes/USB` or `E:\`.
+    #[serde(flatten, serialize_with = "crate::commands::serialize_path_entry")]
+    pub path: PathBuf,
+    // The label of the volume, or the name of its mount point when it has none.
+    pub name: String,
+    // The file system of the volume, e.g. `apfs`, `ext4` or `NTFS`.
+    pub fs_type: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    // The size of the volume in bytes.
+    pub total: u64,
+    // The free space in bytes available to the current user.
+    pub available: u64,
+    // The number of inodes of the volume, `None` when the file system has no fixed number, such as btrfs, FAT or NTFS.
+    pub total_inodes: Option<u64>,
+    // The number of free inodes available to the current user, each file or directory takes one.
+    pub available_inodes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VolumeEventKind {
+    Mounted,
+    Unmounted,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeEvent {
+    pub kind: VolumeEventKind,
+    pub volume: Volume,
+}
+
+impl MountPoint {
+    /// Whether the mount is a user-space device mount (gvfs, MTP) that only handles plain, sequential copies well.
+    pub fn is_device(&self) -> bool {
+        let fs_type = self.fs_type.as_str();
+
+        (fs_type == "fuse" || fs_type.starts_with("fuse."))
+            && (fs_type.contains("gvfs") || fs_type.contains("mtp"))
+    }
+}
+
+// `/proc/mounts` escapes spaces, tabs, newlines and backslashes as octal sequences.
+#[cfg(target_os = "linux")]
+fn unescape(field: &str) -> PathBuf {
+    use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+    let mut result = Vec::with_capacity(field.len());
+    let bytes = field.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() {
+            let octal = std::str::from_utf8(&bytes[i + 1..i + 4]).unwrap_or_default();
+
+            if let Ok(value) = u8::from_str_radix(octal, 8) {
+                result.push(value);
+                i += 4;
+                continue;
+            }
+        }
+
+        result.push(bytes[i]);
+        i += 1;
+    }
+
+    PathBuf::from(OsString::from_vec(result))
+}
+
+/// All mount points of the system.
+#[cfg(target_os = "linux")]
+pub(crate) fn mount_points() -> Vec<MountPoint> {
+    let Ok(mounts) = std::fs::read_to_string("/proc/self/mounts") else {
+        return vec![];
+    };
+
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let path = fields.next()?;
+            let fs_type = fields.next()?;
+
+            Some(MountPoint {
+                device: unescape(device).to_string_lossy().to_string(),
+                path: unescape(path),
+                fs_type: fs_type.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// All mount points of the system.
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn mount_points() -> Vec<MountPoint> {
+    vec![]
+}
+
+// The name of a mount point without a label, `/` for the root.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn mount_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// The volumes a user would browse, the mounts of pseudo file systems such as `proc` are left out.
+#[cfg(target_os = "linux")]
+pub(crate) fn volumes() -> Vec<Volume> {
+    const NETWORK_FS_TYPES: &[&str] = &["nfs", "nfs4", "cifs", "smb3", "fuse.sshfs"];
+
+    let mut volumes: Vec<Volume> = Vec::new();
+
+    for mount in mount_points() {
+        // Snap packages are mounted from loop devices.
+        let is_disk = mount.device.starts_with("/dev/") && !mount.device.starts_with("/dev/loop");
+
+        if !is_disk && !NETWORK_FS_TYPES.contains(&mount.fs_type.as_str()) {
+            continue;
+        }
+
+        // A later mount on the same path hides the earlier one.
+        volumes.retain(|volume| volume.path != mount.path);
+        volumes.push(Volume {
+            name: mount_name(&mount.path),
+            path: mount.path,
+            fs_type: mount.fs_type,
+        });
+    }
+
+    volumes
+}
+
+/// The volumes a user would browse, the ones Finder hides such as the system volumes are left out.
+#[cfg(target_os = "macos")]
+pub(crate) fn volumes() -> Vec<Volume> {
+    use std::{ffi::CStr, ffi::OsStr, os::unix::ffi::OsStrExt, ptr};
+
+    let mut mounts: *mut libc::statfs = ptr::null_mut();
+    // The buffer is owned by libc and reused by the next call.
+    let count = unsafe { libc::getmntinfo(&mut mounts, libc::MNT_NOWAIT) };
+
+    if count <= 0 {
+        return vec![];
+    }
+
+    let mounts = unsafe { std::slice::from_raw_parts(mounts, count as usize) };
+
+    mounts
+        .iter()
+        .filter(|mount| mount.f_flags & libc::MNT_DONTBROWSE as u32 == 0)
+        .map(|mount| {
+            let path = unsafe { CStr::from_ptr(mount.f_mntonname.as_ptr()) };
+            let path = PathBuf::from(OsStr::from_bytes(path.to_bytes()));
+            let fs_type = unsafe { CStr::from_ptr(mount.f_fstypename.as_ptr()) };
+
+            Volume {
+                name: mount_name(&path),
+                path,
+                fs_type: fs_type.to_string_lossy().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// The volumes a user would browse, one per drive letter.
+#[cfg(windows)]
+pub(crate) fn volumes() -> Vec<Volume> {
+    use std::{ffi::OsStr, os::windows::ffi::OsStrExt, ptr};
+    use windows_sys::Win32::Storage::FileSystem::{GetLogicalDrives, GetVolumeInformationW};
+
+    fn from_wide(buffer: &[u16]) -> String {
+        let len = buffer.iter().position(|c| *c == 0).unwrap_or(buffer.len());
+
+        String::from_utf16_lossy(&buffer[..len])
+    }
+
+    let drives = unsafe { GetLogicalDrives() };
+
+    (0..26u8)
+        .filter(|letter| drives & (1 << letter) != 0)
+        .filter_map(|letter| {
+            let path = format!("{}:\\", (b'A' + letter) as char);
+            let root: Vec<u16> = OsStr::new(&path).encode_wide().chain(Some(0)).collect();
+            let mut label = [0u16; 261];
+            let mut fs_type = [0u16; 261];
+
+            // Drives without a medium, such as an empty card reader, are left out.
+            if unsafe {
+                GetVolumeInformationW(
+                    root.as_ptr(),
+                    label.as_mut_ptr(),
+                    label.len() as u32,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    fs_type.as_mut_ptr(),
+                    fs_type.len() as u32,
+                )
+            } == 0
+            {
+                return None;
+            }
+
+            let label = from_wide(&label);
+
+            Some(Volume {
+                name: if label.is_empty() {
+                    path[..2].to_string()
+                } else {
+                    label
+                },
+                path: PathBuf::from(path),
+                fs_type: from_wide(&fs_type),
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub(crate) fn volumes() -> Vec<Volume> {
+    vec![]
+}
+
+/// The volume containing an existing path.
+pub(crate) fn volume_of(path: &Path) -> Option<Volume> {
+    let path = dunce::canonicalize(path).ok()?;
+
+    volumes()
+        .into_iter()
+        .filter(|volume| path.starts_with(&volume.path))
+        .max_by_key(|volume| volume.path.components().count())
+}
+
+/// Whether a volume is still mounted at `path`.
+pub(crate) fn is_volume_mounted(path: &Path) -> bool {
+    volumes().iter().any(|volume| volume.path == path)
+}
+
+#[derive(Debug, Default)]
+struct MonitorState {
+    listeners: usize,
+    running: bool,
+}
+
+/// Emits `fs-pro://volume` events as volumes are mounted and unmounted, for as long as something listens to them or a file is
+/// watched.
+///
+/// The file watchers on an unmounted volume are lost, and resumed once it's mounted again.
+#[derive(Debug, Default)]
+pub(crate) struct VolumeMonitor {
+    state: Mutex<MonitorState>,
+}
+
+impl VolumeMonitor {
+    /// Start emitting events for a new listener, until it's removed with [`VolumeMonitor::unlisten`].
+    pub(crate) fn listen<R: Runtime>(&self, app_handle: &AppHandle<R>) {
+        self.state.lock().unwrap().listeners += 1;
+        self.ensure_running(app_handle);
+    }
+
+    pub(crate) fn unlisten(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.listeners = state.listeners.saturating_sub(1);
+    }
+
+    /// Start the monitor if it isn't running, it stops by itself once there are no listeners and no watched files left.
+    pub(crate) fn ensure_running<R: Runtime>(&self, app_handle: &AppHandle<R>) {
+        let mut state = self.state.lock().unwrap();
+
+        if state.running {
+            return;
+        }
+
+        state.running = true;
+
+        let app_handle = app_handle.clone();
+        thread::spawn(move || monitor(app_handle));
+    }
+}
+
+fn monitor<R: Runtime>(app_handle: AppHandle<R>) {
+    let mounts = MountChanges::open();
+    let mut known = volumes();
+
+    loop {
+        let changed = mounts.wait();
+
+        {
+            let fs_pro = app_handle.fs_pro();
+            let mut state = fs_pro.volume_monitor.state.lock().unwrap();
+
+            // Checked under the lock, so a file watched right after still finds the monitor running or starts a new one.
+            if state.listeners == 0 && !fs_pro.watchers.has_files() {
+                state.running = false;
+                return;
+            }
+        }
+
+        if !changed {
+            continue;
+        }
+
+        let current = volumes();
+
+        // A volume remounted with another file system counts as unmounted and mounted again.
+        let unmounted = known
+            .iter()
+            .filter(|volume| !current.contains(volume))
+            .map(|volume| (VolumeEventKind::Unmounted, volume));
+        let mounted = current
+            .iter()
+            .filter(|volume| !known.contains(volume))
+            .map(|volume| (VolumeEventKind::Mounted, volume));
+
+        for (kind, volume) in unmounted.chain(mounted) {
+            let event = VolumeEvent {
+                kind,
+                volume: volume.clone(),
+            };
+
+            let _ = app_handle.emit("fs-pro://volume", event.clone());
+            app_handle
+                .fs_pro()
+                .watchers
+                .volume_changed(&app_handle, &event);
+        }
+
+        known = current;
+    }
+}
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+// Linux flags the mount table of a process as changed when anything is mounted or unmounted, so the volumes are only listed
+// again then. Elsewhere they are listed every second.
+struct MountChanges {
+    #[cfg(target_os = "linux")]
+    mounts: Option<std::fs::File>,
+}
+
+impl MountChanges {
+    fn open() -> Self {
+        Self {
+            #[cfg(target_os = "linux")]
+            mounts: std::fs::File::open("/proc/self/mounts").ok(),
+        }
+    }
+
+    // Wait up to a second, returns whether the volumes may have changed meanwhile.
+    #[cfg(target_os = "linux")]
+    fn wait(&self) -> bool {
+        use std::os::fd::AsRawFd;
+
+        let Some(mounts) = &self.mounts else {
+            thread::sleep(CHECK_INTERVAL);
+            return true;
+        };
+
+        let mut fd = libc::pollfd {
+            fd: mounts.as_raw_fd(),
+            events: libc::POLLPRI,
+            revents: 0,
+        };
+
+        match unsafe { libc::poll(&mut fd, 1, CHECK_INTERVAL.as_millis() as libc::c_int) } {
+            // Polling clears the flag again.
+            0 => false,
+            n if n > 0 => true,
+            _ => {
+                thread::sleep(CHECK_INTERVAL);
+                true
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn wait(&self) -> bool {
+        thread::sleep(CHECK_INTERVAL);
+        true
+    }
+}
+
+/// The mount point containing `path`, resolved through its nearest existing ancestor.
+pub(crate) fn mount_point_of(path: &Path) -> Option<MountPoint> {
+    let path = path
+        .ancestors()
+        .find_map(|ancestor| ancestor.canonicalize().ok())?;
+
+    mount_points()
+        .into_iter()
+        .filter(|mount| path.starts_with(&mount.path))
+        .max_by_key(|mount| mount.path.components().count())
+}
+
+/// Whether a mount point is still mounted.
+pub(crate) fn is_mounted(path: &Path) -> bool {
+    mount_points().iter().any(|mount| mount.path == path)
+}
+
+/// Whether an I/O error means the device behind a FUSE mount went away (`ENOTCONN`).
+pub(crate) fn is_disconnected(err: &io::Error) -> bool {
+    cfg!(target_os = "linux") && err.raw_os_error() == Some(107)
+}
+
+/// An identifier of the volume an existing path is on.
+#[cfg(unix)]
+pub(crate) fn volume_id(path: &Path) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    std::fs::metadata(path)
+        .ok()
+        .map(|metadata| metadata.dev().to_string())
+}
+
+#[cfg(windows)]
+pub(crate) fn volume_id(path: &Path) -> Option<String> {
+    use std::path::Component;
+
+    match path.components().next() {
+        Some(Component::Prefix(prefix)) => {
+            Some(prefix.as_os_str().to_string_lossy().to_lowercase())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn volume_id(_path: &Path) -> Option<String> {
+    None
+}
+
+/// The size and free space of the volume of an existing path.
+#[cfg(unix)]
+pub(crate) fn disk_usage(path: &Path) -> io::Result<DiskUsage> {
+    use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt};
+
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+
+    if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let stat = unsafe { stat.assume_init() };
+
+    // The field types differ between platforms.
+    #[allow(clippy::unnecessary_cast)]
+    let (total_inodes, available_inodes) = (stat.f_files as u64, stat.f_favail as u64);
+
+    // File systems without a fixed number of inodes, such as btrfs or FAT, report none.
+    let has_inodes = total_inodes > 0;
+
+    #[allow(clippy::unnecessary_cast)]
+    Ok(DiskUsage {
+        total: stat.f_blocks as u64 * stat.f_frsize as u64,
+        available: stat.f_bavail as u64 * stat.f_frsize as u64,
+        total_inodes: has_inodes.then_some(total_inodes),
+        available_inodes: has_inodes.then_some(available_inodes),
+    })
+}
+
+#[cfg(windows)]
+pub(crate) fn disk_usage(path: &Path) -> io::Result<DiskUsage> {
+    use std::{os::windows::ffi::OsStrExt, ptr};
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available = 0;
+    let mut total = 0;
+
+    if unsafe { GetDiskFreeSpaceExW(path.as_ptr(), &mut available, &mut total, ptr::null_mut()) }
+        == 0
+    {
+        return Err(io::Error::last_os_error());
+    }
+
+    // NTFS has no fixed number of file records.
+    Ok(DiskUsage {
+        total,
+        available,
+        total_inodes: None,
+        available_inodes: None,
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn disk_usage(_path: &Path) -> io::Result<DiskUsage> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+/// Whether `path` can be written to: an existing file opened for writing, or new files created in an existing directory. A path
+/// that doesn't exist yet is checked through its nearest existing ancestor, which it would be created in.
+///
+/// Read-only mounts are detected from the mount flags, otherwise a hidden probe file is created and removed again, which also
+/// catches missing permissions and media that are write-protected without the mount saying so.
+pub(crate) fn is_writable(path: &Path) -> io::Result<bool> {
+    let Some(existing) = path.ancestors().find(|ancestor| ancestor.exists()) else {
+        return Err(io::ErrorKind::NotFound.into());
+    };
+
+    if is_read_only_mount(existing) {
+        return Ok(false);
+    }
+
+    let probed = if existing.is_dir() {
+        probe_dir(existing)
+    } else {
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(existing)
+            .map(drop)
+    };
+
+    match probed {
+        Ok(()) => Ok(true),
+        Err(err) if is_write_denied(&err) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+// Create and remove a probe file that doesn't collide with another check, e.g. `.fs-pro-probe-1700000000000000000`.
+fn probe_dir(dir: &Path) -> io::Result<()> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    let probe = dir.join(format!(".fs-pro-probe-{}", nanos));
+
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)?;
+
+    std::fs::remove_file(&probe)
+}
+
+// Whether the error means the path can't be written rather than that the check itself failed.
+fn is_write_denied(err: &io::Error) -> bool {
+    // `EROFS` on Unix and `ERROR_WRITE_PROTECT` on Windows, for write-protected media.
+    const READ_ONLY: i32 = if cfg!(windows) { 19 } else { 30 };
+
+    err.kind() == io::ErrorKind::PermissionDenied || err.raw_os_error() == Some(READ_ONLY)
+}
+
+#[cfg(unix)]
+fn is_read_only_mount(path: &Path) -> bool {
+    use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt};
+
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+
+    if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return false;
+    }
+
+    let stat = unsafe { stat.assume_init() };
+
+    // The field types differ between platforms.
+    #[allow(clippy::unnecessary_cast)]
+    let read_only = stat.f_flag as u64 & libc::ST_RDONLY as u64 != 0;
+
+    read_only
+}
+
+#[cfg(not(unix))]
+fn is_read_only_mount(_path: &Path) -> bool {
+    false
+}
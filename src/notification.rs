@@ -0,0 +1,232 @@
+use crate::{commands::NotificationOptions, Error, FsProExt, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::{collections::HashMap, io, path::Path, process::Command, thread};
+use tauri::{AppHandle, Emitter, Runtime};
+
+// The texts shown when the options leave them out.
+const DEFAULT_TITLE: &str = "{name}";
+const DEFAULT_BODY: &str = "Completed";
+const DEFAULT_FAILURE_BODY: &str = "{error}";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationError {
+    // The title of the notification that could not be shown.
+    pub title: String,
+    // Why it could not be shown, such as `notify-send` missing.
+    pub message: String,
+}
+
+/// Show the notification asked for with `options` once a command from `src_path` to `dst_path` finished with `result`. Cancelled
+/// operations are not notified, they were stopped by the app itself.
+///
+/// The texts are templates: `{name}`, `{srcPath}` and `{dstPath}` are replaced with the name of the source and the paths, `{error}`
+/// with the message of a failure, and the name of any field of the report the command returns with its value, arrays with their
+/// length, e.g. `{entriesExtracted}` or `{inaccessible}`. Unknown placeholders are left as they are.
+///
+/// The notification is shown on another thread so the result of the command stays the same, a failure to show it is emitted as the
+/// `fs-pro://notification-error` event instead.
+pub(crate) fn finished<R: Runtime, T: Serialize>(
+    app_handle: &AppHandle<R>,
+    options: Option<&NotificationOptions>,
+    src_path: &Path,
+    dst_path: &Path,
+    result: &Result<T>,
+) {
+    let Some(options) = options else {
+        return;
+    };
+    let mut values = HashMap::from([
+        (
+            "name".to_string(),
+            src_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        ),
+        ("srcPath".to_string(), src_path.display().to_string()),
+        ("dstPath".to_string(), dst_path.display().to_string()),
+    ]);
+    let title = options.title.as_deref().unwrap_or(DEFAULT_TITLE);
+
+    let (title, body) = match result {
+        Ok(report) => {
+            if let Ok(Value::Object(fields)) = serde_json::to_value(report) {
+                for (key, value) in fields {
+                    let value = match value {
+                        Value::String(value) => value,
+                        Value::Array(items) => items.len().to_string(),
+                        Value::Number(number) => number.to_string(),
+                        Value::Bool(flag) => flag.to_string(),
+                        Value::Null | Value::Object(_) => continue,
+                    };
+
+                    values.insert(key, value);
+                }
+            }
+
+            (title, options.body.as_deref().unwrap_or(DEFAULT_BODY))
+        }
+        Err(Error::Cancelled) => return,
+        Err(err) => {
+            let redaction = app_handle.fs_pro().settings.path_redaction;
+
+            values.insert("error".to_string(), err.redacted_message(redaction));
+
+            (
+                options.failure_title.as_deref().unwrap_or(title),
+                options
+                    .failure_body
+                    .as_deref()
+                    .unwrap_or(DEFAULT_FAILURE_BODY),
+            )
+        }
+    };
+
+    let title = render(title, &values);
+    let body = render(body, &values);
+    let app_name = app_handle.package_info().name.clone();
+    let identifier = app_handle.config().identifier.clone();
+    let app_handle = app_handle.clone();
+
+    thread::spawn(move || {
+        if let Err(err) = platform::show(&app_name, &identifier, &title, &body) {
+            let error = NotificationError {
+                title,
+                message: err.to_string(),
+            };
+            let _ = app_handle.emit("fs-pro://notification-error", error);
+        }
+    });
+}
+
+// Replace the `{key}` placeholders of a template in a single pass, so values that look like placeholders are kept as they are.
+fn render(template: &str, values: &HashMap<String, String>) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+
+        let after = &rest[start + 1..];
+        let value = after
+            .find('}')
+            .and_then(|end| Some((end, values.get(&after[..end])?)));
+
+        match value {
+            Some((end, value)) => {
+                text.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+
+    text.push_str(rest);
+    text
+}
+
+fn run(command: &mut Command) -> io::Result<()> {
+    // Name the program, which is most likely missing when it can't be started.
+    let output = command.output().map_err(|err| {
+        let program = command.get_program().to_string_lossy().into_owned();
+
+        io::Error::new(err.kind(), format!("{}: {}", program, err))
+    })?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    Err(io::Error::other(
+        String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    ))
+}
+
+#[cfg(windows)]
+mod platform {
+    use crate::xml::escape as escape_xml;
+    use std::{io, os::windows::process::CommandExt, process::Command};
+    use windows_sys::Win32::System::Threading::CREATE_NO_WINDOW;
+
+    // Toasts are shown on behalf of the app user model id, which installers of Tauri apps set to the bundle identifier.
+    const SCRIPT: &str = "\
+        [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+        [Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime] > $null; \
+        $xml = New-Object Windows.Data.Xml.Dom.XmlDocument; \
+        $xml.LoadXml($env:FS_PRO_TOAST); \
+        $toast = New-Object Windows.UI.Notifications.ToastNotification $xml; \
+        [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier($env:FS_PRO_APP_ID).Show($toast)";
+
+    pub(super) fn show(
+        _app_name: &str,
+        identifier: &str,
+        title: &str,
+        body: &str,
+    ) -> io::Result<()> {
+        let toast = format!(
+            "<toast><visual><binding template=\"ToastGeneric\"><text>{}</text><text>{}</text></binding></visual></toast>",
+            escape_xml(title),
+            escape_xml(body)
+        );
+
+        // The texts are passed in the environment rather than quoted into the script.
+        super::run(
+            Command::new("powershell")
+                .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+                .env("FS_PRO_TOAST", toast)
+                .env("FS_PRO_APP_ID", identifier)
+                .creation_flags(CREATE_NO_WINDOW),
+        )
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::{io, process::Command};
+
+    pub(super) fn show(
+        _app_name: &str,
+        _identifier: &str,
+        title: &str,
+        body: &str,
+    ) -> io::Result<()> {
+        // The texts are passed as arguments of the script rather than quoted into it.
+        super::run(Command::new("osascript").args([
+            "-e",
+            "on run argv",
+            "-e",
+            "display notification (item 2 of argv) with title (item 1 of argv)",
+            "-e",
+            "end run",
+            title,
+            body,
+        ]))
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod platform {
+    use std::{io, process::Command};
+
+    // Through the desktop notification service of freedesktop.org, which `notify-send` of libnotify talks to.
+    pub(super) fn show(
+        app_name: &str,
+        _identifier: &str,
+        title: &str,
+        body: &str,
+    ) -> io::Result<()> {
+        super::run(
+            Command::new("notify-send")
+                .arg("--app-name")
+                .arg(app_name)
+                .arg("--")
+                .arg(title)
+                .arg(body),
+        )
+    }
+}
@@ -0,0 +1,793 @@
+use crate::{mount, retry::RetryPolicy, unlink, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Component, Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum Operation {
+    // Copy a file or directory, `dst` is the full path of the copy.
+    Copy { src: PathBuf, dst: PathBuf },
+    // Move a file or directory, `dst` is the full path of the new location.
+    Move { src: PathBuf, dst: PathBuf },
+    // Give a file or directory a new name in the same directory.
+    Rename { path: PathBuf, name: String },
+    // Delete a file or directory.
+    Delete { path: PathBuf },
+    // Create a directory, its parent must exist.
+    Mkdir { path: PathBuf },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PlanIssueKind {
+    // The path does not exist at this point of the plan.
+    NotFound,
+    // The destination already exists at this point of the plan.
+    AlreadyExists,
+    // The parent directory of the destination does not exist, or is not a directory, at this point of the plan.
+    ParentNotFound,
+    // The path is not absolute, contains `..` or is a root.
+    InvalidPath,
+    // A directory would be copied or moved into itself.
+    InsideSource,
+    // The volume of the destination runs out of space at this operation.
+    InsufficientSpace,
+    // The volume of the destination runs out of inodes at this operation, only on file systems with a fixed number such as ext4.
+    InsufficientInodes,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanIssue {
+    // The index of the operation.
+    pub index: usize,
+    pub kind: PlanIssueKind,
+    // The path the issue is about.
+    #[serde(flatten, serialize_with = "crate::commands::serialize_path_entry")]
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Plan {
+    // The problems found, the operations can only be committed when there are none.
+    pub issues: Vec<PlanIssue>,
+    // The number of bytes the copies and the moves across volumes write.
+    pub required_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitReport {
+    // Whether every operation was applied.
+    pub committed: bool,
+    // The index of the operation that failed.
+    pub failed_at: Option<usize>,
+    // The error of the operation that failed.
+    pub error: Option<Error>,
+    // The paths that could not be restored when rolling back the applied operations.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub not_rolled_back: Vec<PathBuf>,
+    // Where the deleted paths that could not be moved to the trash are kept instead, to remove or restore.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub not_trashed: Vec<PathBuf>,
+    // The id to undo the operations with, `None` when they were not committed.
+    pub operation_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    // The id to undo the operations with.
+    pub operation_id: String,
+    pub operations: Vec<Operation>,
+    // When the operations were committed, in milliseconds since the Unix epoch.
+    pub committed_at: u128,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoReport {
+    pub operation_id: String,
+    // Whether every operation was reverted.
+    pub undone: bool,
+    // The paths that could not be restored, e.g. because another file took their place in the meantime.
+    #[serde(serialize_with = "crate::commands::serialize_path_entries")]
+    pub not_undone: Vec<PathBuf>,
+}
+
+// The state of a path after some of the operations, as far as it differs from the disk.
+#[derive(Debug, Clone)]
+enum Simulated {
+    Missing,
+    Created,
+    // The contents are those of a path on disk, moved or copied here.
+    Alias(PathBuf),
+}
+
+#[derive(Default)]
+struct Simulation {
+    paths: HashMap<PathBuf, Simulated>,
+}
+
+fn join(base: &Path, rest: &Path) -> PathBuf {
+    if rest.as_os_str().is_empty() {
+        base.to_path_buf()
+    } else {
+        base.join(rest)
+    }
+}
+
+impl Simulation {
+    // The closest simulated ancestor of the path, including the path itself, and the rest of the path below it.
+    fn lookup<'a>(&self, path: &'a Path) -> Option<(&Simulated, &'a Path)> {
+        path.ancestors().find_map(|ancestor| {
+            let state = self.paths.get(ancestor)?;
+            let rest = path.strip_prefix(ancestor).unwrap_or(Path::new(""));
+
+            Some((state, rest))
+        })
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        match self.lookup(path) {
+            Some((Simulated::Missing, _)) => false,
+            Some((Simulated::Created, rest)) => rest.as_os_str().is_empty(),
+            Some((Simulated::Alias(real), rest)) => join(real, rest).exists(),
+            None => path.exists(),
+        }
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        match self.lookup(path) {
+            Some((Simulated::Missing, _)) => false,
+            // Only directories are created.
+            Some((Simulated::Created, rest)) => rest.as_os_str().is_empty(),
+            Some((Simulated::Alias(real), rest)) => join(real, rest).is_dir(),
+            None => path.is_dir(),
+        }
+    }
+
+    // Where the contents of the path are on disk, `None` for directories created by the plan.
+    fn real(&self, path: &Path) -> Option<PathBuf> {
+        match self.lookup(path) {
+            Some((Simulated::Alias(real), rest)) => Some(join(real, rest)),
+            Some(_) => None,
+            None => Some(path.to_path_buf()),
+        }
+    }
+
+    fn set(&mut self, path: &Path, state: Simulated) {
+        self.paths
+            .retain(|simulated, _| !simulated.starts_with(path));
+        self.paths.insert(path.to_path_buf(), state);
+    }
+
+    fn copy(&mut self, src: &Path, dst: &Path) {
+        let state = match self.real(src) {
+            Some(real) => Simulated::Alias(real),
+            None => Simulated::Created,
+        };
+
+        // Carry over what the plan already changed below the source.
+        let below: Vec<_> = self
+            .paths
+            .iter()
+            .filter(|(path, _)| path.starts_with(src) && path.as_path() != src)
+            .map(|(path, state)| (join(dst, path.strip_prefix(src).unwrap()), state.clone()))
+            .collect();
+
+        self.set(dst, state);
+        self.paths.extend(below);
+    }
+}
+
+fn is_valid_path(path: &Path) -> bool {
+    path.is_absolute()
+        && path.parent().is_some()
+        && !path
+            .components()
+            .any(|component| matches!(component, Component::ParentDir | Component::CurDir))
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+
+    matches!(components.next(), Some(Component::Normal(normal)) if normal == name)
+        && components.next().is_none()
+}
+
+// The volume of the destination and its closest existing ancestor, with the bytes and the number of files and directories written
+// to it, `None` for moves within a volume.
+fn volume_bytes(src: &Path, dst: &Path, is_copy: bool) -> Option<(String, PathBuf, u64, u64)> {
+    let existing = dst.ancestors().find(|ancestor| ancestor.exists())?;
+    let volume = mount::volume_id(existing)?;
+
+    if !is_copy {
+        let src_existing = src.ancestors().find(|ancestor| ancestor.exists())?;
+
+        if mount::volume_id(src_existing).as_ref() == Some(&volume) {
+            return None;
+        }
+    }
+
+    // The files and directories written, each one takes an inode.
+    let (mut bytes, mut entries) = (0, 0);
+
+    for entry in WalkDir::new(src).into_iter().filter_map(|entry| entry.ok()) {
+        entries += 1;
+
+        if !entry.file_type().is_dir() {
+            bytes += entry.metadata().map_or(0, |metadata| metadata.len());
+        }
+    }
+
+    Some((volume, existing.to_path_buf(), bytes, entries))
+}
+
+/// Check a list of operations against the disk and against each other, without changing anything.
+pub(crate) fn plan(operations: &[Operation]) -> Plan {
+    let mut simulation = Simulation::default();
+    let mut issues = Vec::new();
+    let mut required_bytes = 0;
+    // The bytes and the inodes still free on every volume written to, `None` when unknown or already reported.
+    let mut available: HashMap<String, (Option<u64>, Option<u64>)> = HashMap::new();
+
+    for (index, operation) in operations.iter().enumerate() {
+        let mut issue = |kind: PlanIssueKind, path: &Path| {
+            issues.push(PlanIssue {
+                index,
+                kind,
+                path: path.to_path_buf(),
+            })
+        };
+
+        let (src, dst, is_copy) = match operation {
+            Operation::Copy { src, dst } => (src.clone(), dst.clone(), true),
+            Operation::Move { src, dst } => (src.clone(), dst.clone(), false),
+            Operation::Rename { path, name } => {
+                let dst = path.with_file_name(name);
+
+                if !is_valid_name(name) {
+                    issue(PlanIssueKind::InvalidPath, &dst);
+                    continue;
+                }
+
+                (path.clone(), dst, false)
+            }
+            Operation::Delete { path } => {
+                if !is_valid_path(path) {
+                    issue(PlanIssueKind::InvalidPath, path);
+                } else if !simulation.exists(path) {
+                    issue(PlanIssueKind::NotFound, path);
+                } else {
+                    simulation.set(path, Simulated::Missing);
+                }
+
+                continue;
+            }
+            Operation::Mkdir { path } => {
+                if !is_valid_path(path) {
+                    issue(PlanIssueKind::InvalidPath, path);
+                } else if simulation.exists(path) {
+                    issue(PlanIssueKind::AlreadyExists, path);
+                } else if !path
+                    .parent()
+                    .is_some_and(|parent| simulation.is_dir(parent))
+                {
+                    issue(PlanIssueKind::ParentNotFound, path);
+                } else {
+                    simulation.set(path, Simulated::Created);
+                }
+
+                continue;
+            }
+        };
+
+        if let Some(invalid) = [&src, &dst].into_iter().find(|path| !is_valid_path(path)) {
+            issue(PlanIssueKind::InvalidPath, invalid);
+            continue;
+        }
+
+        if !simulation.exists(&src) {
+            issue(PlanIssueKind::NotFound, &src);
+            continue;
+        }
+
+        if simulation.exists(&dst) {
+            issue(PlanIssueKind::AlreadyExists, &dst);
+            continue;
+        }
+
+        if !dst.parent().is_some_and(|parent| simulation.is_dir(parent)) {
+            issue(PlanIssueKind::ParentNotFound, &dst);
+            continue;
+        }
+
+        if dst.starts_with(&src) {
+            issue(PlanIssueKind::InsideSource, &dst);
+            continue;
+        }
+
+        let bytes = simulation
+            .real(&src)
+            .and_then(|real| volume_bytes(&real, &dst, is_copy));
+
+        if let Some((volume, existing, bytes, entries)) = bytes {
+            let (free, free_inodes) =
+                available
+                    .entry(volume)
+                    .or_insert_with(|| match mount::disk_usage(&existing) {
+                        Ok(usage) => (Some(usage.available), usage.available_inodes),
+                        Err(_) => (None, None),
+                    });
+
+            match *free {
+                Some(left) if left < bytes => {
+                    issue(PlanIssueKind::InsufficientSpace, &dst);
+                    // Report every volume only once.
+                    *free = None;
+                }
+                Some(left) => *free = Some(left - bytes),
+                None => {}
+            }
+
+            match *free_inodes {
+                Some(left) if left < entries => {
+                    issue(PlanIssueKind::InsufficientInodes, &dst);
+                    *free_inodes = None;
+                }
+                Some(left) => *free_inodes = Some(left - entries),
+                None => {}
+            }
+
+            required_bytes += bytes;
+        }
+
+        simulation.copy(&src, &dst);
+
+        if !is_copy {
+            simulation.set(&src, Simulated::Missing);
+        }
+    }
+
+    Plan {
+        issues,
+        required_bytes,
+    }
+}
+
+// How to revert an applied operation.
+enum Undo {
+    Remove(PathBuf),
+    // Only removed when it is still empty, a directory may have been filled since it was created.
+    RemoveDir(PathBuf),
+    MoveBack { from: PathBuf, to: PathBuf },
+    // A move that may have stopped part way, what reached the destination is merged back into the source.
+    MergeBack { from: PathBuf, to: PathBuf },
+    // A deleted path is kept next to where it was until every operation succeeded, and after when there is no trash.
+    Restore { staged: PathBuf, path: PathBuf },
+    // Then it's moved to the trash under its staged name.
+    RestoreFromTrash { staged: PathBuf, path: PathBuf },
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default()
+}
+
+fn copy_path(src: &Path, dst: &Path) -> io::Result<()> {
+    for entry in WalkDir::new(src) {
+        let entry = entry?;
+        let target = join(dst, entry.path().strip_prefix(src).unwrap_or(Path::new("")));
+
+        if entry.file_type().is_dir() {
+            fs::create_dir(&target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Rename, or copy and delete the source when the destination is on another volume.
+fn move_path(src: &Path, dst: &Path) -> io::Result<()> {
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+
+    if let Err(err) = copy_path(src, dst) {
+        let _ = unlink::remove_all(dst, &RetryPolicy::default());
+        return Err(err);
+    }
+
+    unlink::remove_all(src, &RetryPolicy::default()).map(|_| ())
+}
+
+fn apply(operation: &Operation, undo: &mut Vec<Undo>) -> io::Result<()> {
+    match operation {
+        Operation::Copy { src, dst } => {
+            // Registered first, so a partial copy is cleaned up as well.
+            undo.push(Undo::Remove(dst.clone()));
+            copy_path(src, dst)
+        }
+        Operation::Move { src, dst } => {
+            move_path(src, dst)?;
+            undo.push(Undo::MoveBack {
+                from: dst.clone(),
+                to: src.clone(),
+            });
+            Ok(())
+        }
+        Operation::Rename { path, name } => {
+            let dst = path.with_file_name(name);
+
+            fs::rename(path, &dst)?;
+            undo.push(Undo::MoveBack {
+                from: dst,
+                to: path.clone(),
+            });
+            Ok(())
+        }
+        Operation::Delete { path } => {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let staged = path.with_file_name(format!(".{}.fs-pro-deleted-{}", name, now_millis()));
+
+            fs::rename(path, &staged)?;
+            undo.push(Undo::Restore {
+                staged,
+                path: path.clone(),
+            });
+            Ok(())
+        }
+        Operation::Mkdir { path } => {
+            fs::create_dir(path)?;
+            undo.push(Undo::RemoveDir(path.clone()));
+            Ok(())
+        }
+    }
+}
+
+// Move a path the system trash kept back to where it was deleted.
+#[cfg(any(
+    target_os = "windows",
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+))]
+fn restore_from_trash(path: &Path) -> io::Result<()> {
+    let item = trash::os_limited::list()
+        .map_err(io::Error::other)?
+        .into_iter()
+        .find(|item| item.original_path() == path)
+        .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+
+    trash::os_limited::restore_all([item]).map_err(io::Error::other)
+}
+
+// The trash can't be listed on macOS, but items of the home volume keep their unique staged name in `~/.Trash`.
+#[cfg(target_os = "macos")]
+fn restore_from_trash(path: &Path) -> io::Result<()> {
+    let home = std::env::var_os("HOME").ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+    let name = path.file_name().unwrap_or_default();
+
+    move_path(&Path::new(&home).join(".Trash").join(name), path)
+}
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+fn restore_from_trash(_path: &Path) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+// Move back to a path, unless another file took its place in the meantime.
+fn move_back(from: &Path, to: &Path) -> io::Result<()> {
+    if to.symlink_metadata().is_ok() {
+        return Err(io::ErrorKind::AlreadyExists.into());
+    }
+
+    move_path(from, to)
+}
+
+// Merge back what a move left at `from` into the source at `to`. A file found at both is the original left in place, so the copy
+// at `from` is dropped.
+fn merge_back(from: &Path, to: &Path) -> io::Result<()> {
+    let Ok(from_metadata) = from.symlink_metadata() else {
+        // Nothing reached the destination.
+        return Ok(());
+    };
+    let Ok(to_metadata) = to.symlink_metadata() else {
+        return move_path(from, to);
+    };
+
+    match (from_metadata.is_dir(), to_metadata.is_dir()) {
+        (true, true) => {
+            for entry in fs::read_dir(from)? {
+                let entry = entry?;
+                merge_back(&entry.path(), &to.join(entry.file_name()))?;
+            }
+
+            fs::remove_dir(from)
+        }
+        (false, false) => unlink::remove_all(from, &RetryPolicy::default()).map(|_| ()),
+        _ => Err(io::ErrorKind::AlreadyExists.into()),
+    }
+}
+
+fn revert(step: Undo) -> (io::Result<()>, PathBuf) {
+    match step {
+        Undo::Remove(path) => {
+            let result = unlink::remove_all(&path, &RetryPolicy::default()).map(|_| ());
+            (result, path)
+        }
+        Undo::RemoveDir(path) => (fs::remove_dir(&path), path),
+        Undo::MoveBack { from, to } => (move_back(&from, &to), to),
+        Undo::MergeBack { from, to } => (merge_back(&from, &to), to),
+        Undo::Restore { staged, path } => (move_back(&staged, &path), path),
+        Undo::RestoreFromTrash { staged, path } => {
+            let result = restore_from_trash(&staged).and_then(|_| move_back(&staged, &path));
+            (result, path)
+        }
+    }
+}
+
+// Revert the applied operations, last first, returns the paths that could not be restored.
+fn rollback(undo: Vec<Undo>) -> Vec<PathBuf> {
+    let mut not_rolled_back = Vec::new();
+
+    for step in undo.into_iter().rev() {
+        let is_removal = matches!(step, Undo::Remove(_) | Undo::RemoveDir(_));
+
+        match revert(step) {
+            (Ok(()), _) => {}
+            // Nothing left to remove.
+            (Err(err), _) if is_removal && err.kind() == io::ErrorKind::NotFound => {}
+            (Err(_), path) => not_rolled_back.push(path),
+        }
+    }
+
+    not_rolled_back
+}
+
+// A unique name next to a path that is about to be overwritten.
+fn overwritten_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    path.with_file_name(format!(".{}.fs-pro-overwritten-{}", name, now_millis()))
+}
+
+/// The steps to revert a transfer while it runs, so a failure can leave the source and the destination as they were.
+#[derive(Default)]
+pub(crate) struct Journal {
+    undo: Vec<Undo>,
+}
+
+impl Journal {
+    /// Create a directory and its missing parents, each one is removed on rollback while it's still empty.
+    pub(crate) fn create_dir_all(&mut self, path: &Path) -> io::Result<()> {
+        let missing: Vec<&Path> = path
+            .ancestors()
+            .take_while(|ancestor| ancestor.symlink_metadata().is_err())
+            .collect();
+
+        for dir in missing.into_iter().rev() {
+            match fs::create_dir(dir) {
+                Ok(()) => self.undo.push(Undo::RemoveDir(dir.to_path_buf())),
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move an existing path aside before it's overwritten, it's put back on rollback and removed by [`Journal::finish`].
+    pub(crate) fn stage(&mut self, path: &Path) -> io::Result<()> {
+        let staged = overwritten_path(path);
+
+        fs::rename(path, &staged)?;
+        self.undo.push(Undo::Restore {
+            staged,
+            path: path.to_path_buf(),
+        });
+        Ok(())
+    }
+
+    /// Move an existing path to the trash before it's overwritten, under its staged name so it's found there again on rollback.
+    pub(crate) fn trash(&mut self, path: &Path) -> io::Result<()> {
+        let staged = overwritten_path(path);
+
+        fs::rename(path, &staged)?;
+
+        if let Err(err) = trash::delete(&staged) {
+            let _ = fs::rename(&staged, path);
+            return Err(io::Error::other(err));
+        }
+
+        self.undo.push(Undo::RestoreFromTrash {
+            staged,
+            path: path.to_path_buf(),
+        });
+        Ok(())
+    }
+
+    /// Register a copy before it's made, so a partial copy is removed as well.
+    pub(crate) fn copying(&mut self, dst: &Path) {
+        self.undo.push(Undo::Remove(dst.to_path_buf()));
+    }
+
+    /// Register a move before it's made, so what reached the destination of a partial move is put back as well.
+    pub(crate) fn moving(&mut self, src: &Path, dst: &Path) {
+        self.undo.push(Undo::MergeBack {
+            from: dst.to_path_buf(),
+            to: src.to_path_buf(),
+        });
+    }
+
+    /// Keep the steps of another journal, reverted before these.
+    pub(crate) fn append(&mut self, other: Journal) {
+        self.undo.extend(other.undo);
+    }
+
+    /// Revert the steps, last first, returns the paths that could not be restored.
+    pub(crate) fn rollback(self) -> Vec<PathBuf> {
+        rollback(self.undo)
+    }
+
+    /// Remove the paths moved aside once the transfer succeeded.
+    pub(crate) fn finish(self) {
+        for step in self.undo {
+            if let Undo::Restore { staged, .. } = step {
+                let _ = unlink::remove_all(&staged, &RetryPolicy::default());
+            }
+        }
+    }
+}
+
+// How many committed operation lists are kept to be undone.
+const HISTORY_LIMIT: usize = 50;
+
+/// The committed operations that can be undone, oldest first.
+#[derive(Default)]
+pub(crate) struct History {
+    next_id: AtomicU64,
+    entries: Mutex<Vec<(HistoryEntry, Vec<Undo>)>>,
+}
+
+impl History {
+    fn push(
+        &self,
+        operation_id: Option<String>,
+        operations: &[Operation],
+        undo: Vec<Undo>,
+    ) -> String {
+        let operation_id = operation_id
+            .unwrap_or_else(|| self.next_id.fetch_add(1, Ordering::Relaxed).to_string());
+        let mut entries = self.entries.lock().unwrap();
+
+        entries.retain(|(entry, _)| entry.operation_id != operation_id);
+        entries.push((
+            HistoryEntry {
+                operation_id: operation_id.clone(),
+                operations: operations.to_vec(),
+                committed_at: now_millis(),
+            },
+            undo,
+        ));
+
+        if entries.len() > HISTORY_LIMIT {
+            entries.remove(0);
+        }
+
+        operation_id
+    }
+
+    /// The entries that can be undone, most recent first.
+    pub(crate) fn entries(&self) -> Vec<HistoryEntry> {
+        let entries = self.entries.lock().unwrap();
+
+        entries
+            .iter()
+            .rev()
+            .map(|(entry, _)| entry.clone())
+            .collect()
+    }
+
+    /// Revert the operations committed with the id, or the most recent ones, and remove them from the history.
+    pub(crate) fn undo(&self, operation_id: Option<&str>) -> Result<UndoReport> {
+        let (entry, undo) = {
+            let mut entries = self.entries.lock().unwrap();
+            let position = match operation_id {
+                Some(id) => entries
+                    .iter()
+                    .position(|(entry, _)| entry.operation_id == id),
+                None => entries.len().checked_sub(1),
+            };
+
+            match position {
+                Some(position) => entries.remove(position),
+                None => return Err(Error::NothingToUndo),
+            }
+        };
+
+        let not_undone = rollback(undo);
+
+        Ok(UndoReport {
+            operation_id: entry.operation_id,
+            undone: not_undone.is_empty(),
+            not_undone,
+        })
+    }
+}
+
+/// Check the operations with [`plan`], then apply them in order, reverting the applied ones when one fails.
+/// Committed operations are added to the history under the id, to be reverted with [`History::undo`].
+pub(crate) fn commit(
+    operations: &[Operation],
+    operation_id: Option<String>,
+    history: &History,
+) -> Result<CommitReport> {
+    let plan = plan(operations);
+
+    if !plan.issues.is_empty() {
+        return Err(Error::InvalidPlan(plan.issues.len()));
+    }
+
+    let mut undo = Vec::new();
+
+    for (index, operation) in operations.iter().enumerate() {
+        if let Err(err) = apply(operation, &mut undo) {
+            return Ok(CommitReport {
+                committed: false,
+                failed_at: Some(index),
+                error: Some(err.into()),
+                not_rolled_back: rollback(undo),
+                not_trashed: vec![],
+                operation_id: None,
+            });
+        }
+    }
+
+    let mut not_trashed = Vec::new();
+
+    // Deleted paths only go to the trash once every operation succeeded. Without a trash they stay staged, still undoable.
+    let undo = undo
+        .into_iter()
+        .map(|step| match step {
+            Undo::Restore { staged, path } => match trash::delete(&staged) {
+                Ok(()) => Undo::RestoreFromTrash { staged, path },
+                Err(_) => {
+                    not_trashed.push(staged.clone());
+                    Undo::Restore { staged, path }
+                }
+            },
+            step => step,
+        })
+        .collect();
+
+    let operation_id = history.push(operation_id, operations, undo);
+
+    Ok(CommitReport {
+        committed: true,
+        failed_at: None,
+        error: None,
+        not_rolled_back: vec![],
+        not_trashed,
+        operation_id: Some(operation_id),
+    })
+}
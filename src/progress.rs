@@ -0,0 +1,55 @@
+use std::time::Instant;
+
+// How quickly the throughput follows changes, in seconds. Longer is steadier but slower to catch up with a new speed.
+const SMOOTHING_SECS: f64 = 3.0;
+
+/// Estimates the throughput and the time remaining of an operation from the bytes done so far.
+///
+/// The throughput is an exponential moving average weighted by the time between samples, so a burst of small files or a stall
+/// doesn't make the estimate jump, however often it's sampled.
+pub(crate) struct RateEstimator {
+    last: (Instant, u64),
+    bytes_per_second: Option<f64>,
+}
+
+impl RateEstimator {
+    pub(crate) fn new() -> Self {
+        Self {
+            last: (Instant::now(), 0),
+            bytes_per_second: None,
+        }
+    }
+
+    /// Record the bytes done so far out of `total_bytes`, returns the smoothed bytes per second and the estimated milliseconds
+    /// remaining, which is `None` as long as nothing is moving.
+    pub(crate) fn update(&mut self, done_bytes: u64, total_bytes: u64) -> (u64, Option<u64>) {
+        let now = Instant::now();
+        let (last_at, last_bytes) = self.last;
+        let elapsed = now.duration_since(last_at).as_secs_f64();
+
+        // Samples closer together than the clock can tell apart are added to the next one.
+        if elapsed > 0.0 && done_bytes >= last_bytes {
+            let rate = (done_bytes - last_bytes) as f64 / elapsed;
+            let weight = 1.0 - (-elapsed / SMOOTHING_SECS).exp();
+
+            self.bytes_per_second = Some(match self.bytes_per_second {
+                Some(smoothed) => smoothed + weight * (rate - smoothed),
+                None => rate,
+            });
+            self.last = (now, done_bytes);
+        }
+
+        let bytes_per_second = self.bytes_per_second.unwrap_or_default();
+        let remaining_bytes = total_bytes.saturating_sub(done_bytes);
+
+        let remaining_ms = if remaining_bytes == 0 {
+            Some(0)
+        } else if bytes_per_second > 0.0 {
+            Some((remaining_bytes as f64 / bytes_per_second * 1000.0) as u64)
+        } else {
+            None
+        };
+
+        (bytes_per_second as u64, remaining_ms)
+    }
+}
@@ -0,0 +1,63 @@
+use std::{io, thread, time::Duration};
+
+/// How operations on files that are briefly locked by other processes are retried.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetryPolicy {
+    attempts: u32,
+    delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            delay: Duration::from_millis(50),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub(crate) fn new(attempts: Option<u32>, delay: Option<u64>) -> Self {
+        let default = Self::default();
+
+        Self {
+            attempts: attempts.unwrap_or(default.attempts),
+            delay: delay.map(Duration::from_millis).unwrap_or(default.delay),
+        }
+    }
+
+    /// Run `op` until it succeeds or fails with an error `is_locked` rejects, waiting twice as long after every failed attempt.
+    /// Returns the result and whether `op` had to be retried.
+    pub(crate) fn run<T, E>(
+        &self,
+        is_locked: impl Fn(&E) -> bool,
+        mut op: impl FnMut() -> Result<T, E>,
+    ) -> (Result<T, E>, bool) {
+        let mut attempt = 0;
+
+        loop {
+            match op() {
+                Err(err) if attempt < self.attempts && is_locked(&err) => {
+                    thread::sleep(self.delay * (1 << attempt.min(6)));
+                    attempt += 1;
+                }
+                result => return (result, attempt > 0),
+            }
+        }
+    }
+}
+
+/// Whether the error is Windows refusing access to a file that is open elsewhere, e.g. by an antivirus scanner or the indexer.
+pub(crate) fn is_locked(err: &io::Error) -> bool {
+    // ERROR_ACCESS_DENIED, ERROR_SHARING_VIOLATION, ERROR_LOCK_VIOLATION
+    cfg!(windows) && matches!(err.raw_os_error(), Some(5 | 32 | 33))
+}
+
+/// Like [`is_locked`], `fs_extra` keeps no OS error code for access denied errors.
+pub(crate) fn is_fs_extra_locked(err: &fs_extra::error::Error) -> bool {
+    match &err.kind {
+        fs_extra::error::ErrorKind::PermissionDenied => cfg!(windows),
+        fs_extra::error::ErrorKind::Io(err) => is_locked(err),
+        _ => false,
+    }
+}
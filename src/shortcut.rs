@@ -0,0 +1,145 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+// Shell link flags, see [MS-SHLLINK] 2.1.1.
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x1;
+const HAS_LINK_INFO: u32 = 0x2;
+const HAS_NAME: u32 = 0x4;
+const HAS_RELATIVE_PATH: u32 = 0x8;
+const IS_UNICODE: u32 = 0x80;
+
+// Link info flags, see [MS-SHLLINK] 2.3.
+const VOLUME_ID_AND_LOCAL_BASE_PATH: u32 = 0x1;
+const COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX: u32 = 0x2;
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+// A NUL-terminated string in the system code page, decoded as UTF-8 with replacements.
+fn ansi_string_at(data: &[u8], offset: usize) -> Option<String> {
+    let bytes = data.get(offset..)?;
+    let len = bytes.iter().position(|byte| *byte == 0)?;
+
+    Some(String::from_utf8_lossy(&bytes[..len]).to_string())
+}
+
+// A NUL-terminated UTF-16 string.
+fn wide_string_at(data: &[u8], offset: usize) -> Option<String> {
+    let units: Vec<u16> = data
+        .get(offset..)?
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|unit| *unit != 0)
+        .collect();
+
+    Some(String::from_utf16_lossy(&units))
+}
+
+// The size in bytes of a counted string in the string data section, including its count.
+fn string_data_len(data: &[u8], offset: usize, is_unicode: bool) -> Option<usize> {
+    let count = u16_at(data, offset)? as usize;
+
+    Some(2 + if is_unicode { count * 2 } else { count })
+}
+
+fn string_data_at(data: &[u8], offset: usize, is_unicode: bool) -> Option<String> {
+    let count = u16_at(data, offset)? as usize;
+    let start = offset + 2;
+
+    if is_unicode {
+        let units: Vec<u16> = data
+            .get(start..start + count * 2)?
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+
+        Some(String::from_utf16_lossy(&units))
+    } else {
+        Some(String::from_utf8_lossy(data.get(start..start + count)?).to_string())
+    }
+}
+
+fn link_info_target(info: &[u8]) -> Option<PathBuf> {
+    let header_size = u32_at(info, 4)?;
+    let flags = u32_at(info, 8)?;
+    // The Unicode offsets are only present in the larger header.
+    let has_unicode = header_size >= 0x24;
+
+    let suffix = if has_unicode {
+        wide_string_at(info, u32_at(info, 32)? as usize)
+    } else {
+        ansi_string_at(info, u32_at(info, 24)? as usize)
+    }
+    .unwrap_or_default();
+
+    if flags & VOLUME_ID_AND_LOCAL_BASE_PATH != 0 {
+        let base = if has_unicode && u32_at(info, 28)? != 0 {
+            wide_string_at(info, u32_at(info, 28)? as usize)?
+        } else {
+            ansi_string_at(info, u32_at(info, 16)? as usize)?
+        };
+
+        return Some(PathBuf::from(base + &suffix));
+    }
+
+    if flags & COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX != 0 {
+        let network = info.get(u32_at(info, 20)? as usize..)?;
+        let net_name = ansi_string_at(network, u32_at(network, 8)? as usize)?;
+
+        if suffix.is_empty() {
+            return Some(PathBuf::from(net_name));
+        }
+
+        return Some(PathBuf::from(format!("{}\\{}", net_name, suffix)));
+    }
+
+    None
+}
+
+/// The target of a Windows shortcut (`.lnk`) file, read from its link info or, failing that, its relative path.
+pub(crate) fn lnk_target(path: &Path) -> Option<PathBuf> {
+    let data = fs::read(path).ok()?;
+
+    if u32_at(&data, 0)? != 0x4C {
+        return None;
+    }
+
+    let flags = u32_at(&data, 0x14)?;
+    let is_unicode = flags & IS_UNICODE != 0;
+    let mut offset = 0x4C;
+
+    if flags & HAS_LINK_TARGET_ID_LIST != 0 {
+        offset += 2 + u16_at(&data, offset)? as usize;
+    }
+
+    if flags & HAS_LINK_INFO != 0 {
+        if let Some(target) = link_info_target(data.get(offset..)?) {
+            return Some(target);
+        }
+
+        offset += u32_at(&data, offset)? as usize;
+    }
+
+    if flags & HAS_NAME != 0 {
+        offset += string_data_len(&data, offset, is_unicode)?;
+    }
+
+    if flags & HAS_RELATIVE_PATH != 0 {
+        let relative_path = string_data_at(&data, offset, is_unicode)?;
+
+        return Some(path.parent()?.join(relative_path));
+    }
+
+    None
+}
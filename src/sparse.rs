@@ -0,0 +1,280 @@
+use crate::Result;
+use serde::Serialize;
+use std::{
+    fs::{File, OpenOptions},
+    io,
+    path::Path,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtentKind {
+    // Bytes stored on disk.
+    Data,
+    // A range without storage, read back as zeros.
+    Hole,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Extent {
+    // The offset of the range in bytes.
+    pub offset: u64,
+    // The length of the range in bytes.
+    pub len: u64,
+    pub kind: ExtentKind,
+}
+
+/// Deallocate `len` bytes of the file at `path` from `offset`, the range reads back as zeros and the size of the file stays the same.
+pub(crate) fn punch_hole(path: &Path, offset: u64, len: u64) -> Result<()> {
+    let file = OpenOptions::new().write(true).open(path)?;
+
+    if len == 0 {
+        return Ok(());
+    }
+
+    if offset
+        .checked_add(len)
+        .map_or(true, |end| end > i64::MAX as u64)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "The range is beyond the largest possible file",
+        )
+        .into());
+    }
+
+    Ok(platform::punch_hole(&file, offset, len)?)
+}
+
+/// The data and holes of the file at `path` in order, covering the whole file. A file system without holes gives a single data
+/// extent.
+pub(crate) fn extents(path: &Path) -> Result<Vec<Extent>> {
+    let file = File::open(path)?;
+    let size = file.metadata()?.len();
+
+    let mut extents = Vec::new();
+
+    for (offset, len) in platform::data_ranges(&file, size)? {
+        let end = extents
+            .last()
+            .map_or(0, |extent: &Extent| extent.offset + extent.len);
+
+        if offset > end {
+            extents.push(Extent {
+                offset: end,
+                len: offset - end,
+                kind: ExtentKind::Hole,
+            });
+        }
+
+        extents.push(Extent {
+            offset,
+            len,
+            kind: ExtentKind::Data,
+        });
+    }
+
+    let end = extents
+        .last()
+        .map_or(0, |extent: &Extent| extent.offset + extent.len);
+
+    if size > end {
+        extents.push(Extent {
+            offset: end,
+            len: size - end,
+            kind: ExtentKind::Hole,
+        });
+    }
+
+    Ok(extents)
+}
+
+#[cfg(unix)]
+mod platform {
+    use std::{fs::File, io, os::fd::AsRawFd};
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(super) fn punch_hole(file: &File, offset: u64, len: u64) -> io::Result<()> {
+        let result = unsafe {
+            libc::fallocate(
+                file.as_raw_fd(),
+                libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+                offset as libc::off_t,
+                len as libc::off_t,
+            )
+        };
+
+        match result {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+
+    // APFS only deallocates whole blocks, the partial blocks at the edges of the range are zeroed instead.
+    #[cfg(target_os = "macos")]
+    pub(super) fn punch_hole(file: &File, offset: u64, len: u64) -> io::Result<()> {
+        use std::os::unix::fs::{FileExt, MetadataExt};
+
+        let metadata = file.metadata()?;
+        let block_size = metadata.blksize().max(1);
+        let end = (offset + len).min(metadata.len());
+        let start = offset.div_ceil(block_size) * block_size;
+        let stop = end / block_size * block_size;
+
+        if start >= stop {
+            return zero(file, offset, end);
+        }
+
+        zero(file, offset, start)?;
+        zero(file, stop, end)?;
+
+        let hole = libc::fpunchhole_t {
+            fp_flags: 0,
+            reserved: 0,
+            fp_offset: start as libc::off_t,
+            fp_length: (stop - start) as libc::off_t,
+        };
+
+        return match unsafe { libc::fcntl(file.as_raw_fd(), libc::F_PUNCHHOLE, &hole) } {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        };
+
+        fn zero(file: &File, start: u64, end: u64) -> io::Result<()> {
+            match end > start {
+                true => file.write_all_at(&vec![0; (end - start) as usize], start),
+                false => Ok(()),
+            }
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+    pub(super) fn punch_hole(_file: &File, _offset: u64, _len: u64) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Punching holes is not supported on this platform",
+        ))
+    }
+
+    // The ranges with data, found by seeking from one hole to the next data.
+    pub(super) fn data_ranges(file: &File, size: u64) -> io::Result<Vec<(u64, u64)>> {
+        let fd = file.as_raw_fd();
+        let mut ranges = Vec::new();
+        let mut offset = 0;
+
+        while offset < size {
+            let data = unsafe { libc::lseek(fd, offset as libc::off_t, libc::SEEK_DATA) };
+
+            if data == -1 {
+                let err = io::Error::last_os_error();
+
+                return match err.raw_os_error() {
+                    // Only holes up to the end of the file.
+                    Some(libc::ENXIO) => Ok(ranges),
+                    // The file system doesn't keep track of holes.
+                    Some(libc::EINVAL) if ranges.is_empty() => Ok(vec![(0, size)]),
+                    _ => Err(err),
+                };
+            }
+
+            let hole = match unsafe { libc::lseek(fd, data, libc::SEEK_HOLE) } {
+                -1 => return Err(io::Error::last_os_error()),
+                hole => (hole as u64).min(size),
+            };
+
+            ranges.push((data as u64, hole - data as u64));
+            offset = hole;
+        }
+
+        Ok(ranges)
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::{ffi::c_void, fs::File, io, mem, os::windows::io::AsRawHandle, ptr};
+    use windows_sys::Win32::{
+        Foundation::ERROR_MORE_DATA,
+        System::{
+            Ioctl::{
+                FILE_ALLOCATED_RANGE_BUFFER, FILE_SET_SPARSE_BUFFER, FILE_ZERO_DATA_INFORMATION,
+                FSCTL_QUERY_ALLOCATED_RANGES, FSCTL_SET_SPARSE, FSCTL_SET_ZERO_DATA,
+            },
+            IO::DeviceIoControl,
+        },
+    };
+
+    // Only a file marked as sparse gives the zeroed range back, others get zeros written.
+    pub(super) fn punch_hole(file: &File, offset: u64, len: u64) -> io::Result<()> {
+        let sparse = FILE_SET_SPARSE_BUFFER { SetSparse: true };
+
+        control(file, FSCTL_SET_SPARSE, &sparse, &mut [] as &mut [u8])?;
+
+        let zero = FILE_ZERO_DATA_INFORMATION {
+            FileOffset: offset as i64,
+            BeyondFinalZero: (offset + len) as i64,
+        };
+
+        control(file, FSCTL_SET_ZERO_DATA, &zero, &mut [] as &mut [u8])?;
+
+        Ok(())
+    }
+
+    // The allocated ranges, asked for again from the end of the last one while they don't fit.
+    pub(super) fn data_ranges(file: &File, size: u64) -> io::Result<Vec<(u64, u64)>> {
+        let mut ranges = Vec::new();
+        let mut buffer = [FILE_ALLOCATED_RANGE_BUFFER::default(); 64];
+        let mut offset = 0;
+
+        while offset < size {
+            let query = FILE_ALLOCATED_RANGE_BUFFER {
+                FileOffset: offset as i64,
+                Length: (size - offset) as i64,
+            };
+
+            let (count, more) =
+                match control(file, FSCTL_QUERY_ALLOCATED_RANGES, &query, &mut buffer) {
+                    Ok(count) => (count, false),
+                    Err(err) if err.raw_os_error() == Some(ERROR_MORE_DATA as i32) => {
+                        (buffer.len(), true)
+                    }
+                    Err(err) => return Err(err),
+                };
+
+            for range in &buffer[..count] {
+                ranges.push((range.FileOffset as u64, range.Length as u64));
+            }
+
+            match (more, buffer[..count].last()) {
+                (true, Some(last)) => offset = (last.FileOffset + last.Length) as u64,
+                _ => break,
+            }
+        }
+
+        Ok(ranges)
+    }
+
+    // Send a control code to the file system, returns the number of items written to `output`.
+    fn control<I, O>(file: &File, code: u32, input: &I, output: &mut [O]) -> io::Result<usize> {
+        let mut returned = 0;
+
+        let succeeded = unsafe {
+            DeviceIoControl(
+                file.as_raw_handle(),
+                code,
+                (input as *const I).cast::<c_void>(),
+                mem::size_of::<I>() as u32,
+                output.as_mut_ptr().cast::<c_void>(),
+                mem::size_of_val(output) as u32,
+                &mut returned,
+                ptr::null_mut(),
+            )
+        };
+
+        match succeeded {
+            0 => Err(io::Error::last_os_error()),
+            _ => Ok(returned as usize / mem::size_of::<O>().max(1)),
+        }
+    }
+}
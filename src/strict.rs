@@ -0,0 +1,169 @@
+use crate::{Error, FsProExt};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tauri::{
+    ipc::{Invoke, InvokeBody},
+    Runtime,
+};
+
+// The arguments and option fields holding paths on disk by command, as JSON pointers into the payload where `*` stands for every
+// item of an array, e.g. `/options/ignore/path`. Other fields are left alone whatever their name, such as the icon of
+// `create_launcher`, which may be an icon theme name on Linux.
+const PATH_ARGS: &[(&[&str], &[&str])] = &[
+    (
+        &[
+            "is_exist",
+            "is_file",
+            "is_dir",
+            "parse_path",
+            "read_dir_stream",
+            "size",
+            "size_report",
+            "name",
+            "extname",
+            "full_name",
+            "parent_name",
+            "exe_info",
+            "metadata",
+            "archive_entries",
+            "remove",
+            "watch_file",
+            "benchmark",
+            "disk_usage",
+            "is_writable",
+            "create_launcher",
+            "punch_hole",
+            "extents",
+            "change_token",
+            "has_changed",
+            "diagnose",
+        ],
+        &["/path"],
+    ),
+    (&["stat_many", "resolve_dropped"], &["/paths/*"]),
+    (
+        &[
+            "recent_changes",
+            "largest",
+            "type_stats",
+            "organize",
+            "index_build",
+        ],
+        &["/dir"],
+    ),
+    (&["icon", "icon_asset"], &["/path", "/options/savePath"]),
+    (&["icon_for_extension"], &["/options/savePath"]),
+    (&["backup"], &["/path", "/options/dir"]),
+    (
+        &[
+            "decompress",
+            "convert_archive",
+            "copy_file",
+            "clone_metadata",
+            "gzip",
+            "gunzip",
+        ],
+        &["/srcPath", "/dstPath"],
+    ),
+    (
+        &["compress", "auto_sync_start"],
+        &["/srcPath", "/dstPath", "/options/ignore/path"],
+    ),
+    (
+        &["compress_many"],
+        &["/srcPaths/*", "/dstPath", "/options/ignore/path"],
+    ),
+    (
+        &["compress_manifest"],
+        &["/manifest", "/manifest/*/src", "/dstPath"],
+    ),
+    (
+        &["transfer", "copy"],
+        &[
+            "/srcPath",
+            "/dstPath",
+            "/options/ignore/path",
+            "/options/backup/dir",
+        ],
+    ),
+    (
+        &["plan_operations", "commit_operations"],
+        &[
+            "/operations/*/src",
+            "/operations/*/dst",
+            "/operations/*/path",
+        ],
+    ),
+    (&["import_files"], &["/files/*", "/dstDir"]),
+    (
+        &["bidirectional_sync"],
+        &["/leftPath", "/rightPath", "/options/ignore/path"],
+    ),
+    (&["store_cas"], &["/srcPath", "/casRoot"]),
+    (&["retrieve_cas"], &["/casRoot"]),
+];
+
+/// Wrap the invoke handler of the plugin to reject the commands called with relative paths, when required with
+/// [`crate::Builder::require_absolute_paths`] for the app of the command.
+pub(crate) fn handler<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        let settings = invoke.message.webview_ref().fs_pro().settings;
+
+        if !settings.require_absolute_paths {
+            return handler(invoke);
+        }
+
+        let relative = match invoke.message.payload() {
+            InvokeBody::Json(payload) => relative_path(invoke.message.command(), payload),
+            _ => None,
+        };
+
+        match relative {
+            Some(path) => {
+                // Rejected in place rather than once a command returns, so the redaction is set here.
+                drop(settings.path_redaction.redacting());
+                invoke.resolver.reject(Error::RelativePath(path));
+                true
+            }
+            None => handler(invoke),
+        }
+    }
+}
+
+// The first relative path among the path arguments of the command.
+fn relative_path(command: &str, payload: &Value) -> Option<PathBuf> {
+    let (_, pointers) = PATH_ARGS
+        .iter()
+        .find(|(commands, _)| commands.contains(&command))?;
+
+    pointers.iter().find_map(|pointer| {
+        let segments: Vec<&str> = pointer.split('/').skip(1).collect();
+
+        relative_at(payload, &segments)
+    })
+}
+
+// A missing field or a value that isn't a path, such as the entries of a manifest given in place of its path, is skipped. A path
+// given as a `PathEntry` is checked by its string form, which keeps the root of the raw path.
+fn relative_at(value: &Value, segments: &[&str]) -> Option<PathBuf> {
+    match (segments.split_first(), value) {
+        (None, Value::String(path)) => relative(path),
+        (None, Value::Object(entry)) => relative_at(entry.get("path")?, &[]),
+        (Some((&"*", rest)), Value::Array(items)) => {
+            items.iter().find_map(|item| relative_at(item, rest))
+        }
+        (Some((key, rest)), Value::Object(fields)) => relative_at(fields.get(*key)?, rest),
+        _ => None,
+    }
+}
+
+fn relative(path: &str) -> Option<PathBuf> {
+    match path {
+        // Dropped files may come as `file://` URIs, which are absolute.
+        path if path.starts_with("file://") => None,
+        path if Path::new(path).is_absolute() => None,
+        path => Some(PathBuf::from(path)),
+    }
+}
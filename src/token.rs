@@ -0,0 +1,91 @@
+use crate::Result;
+use sha2::{Digest, Sha256};
+use std::{fs, path::Path, time::UNIX_EPOCH};
+
+/// A token of the state of the file or directory at `path`, which changes when it's modified, resized or replaced by another file.
+///
+/// It's the first 8 bytes of a SHA-256 hash, in hex, of the modification time, the size and the identity of the file: the device
+/// and inode, or the volume and file index on Windows. Writing a new file and renaming it over the old one changes the token even
+/// when the time and size come out the same.
+pub(crate) fn compute(path: &Path) -> Result<String> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+
+    let mut hasher = Sha256::new();
+    hasher.update(modified.to_le_bytes());
+    hasher.update(metadata.len().to_le_bytes());
+
+    for value in platform::identity(path, &metadata)? {
+        hasher.update(value.to_le_bytes());
+    }
+
+    let hash = hasher.finalize();
+
+    Ok(hash[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Whether the file or directory at `path` has changed since `token` was computed, which it has when it no longer exists.
+pub(crate) fn has_changed(path: &Path, token: &str) -> Result<bool> {
+    if !path.exists() {
+        return Ok(true);
+    }
+
+    Ok(!compute(path)?.eq_ignore_ascii_case(token.trim()))
+}
+
+#[cfg(unix)]
+mod platform {
+    use std::{fs::Metadata, io, os::unix::fs::MetadataExt, path::Path};
+
+    // The inode change time is part of it too, so restoring the modification time after a write doesn't hide the write.
+    pub(super) fn identity(_path: &Path, metadata: &Metadata) -> io::Result<Vec<u64>> {
+        Ok(vec![
+            metadata.dev(),
+            metadata.ino(),
+            metadata.ctime() as u64,
+            metadata.ctime_nsec() as u64,
+        ])
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::{
+        fs::{Metadata, OpenOptions},
+        io,
+        mem::MaybeUninit,
+        os::windows::{fs::OpenOptionsExt, io::AsRawHandle},
+        path::Path,
+    };
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileInformationByHandle, FILE_FLAG_BACKUP_SEMANTICS,
+    };
+
+    // Directories can only be opened with backup semantics. Opening without access rights reads the information all the same,
+    // even of a file another process has locked.
+    pub(super) fn identity(path: &Path, _metadata: &Metadata) -> io::Result<Vec<u64>> {
+        let file = OpenOptions::new()
+            .access_mode(0)
+            .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+            .open(path)?;
+        let mut info = MaybeUninit::uninit();
+
+        if unsafe { GetFileInformationByHandle(file.as_raw_handle(), info.as_mut_ptr()) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let info = unsafe { info.assume_init() };
+
+        Ok(vec![
+            info.dwVolumeSerialNumber as u64,
+            (info.nFileIndexHigh as u64) << 32 | info.nFileIndexLow as u64,
+        ])
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{FsProExt, PathRedaction, Settings};
+use std::{path::Path, time::Instant};
+use tauri::{ipc::Invoke, Runtime};
+use tracing::{field, level_filters::LevelFilter, Level, Span};
+
+// The target of all spans and events of the plugin, to filter them by, e.g. `tauri_plugin_fs_pro=debug`.
+const TARGET: &str = "tauri_plugin_fs_pro";
+
+// The payload arguments recorded for every command.
+const TRACED_ARGS: &[&str] = &["path", "srcPath", "dstPath", "dir", "operationId"];
+
+// Spans and events can only be created with a level known at compile time, so each level has its own callsite.
+macro_rules! at_level {
+    ($level:expr, $macro:ident!($($args:tt)*)) => {
+        match $level {
+            Level::ERROR => tracing::$macro!(target: TARGET, Level::ERROR, $($args)*),
+            Level::WARN => tracing::$macro!(target: TARGET, Level::WARN, $($args)*),
+            Level::INFO => tracing::$macro!(target: TARGET, Level::INFO, $($args)*),
+            Level::DEBUG => tracing::$macro!(target: TARGET, Level::DEBUG, $($args)*),
+            Level::TRACE => tracing::$macro!(target: TARGET, Level::TRACE, $($args)*),
+        }
+    };
+}
+
+// The level the plugin traces at for an app, `None` when tracing is turned off.
+fn level(settings: &Settings) -> Option<Level> {
+    settings
+        .tracing_level
+        .unwrap_or(LevelFilter::DEBUG)
+        .into_level()
+}
+
+/// Wrap the invoke handler of the plugin to record every command invocation.
+pub(crate) fn handler<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        invoked(&invoke);
+        handler(invoke)
+    }
+}
+
+// Record a command invocation with the paths and the operation id it was called with.
+fn invoked<R: Runtime>(invoke: &Invoke<R>) {
+    let settings = invoke.message.webview_ref().fs_pro().settings;
+    let Some(level) = level(&settings) else {
+        return;
+    };
+    let redaction = settings.path_redaction;
+    let command = invoke.message.command();
+    let args = match invoke.message.payload() {
+        tauri::ipc::InvokeBody::Json(payload) => TRACED_ARGS
+            .iter()
+            .filter_map(|name| match payload.get(name)? {
+                serde_json::Value::String(value) if name.ends_with("Id") => {
+                    Some(format!("{}={}", name, value))
+                }
+                serde_json::Value::String(value) => {
+                    Some(format!("{}={}", name, redaction.apply(Path::new(value))))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => String::new(),
+    };
+
+    at_level!(level, event!(command, args = %args, "invoked"));
+}
+
+/// A span around a long-running command, which records how long it took once dropped.
+pub(crate) struct Traced {
+    span: Span,
+    level: Option<Level>,
+    // Paths are redacted like in error messages.
+    redaction: PathRedaction,
+    started: Instant,
+    bytes: Option<u64>,
+    succeeded: bool,
+}
+
+/// Start the span of a command working on `path`, with the settings of its app.
+pub(crate) fn command(
+    settings: &Settings,
+    name: &'static str,
+    path: &Path,
+    operation_id: Option<&str>,
+) -> Traced {
+    let level = level(settings);
+    let redaction = settings.path_redaction;
+    let span = match level {
+        Some(level) => at_level!(
+            level,
+            span!(
+                "command",
+                command = name,
+                path = %redaction.apply(path),
+                dst_path = field::Empty,
+                operation_id = operation_id,
+            )
+        ),
+        None => Span::none(),
+    };
+
+    Traced {
+        span,
+        level,
+        redaction,
+        started: Instant::now(),
+        bytes: None,
+        succeeded: false,
+    }
+}
+
+impl Traced {
+    pub(crate) fn dst_path(self, path: &Path) -> Self {
+        self.span
+            .record("dst_path", field::display(self.redaction.apply(path)));
+        self
+    }
+
+    /// The number of bytes the command measured, read or wrote.
+    pub(crate) fn bytes(&mut self, bytes: u64) {
+        self.bytes = Some(bytes);
+    }
+
+    /// Mark the command as succeeded, it failed if it returns without.
+    pub(crate) fn succeeded(&mut self) {
+        self.succeeded = true;
+    }
+}
+
+impl Drop for Traced {
+    fn drop(&mut self) {
+        let Some(level) = self.level else {
+            return;
+        };
+        let duration_ms = self.started.elapsed().as_millis() as u64;
+        let bytes = self.bytes;
+
+        self.span.in_scope(|| {
+            if self.succeeded {
+                at_level!(level, event!(duration_ms, bytes, "finished"));
+            } else {
+                at_level!(level, event!(duration_ms, "failed"));
+            }
+        });
+    }
+}
@@ -0,0 +1,160 @@
+use crate::retry::{is_locked, RetryPolicy};
+use std::{
+    fs, io, panic,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+};
+use walkdir::WalkDir;
+
+// A directory whose entries are pending deletion isn't empty yet on Windows.
+fn is_transient(err: &io::Error) -> bool {
+    // ERROR_DIR_NOT_EMPTY
+    is_locked(err) || (cfg!(windows) && err.raw_os_error() == Some(145))
+}
+
+// Run `op` with the retry policy, noting `path` when it had to be retried. A path that is already gone counts as removed.
+fn retry(
+    policy: &RetryPolicy,
+    retried: &Mutex<Vec<PathBuf>>,
+    path: &Path,
+    op: impl FnMut() -> io::Result<()>,
+) -> io::Result<()> {
+    let (result, was_retried) = policy.run(is_transient, op);
+
+    if was_retried {
+        retried.lock().unwrap().push(path.to_path_buf());
+    }
+
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+// Read-only files can't be deleted on Windows, returns whether the attribute was cleared.
+#[cfg(windows)]
+fn clear_readonly(path: &Path) -> bool {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return false;
+    };
+
+    let mut permissions = metadata.permissions();
+
+    if !permissions.readonly() {
+        return false;
+    }
+
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(false);
+
+    fs::set_permissions(path, permissions).is_ok()
+}
+
+#[cfg(not(windows))]
+fn clear_readonly(_path: &Path) -> bool {
+    false
+}
+
+fn remove_file(policy: &RetryPolicy, retried: &Mutex<Vec<PathBuf>>, path: &Path) -> io::Result<()> {
+    retry(policy, retried, path, || match fs::remove_file(path) {
+        Err(err) if err.kind() == io::ErrorKind::PermissionDenied && clear_readonly(path) => {
+            fs::remove_file(path)
+        }
+        // Symbolic links and junctions to directories are removed like directories on Windows.
+        Err(err) if cfg!(windows) && fs::symlink_metadata(path).is_ok_and(|m| m.is_symlink()) => {
+            fs::remove_dir(path).map_err(|_| err)
+        }
+        result => result,
+    })
+}
+
+// A `\\?\` path lifts the 260 character limit for everything below it on Windows. Only the parent is canonicalized, so a symbolic
+// link is removed rather than its target.
+#[cfg(windows)]
+fn extended_length(path: &Path) -> PathBuf {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return path.to_path_buf();
+    };
+
+    let parent = if parent.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        parent
+    };
+
+    fs::canonicalize(parent)
+        .map(|parent| parent.join(name))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(not(windows))]
+fn extended_length(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+/// Remove a file or a directory tree without following symbolic links. The files are unlinked in parallel, then the directories
+/// deepest first. Returns the paths that had to be retried.
+pub(crate) fn remove_all(path: &Path, policy: &RetryPolicy) -> io::Result<Vec<PathBuf>> {
+    let path = extended_length(path);
+    let retried = Mutex::new(Vec::new());
+
+    if !fs::symlink_metadata(&path)?.is_dir() {
+        remove_file(policy, &retried, &path)?;
+
+        return Ok(retried.into_inner().unwrap());
+    }
+
+    let mut files = Vec::new();
+    let mut dirs = Vec::new();
+
+    for entry in WalkDir::new(&path) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            // Something else removed the entry while walking.
+            Err(err) if err.io_error().map(io::Error::kind) == Some(io::ErrorKind::NotFound) => {
+                continue
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if entry.file_type().is_dir() {
+            dirs.push(entry.into_path());
+        } else {
+            files.push(entry.into_path());
+        }
+    }
+
+    let workers = thread::available_parallelism()
+        .map_or(4, |n| n.get())
+        .min(16);
+    let chunk_size = files.len().div_ceil(workers).max(1);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let retried = &retried;
+
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .try_for_each(|file| remove_file(policy, retried, file))
+                })
+            })
+            .collect();
+
+        handles.into_iter().try_for_each(|handle| {
+            handle
+                .join()
+                .unwrap_or_else(|payload| panic::resume_unwind(payload))
+        })
+    })?;
+
+    // The walk lists parents before their children.
+    for dir in dirs.iter().rev() {
+        retry(policy, &retried, dir, || fs::remove_dir(dir))?;
+    }
+
+    Ok(retried.into_inner().unwrap())
+}
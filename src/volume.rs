@@ -0,0 +1,249 @@
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+/// An archive stored in a single file or split into numbered volumes of a fixed size, e.g. `backup.tar.gz.001`, `.002` and so on,
+/// read and written as one continuous file.
+///
+/// The volumes are plain byte ranges of the archive, so a set joined with `cat` gives back the whole archive, and a set split by
+/// other tools such as `split` can be read too.
+pub(crate) struct Volumes {
+    // The path of the archive without the volume number.
+    path: PathBuf,
+    // The size of each volume when writing a split archive, the last file grows without limit otherwise.
+    volume_size: Option<u64>,
+    files: Vec<File>,
+    lens: Vec<u64>,
+    pos: u64,
+    // The volume and the offset in it the file cursor is at, to save a seek on sequential reads and writes.
+    cursor: Option<(usize, u64)>,
+}
+
+impl From<File> for Volumes {
+    fn from(file: File) -> Self {
+        let len = file
+            .metadata()
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+
+        Self::with_files(PathBuf::new(), None, vec![file], vec![len])
+    }
+}
+
+impl Volumes {
+    fn with_files(
+        path: PathBuf,
+        volume_size: Option<u64>,
+        files: Vec<File>,
+        lens: Vec<u64>,
+    ) -> Self {
+        Self {
+            path,
+            volume_size,
+            files,
+            lens,
+            pos: 0,
+            cursor: None,
+        }
+    }
+
+    /// Create an archive at `path`, split into volumes of `volume_size` bytes named after it when given. Volumes left over from an
+    /// earlier, longer set are removed, so they aren't read back as part of the new one.
+    pub(crate) fn create(path: &Path, volume_size: Option<u64>) -> io::Result<Self> {
+        let Some(volume_size) = volume_size else {
+            return Ok(File::create(path)?.into());
+        };
+
+        if volume_size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "The volume size must be greater than 0",
+            ));
+        }
+
+        remove(path)?;
+
+        Ok(Self::with_files(
+            path.to_path_buf(),
+            Some(volume_size),
+            Vec::new(),
+            Vec::new(),
+        ))
+    }
+
+    /// Open the archive at `path`, either a single file or a set of volumes given by its first volume, e.g. `backup.tar.gz.001`,
+    /// or by its name without the number when there is no such file.
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        let base = match volume_number(path) {
+            Some(1) => path.with_extension(""),
+            _ if !path.exists() && volume_path(path, 1).is_file() => path.to_path_buf(),
+            _ => return Ok(File::open(path)?.into()),
+        };
+
+        let (files, lens) = paths(&base)
+            .into_iter()
+            .map(|path| {
+                let file = File::open(path)?;
+                let len = file.metadata()?.len();
+
+                Ok((file, len))
+            })
+            .collect::<io::Result<(Vec<_>, Vec<_>)>>()?;
+
+        Ok(Self::with_files(base, None, files, lens))
+    }
+
+    // The volume `pos` falls in and the offset in it.
+    fn locate(&self, pos: u64) -> (usize, u64) {
+        if let Some(volume_size) = self.volume_size {
+            return ((pos / volume_size) as usize, pos % volume_size);
+        }
+
+        let mut start = 0;
+
+        for (index, len) in self.lens.iter().enumerate() {
+            if index == self.lens.len() - 1 || pos < start + len {
+                return (index, pos - start);
+            }
+
+            start += len;
+        }
+
+        (0, pos)
+    }
+
+    // Move the cursor of the volume to the offset, unless it's already there.
+    fn seek_volume(&mut self, index: usize, offset: u64) -> io::Result<()> {
+        if self.cursor != Some((index, offset)) {
+            self.files[index].seek(SeekFrom::Start(offset))?;
+        }
+
+        Ok(())
+    }
+
+    fn advance(&mut self, index: usize, offset: u64, count: usize) {
+        self.pos += count as u64;
+        self.cursor = Some((index, offset + count as u64));
+    }
+}
+
+impl Read for Volumes {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let (index, offset) = self.locate(self.pos);
+
+        if index >= self.files.len() {
+            return Ok(0);
+        }
+
+        self.seek_volume(index, offset)?;
+
+        let read = self.files[index].read(buf)?;
+
+        self.advance(index, offset, read);
+
+        Ok(read)
+    }
+}
+
+impl Write for Volumes {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let (index, offset) = self.locate(self.pos);
+        let buf = match self.volume_size {
+            Some(volume_size) => &buf[..buf.len().min((volume_size - offset) as usize)],
+            None => buf,
+        };
+
+        while self.files.len() <= index {
+            self.files
+                .push(File::create(volume_path(&self.path, self.files.len() + 1))?);
+            self.lens.push(0);
+        }
+
+        self.seek_volume(index, offset)?;
+
+        let written = self.files[index].write(buf)?;
+
+        self.lens[index] = self.lens[index].max(offset + written as u64);
+        self.advance(index, offset, written);
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.files.iter_mut().try_for_each(|file| file.flush())
+    }
+}
+
+impl Seek for Volumes {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::End(delta) => self.lens.iter().sum::<u64>().checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.pos.checked_add_signed(delta),
+        };
+
+        self.pos = pos.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "Seek to a negative position")
+        })?;
+
+        Ok(self.pos)
+    }
+}
+
+// The volume number of a path, e.g. `1` for `backup.tar.gz.001`.
+fn volume_number(path: &Path) -> Option<usize> {
+    let extension = path.extension()?.to_str()?;
+
+    match extension.len() == 3 && extension.bytes().all(|byte| byte.is_ascii_digit()) {
+        true => extension.parse().ok(),
+        false => None,
+    }
+}
+
+/// The path of a volume of the archive at `path`, numbered from `1`.
+pub(crate) fn volume_path(path: &Path, number: usize) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(format!(".{:03}", number));
+
+    PathBuf::from(name)
+}
+
+/// The paths of the volumes of the archive at `path` in order, empty when it's not split.
+pub(crate) fn paths(path: &Path) -> Vec<PathBuf> {
+    (1..)
+        .map(|number| volume_path(path, number))
+        .take_while(|path| path.is_file())
+        .collect()
+}
+
+/// Move the archive written at `from` in place of the one at `to`, volume by volume when it's split. The volumes of an earlier
+/// set at `to` beyond the new ones are removed, so they aren't read as part of it.
+pub(crate) fn rename(from: &Path, to: &Path) -> io::Result<()> {
+    let volumes = paths(from);
+
+    if volumes.is_empty() {
+        return fs::rename(from, to);
+    }
+
+    for (index, volume) in volumes.iter().enumerate() {
+        fs::rename(volume, volume_path(to, index + 1))?;
+    }
+
+    for stale in paths(to).into_iter().skip(volumes.len()) {
+        fs::remove_file(stale)?;
+    }
+
+    Ok(())
+}
+
+/// Remove the volumes of the archive at `path`.
+pub(crate) fn remove(path: &Path) -> io::Result<()> {
+    for path in paths(path) {
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
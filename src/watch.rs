@@ -0,0 +1,303 @@
+use crate::{
+    events, metadata,
+    mount::{self, VolumeEvent, VolumeEventKind},
+    Error, FsProExt, Metadata, Result, WatchFileOptions,
+};
+use notify::{event::ModifyKind, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use tauri::{async_runtime, AppHandle, Emitter, Runtime};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchFileEventKind {
+    // The content or the attributes of the file changed.
+    Modify,
+    // The file was deleted.
+    Remove,
+    // The file was moved away from the watched path.
+    Rename,
+    // The volume of the file was unmounted. The watch ends, or waits for the volume to be mounted again with `auto_resume`.
+    Lost,
+    // The volume of a lost file was mounted again and the file is watched again.
+    Resume,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchFileEvent {
+    id: u32,
+    kind: WatchFileEventKind,
+    #[serde(flatten, serialize_with = "crate::commands::serialize_path_entry")]
+    path: PathBuf,
+    // The metadata of the file after the change, `None` once it no longer exists.
+    metadata: Option<Metadata>,
+}
+
+// A watched file, kept while its watch is lost until its volume is mounted again.
+struct WatchedFile {
+    path: PathBuf,
+    // The mount path of the volume the file is on.
+    volume: Option<PathBuf>,
+    auto_resume: bool,
+    min_interval: Duration,
+}
+
+/// The file watchers managed by the plugin.
+#[derive(Default)]
+pub(crate) struct Watchers {
+    next_id: AtomicU32,
+    watchers: Mutex<HashMap<u32, RecommendedWatcher>>,
+    // Every watch, the lost ones have no watcher.
+    files: Mutex<HashMap<u32, WatchedFile>>,
+}
+
+fn event_kind(kind: &EventKind) -> Option<WatchFileEventKind> {
+    match kind {
+        EventKind::Modify(ModifyKind::Name(_)) => Some(WatchFileEventKind::Rename),
+        EventKind::Modify(_) => Some(WatchFileEventKind::Modify),
+        EventKind::Remove(_) => Some(WatchFileEventKind::Remove),
+        _ => None,
+    }
+}
+
+// The kinds of the events of a watcher, the modifications that come sooner than `interval` after the previous event are taken as
+// one, sent once the interval passed with the metadata of the file by then. Removals and renames are never held back.
+struct Throttled<'a> {
+    rx: &'a mpsc::Receiver<notify::Result<notify::Event>>,
+    interval: Duration,
+    last: Option<Instant>,
+    // A removal or rename that came while modifications were held back.
+    pending: Option<WatchFileEventKind>,
+}
+
+impl Iterator for Throttled<'_> {
+    type Item = WatchFileEventKind;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let kind = match self.pending.take() {
+            Some(kind) => kind,
+            None => self
+                .rx
+                .iter()
+                .find_map(|event| event.ok().and_then(|event| event_kind(&event.kind)))?,
+        };
+
+        let wait = self.last.map_or(Duration::ZERO, |last| {
+            self.interval.saturating_sub(last.elapsed())
+        });
+
+        if kind == WatchFileEventKind::Modify && !wait.is_zero() {
+            thread::sleep(wait);
+
+            // The modifications that came in the meantime are part of this one.
+            self.pending = self
+                .rx
+                .try_iter()
+                .filter_map(|event| event.ok().and_then(|event| event_kind(&event.kind)))
+                .find(|kind| *kind != WatchFileEventKind::Modify);
+        }
+
+        self.last = Some(Instant::now());
+        Some(kind)
+    }
+}
+
+fn emit<R: Runtime>(app_handle: &AppHandle<R>, id: u32, kind: WatchFileEventKind, path: &Path) {
+    let metadata = async_runtime::block_on(metadata(app_handle.clone(), path.into(), None)).ok();
+
+    let _ = app_handle.emit(
+        "fs-pro://watch-file",
+        WatchFileEvent {
+            id,
+            kind,
+            path: path.to_path_buf(),
+            metadata,
+        },
+    );
+}
+
+impl Watchers {
+    /// Watch a single file and emit `fs-pro://watch-file` events until [`Watchers::unwatch`] is called.
+    pub(crate) fn watch_file<R: Runtime>(
+        &self,
+        app_handle: AppHandle<R>,
+        path: PathBuf,
+        options: Option<WatchFileOptions>,
+    ) -> Result<u32> {
+        if !path.is_file() {
+            return Err(Error::NotFound(path));
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (auto_resume, min_interval) = match options {
+            Some(opt) => (opt.auto_resume, opt.min_interval_ms),
+            None => (None, None),
+        };
+        let min_interval = events::interval(&app_handle.fs_pro().settings, min_interval);
+
+        self.start(app_handle.clone(), id, path.clone(), min_interval)?;
+        self.files.lock().unwrap().insert(
+            id,
+            WatchedFile {
+                volume: mount::volume_of(&path).map(|volume| volume.path),
+                path,
+                auto_resume: auto_resume.unwrap_or(false),
+                min_interval,
+            },
+        );
+        app_handle
+            .fs_pro()
+            .volume_monitor
+            .ensure_running(&app_handle);
+
+        Ok(id)
+    }
+
+    fn start<R: Runtime>(
+        &self,
+        app_handle: AppHandle<R>,
+        id: u32,
+        path: PathBuf,
+        min_interval: Duration,
+    ) -> Result<()> {
+        let (tx, rx) = mpsc::channel();
+
+        let mut watcher = notify::recommended_watcher(tx)?;
+        watcher.watch(&path, RecursiveMode::NonRecursive)?;
+        self.watchers.lock().unwrap().insert(id, watcher);
+
+        // Events are handled on a separate thread, because a watcher can't be changed from inside its own handler.
+        // The thread ends once the watcher, and with it the sender, is dropped.
+        thread::spawn(move || {
+            let kinds = Throttled {
+                rx: &rx,
+                interval: min_interval,
+                last: None,
+                pending: None,
+            };
+
+            for mut kind in kinds {
+                let watchers = &app_handle.fs_pro().watchers;
+
+                // Editors often save by replacing the file, which ends the watch on the old file. Follow the new one.
+                if kind != WatchFileEventKind::Modify && path.is_file() {
+                    let mut watchers = watchers.watchers.lock().unwrap();
+
+                    let Some(watcher) = watchers.get_mut(&id) else {
+                        break;
+                    };
+
+                    let _ = watcher.unwatch(&path);
+
+                    if watcher.watch(&path, RecursiveMode::NonRecursive).is_ok() {
+                        kind = WatchFileEventKind::Modify;
+                    }
+                }
+
+                // The file is gone with its volume rather than removed.
+                if kind != WatchFileEventKind::Modify && watchers.is_volume_unmounted(id) {
+                    watchers.lose(&app_handle, id);
+                    break;
+                }
+
+                emit(&app_handle, id, kind, &path);
+
+                if kind != WatchFileEventKind::Modify {
+                    watchers.unwatch(id);
+                    break;
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn is_volume_unmounted(&self, id: u32) -> bool {
+        let files = self.files.lock().unwrap();
+
+        files
+            .get(&id)
+            .and_then(|file| file.volume.as_ref())
+            .is_some_and(|volume| !mount::is_volume_mounted(volume))
+    }
+
+    // Stop the watcher of a file whose volume was unmounted, the watch is kept to be resumed with `auto_resume`.
+    fn lose<R: Runtime>(&self, app_handle: &AppHandle<R>, id: u32) {
+        // Lost already, or unwatched.
+        if self.watchers.lock().unwrap().remove(&id).is_none() {
+            return;
+        }
+
+        let path = {
+            let mut files = self.files.lock().unwrap();
+
+            match files.get(&id) {
+                Some(file) if file.auto_resume => file.path.clone(),
+                Some(_) => files.remove(&id).unwrap().path,
+                None => return,
+            }
+        };
+
+        emit(app_handle, id, WatchFileEventKind::Lost, &path);
+    }
+
+    /// Lose the watches of the files on an unmounted volume, and resume the lost watches on a mounted volume.
+    pub(crate) fn volume_changed<R: Runtime>(
+        &self,
+        app_handle: &AppHandle<R>,
+        event: &VolumeEvent,
+    ) {
+        let watched: Vec<(u32, PathBuf, Duration)> = {
+            let files = self.files.lock().unwrap();
+            let watchers = self.watchers.lock().unwrap();
+
+            files
+                .iter()
+                .filter(|(id, file)| {
+                    file.volume.as_ref() == Some(&event.volume.path)
+                        && watchers.contains_key(id) == (event.kind == VolumeEventKind::Unmounted)
+                })
+                .map(|(id, file)| (*id, file.path.clone(), file.min_interval))
+                .collect()
+        };
+
+        for (id, path, min_interval) in watched {
+            if event.kind == VolumeEventKind::Unmounted {
+                self.lose(app_handle, id);
+                continue;
+            }
+
+            // The file may have been removed while its volume was away.
+            if path.is_file()
+                && self
+                    .start(app_handle.clone(), id, path.clone(), min_interval)
+                    .is_ok()
+            {
+                emit(app_handle, id, WatchFileEventKind::Resume, &path);
+            } else if self.files.lock().unwrap().remove(&id).is_some() {
+                emit(app_handle, id, WatchFileEventKind::Remove, &path);
+            }
+        }
+    }
+
+    /// Whether any file is watched, including the lost watches waiting for their volume.
+    pub(crate) fn has_files(&self) -> bool {
+        !self.files.lock().unwrap().is_empty()
+    }
+
+    /// Stop a watcher, returns `false` if there is no watcher with the id.
+    pub(crate) fn unwatch(&self, id: u32) -> bool {
+        self.watchers.lock().unwrap().remove(&id);
+        self.files.lock().unwrap().remove(&id).is_some()
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{retry::RetryPolicy, unlink, Result};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fs, io, mem,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Mutex,
+    },
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    // The id to dispose the workspace with.
+    pub id: u32,
+    // The empty directory created for the workspace.
+    #[serde(flatten, serialize_with = "crate::commands::serialize_path_entry")]
+    pub path: PathBuf,
+}
+
+/// The scratch directories handed out to multi-step jobs, removed when disposed or when the app exits.
+///
+/// Each directory is named after the process and the id, e.g. `1234-1`, so the ones left behind by a crash can be told apart from
+/// the ones of another running instance of the app.
+#[derive(Default)]
+pub(crate) struct Workspaces {
+    next_id: AtomicU32,
+    dirs: Mutex<HashMap<u32, PathBuf>>,
+}
+
+impl Workspaces {
+    /// Create an empty workspace inside `root`.
+    pub(crate) fn create(&self, root: &Path) -> Result<Workspace> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let path = root.join(format!("{}-{}", process::id(), id));
+
+        fs::create_dir_all(&path)?;
+        self.dirs.lock().unwrap().insert(id, path.clone());
+
+        Ok(Workspace { id, path })
+    }
+
+    /// Remove a workspace with everything in it, returns `false` if there is no workspace with the id.
+    pub(crate) fn dispose(&self, id: u32) -> Result<bool> {
+        let Some(path) = self.dirs.lock().unwrap().remove(&id) else {
+            return Ok(false);
+        };
+
+        match unlink::remove_all(&path, &RetryPolicy::default()) {
+            Ok(_) => Ok(true),
+            // Removed from outside the plugin already.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(true),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Remove all workspaces, when the app exits.
+    pub(crate) fn dispose_all(&self) {
+        let dirs = mem::take(&mut *self.dirs.lock().unwrap());
+
+        for path in dirs.into_values() {
+            let _ = unlink::remove_all(&path, &RetryPolicy::default());
+        }
+    }
+}
+
+/// Remove the workspaces in `root` left behind by runs of the app that crashed or were killed.
+pub(crate) fn recover(root: &Path) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let pid = name
+            .to_str()
+            .and_then(|name| name.split_once('-'))
+            .and_then(|(pid, _)| pid.parse::<u32>().ok());
+
+        // Unknown entries are left alone.
+        if pid.is_some_and(|pid| pid != process::id() && !is_running(pid)) {
+            let _ = unlink::remove_all(&entry.path(), &RetryPolicy::default());
+        }
+    }
+}
+
+// Whether a process with the id is running, a process of another user counts too.
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+
+    let signalled = unsafe { libc::kill(pid, 0) == 0 };
+
+    signalled || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(windows)]
+fn is_running(pid: u32) -> bool {
+    use windows_sys::Win32::{
+        Foundation::{CloseHandle, STILL_ACTIVE},
+        System::Threading::{GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION},
+    };
+
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+
+        if process.is_null() {
+            return false;
+        }
+
+        let mut exit_code = 0;
+        let running =
+            GetExitCodeProcess(process, &mut exit_code) != 0 && exit_code == STILL_ACTIVE as u32;
+
+        CloseHandle(process);
+
+        running
+    }
+}
+
+// Without a way to tell, the workspaces are kept rather than pulled from under a running instance.
+#[cfg(not(any(unix, windows)))]
+fn is_running(_pid: u32) -> bool {
+    true
+}
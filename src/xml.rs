@@ -0,0 +1,8 @@
+/// Escape the characters with a meaning in XML, for text and attribute values in double quotes.
+pub(crate) fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}